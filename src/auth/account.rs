@@ -0,0 +1,67 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The stored identity chosen with `--account` / `OXIDATE_ACCOUNT`; unset for the default one
+static SELECTED: OnceLock<String> = OnceLock::new();
+
+/// Use the account `name` for the rest of the process. Its credentials, offline
+/// changes and calendar preferences are kept apart from every other account's.
+pub fn select(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!(
+            "Invalid account name \"{}\": use letters, digits, '-' and '_'",
+            name
+        );
+    }
+
+    SELECTED
+        .set(name.to_string())
+        .map_err(|_| anyhow::anyhow!("An account was already selected"))
+}
+
+pub fn selected() -> Option<&'static str> {
+    SELECTED.get().map(String::as_str)
+}
+
+/// Where the selected account's data lives
+pub fn data_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().context("Failed to get config directory")?;
+
+    Ok(account_dir(&config_dir.join("oxidate"), selected()))
+}
+
+/// The default account keeps the original layout directly in the app directory
+fn account_dir(app_dir: &Path, account: Option<&str>) -> PathBuf {
+    match account {
+        Some(name) => app_dir.join("accounts").join(name),
+        None => app_dir.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_dir() {
+        let app_dir = Path::new("/home/me/.config/oxidate");
+
+        assert_eq!(account_dir(app_dir, None), app_dir);
+        assert_eq!(
+            account_dir(app_dir, Some("work")),
+            Path::new("/home/me/.config/oxidate/accounts/work")
+        );
+    }
+
+    #[test]
+    fn test_select_rejects_path_like_names() {
+        assert!(select("../work").is_err());
+        assert!(select("").is_err());
+        assert!(select("my work").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod account;
 pub mod oauth;
 pub mod port;
 pub mod tokens;
@@ -8,17 +9,18 @@ pub use tokens::Tokens;
 use anyhow::Result;
 
 /// Main authentication workflow
-/// Checks if tokens exist, if not runs OAuth flow
-pub async fn authenticate() -> Result<Tokens> {
+/// Checks if tokens exist, if not runs OAuth flow.
+/// `read_only` and `open_browser` only affect a new login: existing tokens keep their scope.
+pub async fn authenticate(read_only: bool, open_browser: bool) -> Result<Tokens> {
     if Tokens::exists() {
-        println!("Loading existing credentials...");
+        eprintln!("Loading existing credentials...");
         Tokens::load()
     } else {
-        println!("No credentials found. Starting OAuth flow...");
+        eprintln!("No credentials found. Starting OAuth flow...");
         let oauth_client = OAuthClient::new()?;
-        let tokens = oauth_client.run_flow().await?;
+        let tokens = oauth_client.run_flow(read_only, open_browser).await?;
         tokens.save()?;
-        println!("Credentials saved successfully!");
+        eprintln!("Credentials saved successfully!");
         Ok(tokens)
     }
 }
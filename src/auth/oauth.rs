@@ -9,6 +9,16 @@ use oauth2::{
 };
 use std::io::{BufRead, BufReader, Write};
 use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+const CALENDAR_SCOPE: &str = "https://www.googleapis.com/auth/calendar";
+const CALENDAR_READONLY_SCOPE: &str = "https://www.googleapis.com/auth/calendar.readonly";
+/// Saved and "other" contacts, to complete attendee names to email addresses
+const CONTACTS_SCOPES: [&str; 2] = [
+    "https://www.googleapis.com/auth/contacts.readonly",
+    "https://www.googleapis.com/auth/contacts.other.readonly",
+];
 
 pub struct OAuthClient {
     client: BasicClient,
@@ -48,13 +58,25 @@ impl OAuthClient {
             .context("GOOGLE_CLIENT_SECRET not set at compile time")
     }
 
-    pub fn get_authorization_url(&self) -> (String, CsrfToken) {
+    /// `read_only` asks only for permission to view calendars; otherwise
+    /// contacts are requested too, for inviting attendees by name
+    pub fn get_authorization_url(&self, read_only: bool) -> (String, CsrfToken) {
+        let scopes: Vec<&str> = if read_only {
+            vec![CALENDAR_READONLY_SCOPE]
+        } else {
+            [CALENDAR_SCOPE]
+                .into_iter()
+                .chain(CONTACTS_SCOPES)
+                .collect()
+        };
         let (url, csrf) = self
             .client
             .authorize_url(CsrfToken::new_random)
-            .add_scope(Scope::new(
-                "https://www.googleapis.com/auth/calendar".to_string(),
-            ))
+            .add_scopes(
+                scopes
+                    .into_iter()
+                    .map(|scope| Scope::new(scope.to_string())),
+            )
             .url();
 
         (url.to_string(), csrf)
@@ -66,10 +88,14 @@ impl OAuthClient {
     }
 
     pub fn listen_for_callback(&self) -> Result<String> {
-        let listener = TcpListener::bind(format!("127.0.0.1:{}", self.port))
-            .context("Failed to bind to port")?;
+        Self::accept_callback(self.port)
+    }
+
+    fn accept_callback(port: u16) -> Result<String> {
+        let listener =
+            TcpListener::bind(format!("127.0.0.1:{}", port)).context("Failed to bind to port")?;
 
-        println!("Waiting for OAuth callback on port {}...", self.port);
+        eprintln!("Waiting for OAuth callback on port {}...", port);
 
         // Accept one connection
         let (mut stream, _) = listener.accept().context("Failed to accept connection")?;
@@ -92,6 +118,52 @@ impl OAuthClient {
         Ok(code)
     }
 
+    /// Wait for the callback or for the user to paste the address the browser
+    /// was sent to, whichever comes first. On a remote machine the redirect to
+    /// localhost fails in the user's browser, but its address still has the code.
+    fn callback_or_paste(&self) -> Result<String> {
+        let (sender, receiver) = mpsc::channel();
+
+        let port = self.port;
+        let callback = sender.clone();
+        thread::spawn(move || {
+            // Only the paste can succeed if the port cannot be bound
+            if let Ok(code) = Self::accept_callback(port) {
+                let _ = callback.send(Ok(code));
+            }
+        });
+        thread::spawn(move || {
+            let mut line = String::new();
+            let pasted = std::io::stdin()
+                .read_line(&mut line)
+                .context("Failed to read pasted address")
+                .and_then(|_| Self::extract_code_from_pasted(&line));
+            let _ = sender.send(pasted);
+        });
+
+        receiver
+            .recv()
+            .context("Stopped waiting for authorization")?
+    }
+
+    /// The code from a pasted redirect address (`http://localhost:8080/?code=...`)
+    /// or from the bare code
+    fn extract_code_from_pasted(input: &str) -> Result<String> {
+        let input = input.trim();
+        if input.is_empty() {
+            anyhow::bail!("Nothing was pasted");
+        }
+        if !input.contains('?') {
+            return Ok(input.to_string());
+        }
+
+        let url = reqwest::Url::parse(input).context("Pasted text is not an address")?;
+        url.query_pairs()
+            .find(|(key, _)| key == "code")
+            .map(|(_, code)| code.into_owned())
+            .context("Authorization code not found in pasted address")
+    }
+
     fn extract_code_from_request(request_line: &str) -> Result<String> {
         // Request line format: GET /?code=...&state=... HTTP/1.1
         let parts: Vec<&str> = request_line.split_whitespace().collect();
@@ -131,15 +203,23 @@ impl OAuthClient {
         Ok(Tokens::new(access_token, refresh_token))
     }
 
-    pub async fn run_flow(&self) -> Result<Tokens> {
-        let (auth_url, _csrf_token) = self.get_authorization_url();
+    /// `open_browser: false` only prints the address, for remote sessions
+    pub async fn run_flow(&self, read_only: bool, open_browser: bool) -> Result<Tokens> {
+        let (auth_url, _csrf_token) = self.get_authorization_url(read_only);
 
-        println!("Opening browser for authentication...");
-        println!("If the browser doesn't open, visit: {}", auth_url);
+        let code = if open_browser {
+            eprintln!("Opening browser for authentication...");
+            eprintln!("If the browser doesn't open, visit: {}", auth_url);
 
-        self.open_browser(&auth_url)?;
+            self.open_browser(&auth_url)?;
+            self.listen_for_callback()?
+        } else {
+            eprintln!("Visit this address in a browser to authenticate:");
+            eprintln!("\n  {}\n", auth_url);
+            eprintln!("If the browser then fails to load localhost, paste its address here:");
 
-        let code = self.listen_for_callback()?;
+            self.callback_or_paste()?
+        };
         let tokens = self.exchange_code(code).await?;
 
         Ok(tokens)
@@ -154,8 +234,7 @@ mod tests {
     fn test_oauth_client_new() {
         let result = OAuthClient::new();
         // This might fail if credentials aren't set, but structure should be valid
-        if result.is_ok() {
-            let client = result.unwrap();
+        if let Ok(client) = result {
             assert!(client.port > 0);
         }
     }
@@ -163,15 +242,40 @@ mod tests {
     #[test]
     fn test_get_authorization_url() {
         if let Ok(client) = OAuthClient::new() {
-            let (url, csrf_token) = client.get_authorization_url();
+            let (url, csrf_token) = client.get_authorization_url(false);
 
             assert!(url.contains("accounts.google.com"));
             assert!(url.contains("oauth2"));
             assert!(url.contains("calendar"));
+            assert!(!url.contains("readonly"));
             assert!(!csrf_token.secret().is_empty());
         }
     }
 
+    #[test]
+    fn test_get_authorization_url_read_only() {
+        if let Ok(client) = OAuthClient::new() {
+            let (url, _) = client.get_authorization_url(true);
+
+            assert!(url.contains("calendar.readonly"));
+        }
+    }
+
+    #[test]
+    fn test_extract_code_from_pasted() {
+        let pasted = "http://localhost:8080/?state=abc&code=4%2F0AbCd&scope=calendar\n";
+        assert_eq!(
+            OAuthClient::extract_code_from_pasted(pasted).unwrap(),
+            "4/0AbCd"
+        );
+        assert_eq!(
+            OAuthClient::extract_code_from_pasted(" 4/0AbCd ").unwrap(),
+            "4/0AbCd"
+        );
+        assert!(OAuthClient::extract_code_from_pasted("http://localhost:8080/?state=abc").is_err());
+        assert!(OAuthClient::extract_code_from_pasted("\n").is_err());
+    }
+
     #[test]
     fn test_extract_code_from_request() {
         let request = "GET /?code=test_code_123&state=random_state HTTP/1.1";
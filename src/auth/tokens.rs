@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::account;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Tokens {
     pub access_token: String,
@@ -17,11 +19,9 @@ impl Tokens {
         }
     }
 
-    fn get_storage_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir().context("Failed to get config directory")?;
-
-        let app_config_dir = config_dir.join("oxidate");
-        Ok(app_config_dir.join("token.json"))
+    /// Credentials of the selected account (see `account::select`)
+    pub fn get_storage_path() -> Result<PathBuf> {
+        Ok(account::data_dir()?.join("token.json"))
     }
 
     pub fn exists() -> bool {
@@ -32,18 +32,7 @@ impl Tokens {
     }
 
     pub fn save(&self) -> Result<()> {
-        let tokens_path = Self::get_storage_path()?;
-
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = tokens_path.parent() {
-            fs::create_dir_all(parent).context("Failed to create config directory")?;
-        }
-
-        let json = serde_json::to_string_pretty(self).context("Failed to serialize tokens")?;
-
-        fs::write(&tokens_path, json).context("Failed to write tokens file")?;
-
-        Ok(())
+        self.save_to(&Self::get_storage_path()?)
     }
 
     pub fn load() -> Result<Self> {
@@ -66,8 +55,8 @@ impl Tokens {
         Ok(())
     }
 
-    // Test-only methods that accept custom paths
-    #[cfg(test)]
+    /// Written beside `path` and renamed over it, so a crash or a concurrent
+    /// save never leaves a truncated file behind
     fn save_to(&self, path: &std::path::Path) -> Result<()> {
         // Create parent directory if it doesn't exist
         if let Some(parent) = path.parent() {
@@ -76,11 +65,14 @@ impl Tokens {
 
         let json = serde_json::to_string_pretty(self).context("Failed to serialize tokens")?;
 
-        fs::write(path, json).context("Failed to write tokens file")?;
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temp_path, json).context("Failed to write tokens file")?;
+        fs::rename(&temp_path, path).context("Failed to replace tokens file")?;
 
         Ok(())
     }
 
+    // Test-only methods that accept custom paths
     #[cfg(test)]
     fn load_from(path: &std::path::Path) -> Result<Self> {
         let json = fs::read_to_string(path).context("Failed to read tokens file")?;
@@ -139,6 +131,24 @@ mod tests {
         // temp_dir is automatically cleaned up when dropped
     }
 
+    #[test]
+    fn test_save_replaces_tokens_without_leftovers() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let token_path = temp_dir.path().join("token.json");
+
+        Tokens::new("old".to_string(), "refresh".to_string())
+            .save_to(&token_path)
+            .expect("Failed to save tokens");
+        let refreshed = Tokens::new("new".to_string(), "refresh".to_string());
+        refreshed
+            .save_to(&token_path)
+            .expect("Failed to save tokens");
+
+        assert_eq!(Tokens::load_from(&token_path).unwrap(), refreshed);
+        let files = fs::read_dir(temp_dir.path()).unwrap().count();
+        assert_eq!(files, 1);
+    }
+
     #[test]
     fn test_delete_tokens() {
         let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
@@ -0,0 +1,183 @@
+use anyhow::{bail, Context, Result};
+
+use super::models::Event;
+
+/// Requests Google accepts in one batch call
+pub const MAX_BATCH_SIZE: usize = 50;
+pub const BATCH_URL: &str = "https://www.googleapis.com/batch/calendar/v3";
+const BOUNDARY: &str = "oxidate_batch";
+
+/// Content-Type of a request body built by `insert_body`
+pub fn content_type() -> String {
+    format!("multipart/mixed; boundary={}", BOUNDARY)
+}
+
+/// A multipart/mixed body with one `POST {events_path}` part per event.
+/// `events_path` is the already-encoded path, e.g. `/calendar/v3/calendars/primary/events`.
+pub fn insert_body(events_path: &str, events: &[Event]) -> Result<String> {
+    let mut body = String::new();
+
+    for (i, event) in events.iter().enumerate() {
+        let json = serde_json::to_string(event).context("Failed to serialize event")?;
+        body.push_str(&format!(
+            "--{}\r\nContent-Type: application/http\r\nContent-ID: <item{}>\r\n\r\n\
+             POST {} HTTP/1.1\r\nContent-Type: application/json\r\n\r\n{}\r\n",
+            BOUNDARY,
+            i + 1,
+            events_path,
+            json
+        ));
+    }
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+
+    Ok(body)
+}
+
+/// Split a batch response into one result per request, in request order.
+/// Parts come back as `<response-itemN>` and may be out of order.
+pub fn parse_insert_response(
+    content_type: &str,
+    body: &str,
+    expected: usize,
+) -> Result<Vec<Result<Event>>> {
+    let boundary = content_type
+        .split(';')
+        .filter_map(|param| param.trim().strip_prefix("boundary="))
+        .next()
+        .map(|b| b.trim_matches('"'))
+        .context("Batch response has no boundary")?;
+
+    let mut results: Vec<Option<Result<Event>>> = (0..expected).map(|_| None).collect();
+    let body = body.replace("\r\n", "\n");
+    let delimiter = format!("--{}", boundary);
+
+    // The first piece is the preamble, the last starts with the closing `--`
+    for (position, part) in body.split(&delimiter).skip(1).enumerate() {
+        if part.starts_with("--") {
+            break;
+        }
+        let Some((headers, response)) = part.trim_start_matches('\n').split_once("\n\n") else {
+            continue;
+        };
+        let index = content_index(headers).unwrap_or(position);
+        if let Some(slot) = results.get_mut(index) {
+            *slot = Some(parse_part(response));
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(anyhow::anyhow!("No response in batch"))))
+        .collect())
+}
+
+/// Zero-based request index from `Content-ID: <response-item3>`
+fn content_index(headers: &str) -> Option<usize> {
+    let value = headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("content-id")
+            .then(|| value.trim())
+    })?;
+    let digits: String = value
+        .trim_end_matches('>')
+        .chars()
+        .rev()
+        .take_while(char::is_ascii_digit)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+
+    digits.parse::<usize>().ok()?.checked_sub(1)
+}
+
+/// `HTTP/1.1 200 OK`, headers, blank line, JSON
+fn parse_part(response: &str) -> Result<Event> {
+    let status_line = response.lines().next().unwrap_or_default();
+    let status: u16 = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .with_context(|| format!("Malformed batch part: {:?}", status_line))?;
+    let json = response
+        .split_once("\n\n")
+        .map_or("", |(_, json)| json)
+        .trim();
+
+    if !(200..300).contains(&status) {
+        let message = serde_json::from_str::<serde_json::Value>(json)
+            .ok()
+            .and_then(|value| value["error"]["message"].as_str().map(str::to_string));
+        match message {
+            Some(message) => bail!("{} ({})", message, status),
+            None => bail!("API returned {}", status_line.trim()),
+        }
+    }
+
+    serde_json::from_str(json).context("Failed to parse event in batch response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn event(summary: &str) -> Event {
+        let at = |date: &str| EventDateTime {
+            date_time: None,
+            date: Some(date.to_string()),
+            time_zone: None,
+        };
+        Event {
+            id: String::new(),
+            ..Event::test(summary, at("2025-07-02"), at("2025-07-03"))
+        }
+    }
+
+    #[test]
+    fn test_insert_body() {
+        let body = insert_body(
+            "/calendar/v3/calendars/primary/events",
+            &[event("One"), event("Two")],
+        )
+        .unwrap();
+
+        assert_eq!(body.matches("--oxidate_batch\r\n").count(), 2);
+        assert!(body.contains("Content-ID: <item2>"));
+        assert!(body.contains("POST /calendar/v3/calendars/primary/events HTTP/1.1"));
+        assert!(body.contains("\"summary\":\"Two\""));
+        assert!(body.ends_with("--oxidate_batch--\r\n"));
+    }
+
+    #[test]
+    fn test_parse_insert_response_in_request_order() {
+        let body = "--batch_xyz\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item2>\r\n\r\n\
+            HTTP/1.1 400 Bad Request\r\n\
+            Content-Type: application/json\r\n\r\n\
+            {\"error\": {\"code\": 400, \"message\": \"Invalid start time.\"}}\r\n\
+            --batch_xyz\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item1>\r\n\r\n\
+            HTTP/1.1 200 OK\r\n\
+            Content-Type: application/json; charset=UTF-8\r\n\r\n\
+            {\"id\": \"evt1\", \"summary\": \"One\", \"start\": {\"date\": \"2025-07-02\"}, \"end\": {\"date\": \"2025-07-03\"}}\r\n\
+            --batch_xyz--\r\n";
+
+        let results =
+            parse_insert_response("multipart/mixed; boundary=batch_xyz", body, 3).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, "evt1");
+        let error = results[1].as_ref().unwrap_err().to_string();
+        assert_eq!(error, "Invalid start time. (400)");
+        assert!(results[2].is_err());
+    }
+
+    #[test]
+    fn test_parse_insert_response_without_boundary() {
+        assert!(parse_insert_response("application/json", "{}", 1).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use super::models::Event;
+
+/// Video conferencing services whose links are recognized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Meet,
+    Zoom,
+    Teams,
+    Webex,
+}
+
+impl Provider {
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Meet => "Google Meet",
+            Provider::Zoom => "Zoom",
+            Provider::Teams => "Microsoft Teams",
+            Provider::Webex => "Webex",
+        }
+    }
+
+    /// The provider of a join link, judged by host and path so that e.g. a
+    /// link to zoom.us's pricing page is not taken for a meeting
+    fn of(url: &str) -> Option<Self> {
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let (authority, path) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+        let host = authority
+            .rsplit('@')
+            .next()
+            .unwrap_or_default()
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let is = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+
+        if host == "meet.google.com" && path.len() > 1 {
+            Some(Provider::Meet)
+        } else if (is("zoom.us") || is("zoomgov.com"))
+            && ["/j/", "/my/", "/w/", "/s/"]
+                .iter()
+                .any(|prefix| path.starts_with(prefix))
+        {
+            Some(Provider::Zoom)
+        } else if (host == "teams.microsoft.com"
+            && (path.starts_with("/l/meetup-join/") || path.starts_with("/meet/")))
+            || (host == "teams.live.com" && path.starts_with("/meet/"))
+        {
+            Some(Provider::Teams)
+        } else if is("webex.com") && path.len() > 1 {
+            Some(Provider::Webex)
+        } else {
+            None
+        }
+    }
+}
+
+/// The link to join an event's call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoCall {
+    /// None for conference data of a service not listed in `Provider`
+    pub provider: Option<Provider>,
+    pub url: String,
+}
+
+impl VideoCall {
+    /// e.g. "Zoom", or "Video call" for unknown services
+    pub fn name(&self) -> &'static str {
+        self.provider.map_or("Video call", Provider::name)
+    }
+}
+
+/// The event's call: its conference data, then the Meet link, then a known
+/// meeting link in the location or description
+pub fn video_call(event: &Event) -> Option<VideoCall> {
+    let conference = event
+        .conference_data
+        .iter()
+        .flat_map(|data| &data.entry_points)
+        .find(|entry_point| entry_point.entry_point_type == "video")
+        .map(|entry_point| VideoCall {
+            provider: Provider::of(&entry_point.uri),
+            url: entry_point.uri.clone(),
+        });
+
+    conference
+        .or_else(|| {
+            event.hangout_link.as_ref().map(|url| VideoCall {
+                provider: Some(Provider::Meet),
+                url: url.clone(),
+            })
+        })
+        .or_else(|| find_in_text(event.location.as_deref()?))
+        .or_else(|| find_in_text(event.description.as_deref()?))
+}
+
+/// The first meeting link in free text, which may be HTML from Google's editor
+fn find_in_text(text: &str) -> Option<VideoCall> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>'))
+        .filter_map(|word| {
+            let start = word.find("http://").or_else(|| word.find("https://"))?;
+            let url = word[start..]
+                .trim_end_matches(['.', ',', ';', ':', ')', ']', '!'])
+                .replace("&amp;", "&");
+            Some(VideoCall {
+                provider: Some(Provider::of(&url)?),
+                url,
+            })
+        })
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::{ConferenceData, EntryPoint, EventDateTime};
+
+    fn event() -> Event {
+        let at = EventDateTime {
+            date_time: Some("2025-06-16T09:00:00Z".to_string()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            id: "sync".to_string(),
+            ..Event::test("Sync", at.clone(), at)
+        }
+    }
+
+    #[test]
+    fn test_provider_of_join_links() {
+        let provider = |url| Provider::of(url);
+
+        assert_eq!(
+            provider("https://meet.google.com/abc-defg-hij"),
+            Some(Provider::Meet)
+        );
+        assert_eq!(
+            provider("https://us02web.zoom.us/j/123456789?pwd=abc"),
+            Some(Provider::Zoom)
+        );
+        assert_eq!(
+            provider("https://teams.microsoft.com/l/meetup-join/19%3ameeting"),
+            Some(Provider::Teams)
+        );
+        assert_eq!(
+            provider("https://acme.webex.com/meet/jdoe"),
+            Some(Provider::Webex)
+        );
+        assert_eq!(provider("https://zoom.us/pricing"), None);
+        assert_eq!(provider("https://notzoom.us/j/1"), None);
+        assert_eq!(provider("https://example.com/meet.google.com/x"), None);
+    }
+
+    #[test]
+    fn test_video_call_prefers_conference_data() {
+        let mut event = event();
+        event.description = Some("Dial in: https://zoom.us/j/1".to_string());
+        event.hangout_link = Some("https://meet.google.com/abc-defg-hij".to_string());
+        event.conference_data = Some(ConferenceData {
+            entry_points: vec![
+                EntryPoint {
+                    entry_point_type: "phone".to_string(),
+                    uri: "tel:+1-555-0100".to_string(),
+                },
+                EntryPoint {
+                    entry_point_type: "video".to_string(),
+                    uri: "https://acme.webex.com/acme/j.php?MTID=m1".to_string(),
+                },
+            ],
+        });
+
+        let call = video_call(&event).unwrap();
+        assert_eq!(call.provider, Some(Provider::Webex));
+        assert_eq!(call.url, "https://acme.webex.com/acme/j.php?MTID=m1");
+
+        event.conference_data = None;
+        assert_eq!(video_call(&event).unwrap().provider, Some(Provider::Meet));
+    }
+
+    #[test]
+    fn test_video_call_in_location_and_description() {
+        let mut event = event();
+        assert_eq!(video_call(&event), None);
+
+        event.description = Some(
+            "Agenda: https://docs.google.com/d/1<br>Join: \
+             <a href=\"https://teams.microsoft.com/l/meetup-join/19%3a1?a=1&amp;b=2\">here</a>"
+                .to_string(),
+        );
+        assert_eq!(
+            video_call(&event).unwrap().url,
+            "https://teams.microsoft.com/l/meetup-join/19%3a1?a=1&b=2"
+        );
+
+        event.location = Some("Room 4 / https://zoom.us/j/987654321.".to_string());
+        let call = video_call(&event).unwrap();
+        assert_eq!(call.url, "https://zoom.us/j/987654321");
+        assert_eq!(call.name(), "Zoom");
+    }
+}
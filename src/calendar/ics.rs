@@ -0,0 +1,880 @@
+use anyhow::{bail, Context, Result};
+use chrono::{
+    DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc,
+    Weekday,
+};
+
+use super::local::overlaps;
+use super::models::{Event, EventDateTime};
+
+/// A content line: `NAME;PARAM=VALUE;...:value`
+#[derive(Debug, PartialEq)]
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// How often an RRULE repeats
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The parts of an RRULE oxidate expands. UNTIL is in the same time frame as
+/// DTSTART, which RFC 5545 requires of it.
+#[derive(Debug, PartialEq)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+    /// BYDAY of weekly rules, Monday first
+    weekdays: Vec<Weekday>,
+}
+
+/// Parse the VEVENTs of an iCalendar (.ics) document into events ready for insertion.
+/// Only the fields oxidate shows are kept; recurrence rules and alarms are ignored.
+pub fn parse_events(input: &str) -> Result<Vec<Event>> {
+    let lines = unfold(input);
+    components(&lines)?
+        .iter()
+        .map(|properties| build_event(properties))
+        .collect()
+}
+
+/// The events of an iCalendar document overlapping `time_min..time_max`, each
+/// with the index of its VEVENT. Recurring events are expanded lazily: only
+/// their occurrences within the range are built, each with its start (e.g.
+/// `20250616T090000Z`) as id, skipping EXDATEs and the instances other
+/// VEVENTs override with a RECURRENCE-ID. Rules oxidate can't expand keep
+/// just their first occurrence.
+pub fn parse_occurrences(
+    input: &str,
+    time_min: DateTime<Utc>,
+    time_max: DateTime<Utc>,
+) -> Result<Vec<(usize, Event)>> {
+    let lines = unfold(input);
+    let components = components(&lines)?;
+
+    let find = |properties: &[Property], name: &str| {
+        properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| property.value.trim().to_string())
+    };
+    let overridden: Vec<(String, NaiveDateTime)> = components
+        .iter()
+        .filter_map(|properties| {
+            let uid = find(properties, "UID")?;
+            let recurrence_id = parse_stamp(&find(properties, "RECURRENCE-ID")?)?;
+            Some((uid, recurrence_id))
+        })
+        .collect();
+
+    let mut events = Vec::new();
+    for (index, properties) in components.iter().enumerate() {
+        let event = build_event(properties)?;
+        let rule = match find(properties, "RRULE").map(|rule| parse_rule(&rule)) {
+            Some(Ok(rule)) => rule,
+            not_expanded => {
+                if let Some(Err(e)) = not_expanded {
+                    tracing::debug!("Not expanding recurring event: {:#}", e);
+                }
+                if overlaps(&event, time_min, time_max) {
+                    events.push((index, event));
+                }
+                continue;
+            }
+        };
+
+        let uid = find(properties, "UID");
+        let excluded: Vec<NaiveDateTime> = properties
+            .iter()
+            .filter(|property| property.name == "EXDATE")
+            .flat_map(|property| property.value.split(','))
+            .filter_map(parse_stamp)
+            .chain(
+                overridden
+                    .iter()
+                    .filter(|(overridden_uid, _)| Some(overridden_uid) == uid.as_ref())
+                    .map(|(_, at)| *at),
+            )
+            .collect();
+
+        let start_value = find(properties, "DTSTART").unwrap_or_default();
+        let start = parse_stamp(&start_value).context("Invalid DTSTART")?;
+        // Occurrences ending well before the range are skipped without
+        // building them; the slack covers the offset between DTSTART's time
+        // frame and local time
+        let span = match (event.start.to_local(), event.end.to_local()) {
+            (Some(start), Some(end)) => end - start,
+            _ => Duration::days(1),
+        };
+        let first = time_min.with_timezone(&Local).naive_local() - Duration::days(1);
+        let last = time_max.with_timezone(&Local).date_naive() + Duration::days(1);
+
+        for at in occurrences(&rule, start, last) {
+            if at + span < first || excluded.contains(&at) {
+                continue;
+            }
+            let mut occurrence = build_occurrence(properties, at - start)?;
+            occurrence.id = format_stamp(&start_value, at);
+            if overlaps(&occurrence, time_min, time_max) {
+                events.push((index, occurrence));
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// The properties of each VEVENT in the unfolded `lines`
+fn components(lines: &[String]) -> Result<Vec<Vec<Property<'_>>>> {
+    let mut components = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+
+    for line in lines {
+        let property = parse_property(line)?;
+        match (property.name.as_str(), property.value) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                let properties = current.take().context("END:VEVENT without BEGIN")?;
+                components.push(properties);
+            }
+            _ => {
+                if let Some(ref mut properties) = current {
+                    properties.push(property);
+                }
+            }
+        }
+    }
+
+    if current.is_some() {
+        bail!("Unterminated VEVENT");
+    }
+    Ok(components)
+}
+
+/// Serialize one event as an iCalendar document for people outside Google
+/// Calendar. `stamp` is the time of export (DTSTAMP).
+pub fn write_event(event: &Event, stamp: DateTime<Utc>) -> Result<String> {
+    let uid = if event.id.is_empty() {
+        format!("{}@oxidate", stamp.timestamp())
+    } else {
+        format!("{}@google.com", event.id)
+    };
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//oxidate//EN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+        format_date_time("DTSTART", &event.start)?,
+        format_date_time("DTEND", &event.end)?,
+    ];
+    let texts = [
+        ("SUMMARY", &event.summary),
+        ("LOCATION", &event.location),
+        ("DESCRIPTION", &event.description),
+    ];
+    for (name, value) in texts {
+        if let Some(value) = value {
+            lines.push(format!("{}:{}", name, escape(value)));
+        }
+    }
+    if let Some(ref link) = event.html_link {
+        lines.push(format!("URL:{}", link));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    Ok(lines
+        .iter()
+        .map(|line| fold(line))
+        .map(|line| line + "\r\n")
+        .collect())
+}
+
+/// The inverse of `parse_date_time`: a DATE, a UTC time, or a TZID-zoned time
+fn format_date_time(name: &str, value: &EventDateTime) -> Result<String> {
+    if let (None, Some(date)) = (&value.date_time, &value.date) {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date: {}", date))?;
+        return Ok(format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")));
+    }
+
+    let date_time = value
+        .date_time
+        .as_deref()
+        .with_context(|| format!("Event has no {}", name))?;
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(date_time) {
+        return Ok(format!(
+            "{}:{}",
+            name,
+            date_time.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ")
+        ));
+    }
+
+    // Zoned times are written without an offset
+    let naive = NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M:%S")
+        .with_context(|| format!("Invalid date-time: {}", date_time))?;
+    let zone = value
+        .time_zone
+        .as_deref()
+        .with_context(|| format!("{} has neither an offset nor a time zone", date_time))?;
+    Ok(format!(
+        "{};TZID={}:{}",
+        name,
+        zone,
+        naive.format("%Y%m%dT%H%M%S")
+    ))
+}
+
+/// TEXT escaping, the inverse of `unescape`
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Break lines longer than 75 octets, continuing them after a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
+
+/// Join folded lines: a line starting with a space or tab continues the previous one
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn parse_property(line: &str) -> Result<Property<'_>> {
+    // The value may contain ':' (e.g. URLs), parameters may quote it
+    let mut in_quotes = false;
+    let colon = line
+        .char_indices()
+        .find(|(_, c)| {
+            if *c == '"' {
+                in_quotes = !in_quotes;
+            }
+            *c == ':' && !in_quotes
+        })
+        .map(|(index, _)| index)
+        .with_context(|| format!("Malformed line: {}", line))?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next().unwrap_or_default().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.to_ascii_uppercase(), value.trim_matches('"')))
+        .collect();
+
+    Ok(Property {
+        name,
+        params,
+        value,
+    })
+}
+
+fn build_event(properties: &[Property]) -> Result<Event> {
+    let find = |name: &str| properties.iter().find(|property| property.name == name);
+    let text = |name: &str| find(name).map(|property| unescape(property.value));
+
+    let start_property = find("DTSTART").context("VEVENT without DTSTART")?;
+    let start = parse_date_time(start_property)?;
+    let end = match (find("DTEND"), find("DURATION")) {
+        (Some(end), _) => parse_date_time(end)?,
+        (None, Some(duration)) => add_duration(&start, parse_duration(duration.value)?)?,
+        // RFC 5545: a date-only event lasts a day, a timed one is instantaneous
+        (None, None) if start.is_all_day() => add_duration(&start, Duration::days(1))?,
+        (None, None) => start.clone(),
+    };
+
+    Ok(Event {
+        id: String::new(),
+        summary: text("SUMMARY"),
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        start,
+        end,
+        status: None,
+        html_link: None,
+        hangout_link: None,
+        attendees: None,
+        calendar_id: None,
+        updated: None,
+        etag: None,
+        reminders: None,
+        attachments: None,
+        conference_data: None,
+        ical_uid: None,
+    })
+}
+
+/// The event of `properties` moved by `offset` in DTSTART's own time frame,
+/// so a 9:00 meeting stays at 9:00 across daylight saving changes
+fn build_occurrence(properties: &[Property], offset: Duration) -> Result<Event> {
+    let moved: Vec<Option<String>> = properties
+        .iter()
+        .map(|property| match property.name.as_str() {
+            "DTSTART" | "DTEND" => {
+                parse_stamp(property.value).map(|at| format_stamp(property.value, at + offset))
+            }
+            _ => None,
+        })
+        .collect();
+    let properties: Vec<Property> = properties
+        .iter()
+        .zip(&moved)
+        .map(|(property, moved)| Property {
+            name: property.name.clone(),
+            params: property.params.clone(),
+            value: moved.as_deref().unwrap_or(property.value),
+        })
+        .collect();
+
+    build_event(&properties)
+}
+
+/// A DATE or DATE-TIME value as written, without its time frame
+fn parse_stamp(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_end_matches('Z');
+    match value.len() {
+        8 => NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0),
+        _ => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok(),
+    }
+}
+
+/// `at` written the way `like` is: as a DATE, or a DATE-TIME in UTC or not
+fn format_stamp(like: &str, at: NaiveDateTime) -> String {
+    let like = like.trim();
+    if like.len() == 8 {
+        at.format("%Y%m%d").to_string()
+    } else if like.ends_with('Z') {
+        at.format("%Y%m%dT%H%M%SZ").to_string()
+    } else {
+        at.format("%Y%m%dT%H%M%S").to_string()
+    }
+}
+
+/// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20251231T235959Z`. Rules using
+/// other parts, e.g. BYMONTHDAY or the second Tuesday of a month, are refused
+/// rather than expanded wrongly.
+fn parse_rule(value: &str) -> Result<Rule> {
+    let mut frequency = None;
+    let mut rule = Rule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        weekdays: Vec::new(),
+    };
+
+    for part in value.split(';').filter(|part| !part.is_empty()) {
+        let (name, value) = part
+            .split_once('=')
+            .with_context(|| format!("Malformed recurrence rule: {}", part))?;
+        match name.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    other => bail!("Unsupported recurrence frequency {}", other),
+                })
+            }
+            "INTERVAL" => {
+                rule.interval = value
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .with_context(|| format!("Invalid interval: {}", value))?
+            }
+            "COUNT" => {
+                rule.count = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("Invalid count: {}", value))?,
+                )
+            }
+            "UNTIL" => {
+                let until = parse_stamp(value)
+                    .with_context(|| format!("Invalid end of recurrence: {}", value))?;
+                // A date-only UNTIL includes that day
+                rule.until = Some(match value.trim().len() {
+                    8 => until + Duration::days(1) - Duration::seconds(1),
+                    _ => until,
+                });
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    let weekday = match day.to_ascii_uppercase().as_str() {
+                        "MO" => Weekday::Mon,
+                        "TU" => Weekday::Tue,
+                        "WE" => Weekday::Wed,
+                        "TH" => Weekday::Thu,
+                        "FR" => Weekday::Fri,
+                        "SA" => Weekday::Sat,
+                        "SU" => Weekday::Sun,
+                        other => bail!("Unsupported weekday {}", other),
+                    };
+                    rule.weekdays.push(weekday);
+                }
+            }
+            // Weeks start on Monday unless told otherwise, which only matters
+            // for weekly rules with an interval and several days
+            "WKST" => {}
+            other => bail!("Unsupported recurrence rule part {}", other),
+        }
+    }
+
+    rule.frequency = frequency.context("Recurrence rule without FREQ")?;
+    if !rule.weekdays.is_empty() && rule.frequency != Frequency::Weekly {
+        bail!("BYDAY is only supported for weekly rules");
+    }
+    rule.weekdays.sort_by_key(Weekday::num_days_from_monday);
+    rule.weekdays.dedup();
+    Ok(rule)
+}
+
+/// The starts of `rule`'s occurrences from `start` on, in order. They are
+/// generated one period (day, week, month or year) at a time and never past
+/// `last`, so a rule repeating forever costs only what is asked of it.
+fn occurrences(
+    rule: &Rule,
+    start: NaiveDateTime,
+    last: NaiveDate,
+) -> impl Iterator<Item = NaiveDateTime> + '_ {
+    let date = start.date();
+    (0u32..)
+        .map_while(move |n| period_start(rule.frequency, date, n.checked_mul(rule.interval)?))
+        .take_while(move |period| *period <= last)
+        .flat_map(move |period| period_dates(rule, date, period))
+        .map(move |day| day.and_time(start.time()))
+        .filter(move |at| *at >= start)
+        .take_while(move |at| rule.until.is_none_or(|until| *at <= until))
+        .take(rule.count.unwrap_or(usize::MAX))
+}
+
+/// The first day of the period `periods` periods after the one containing `date`
+fn period_start(frequency: Frequency, date: NaiveDate, periods: u32) -> Option<NaiveDate> {
+    match frequency {
+        Frequency::Daily => date.checked_add_days(Days::new(periods.into())),
+        Frequency::Weekly => {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday().into());
+            monday.checked_add_days(Days::new(u64::from(periods) * 7))
+        }
+        Frequency::Monthly => date.with_day(1)?.checked_add_months(Months::new(periods)),
+        Frequency::Yearly => date
+            .with_day(1)?
+            .with_month(1)?
+            .checked_add_months(Months::new(periods.checked_mul(12)?)),
+    }
+}
+
+/// The days within the period beginning on `period` that an event starting on
+/// `date` repeats on; months without its day of month are skipped, as RFC
+/// 5545 asks
+fn period_dates(rule: &Rule, date: NaiveDate, period: NaiveDate) -> Vec<NaiveDate> {
+    match rule.frequency {
+        Frequency::Daily => vec![period],
+        Frequency::Weekly if rule.weekdays.is_empty() => {
+            vec![period + Duration::days(date.weekday().num_days_from_monday().into())]
+        }
+        Frequency::Weekly => rule
+            .weekdays
+            .iter()
+            .map(|weekday| period + Duration::days(weekday.num_days_from_monday().into()))
+            .collect(),
+        Frequency::Monthly => period.with_day(date.day()).into_iter().collect(),
+        Frequency::Yearly => NaiveDate::from_ymd_opt(period.year(), date.month(), date.day())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// DATE (`20250702`), UTC (`20250702T140000Z`), zoned (`TZID=...`) or floating local time
+fn parse_date_time(property: &Property) -> Result<EventDateTime> {
+    let value = property.value.trim();
+
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .with_context(|| format!("Invalid date: {}", value))?;
+        return Ok(EventDateTime {
+            date_time: None,
+            date: Some(date.format("%Y-%m-%d").to_string()),
+            time_zone: None,
+        });
+    }
+
+    let (local, utc) = match value.strip_suffix('Z') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let naive = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+        .with_context(|| format!("Invalid date-time: {}", value))?;
+
+    Ok(match (utc, property.param("TZID")) {
+        (true, _) => EventDateTime {
+            date_time: Some(Utc.from_utc_datetime(&naive).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        },
+        // Google resolves the zone itself when the offset is omitted
+        (false, Some(tzid)) => EventDateTime {
+            date_time: Some(naive.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            date: None,
+            time_zone: Some(tzid.to_string()),
+        },
+        (false, None) => EventDateTime {
+            date_time: Some(
+                Local
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .with_context(|| format!("{} does not exist in the local time zone", naive))?
+                    .to_rfc3339(),
+            ),
+            date: None,
+            time_zone: None,
+        },
+    })
+}
+
+fn add_duration(start: &EventDateTime, duration: Duration) -> Result<EventDateTime> {
+    let mut end = start.clone();
+
+    if let Some(ref date) = start.date {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")?;
+        // All-day events move in whole days, at least one
+        let days = duration.num_days().max(1);
+        end.date = Some((date + Duration::days(days)).format("%Y-%m-%d").to_string());
+    } else if let Some(ref date_time) = start.date_time {
+        end.date_time = Some(match chrono::DateTime::parse_from_rfc3339(date_time) {
+            Ok(date_time) => (date_time + duration).to_rfc3339(),
+            // Zoned times are written without an offset
+            Err(_) => (NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M:%S")? + duration)
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string(),
+        });
+    }
+
+    Ok(end)
+}
+
+/// ISO 8601 durations as used by iCalendar, e.g. `PT1H30M`, `P1D`, `P2W`
+fn parse_duration(value: &str) -> Result<Duration> {
+    let body = value
+        .trim()
+        .trim_start_matches('+')
+        .strip_prefix('P')
+        .with_context(|| format!("Invalid duration: {}", value))?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in body.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: i64 = number
+                    .parse()
+                    .with_context(|| format!("Invalid duration: {}", value))?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => bail!("Invalid duration: {}", value),
+                };
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+/// Undo TEXT escaping: `\n`, `\,`, `\;` and `\\`
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => output.push('\n'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVITE: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:123@example.com\r\n\
+SUMMARY:Dentist\\, check-up\r\n\
+DESCRIPTION:Bring the forms\\nand insurance \r\n card\r\n\
+LOCATION:Main St. 4\r\n\
+DTSTART:20250702T140000Z\r\n\
+DTEND:20250702T150000Z\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_utc_event() {
+        let events = parse_events(INVITE).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.summary.as_deref(), Some("Dentist, check-up"));
+        assert_eq!(
+            event.description.as_deref(),
+            Some("Bring the forms\nand insurance card")
+        );
+        assert_eq!(event.location.as_deref(), Some("Main St. 4"));
+        assert_eq!(
+            event.start.date_time.as_deref(),
+            Some("2025-07-02T14:00:00+00:00")
+        );
+        assert_eq!(
+            event.end.date_time.as_deref(),
+            Some("2025-07-02T15:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_all_day_without_end() {
+        let ics = "BEGIN:VEVENT\nSUMMARY:Holiday\nDTSTART;VALUE=DATE:20250702\nEND:VEVENT\n";
+
+        let event = &parse_events(ics).unwrap()[0];
+
+        assert_eq!(event.start.date.as_deref(), Some("2025-07-02"));
+        assert_eq!(event.end.date.as_deref(), Some("2025-07-03"));
+    }
+
+    #[test]
+    fn test_parse_zoned_event_with_duration() {
+        let ics = "BEGIN:VEVENT\n\
+SUMMARY:Call\n\
+DTSTART;TZID=\"Europe/Berlin\":20250702T090000\n\
+DURATION:PT1H30M\n\
+END:VEVENT\n";
+
+        let event = &parse_events(ics).unwrap()[0];
+
+        assert_eq!(
+            event.start.date_time.as_deref(),
+            Some("2025-07-02T09:00:00")
+        );
+        assert_eq!(event.start.time_zone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(event.end.date_time.as_deref(), Some("2025-07-02T10:30:00"));
+        assert_eq!(event.end.time_zone.as_deref(), Some("Europe/Berlin"));
+    }
+
+    #[test]
+    fn test_parse_multiple_events_and_ignores_other_components() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VTIMEZONE\nTZID:Europe/Berlin\nEND:VTIMEZONE\n\
+BEGIN:VEVENT\nSUMMARY:One\nDTSTART:20250702T090000Z\nEND:VEVENT\n\
+BEGIN:VEVENT\nSUMMARY:Two\nDTSTART:20250703T090000Z\nEND:VEVENT\n\
+END:VCALENDAR\n";
+
+        let events = parse_events(ics).unwrap();
+
+        let summaries: Vec<_> = events.iter().map(|e| e.summary.as_deref()).collect();
+        assert_eq!(summaries, vec![Some("One"), Some("Two")]);
+        assert_eq!(events[0].end, events[0].start);
+    }
+
+    #[test]
+    fn test_write_event_round_trips() {
+        let mut event = parse_events(INVITE).unwrap().remove(0);
+        event.id = "abc123".to_string();
+        event.description = Some(format!("Bring: {}\nThanks, me", "x-ray; ".repeat(20)));
+        let stamp = Utc.with_ymd_and_hms(2025, 6, 1, 8, 0, 0).unwrap();
+
+        let written = write_event(&event, stamp).unwrap();
+
+        assert!(written.contains("UID:abc123@google.com\r\n"));
+        assert!(written.contains("DTSTAMP:20250601T080000Z\r\n"));
+        assert!(written
+            .lines()
+            .all(|line| line.trim_end_matches('\r').len() <= 75));
+        let parsed = parse_events(&written).unwrap().remove(0);
+        assert_eq!(parsed.summary, event.summary);
+        assert_eq!(parsed.description, event.description);
+        assert_eq!(parsed.location, event.location);
+        assert_eq!(
+            parsed.start.to_local().unwrap(),
+            event.start.to_local().unwrap()
+        );
+        assert_eq!(
+            parsed.end.to_local().unwrap(),
+            event.end.to_local().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_all_day_and_zoned_dates() {
+        let all_day = EventDateTime {
+            date_time: None,
+            date: Some("2025-07-02".to_string()),
+            time_zone: None,
+        };
+        let zoned = EventDateTime {
+            date_time: Some("2025-07-02T09:30:00".to_string()),
+            date: None,
+            time_zone: Some("Europe/Berlin".to_string()),
+        };
+
+        assert_eq!(
+            format_date_time("DTSTART", &all_day).unwrap(),
+            "DTSTART;VALUE=DATE:20250702"
+        );
+        assert_eq!(
+            format_date_time("DTSTART", &zoned).unwrap(),
+            "DTSTART;TZID=Europe/Berlin:20250702T093000"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_events("BEGIN:VEVENT\nSUMMARY:No start\nEND:VEVENT\n").is_err());
+        assert!(parse_events("BEGIN:VEVENT\nDTSTART:20250702T090000Z\n").is_err());
+        assert!(parse_events("BEGIN:VEVENT\nDTSTART:tomorrow\nEND:VEVENT\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let rule = parse_rule("FREQ=WEEKLY;INTERVAL=2;BYDAY=WE,MO;UNTIL=20251231").unwrap();
+
+        assert_eq!(rule.frequency, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Wed]);
+        assert_eq!(rule.until, parse_stamp("20251231T235959"));
+        assert!(parse_rule("FREQ=MONTHLY;BYDAY=2TU").is_err());
+        assert!(parse_rule("FREQ=MONTHLY;BYMONTHDAY=-1").is_err());
+        assert!(parse_rule("INTERVAL=2").is_err());
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let start = parse_stamp("20250131T090000").unwrap();
+        let last = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        let days = |rule: &str| -> Vec<String> {
+            occurrences(&parse_rule(rule).unwrap(), start, last)
+                .map(|at| at.format("%m-%d").to_string())
+                .collect()
+        };
+
+        // Friday, January 31: months without a 31st are skipped
+        assert_eq!(days("FREQ=MONTHLY"), vec!["01-31", "03-31", "05-31"]);
+        assert_eq!(
+            days("FREQ=WEEKLY;BYDAY=MO,FR;COUNT=4"),
+            vec!["01-31", "02-03", "02-07", "02-10"]
+        );
+        assert_eq!(
+            days("FREQ=DAILY;INTERVAL=10;UNTIL=20250220"),
+            vec!["01-31", "02-10", "02-20"]
+        );
+        // Never past `last`, however long the rule runs
+        assert_eq!(days("FREQ=YEARLY"), vec!["01-31"]);
+        assert_eq!(days("FREQ=DAILY").len(), 151);
+    }
+
+    #[test]
+    fn test_parse_occurrences_within_range() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VEVENT\nUID:standup\nSUMMARY:Standup\n\
+DTSTART:20200106T090000Z\nDTEND:20200106T091500Z\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE\nEXDATE:20250618T090000Z\nEND:VEVENT\n\
+BEGIN:VEVENT\nUID:standup\nSUMMARY:Standup (moved)\n\
+RECURRENCE-ID:20250616T090000Z\nDTSTART:20250616T110000Z\nEND:VEVENT\n\
+END:VCALENDAR\n";
+
+        let events = parse_occurrences(
+            ics,
+            "2025-06-15T12:00:00Z".parse().unwrap(),
+            "2025-06-26T12:00:00Z".parse().unwrap(),
+        )
+        .unwrap();
+
+        let found: Vec<(usize, &str, Option<&str>)> = events
+            .iter()
+            .map(|(index, event)| (*index, event.id.as_str(), event.end.date_time.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, "20250623T090000Z", Some("2025-06-23T09:15:00+00:00")),
+                (0, "20250625T090000Z", Some("2025-06-25T09:15:00+00:00")),
+                (1, "", Some("2025-06-16T11:00:00+00:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT1H30M").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("P1W2D").unwrap(), Duration::days(9));
+        assert!(parse_duration("1H").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use super::models::Event;
+use crate::auth::account;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        rowid INTEGER PRIMARY KEY,
+        calendar_id TEXT NOT NULL,
+        event_id TEXT NOT NULL,
+        start_ts INTEGER NOT NULL,
+        json TEXT NOT NULL,
+        UNIQUE (calendar_id, event_id)
+    );
+    CREATE INDEX IF NOT EXISTS events_start ON events (start_ts);
+    CREATE VIRTUAL TABLE IF NOT EXISTS events_fts
+        USING fts5(summary, description, location, attendees);
+";
+
+/// A SQLite copy of every event fetched so far, with full-text search over
+/// titles, descriptions, locations and attendees. It keeps events outside the
+/// window the TUI holds in memory, so old events stay searchable offline.
+pub struct EventIndex {
+    connection: Connection,
+}
+
+impl EventIndex {
+    /// The selected account's index, or None unless `enabled` (`--index`)
+    pub fn open(enabled: bool) -> Result<Option<Self>> {
+        if !enabled {
+            return Ok(None);
+        }
+
+        let dir = account::data_dir()?;
+        std::fs::create_dir_all(&dir).context("Failed to create data directory")?;
+        Self::open_at(&dir.join("events.db")).map(Some)
+    }
+
+    fn open_at(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Failed to open event index {}", path.display()))?;
+        Self::with_connection(connection)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection
+            .execute_batch(SCHEMA)
+            .context("Failed to create event index")?;
+        Ok(Self { connection })
+    }
+
+    /// Replace what is stored for `calendar_id` between `from` and `to` with
+    /// freshly fetched `events`, so events deleted on the server disappear too
+    pub fn store_range(
+        &mut self,
+        calendar_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        events: &[Event],
+    ) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute(
+            "DELETE FROM events_fts WHERE rowid IN
+                (SELECT rowid FROM events WHERE calendar_id = ?1 AND start_ts >= ?2 AND start_ts < ?3)",
+            params![calendar_id, from.timestamp(), to.timestamp()],
+        )?;
+        transaction.execute(
+            "DELETE FROM events WHERE calendar_id = ?1 AND start_ts >= ?2 AND start_ts < ?3",
+            params![calendar_id, from.timestamp(), to.timestamp()],
+        )?;
+
+        for event in events {
+            let Some(start) = event.start.to_local() else {
+                continue;
+            };
+            // An event starting before `from` may already be stored
+            transaction.execute(
+                "DELETE FROM events_fts WHERE rowid IN
+                    (SELECT rowid FROM events WHERE calendar_id = ?1 AND event_id = ?2)",
+                params![calendar_id, event.id],
+            )?;
+            transaction.execute(
+                "DELETE FROM events WHERE calendar_id = ?1 AND event_id = ?2",
+                params![calendar_id, event.id],
+            )?;
+
+            let json = serde_json::to_string(event).context("Failed to serialize event")?;
+            transaction.execute(
+                "INSERT INTO events (calendar_id, event_id, start_ts, json) VALUES (?1, ?2, ?3, ?4)",
+                params![calendar_id, event.id, start.timestamp(), json],
+            )?;
+            transaction.execute(
+                "INSERT INTO events_fts (rowid, summary, description, location, attendees)
+                    VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    transaction.last_insert_rowid(),
+                    event.summary,
+                    event.description,
+                    event.location,
+                    attendee_text(event),
+                ],
+            )?;
+        }
+
+        transaction.commit().context("Failed to update event index")
+    }
+
+    /// Events where every word of `query` starts a word of the title,
+    /// description, location or an attendee, soonest first
+    pub fn search(
+        &self,
+        query: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<Event>> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut statement = self.connection.prepare(
+            "SELECT e.calendar_id, e.json FROM events_fts f JOIN events e ON e.rowid = f.rowid
+                WHERE events_fts MATCH ?1 AND e.start_ts >= ?2 AND e.start_ts < ?3
+                ORDER BY e.start_ts LIMIT ?4",
+        )?;
+        let rows = statement.query_map(
+            params![fts_query, from.timestamp(), to.timestamp(), limit as i64],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?;
+
+        rows.map(|row| {
+            let (calendar_id, json) = row?;
+            read_event(calendar_id, &json)
+        })
+        .collect()
+    }
+
+    /// Every stored event starting between `from` and `to`, soonest first,
+    /// e.g. to show a range again without fetching it
+    pub fn events_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Event>> {
+        let mut statement = self.connection.prepare(
+            "SELECT calendar_id, json FROM events
+                WHERE start_ts >= ?1 AND start_ts < ?2 ORDER BY start_ts",
+        )?;
+        let rows = statement.query_map(params![from.timestamp(), to.timestamp()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        rows.map(|row| {
+            let (calendar_id, json) = row?;
+            read_event(calendar_id, &json)
+        })
+        .collect()
+    }
+}
+
+fn read_event(calendar_id: String, json: &str) -> Result<Event> {
+    let mut event: Event = serde_json::from_str(json).context("Corrupt event in index")?;
+    event.calendar_id = Some(calendar_id);
+    Ok(event)
+}
+
+fn attendee_text(event: &Event) -> String {
+    event
+        .attendees
+        .iter()
+        .flatten()
+        .flat_map(|attendee| {
+            [
+                Some(attendee.email.as_str()),
+                attendee.display_name.as_deref(),
+            ]
+        })
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Each word as a quoted prefix term, all required; None without words
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::{Attendee, EventDateTime};
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn event(id: &str, summary: &str, start: &str) -> Event {
+        let time = |time: &str| EventDateTime {
+            date_time: Some(at(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            id: id.to_string(),
+            ..Event::test(summary, time(start), time(start))
+        }
+    }
+
+    fn index() -> EventIndex {
+        EventIndex::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn ids(events: &[Event]) -> Vec<&str> {
+        events.iter().map(|event| event.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_matches_word_prefixes_in_any_field() {
+        let mut index = index();
+        let mut review = event("review", "Design review", "16T10:00");
+        review.attendees = Some(vec![Attendee {
+            email: "ana@example.com".to_string(),
+            display_name: Some("Ana Lima".to_string()),
+            response_status: None,
+            optional: None,
+            is_self: None,
+            comment: None,
+        }]);
+        let mut lunch = event("lunch", "Lunch", "16T12:00");
+        lunch.description = Some("Discuss the design doc".to_string());
+        index
+            .store_range("me", at("01T00:00"), at("30T00:00"), &[lunch, review])
+            .unwrap();
+
+        let results = index
+            .search("desig", at("01T00:00"), at("30T00:00"), 10)
+            .unwrap();
+        assert_eq!(ids(&results), vec!["review", "lunch"]);
+        assert_eq!(results[0].calendar_id.as_deref(), Some("me"));
+
+        let results = index
+            .search("design lima", at("01T00:00"), at("30T00:00"), 10)
+            .unwrap();
+        assert_eq!(ids(&results), vec!["review"]);
+
+        assert!(index
+            .search("design", at("17T00:00"), at("30T00:00"), 10)
+            .unwrap()
+            .is_empty());
+        assert!(index
+            .search("  ", at("01T00:00"), at("30T00:00"), 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_store_range_replaces_the_range() {
+        let mut index = index();
+        index
+            .store_range(
+                "me",
+                at("01T00:00"),
+                at("30T00:00"),
+                &[
+                    event("old", "Standup", "10T09:00"),
+                    event("kept", "Standup", "20T09:00"),
+                ],
+            )
+            .unwrap();
+
+        // Refetching the first half: "old" was deleted on the server, "new" is renamed
+        index
+            .store_range(
+                "me",
+                at("01T00:00"),
+                at("15T00:00"),
+                &[event("new", "Standup moved", "11T09:00")],
+            )
+            .unwrap();
+
+        let results = index
+            .search("standup", at("01T00:00"), at("30T00:00"), 10)
+            .unwrap();
+        assert_eq!(ids(&results), vec!["new", "kept"]);
+    }
+
+    #[test]
+    fn test_store_range_is_per_calendar() {
+        let mut index = index();
+        index
+            .store_range(
+                "me",
+                at("01T00:00"),
+                at("30T00:00"),
+                &[event("a", "Standup", "10T09:00")],
+            )
+            .unwrap();
+        index
+            .store_range("team", at("01T00:00"), at("30T00:00"), &[])
+            .unwrap();
+
+        let results = index
+            .search("standup", at("01T00:00"), at("30T00:00"), 10)
+            .unwrap();
+        assert_eq!(ids(&results), vec!["a"]);
+    }
+
+    #[test]
+    fn test_events_between() {
+        let mut index = index();
+        index
+            .store_range(
+                "me",
+                at("01T00:00"),
+                at("30T00:00"),
+                &[
+                    event("late", "Review", "20T09:00"),
+                    event("early", "Standup", "10T09:00"),
+                ],
+            )
+            .unwrap();
+        index
+            .store_range(
+                "team",
+                at("01T00:00"),
+                at("30T00:00"),
+                &[event("offsite", "Offsite", "12T09:00")],
+            )
+            .unwrap();
+
+        let events = index
+            .events_between(at("01T00:00"), at("15T00:00"))
+            .unwrap();
+
+        assert_eq!(ids(&events), vec!["early", "offsite"]);
+        assert_eq!(events[1].calendar_id.as_deref(), Some("team"));
+    }
+
+    #[test]
+    fn test_fts_query_quotes_words() {
+        assert_eq!(
+            fts_query("design \"doc\"").as_deref(),
+            Some("\"design\"* \"\"\"doc\"\"\"*")
+        );
+        assert_eq!(fts_query(""), None);
+    }
+}
@@ -0,0 +1,346 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::ics;
+use super::models::Event;
+
+/// A vdir collection from khal's configuration: a directory of .ics files
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collection {
+    pub name: String,
+    pub path: PathBuf,
+    pub color: Option<String>,
+}
+
+impl Collection {
+    /// The events of the collection overlapping `time_min..time_max`, each
+    /// with its file name as id. Recurring events are expanded for the range
+    /// only, their occurrences' ids suffixed with their start; files that
+    /// fail to parse are skipped.
+    pub fn load_events(
+        &self,
+        time_min: DateTime<Utc>,
+        time_max: DateTime<Utc>,
+    ) -> Result<Vec<Event>> {
+        let entries = fs::read_dir(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+
+        let mut events = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("ics") {
+                continue;
+            }
+            let parsed = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| ics::parse_occurrences(&contents, time_min, time_max));
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    tracing::warn!(file = %path.display(), "Skipping unreadable event: {:#}", e);
+                    continue;
+                }
+            };
+
+            let stem = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            for (index, mut event) in parsed {
+                let id = match index {
+                    0 => stem.clone(),
+                    _ => format!("{}-{}", stem, index),
+                };
+                event.id = if event.id.is_empty() {
+                    id
+                } else {
+                    format!("{}_{}", id, event.id)
+                };
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+/// khal's configuration file, where it exists
+pub fn config_path() -> Option<PathBuf> {
+    let candidates = [
+        dirs::config_dir().map(|dir| dir.join("khal").join("config")),
+        dirs::home_dir().map(|dir| dir.join(".khal").join("khal.conf")),
+    ];
+    candidates.into_iter().flatten().find(|path| path.is_file())
+}
+
+/// The collections of the khal (or vdirsyncer-fed) setup on this machine
+pub fn discover() -> Result<Vec<Collection>> {
+    let path = config_path().context("No khal configuration found")?;
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let home = dirs::home_dir().unwrap_or_default();
+    parse_config(&contents, &home)
+        .with_context(|| format!("Invalid khal config {}", path.display()))
+}
+
+/// The `[calendars]` section of khal's configobj-style config: one
+/// `[[name]]` subsection per calendar with a `path`, or with
+/// `type = discover` a `path` glob whose last component matches directories
+fn parse_config(contents: &str, home: &Path) -> Result<Vec<Collection>> {
+    let mut collections = Vec::new();
+    let mut in_calendars = false;
+    let mut current: Option<(String, Vec<(String, String)>)> = None;
+
+    let mut finish = |current: Option<(String, Vec<(String, String)>)>| -> Result<()> {
+        if let Some((name, keys)) = current {
+            collections.extend(section_collections(&name, &keys, home)?);
+        }
+        Ok(())
+    };
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let depth = line.chars().take_while(|c| *c == '[').count();
+            let name = line.trim_matches(['[', ']']).trim().to_string();
+            if depth == 1 {
+                finish(current.take())?;
+                in_calendars = name == "calendars";
+            } else if depth == 2 && in_calendars {
+                finish(current.take())?;
+                current = Some((name, Vec::new()));
+            }
+            continue;
+        }
+
+        if let (Some((_, keys)), Some((key, value))) = (current.as_mut(), line.split_once('=')) {
+            let value = value.trim().trim_matches(['"', '\'']);
+            keys.push((key.trim().to_string(), value.to_string()));
+        }
+    }
+    finish(current)?;
+
+    Ok(collections)
+}
+
+fn section_collections(
+    name: &str,
+    keys: &[(String, String)],
+    home: &Path,
+) -> Result<Vec<Collection>> {
+    let get = |key: &str| {
+        keys.iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    };
+    let path = expand_home(
+        get("path").with_context(|| format!("Calendar \"{}\" has no path", name))?,
+        home,
+    );
+    let color = get("color").map(str::to_string);
+
+    if get("type") != Some("discover") {
+        return Ok(vec![Collection {
+            name: name.to_string(),
+            color: color.or_else(|| read_meta(&path, "color")),
+            path,
+        }]);
+    }
+
+    // e.g. ~/.calendars/*: every directory matching the last component
+    let pattern = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Ok(Vec::new());
+    };
+
+    let mut collections: Vec<Collection> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|dir| glob_match(&pattern, &dir.to_string_lossy()))
+        })
+        .map(|path| Collection {
+            name: read_meta(&path, "displayname").unwrap_or_else(|| {
+                path.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            }),
+            color: color.clone().or_else(|| read_meta(&path, "color")),
+            path,
+        })
+        .collect();
+    collections.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(collections)
+}
+
+/// vdirsyncer stores collection metadata in files named after the property
+fn read_meta(dir: &Path, name: &str) -> Option<String> {
+    let value = fs::read_to_string(dir.join(name)).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn expand_home(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None if path == "~" => home.to_path_buf(),
+        None => PathBuf::from(path),
+    }
+}
+
+/// `*` matches any run of characters; everything else literally
+fn glob_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|i| name.is_char_boundary(*i))
+                .any(|i| glob_match(rest, &name[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_calendars() {
+        let config = r#"
+[calendars]
+
+  [[work]]
+  path = ~/.calendars/work
+  color = dark green
+
+  [[home]]
+  path = "/srv/cal/home"
+  readonly = True
+
+[locale]
+timeformat = %H:%M
+"#;
+
+        let collections = parse_config(config, Path::new("/home/me")).unwrap();
+
+        assert_eq!(
+            collections,
+            vec![
+                Collection {
+                    name: "work".to_string(),
+                    path: PathBuf::from("/home/me/.calendars/work"),
+                    color: Some("dark green".to_string()),
+                },
+                Collection {
+                    name: "home".to_string(),
+                    path: PathBuf::from("/srv/cal/home"),
+                    color: None,
+                },
+            ]
+        );
+        assert!(parse_config("[calendars]\n[[broken]]\ncolor = red\n", Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn test_parse_config_discovers_collections() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["personal", "team", "notes.txt"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        fs::write(dir.path().join("team/displayname"), "Team\n").unwrap();
+        fs::write(dir.path().join("team/color"), "#ff0000").unwrap();
+        let config = format!(
+            "[calendars]\n[[google]]\ntype = discover\npath = {}/*\n",
+            dir.path().display()
+        );
+
+        let collections = parse_config(&config, Path::new("/home/me")).unwrap();
+
+        let names: Vec<&str> = collections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Team", "notes.txt", "personal"]);
+        assert_eq!(collections[0].color.as_deref(), Some("#ff0000"));
+    }
+
+    #[test]
+    fn test_load_events_in_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let event = |date: &str| {
+            format!(
+                "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:On {}\r\nDTSTART;VALUE=DATE:{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+                date, date
+            )
+        };
+        fs::write(dir.path().join("a.ics"), event("20250616")).unwrap();
+        fs::write(dir.path().join("b.ics"), event("20250901")).unwrap();
+        fs::write(dir.path().join("c.ics"), "garbage").unwrap();
+        fs::write(dir.path().join("displayname"), "Mine").unwrap();
+        let collection = Collection {
+            name: "mine".to_string(),
+            path: dir.path().to_path_buf(),
+            color: None,
+        };
+
+        let events = collection
+            .load_events(
+                "2025-06-01T00:00:00Z".parse().unwrap(),
+                "2025-07-01T00:00:00Z".parse().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "a");
+        assert_eq!(events[0].summary.as_deref(), Some("On 20250616"));
+    }
+
+    #[test]
+    fn test_load_events_expands_recurring_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("gym.ics"),
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Gym\r\nDTSTART;VALUE=DATE:20100601\r\nRRULE:FREQ=MONTHLY\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let collection = Collection {
+            name: "mine".to_string(),
+            path: dir.path().to_path_buf(),
+            color: None,
+        };
+
+        let events = collection
+            .load_events(
+                "2025-06-15T00:00:00Z".parse().unwrap(),
+                "2025-08-15T00:00:00Z".parse().unwrap(),
+            )
+            .unwrap();
+
+        let ids: Vec<&str> = events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, vec!["gym_20250701", "gym_20250801"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "work"));
+        assert!(glob_match("cal_*", "cal_work"));
+        assert!(!glob_match("cal_*", "work"));
+        assert!(glob_match("a*b*c", "a-b-c"));
+        assert!(glob_match("work", "work"));
+    }
+}
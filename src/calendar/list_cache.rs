@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::client::CalendarClient;
+use super::models::Calendar;
+use crate::auth::account;
+
+/// How long the cached calendar list is used before it is fetched again
+const TTL: Duration = Duration::hours(1);
+
+/// The calendar list as last fetched; it rarely changes, so routine refreshes
+/// reuse it instead of paging through `calendarList` every time
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedList {
+    fetched_at: DateTime<Utc>,
+    calendars: Vec<Calendar>,
+}
+
+/// The account's calendars, from the cache while it is younger than `TTL`.
+/// The cache only saves requests: failing to read or write it never fails.
+pub async fn calendars(client: &mut CalendarClient) -> Result<Vec<Calendar>> {
+    let path = get_storage_path()?;
+    if let Some(calendars) = load_fresh(&path, Utc::now()) {
+        return Ok(calendars);
+    }
+
+    let calendars = client.list_calendars().await?;
+    if let Err(e) = save_to(&path, &calendars, Utc::now()) {
+        tracing::warn!("Failed to cache calendar list: {:#}", e);
+    }
+    Ok(calendars)
+}
+
+/// Fetch the list again next time, e.g. on an explicit refresh or after
+/// subscribing to a calendar
+pub fn invalidate() {
+    let Ok(path) = get_storage_path() else {
+        return;
+    };
+    if path.exists() {
+        if let Err(e) = fs::remove_file(&path) {
+            tracing::warn!("Failed to delete cached calendar list: {:#}", e);
+        }
+    }
+}
+
+fn load_fresh(path: &Path, now: DateTime<Utc>) -> Option<Vec<Calendar>> {
+    let json = fs::read_to_string(path).ok()?;
+    let cached: CachedList = match serde_json::from_str(&json) {
+        Ok(cached) => cached,
+        Err(e) => {
+            tracing::warn!("Ignoring invalid calendar list cache: {}", e);
+            return None;
+        }
+    };
+    (now - cached.fetched_at < TTL).then_some(cached.calendars)
+}
+
+fn save_to(path: &Path, calendars: &[Calendar], now: DateTime<Utc>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create config directory")?;
+    }
+
+    let cached = CachedList {
+        fetched_at: now,
+        calendars: calendars.to_vec(),
+    };
+    let json = serde_json::to_string(&cached).context("Failed to serialize calendar list")?;
+    fs::write(path, json).context("Failed to write calendar list cache")?;
+
+    Ok(())
+}
+
+/// Calendars differ per account
+fn get_storage_path() -> Result<PathBuf> {
+    Ok(account::data_dir()?.join("calendar_list.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(id: &str) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: id.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_cached_list_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calendar_list.json");
+        let fetched_at: DateTime<Utc> = "2025-06-16T09:00:00Z".parse().unwrap();
+
+        save_to(&path, &[calendar("work")], fetched_at).unwrap();
+
+        let fresh = load_fresh(&path, fetched_at + Duration::minutes(59)).unwrap();
+        assert_eq!(fresh, vec![calendar("work")]);
+        assert!(load_fresh(&path, fetched_at + TTL).is_none());
+    }
+
+    #[test]
+    fn test_missing_or_invalid_cache_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calendar_list.json");
+        assert!(load_fresh(&path, Utc::now()).is_none());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load_fresh(&path, Utc::now()).is_none());
+    }
+}
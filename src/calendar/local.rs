@@ -0,0 +1,115 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use super::khal::Collection;
+use super::models::{Calendar, Event};
+use super::tasks;
+use super::text::TextCalendar;
+
+/// Events kept in local files, shown as read-only calendars beside Google's
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// A khal/vdirsyncer collection of .ics files
+    Vdir(Collection),
+    /// A plain-text file with one event per line
+    Text(TextCalendar),
+    /// Taskwarrior's pending tasks with a due date
+    Tasks,
+}
+
+impl Source {
+    /// Prefixed by kind, so local ids never clash with Google's
+    pub fn id(&self) -> String {
+        match self {
+            Source::Vdir(collection) => format!("khal:{}", collection.name),
+            Source::Text(text) => format!("text:{}", text.name()),
+            Source::Tasks => TASKS_ID.to_string(),
+        }
+    }
+
+    pub fn calendar(&self) -> Calendar {
+        let (name, description, color) = match self {
+            Source::Vdir(collection) => (
+                collection.name.clone(),
+                collection.path.display().to_string(),
+                collection.color.clone(),
+            ),
+            Source::Text(text) => (text.name(), text.path.display().to_string(), None),
+            Source::Tasks => (
+                "Tasks".to_string(),
+                "task export".to_string(),
+                Some(TASKS_COLOR.to_string()),
+            ),
+        };
+        calendar(self.id(), name, description, color)
+    }
+
+    /// The events overlapping `time_min..time_max`
+    pub fn load_events(
+        &self,
+        time_min: DateTime<Utc>,
+        time_max: DateTime<Utc>,
+    ) -> Result<Vec<Event>> {
+        match self {
+            Source::Vdir(collection) => collection.load_events(time_min, time_max),
+            Source::Text(text) => text.load_events(time_min, time_max),
+            Source::Tasks => tasks::load_events(time_min, time_max),
+        }
+    }
+}
+
+/// The calendar of `Source::Tasks`, whose events the TUI draws as tasks
+const TASKS_ID: &str = "taskwarrior";
+const TASKS_COLOR: &str = "#b39ddb";
+
+pub fn is_task_calendar(calendar_id: &str) -> bool {
+    calendar_id == TASKS_ID
+}
+
+fn calendar(id: String, name: String, description: String, color: Option<String>) -> Calendar {
+    Calendar {
+        id,
+        summary: name,
+        primary: false,
+        time_zone: iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string()),
+        access_role: "reader".to_string(),
+        background_color: color,
+        description: Some(description),
+        default_reminders: Vec::new(),
+        selected: true,
+    }
+}
+
+pub(super) fn overlaps(event: &Event, time_min: DateTime<Utc>, time_max: DateTime<Utc>) -> bool {
+    match (event.start.to_local(), event.end.to_local()) {
+        (Some(start), Some(end)) => start < time_max && (end > time_min || start >= time_min),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_source_calendars_are_read_only_and_prefixed() {
+        let vdir = Source::Vdir(Collection {
+            name: "home".to_string(),
+            path: PathBuf::from("/home/me/.calendars/home"),
+            color: Some("#ff0000".to_string()),
+        });
+        let text = Source::Text(TextCalendar {
+            path: PathBuf::from("/home/me/notes/personal.txt"),
+        });
+
+        let calendar = vdir.calendar();
+        assert_eq!(calendar.id, "khal:home");
+        assert_eq!(calendar.access_role, "reader");
+        assert_eq!(calendar.background_color.as_deref(), Some("#ff0000"));
+        assert_eq!(text.calendar().id, "text:personal");
+        assert_eq!(text.calendar().summary, "personal");
+        assert!(is_task_calendar(&Source::Tasks.calendar().id));
+        assert!(!is_task_calendar(&text.id()));
+    }
+}
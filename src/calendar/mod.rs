@@ -1,2 +1,13 @@
+pub mod batch;
 pub mod client;
+pub mod conference;
+pub mod ics;
+pub mod index;
+pub mod khal;
+pub mod list_cache;
+pub mod local;
 pub mod models;
+pub mod outlook;
+pub mod queue;
+pub mod tasks;
+pub mod text;
@@ -1,4 +1,7 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Calendar {
@@ -13,41 +16,273 @@ pub struct Calendar {
     #[serde(rename = "backgroundColor")]
     pub background_color: Option<String>,
     pub description: Option<String>,
+    /// Reminders for events of this calendar that do not override them
+    #[serde(rename = "defaultReminders", default)]
+    pub default_reminders: Vec<Reminder>,
+    /// Shown in Google Calendar's own calendar list; Google omits it when false
+    #[serde(default)]
+    pub selected: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Event {
+    /// Empty for events that have not been created on the server yet
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
     pub start: EventDateTime,
     pub end: EventDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
-    #[serde(rename = "htmlLink")]
+    #[serde(rename = "htmlLink", skip_serializing_if = "Option::is_none")]
     pub html_link: Option<String>,
+    /// Google Meet link of the event's conference, if any
+    #[serde(rename = "hangoutLink", skip_serializing_if = "Option::is_none")]
+    pub hangout_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attendees: Option<Vec<Attendee>>,
     #[serde(skip)]
     pub calendar_id: Option<String>,
+    /// Last modification time (RFC3339), used to detect remote edits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminders: Option<Reminders>,
+    /// Files attached to the event, usually from Google Drive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+    /// Conference (Meet, or an add-on like Zoom) with its join links
+    #[serde(rename = "conferenceData", skip_serializing_if = "Option::is_none")]
+    pub conference_data: Option<ConferenceData>,
+    /// Shared by the copies of one event in several calendars, e.g. an invite
+    /// seen on both a personal and a shared calendar
+    #[serde(rename = "iCalUID", skip_serializing_if = "Option::is_none")]
+    pub ical_uid: Option<String>,
+}
+
+impl Event {
+    /// True when the authenticated user declined this invitation
+    pub fn is_declined_by_self(&self) -> bool {
+        self.attendees.iter().flatten().any(|attendee| {
+            attendee.is_self == Some(true)
+                && attendee.response_status.as_deref() == Some("declined")
+        })
+    }
+
+    /// The reminders that apply to this event: its overrides, or the
+    /// calendar's defaults when it uses them (also when it says nothing)
+    pub fn effective_reminders(&self, calendar: Option<&Calendar>) -> Vec<Reminder> {
+        match self.reminders {
+            Some(ref reminders) if !reminders.use_default => {
+                reminders.overrides.clone().unwrap_or_default()
+            }
+            _ => calendar
+                .map(|calendar| calendar.default_reminders.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether `other` is this event's copy in another calendar: the same
+    /// iCalUID (or id, without one) at the same start. Recurring instances
+    /// share their iCalUID, hence the start.
+    pub fn is_copy_of(&self, other: &Event) -> bool {
+        let same_event = match (&self.ical_uid, &other.ical_uid) {
+            (Some(uid), Some(other_uid)) => uid == other_uid,
+            _ => !self.id.is_empty() && self.id == other.id,
+        };
+        same_event
+            && self.calendar_id != other.calendar_id
+            && self.start.to_local() == other.start.to_local()
+    }
+
+    /// How a day's events are ordered: all-day ones first, then by start
+    /// (the API's `orderBy=startTime` order)
+    pub fn start_order(&self) -> (bool, Option<DateTime<Local>>) {
+        (!self.start.is_all_day(), self.start.to_local())
+    }
+}
+
+#[cfg(test)]
+impl Event {
+    /// An event for tests, with `summary` also as its id and every other
+    /// field empty; tests set the fields they need with `..Event::test(..)`
+    pub fn test(summary: &str, start: EventDateTime, end: EventDateTime) -> Self {
+        Self {
+            id: summary.to_string(),
+            summary: Some(summary.to_string()),
+            description: None,
+            location: None,
+            start,
+            end,
+            status: None,
+            html_link: None,
+            hangout_link: None,
+            attendees: None,
+            calendar_id: None,
+            updated: None,
+            etag: None,
+            reminders: None,
+            attachments: None,
+            conference_data: None,
+            ical_uid: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Reminders {
+    #[serde(rename = "useDefault")]
+    pub use_default: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<Vec<Reminder>>,
+}
+
+/// A reminder `minutes` before the event, delivered by `method`
+/// (`popup` or `email`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Reminder {
+    pub method: String,
+    pub minutes: u32,
+}
+
+impl Reminder {
+    pub fn is_popup(&self) -> bool {
+        self.method == "popup"
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConferenceData {
+    #[serde(rename = "entryPoints", default)]
+    pub entry_points: Vec<EntryPoint>,
+}
+
+/// A way to join a conference: `video`, `phone`, `sip` or `more`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntryPoint {
+    #[serde(rename = "entryPointType")]
+    pub entry_point_type: String,
+    pub uri: String,
+}
+
+/// A file attached to an event; `file_url` opens it (in Drive for Drive files)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    #[serde(rename = "fileUrl")]
+    pub file_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl Attachment {
+    /// The title, or the URL for attachments without one
+    pub fn name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.file_url)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EventDateTime {
-    #[serde(rename = "dateTime")]
+    #[serde(rename = "dateTime", skip_serializing_if = "Option::is_none")]
     pub date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<String>,
-    #[serde(rename = "timeZone")]
+    #[serde(rename = "timeZone", skip_serializing_if = "Option::is_none")]
     pub time_zone: Option<String>,
 }
 
+impl EventDateTime {
+    /// A whole day, as all-day events start and (exclusively) end
+    pub fn from_date(date: NaiveDate) -> Self {
+        Self {
+            date_time: None,
+            date: Some(date.format("%Y-%m-%d").to_string()),
+            time_zone: None,
+        }
+    }
+
+    /// A wall-clock time in the local time zone
+    pub fn from_local(local: NaiveDateTime) -> Result<Self> {
+        let date_time = Local
+            .from_local_datetime(&local)
+            .earliest()
+            .with_context(|| format!("{} does not exist in the local time zone", local))?;
+
+        Ok(Self {
+            date_time: Some(date_time.to_rfc3339()),
+            date: None,
+            time_zone: None,
+        })
+    }
+
+    /// The instant in local time; all-day dates map to local midnight
+    pub fn to_local(&self) -> Option<DateTime<Local>> {
+        if let Some(ref date_time) = self.date_time {
+            return DateTime::parse_from_rfc3339(date_time)
+                .ok()
+                .map(|dt| dt.with_timezone(&Local));
+        }
+
+        let date = NaiveDate::parse_from_str(self.date.as_deref()?, "%Y-%m-%d").ok()?;
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+    }
+
+    pub fn is_all_day(&self) -> bool {
+        self.date_time.is_none() && self.date.is_some()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Attendee {
     pub email: String,
-    #[serde(rename = "displayName")]
+    #[serde(rename = "displayName", skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
-    #[serde(rename = "responseStatus")]
+    #[serde(rename = "responseStatus", skip_serializing_if = "Option::is_none")]
     pub response_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub optional: Option<bool>,
+    /// Marks the attendee entry of the authenticated user
+    #[serde(rename = "self", skip_serializing_if = "Option::is_none")]
+    pub is_self: Option<bool>,
+    /// The attendee's note accompanying their response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Partial event body for `events.patch`: only the fields that are set are sent,
+/// so untouched fields keep their server-side values
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EventPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<EventDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<EventDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendees: Option<Vec<Attendee>>,
+}
+
+/// A person from the user's contacts who has an email address
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contact {
+    pub name: Option<String>,
+    pub email: String,
 }
 
 // Private response wrappers for API responses
@@ -65,6 +300,87 @@ pub(crate) struct EventsListResponse {
     pub next_page_token: Option<String>,
 }
 
+/// `people:searchContacts` and `otherContacts:search`
+#[derive(Debug, Deserialize)]
+pub(crate) struct ContactSearchResponse {
+    #[serde(default)]
+    pub results: Vec<ContactSearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ContactSearchResult {
+    pub person: Person,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Person {
+    #[serde(default)]
+    pub names: Vec<PersonName>,
+    #[serde(rename = "emailAddresses", default)]
+    pub email_addresses: Vec<PersonEmail>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PersonName {
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PersonEmail {
+    pub value: String,
+}
+
+impl Person {
+    /// One contact per email address of the person
+    pub fn into_contacts(self) -> Vec<Contact> {
+        let name = self.names.into_iter().find_map(|name| name.display_name);
+        self.email_addresses
+            .into_iter()
+            .map(|email| Contact {
+                name: name.clone(),
+                email: email.value,
+            })
+            .collect()
+    }
+}
+
+/// A busy interval from the freebusy API
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TimePeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FreeBusyRequest {
+    #[serde(rename = "timeMin")]
+    pub time_min: DateTime<Utc>,
+    #[serde(rename = "timeMax")]
+    pub time_max: DateTime<Utc>,
+    pub items: Vec<FreeBusyItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FreeBusyItem {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FreeBusyResponse {
+    #[serde(default)]
+    pub calendars: HashMap<String, FreeBusyCalendar>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FreeBusyCalendar {
+    #[serde(default)]
+    pub busy: Vec<TimePeriod>,
+    /// Set instead of `busy` when the calendar could not be queried
+    #[serde(default)]
+    pub errors: Vec<serde_json::Value>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +398,7 @@ mod tests {
 
         assert_eq!(calendar.id, "primary");
         assert_eq!(calendar.summary, "My Calendar");
-        assert_eq!(calendar.primary, false); // default
+        assert!(!calendar.primary); // default
         assert_eq!(calendar.time_zone, "America/New_York");
         assert_eq!(calendar.access_role, "owner");
         assert_eq!(calendar.background_color, None);
@@ -105,7 +421,7 @@ mod tests {
 
         assert_eq!(calendar.id, "calendar123");
         assert_eq!(calendar.summary, "Work Calendar");
-        assert_eq!(calendar.primary, true);
+        assert!(calendar.primary);
         assert_eq!(calendar.time_zone, "Europe/London");
         assert_eq!(calendar.access_role, "writer");
         assert_eq!(calendar.background_color, Some("#0088aa".to_string()));
@@ -203,6 +519,8 @@ mod tests {
         assert_eq!(event.html_link, None);
         assert_eq!(event.attendees, None);
         assert_eq!(event.calendar_id, None);
+        assert_eq!(event.updated, None);
+        assert_eq!(event.etag, None);
     }
 
     #[test]
@@ -222,6 +540,7 @@ mod tests {
             },
             "status": "confirmed",
             "htmlLink": "https://calendar.google.com/event?eid=abc123",
+            "hangoutLink": "https://meet.google.com/abc-defg-hij",
             "attendees": [
                 {
                     "email": "alice@example.com",
@@ -248,6 +567,10 @@ mod tests {
             event.html_link,
             Some("https://calendar.google.com/event?eid=abc123".to_string())
         );
+        assert_eq!(
+            event.hangout_link.as_deref(),
+            Some("https://meet.google.com/abc-defg-hij")
+        );
 
         let attendees = event.attendees.unwrap();
         assert_eq!(attendees.len(), 2);
@@ -407,4 +730,197 @@ mod tests {
         // Verify assignment worked
         assert_eq!(event.calendar_id, Some("cal123".to_string()));
     }
+
+    #[test]
+    fn test_event_deserialize_sync_metadata() {
+        let json = r#"{
+            "id": "event789",
+            "etag": "\"3181161784712000\"",
+            "updated": "2025-11-20T09:15:00.000Z",
+            "start": { "date": "2025-11-28" },
+            "end": { "date": "2025-11-29" }
+        }"#;
+
+        let event: Event = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(event.etag, Some("\"3181161784712000\"".to_string()));
+        assert_eq!(event.updated, Some("2025-11-20T09:15:00.000Z".to_string()));
+    }
+
+    #[test]
+    fn test_event_deserialize_attachments() {
+        let json = r#"{
+            "id": "event790",
+            "start": { "date": "2025-11-28" },
+            "end": { "date": "2025-11-29" },
+            "attachments": [
+                {
+                    "fileUrl": "https://drive.google.com/open?id=abc",
+                    "title": "Agenda",
+                    "mimeType": "application/vnd.google-apps.document",
+                    "fileId": "abc"
+                },
+                { "fileUrl": "https://example.com/slides.pdf" }
+            ]
+        }"#;
+
+        let event: Event = serde_json::from_str(json).expect("Failed to deserialize");
+        let attachments = event.attachments.unwrap();
+
+        assert_eq!(attachments[0].name(), "Agenda");
+        assert_eq!(
+            attachments[0].mime_type.as_deref(),
+            Some("application/vnd.google-apps.document")
+        );
+        assert_eq!(attachments[1].name(), "https://example.com/slides.pdf");
+    }
+
+    #[test]
+    fn test_new_event_serializes_without_empty_fields() {
+        let at = |date_time: &str| EventDateTime {
+            date_time: Some(date_time.to_string()),
+            date: None,
+            time_zone: None,
+        };
+        let event = Event {
+            id: String::new(),
+            calendar_id: Some("primary".to_string()),
+            ..Event::test(
+                "Dentist",
+                at("2025-07-02T14:00:00+02:00"),
+                at("2025-07-02T15:00:00+02:00"),
+            )
+        };
+
+        let json = serde_json::to_value(&event).expect("Failed to serialize");
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "summary": "Dentist",
+                "start": { "dateTime": "2025-07-02T14:00:00+02:00" },
+                "end": { "dateTime": "2025-07-02T15:00:00+02:00" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_event_patch_only_serializes_set_fields() {
+        let patch = EventPatch {
+            summary: Some("Renamed".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&patch).expect("Failed to serialize");
+
+        assert_eq!(json, serde_json::json!({ "summary": "Renamed" }));
+    }
+
+    #[test]
+    fn test_event_declined_by_self() {
+        let json = r#"{
+            "id": "evt1",
+            "start": {"dateTime": "2025-06-15T10:00:00Z"},
+            "end": {"dateTime": "2025-06-15T11:00:00Z"},
+            "attendees": [
+                {"email": "boss@example.com", "responseStatus": "declined"},
+                {"email": "me@example.com", "responseStatus": "declined", "self": true}
+            ]
+        }"#;
+
+        let mut event: Event = serde_json::from_str(json).expect("Failed to deserialize");
+        assert!(event.is_declined_by_self());
+
+        event.attendees.as_mut().unwrap()[1].response_status = Some("accepted".to_string());
+        assert!(!event.is_declined_by_self());
+    }
+
+    #[test]
+    fn test_event_datetime_to_local() {
+        let timed = EventDateTime {
+            date_time: Some("2025-06-15T10:00:00Z".to_string()),
+            date: None,
+            time_zone: None,
+        };
+        assert_eq!(
+            timed.to_local().unwrap().with_timezone(&chrono::Utc),
+            chrono::Utc.with_ymd_and_hms(2025, 6, 15, 10, 0, 0).unwrap()
+        );
+        assert!(!timed.is_all_day());
+
+        let all_day = EventDateTime {
+            date_time: None,
+            date: Some("2025-06-15".to_string()),
+            time_zone: None,
+        };
+        let local = all_day.to_local().unwrap();
+        assert_eq!(
+            local.naive_local(),
+            NaiveDate::from_ymd_opt(2025, 6, 15)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        );
+        assert!(all_day.is_all_day());
+    }
+
+    #[test]
+    fn test_free_busy_response_deserialize() {
+        let json = r#"{
+            "kind": "calendar#freeBusy",
+            "calendars": {
+                "me@example.com": {
+                    "busy": [{"start": "2025-06-16T09:00:00Z", "end": "2025-06-16T10:00:00Z"}]
+                },
+                "other@example.com": {
+                    "errors": [{"domain": "global", "reason": "notFound"}]
+                }
+            }
+        }"#;
+
+        let response: FreeBusyResponse = serde_json::from_str(json).unwrap();
+
+        let mine = &response.calendars["me@example.com"];
+        assert_eq!(mine.busy.len(), 1);
+        assert_eq!(
+            mine.busy[0].end,
+            Utc.with_ymd_and_hms(2025, 6, 16, 10, 0, 0).unwrap()
+        );
+        assert_eq!(response.calendars["other@example.com"].errors.len(), 1);
+    }
+
+    #[test]
+    fn test_contact_search_response_deserialize() {
+        let json = r#"{
+            "results": [
+                {
+                    "person": {
+                        "resourceName": "people/c1",
+                        "names": [{ "displayName": "Ana Lima" }],
+                        "emailAddresses": [
+                            { "value": "ana@example.com" },
+                            { "value": "ana@home.example" }
+                        ]
+                    }
+                },
+                { "person": { "resourceName": "people/c2" } }
+            ]
+        }"#;
+
+        let response: ContactSearchResponse =
+            serde_json::from_str(json).expect("Failed to deserialize");
+        let contacts: Vec<Contact> = response
+            .results
+            .into_iter()
+            .flat_map(|result| result.person.into_contacts())
+            .collect();
+
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[1].name.as_deref(), Some("Ana Lima"));
+        assert_eq!(contacts[1].email, "ana@home.example");
+        assert!(serde_json::from_str::<ContactSearchResponse>("{}")
+            .unwrap()
+            .results
+            .is_empty());
+    }
 }
@@ -0,0 +1,332 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use super::models::{Event, EventDateTime, Reminder, Reminders};
+
+/// Parse Outlook's "Comma Separated Values" calendar export into events
+/// ready for insertion, each with the line its row starts on. Columns are
+/// found by their English header names; attendees are left out so that
+/// importing sends no invitations. Outlook writes dates in the exporting
+/// machine's locale, so `day_first` reads `D/M/Y` instead of `M/D/Y`.
+pub fn parse_events(input: &str, day_first: bool) -> Result<Vec<(usize, Result<Event>)>> {
+    let mut rows = parse_rows(input.trim_start_matches('\u{feff}'))?.into_iter();
+    let (_, header) = rows.next().context("Missing header row")?;
+    let columns = Columns::new(&header)?;
+
+    Ok(rows
+        .filter(|(_, fields)| fields.iter().any(|field| !field.trim().is_empty()))
+        .map(|(line, fields)| {
+            let event = columns
+                .build_event(&fields, day_first)
+                .with_context(|| format!("Invalid row on line {}", line));
+            (line, event)
+        })
+        .collect())
+}
+
+/// Column indexes of the fields oxidate imports
+struct Columns {
+    subject: usize,
+    start_date: usize,
+    start_time: Option<usize>,
+    end_date: Option<usize>,
+    end_time: Option<usize>,
+    all_day: Option<usize>,
+    reminder_on: Option<usize>,
+    reminder_date: Option<usize>,
+    reminder_time: Option<usize>,
+    description: Option<usize>,
+    location: Option<usize>,
+}
+
+impl Columns {
+    fn new(header: &[String]) -> Result<Self> {
+        let find = |name: &str| {
+            header
+                .iter()
+                .position(|column| column.trim().eq_ignore_ascii_case(name))
+        };
+        let require = |name: &str| {
+            find(name)
+                .with_context(|| format!("Missing \"{}\" column; is this an Outlook export?", name))
+        };
+
+        Ok(Columns {
+            subject: require("Subject")?,
+            start_date: require("Start Date")?,
+            start_time: find("Start Time"),
+            end_date: find("End Date"),
+            end_time: find("End Time"),
+            all_day: find("All day event"),
+            reminder_on: find("Reminder on/off"),
+            reminder_date: find("Reminder Date"),
+            reminder_time: find("Reminder Time"),
+            description: find("Description"),
+            location: find("Location"),
+        })
+    }
+
+    fn build_event(&self, fields: &[String], day_first: bool) -> Result<Event> {
+        let get = |index: Option<usize>| {
+            index
+                .and_then(|index| fields.get(index))
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+        };
+        let text = |index: Option<usize>| get(index).map(|field| field.replace("\r\n", "\n"));
+        let date = |index: Option<usize>| {
+            get(index)
+                .map(|value| parse_date(value, day_first))
+                .transpose()
+        };
+        let time = |index: Option<usize>| get(index).map(parse_time).transpose();
+
+        let start_date = date(Some(self.start_date))?.context("Missing start date")?;
+        let end_date = date(self.end_date)?;
+        let all_day = get(self.all_day).is_some_and(is_true);
+        let start_at = start_date.and_time(time(self.start_time)?.unwrap_or(NaiveTime::MIN));
+
+        let (start, end) = if all_day {
+            // Google's end date is exclusive, Outlook's may not be
+            let end_date = end_date
+                .filter(|end| *end > start_date)
+                .unwrap_or(start_date + Duration::days(1));
+            (
+                EventDateTime::from_date(start_date),
+                EventDateTime::from_date(end_date),
+            )
+        } else {
+            let end_at = match (end_date, time(self.end_time)?) {
+                (Some(date), Some(time)) => date.and_time(time),
+                (None, Some(time)) => start_date.and_time(time),
+                _ => start_at + Duration::hours(1),
+            };
+            if end_at < start_at {
+                bail!("Ends before it starts");
+            }
+            (
+                EventDateTime::from_local(start_at)?,
+                EventDateTime::from_local(end_at)?,
+            )
+        };
+
+        let reminders = match (
+            get(self.reminder_on).is_some_and(is_true),
+            date(self.reminder_date)?,
+        ) {
+            (true, Some(reminder_date)) if !all_day => {
+                let remind_at =
+                    reminder_date.and_time(time(self.reminder_time)?.unwrap_or(NaiveTime::MIN));
+                let minutes = (start_at - remind_at).num_minutes();
+                u32::try_from(minutes).ok().map(|minutes| Reminders {
+                    use_default: false,
+                    overrides: Some(vec![Reminder {
+                        method: "popup".to_string(),
+                        minutes,
+                    }]),
+                })
+            }
+            _ => None,
+        };
+
+        Ok(Event {
+            id: String::new(),
+            summary: text(Some(self.subject)),
+            description: text(self.description),
+            location: text(self.location),
+            start,
+            end,
+            status: None,
+            html_link: None,
+            hangout_link: None,
+            attendees: None,
+            calendar_id: None,
+            updated: None,
+            etag: None,
+            reminders,
+            attachments: None,
+            conference_data: None,
+            ical_uid: None,
+        })
+    }
+}
+
+fn is_true(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true")
+}
+
+/// ISO `2025-06-16`, else `6/16/2025` (or `16/6/2025` when `day_first`)
+fn parse_date(value: &str, day_first: bool) -> Result<NaiveDate> {
+    let format = if day_first { "%d/%m/%Y" } else { "%m/%d/%Y" };
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(value, format))
+        .with_context(|| format!("Invalid date: {}", value))
+}
+
+/// `9:00:00 AM`, `9:00 PM`, `21:00:00` or `21:00`
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    ["%I:%M:%S %p", "%I:%M %p", "%H:%M:%S", "%H:%M"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value, format).ok())
+        .with_context(|| format!("Invalid time: {}", value))
+}
+
+/// RFC 4180 records, each with the (1-based) line it starts on. Quoted
+/// fields may hold commas, doubled quotes and line breaks.
+fn parse_rows(input: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            _ if quoted => field.push(c),
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut fields)));
+                row_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        bail!("Unterminated quoted field starting on line {}", row_line);
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        rows.push((row_line, fields));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, NaiveDateTime, TimeZone};
+
+    const HEADER: &str = "\"Subject\",\"Start Date\",\"Start Time\",\"End Date\",\"End Time\",\
+                          \"All day event\",\"Reminder on/off\",\"Reminder Date\",\"Reminder Time\",\
+                          \"Meeting Organizer\",\"Required Attendees\",\"Description\",\"Location\"";
+
+    fn local(date_time: &str) -> String {
+        let naive = NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&naive).unwrap().to_rfc3339()
+    }
+
+    #[test]
+    fn test_parse_rows_quoting() {
+        let rows = parse_rows("a,\"b, c\",\"say \"\"hi\"\"\"\r\n\"multi\r\nline\",,x\n").unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    1,
+                    vec![
+                        "a".to_string(),
+                        "b, c".to_string(),
+                        "say \"hi\"".to_string()
+                    ]
+                ),
+                (
+                    2,
+                    vec!["multi\r\nline".to_string(), String::new(), "x".to_string()]
+                ),
+            ]
+        );
+        assert!(parse_rows("\"open").is_err());
+    }
+
+    #[test]
+    fn test_parse_timed_event_with_reminder() {
+        let input = format!(
+            "\u{feff}{}\r\n\"Design review\",\"6/16/2025\",\"2:30:00 PM\",\"6/16/2025\",\"3:30:00 PM\",\
+             \"False\",\"True\",\"6/16/2025\",\"2:15:00 PM\",\"Ann\",\"bob@example.com\",\
+             \"Agenda:\r\n- mocks\",\"Room 4\"\r\n",
+            HEADER
+        );
+
+        let events = parse_events(&input, false).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let (line, event) = &events[0];
+        let event = event.as_ref().unwrap();
+        assert_eq!(*line, 2);
+        assert_eq!(event.summary.as_deref(), Some("Design review"));
+        assert_eq!(event.start.date_time, Some(local("2025-06-16 14:30")));
+        assert_eq!(event.end.date_time, Some(local("2025-06-16 15:30")));
+        assert_eq!(event.description.as_deref(), Some("Agenda:\n- mocks"));
+        assert_eq!(event.location.as_deref(), Some("Room 4"));
+        assert_eq!(event.attendees, None);
+        let overrides = event
+            .reminders
+            .as_ref()
+            .unwrap()
+            .overrides
+            .as_ref()
+            .unwrap();
+        assert_eq!(overrides[0].minutes, 15);
+    }
+
+    #[test]
+    fn test_parse_all_day_and_day_first_dates() {
+        let input = format!(
+            "{}\n\"Holiday\",\"4/7/2025\",,\"4/7/2025\",,\"True\",\"False\",,,,,,\n\
+             \"Trip\",\"5/7/2025\",,\"8/7/2025\",,\"True\",\"False\",,,,,,\n",
+            HEADER
+        );
+
+        let events = parse_events(&input, true).unwrap();
+
+        let holiday = events[0].1.as_ref().unwrap();
+        assert_eq!(holiday.start.date.as_deref(), Some("2025-07-04"));
+        assert_eq!(holiday.end.date.as_deref(), Some("2025-07-05"));
+        let trip = events[1].1.as_ref().unwrap();
+        assert_eq!(trip.start.date.as_deref(), Some("2025-07-05"));
+        assert_eq!(trip.end.date.as_deref(), Some("2025-07-08"));
+    }
+
+    #[test]
+    fn test_invalid_rows_are_reported_per_line() {
+        let input = format!(
+            "{}\n\"Ok\",\"2025-06-16\",\"09:00\",,,,,,,,,,\n\n\
+             \"Bad date\",\"16/6/2025\",\"09:00\",,,,,,,,,,\n\
+             \"Backwards\",\"6/16/2025\",\"10:00\",\"6/16/2025\",\"9:00\",,,,,,,,\n",
+            HEADER
+        );
+
+        let events = parse_events(&input, false).unwrap();
+
+        let lines: Vec<usize> = events.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![2, 4, 5]);
+        let ok = events[0].1.as_ref().unwrap();
+        assert_eq!(ok.end.date_time, Some(local("2025-06-16 10:00")));
+        assert!(events[1].1.is_err());
+        assert!(events[2].1.is_err());
+    }
+
+    #[test]
+    fn test_missing_columns_are_rejected() {
+        assert!(parse_events("Title,Date\nx,2025-06-16\n", false).is_err());
+        assert!(parse_events("", false).is_err());
+    }
+}
@@ -0,0 +1,556 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use super::client::CalendarClient;
+use super::models::{Event, EventPatch};
+use crate::auth::account;
+
+/// A local change that still has to be sent to Google Calendar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mutation {
+    Create {
+        calendar_id: String,
+        event: Event,
+    },
+    Update {
+        calendar_id: String,
+        event_id: String,
+        patch: EventPatch,
+        base: Revision,
+    },
+    Delete {
+        calendar_id: String,
+        event_id: String,
+        base: Revision,
+    },
+}
+
+impl Mutation {
+    fn describe(&self) -> String {
+        match self {
+            Mutation::Create { event, .. } => format!(
+                "new event \"{}\"",
+                event.summary.as_deref().unwrap_or("(No title)")
+            ),
+            Mutation::Update {
+                event_id, patch, ..
+            } => match patch.summary {
+                Some(ref summary) => format!("edit of \"{}\"", summary),
+                None => format!("edit of event {}", event_id),
+            },
+            Mutation::Delete { event_id, .. } => format!("deletion of event {}", event_id),
+        }
+    }
+}
+
+/// The server revision a local edit was based on
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Revision {
+    pub etag: Option<String>,
+    pub updated: Option<String>,
+}
+
+impl Revision {
+    pub fn of(event: &Event) -> Self {
+        Self {
+            etag: event.etag.clone(),
+            updated: event.updated.clone(),
+        }
+    }
+
+    /// True when `current` was modified on the server after this revision was taken.
+    /// Prefers the etag and falls back to the `updated` timestamp; with neither
+    /// available the edit is assumed to be safe.
+    fn is_stale(&self, current: &Event) -> bool {
+        match (&self.etag, &current.etag) {
+            (Some(base), Some(now)) => base != now,
+            _ => match (&self.updated, &current.updated) {
+                (Some(base), Some(now)) => base != now,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// A queued mutation with an id that stays stable while other tasks edit the queue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Entry {
+    #[serde(default)]
+    id: u64,
+    #[serde(flatten)]
+    mutation: Mutation,
+}
+
+/// Where replayed mutations are sent
+trait Remote {
+    /// Returns Some(reason) when the mutation conflicts with the server state
+    fn send(&mut self, mutation: &Mutation) -> impl Future<Output = Result<Option<String>>> + Send;
+}
+
+impl Remote for CalendarClient {
+    fn send(&mut self, mutation: &Mutation) -> impl Future<Output = Result<Option<String>>> + Send {
+        MutationQueue::apply(mutation, self)
+    }
+}
+
+/// Serializes read-modify-write cycles on the queue file. The TUI pushes
+/// mutations while the loader task is replaying the same file.
+static QUEUE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Result of replaying the queue against the server
+#[derive(Debug, Default)]
+pub struct ReplayReport {
+    /// Human-readable reasons for mutations that were dropped (conflicts or rejections)
+    pub discarded: Vec<String>,
+    /// The dropped mutations themselves, so their local effect can be undone
+    pub dropped: Vec<Mutation>,
+    /// Mutations still waiting, e.g. because the connection dropped mid-replay
+    pub pending: usize,
+}
+
+/// Mutations persisted to disk until they can be replayed
+#[derive(Debug)]
+pub struct MutationQueue {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl MutationQueue {
+    pub fn load() -> Result<Self> {
+        Self::load_from(Self::get_storage_path()?)
+    }
+
+    fn load_from(path: PathBuf) -> Result<Self> {
+        let _guard = QUEUE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = Self::read(&path)?;
+
+        Ok(Self { path, entries })
+    }
+
+    fn read(path: &Path) -> Result<Vec<Entry>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let json = fs::read_to_string(path).context("Failed to read mutation queue")?;
+        let mut entries: Vec<Entry> =
+            serde_json::from_str(&json).context("Failed to deserialize mutation queue")?;
+
+        // Queues written before ids existed: number them deterministically so
+        // every reader agrees until the file is rewritten with real ids
+        let first_free = Self::next_id(&entries);
+        let legacy = entries.iter_mut().filter(|entry| entry.id == 0);
+        for (id, entry) in (first_free..).zip(legacy) {
+            entry.id = id;
+        }
+
+        Ok(entries)
+    }
+
+    fn next_id(entries: &[Entry]) -> u64 {
+        entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1
+    }
+
+    /// Offline changes belong to the account they were made with
+    fn get_storage_path() -> Result<PathBuf> {
+        Ok(account::data_dir()?.join("pending_mutations.json"))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Append a mutation and persist the queue immediately so it survives a crash
+    pub fn push(&mut self, mutation: Mutation) -> Result<()> {
+        self.update(|entries| {
+            let id = Self::next_id(entries);
+            entries.push(Entry { id, mutation });
+        })
+    }
+
+    /// Re-read the file, apply `change` and write the result back under the
+    /// lock, so entries added by another queue instance are never overwritten
+    fn update(&mut self, change: impl FnOnce(&mut Vec<Entry>)) -> Result<()> {
+        let _guard = QUEUE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut entries = Self::read(&self.path)?;
+        change(&mut entries);
+        Self::save_to(&self.path, &entries)?;
+        self.entries = entries;
+
+        Ok(())
+    }
+
+    fn save_to(path: &Path, entries: &[Entry]) -> Result<()> {
+        if entries.is_empty() {
+            if path.exists() {
+                fs::remove_file(path).context("Failed to delete mutation queue")?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+
+        let json =
+            serde_json::to_string_pretty(entries).context("Failed to serialize mutation queue")?;
+        fs::write(path, json).context("Failed to write mutation queue")?;
+
+        Ok(())
+    }
+
+    /// Send queued mutations in order. Stops at the first transient failure
+    /// (typically still offline) so ordering between mutations is preserved.
+    pub async fn replay(&mut self, client: &mut CalendarClient) -> Result<ReplayReport> {
+        self.replay_to(client).await
+    }
+
+    async fn replay_to(&mut self, remote: &mut impl Remote) -> Result<ReplayReport> {
+        let mut report = ReplayReport::default();
+
+        while let Some(Entry { id, mutation }) = self.entries.first().cloned() {
+            let discarded = match remote.send(&mutation).await {
+                Ok(None) => None,
+                Ok(Some(conflict)) => Some(format!("{}: {}", mutation.describe(), conflict)),
+                Err(e) if Self::is_permanent_failure(&e) => Some(format!(
+                    "{}: rejected by server ({})",
+                    mutation.describe(),
+                    e
+                )),
+                Err(e) => {
+                    tracing::info!(
+                        "Offline changes stay queued, {} failed: {:#}",
+                        mutation.describe(),
+                        e
+                    );
+                    break;
+                }
+            };
+
+            if let Some(reason) = discarded {
+                tracing::warn!("Dropped offline change: {}", reason);
+                report.discarded.push(reason);
+                report.dropped.push(mutation);
+            }
+
+            self.update(|entries| entries.retain(|entry| entry.id != id))?;
+        }
+
+        report.pending = self.entries.len();
+        Ok(report)
+    }
+
+    /// Returns Some(reason) when the mutation conflicts with the server state
+    async fn apply(mutation: &Mutation, client: &mut CalendarClient) -> Result<Option<String>> {
+        match mutation {
+            Mutation::Create { calendar_id, event } => {
+                client.insert_event(calendar_id, event).await?;
+                Ok(None)
+            }
+            Mutation::Update {
+                calendar_id,
+                event_id,
+                patch,
+                base,
+            } => match client.get_event(calendar_id, event_id).await? {
+                None => Ok(Some("event was deleted on the server".to_string())),
+                Some(current) if base.is_stale(&current) => {
+                    Ok(Some("event was changed on the server".to_string()))
+                }
+                Some(_) => {
+                    client.patch_event(calendar_id, event_id, patch).await?;
+                    Ok(None)
+                }
+            },
+            Mutation::Delete {
+                calendar_id,
+                event_id,
+                base,
+            } => match client.get_event(calendar_id, event_id).await? {
+                None => Ok(None),
+                Some(current) if base.is_stale(&current) => {
+                    Ok(Some("event was changed on the server".to_string()))
+                }
+                Some(_) => {
+                    client.delete_event(calendar_id, event_id).await?;
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    /// Client errors (bad request, forbidden, ...) will fail again on every
+    /// replay, so they are dropped instead of blocking the queue forever.
+    /// Rate limiting and timeouts are worth retrying.
+    fn is_permanent_failure(error: &anyhow::Error) -> bool {
+        error
+            .chain()
+            .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+            .filter_map(|e| e.status())
+            .any(|status| {
+                status.is_client_error()
+                    && status != reqwest::StatusCode::REQUEST_TIMEOUT
+                    && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn create_event(summary: &str) -> Event {
+        Event {
+            id: String::new(),
+            ..Event::test(
+                summary,
+                EventDateTime {
+                    date_time: Some("2025-07-02T14:00:00+02:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-07-02T15:00:00+02:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_load_from_missing_file_is_empty() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let queue = MutationQueue::load_from(temp_dir.path().join("queue.json")).unwrap();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn test_push_persists_and_reloads() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("queue.json");
+
+        let mut queue = MutationQueue::load_from(path.clone()).unwrap();
+        queue
+            .push(Mutation::Create {
+                calendar_id: "primary".to_string(),
+                event: create_event("Dentist"),
+            })
+            .unwrap();
+        queue
+            .push(Mutation::Delete {
+                calendar_id: "primary".to_string(),
+                event_id: "evt1".to_string(),
+                base: Revision {
+                    etag: Some("\"1\"".to_string()),
+                    updated: None,
+                },
+            })
+            .unwrap();
+
+        let reloaded = MutationQueue::load_from(path).unwrap();
+
+        assert_eq!(reloaded.len(), 2);
+        assert_eq!(reloaded.entries, queue.entries);
+    }
+
+    #[test]
+    fn test_saving_empty_queue_removes_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("queue.json");
+
+        let mut queue = MutationQueue::load_from(path.clone()).unwrap();
+        queue
+            .push(Mutation::Create {
+                calendar_id: "primary".to_string(),
+                event: create_event("Dentist"),
+            })
+            .unwrap();
+        assert!(path.exists());
+
+        queue.update(|entries| entries.clear()).unwrap();
+
+        assert!(!path.exists());
+    }
+
+    /// Accepts everything except "Dinner", which it treats as offline, and
+    /// queues "Dinner" while the first mutation is in flight like the TUI would
+    struct PushingRemote {
+        path: PathBuf,
+        sent: Vec<String>,
+    }
+
+    impl Remote for PushingRemote {
+        fn send(
+            &mut self,
+            mutation: &Mutation,
+        ) -> impl Future<Output = Result<Option<String>>> + Send {
+            let description = mutation.describe();
+            async move {
+                if description == "new event \"Dinner\"" {
+                    anyhow::bail!("offline");
+                }
+                if self.sent.is_empty() {
+                    MutationQueue::load_from(self.path.clone())?.push(Mutation::Create {
+                        calendar_id: "primary".to_string(),
+                        event: create_event("Dinner"),
+                    })?;
+                }
+                self.sent.push(description);
+                Ok(None)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_during_replay_is_kept() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("queue.json");
+
+        let mut queue = MutationQueue::load_from(path.clone()).unwrap();
+        for summary in ["Dentist", "Lunch"] {
+            queue
+                .push(Mutation::Create {
+                    calendar_id: "primary".to_string(),
+                    event: create_event(summary),
+                })
+                .unwrap();
+        }
+
+        let mut remote = PushingRemote {
+            path: path.clone(),
+            sent: Vec::new(),
+        };
+        let report = queue.replay_to(&mut remote).await.unwrap();
+
+        assert_eq!(
+            remote.sent,
+            ["new event \"Dentist\"", "new event \"Lunch\""]
+        );
+        assert_eq!(report.pending, 1);
+        assert!(report.dropped.is_empty());
+
+        let reloaded = MutationQueue::load_from(path).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(
+            reloaded.entries[0].mutation.describe(),
+            "new event \"Dinner\""
+        );
+    }
+
+    #[test]
+    fn test_load_assigns_ids_to_legacy_entries() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("queue.json");
+        let legacy = vec![
+            Mutation::Delete {
+                calendar_id: "primary".to_string(),
+                event_id: "evt1".to_string(),
+                base: Revision::default(),
+            },
+            Mutation::Delete {
+                calendar_id: "primary".to_string(),
+                event_id: "evt2".to_string(),
+                base: Revision::default(),
+            },
+        ];
+        fs::write(&path, serde_json::to_string(&legacy).unwrap()).unwrap();
+
+        let queue = MutationQueue::load_from(path).unwrap();
+        let ids: Vec<u64> = queue.entries.iter().map(|entry| entry.id).collect();
+
+        assert_eq!(ids, [1, 2]);
+    }
+
+    #[test]
+    fn test_load_fails_on_corrupt_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("queue.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert!(MutationQueue::load_from(path).is_err());
+    }
+
+    #[test]
+    fn test_revision_stale_when_etag_differs() {
+        let base = Revision {
+            etag: Some("\"1\"".to_string()),
+            updated: Some("2025-06-01T10:00:00Z".to_string()),
+        };
+        let mut current = create_event("Standup");
+        current.etag = Some("\"2\"".to_string());
+        current.updated = Some("2025-06-01T10:00:00Z".to_string());
+
+        assert!(base.is_stale(&current));
+    }
+
+    #[test]
+    fn test_revision_falls_back_to_updated() {
+        let base = Revision {
+            etag: None,
+            updated: Some("2025-06-01T10:00:00Z".to_string()),
+        };
+        let mut current = create_event("Standup");
+        current.updated = Some("2025-06-02T08:30:00Z".to_string());
+
+        assert!(base.is_stale(&current));
+
+        current.updated = Some("2025-06-01T10:00:00Z".to_string());
+        assert!(!base.is_stale(&current));
+    }
+
+    #[test]
+    fn test_revision_without_metadata_is_not_stale() {
+        let base = Revision::default();
+        let mut current = create_event("Standup");
+        current.etag = Some("\"2\"".to_string());
+
+        assert!(!base.is_stale(&current));
+    }
+
+    #[test]
+    fn test_revision_of_event() {
+        let mut event = create_event("Standup");
+        event.etag = Some("\"7\"".to_string());
+        event.updated = Some("2025-06-01T10:00:00Z".to_string());
+
+        let revision = Revision::of(&event);
+
+        assert_eq!(revision.etag, Some("\"7\"".to_string()));
+        assert_eq!(revision.updated, Some("2025-06-01T10:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn test_describe_mutations() {
+        let create = Mutation::Create {
+            calendar_id: "primary".to_string(),
+            event: create_event("Dentist"),
+        };
+        assert_eq!(create.describe(), "new event \"Dentist\"");
+
+        let delete = Mutation::Delete {
+            calendar_id: "primary".to_string(),
+            event_id: "evt1".to_string(),
+            base: Revision::default(),
+        };
+        assert_eq!(delete.describe(), "deletion of event evt1");
+    }
+
+    #[test]
+    fn test_non_http_errors_are_transient() {
+        let error = anyhow::anyhow!("Failed to send request");
+        assert!(!MutationQueue::is_permanent_failure(&error));
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, Utc};
+use serde::Deserialize;
+use std::process::Command;
+
+use super::local::overlaps;
+use super::models::{Event, EventDateTime};
+
+/// A task as printed by `task export`; only the fields shown are read
+#[derive(Debug, Deserialize)]
+struct Task {
+    uuid: String,
+    description: String,
+    /// UTC in Taskwarrior's compact form, e.g. `20250616T150000Z`
+    due: Option<String>,
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    urgency: Option<f64>,
+}
+
+/// Pending Taskwarrior tasks due within `time_min..time_max` (`--tasks`), as
+/// events at their due time, or all day when due at midnight
+pub fn load_events(time_min: DateTime<Utc>, time_max: DateTime<Utc>) -> Result<Vec<Event>> {
+    let output = Command::new("task")
+        .args([
+            "rc.hooks=off",
+            "rc.verbose=nothing",
+            "status:pending",
+            "export",
+        ])
+        .output()
+        .context("Failed to run `task export`; is Taskwarrior installed?")?;
+    if !output.status.success() {
+        bail!(
+            "`task export` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let events = parse_export(&String::from_utf8_lossy(&output.stdout))?;
+    Ok(events
+        .into_iter()
+        .filter(|event| overlaps(event, time_min, time_max))
+        .collect())
+}
+
+/// The tasks with a due date; tasks whose date can't be read are skipped
+fn parse_export(json: &str) -> Result<Vec<Event>> {
+    let tasks: Vec<Task> =
+        serde_json::from_str(json).context("Invalid output from `task export`")?;
+
+    Ok(tasks
+        .into_iter()
+        .filter_map(|task| {
+            let due = task.due.as_deref()?;
+            match to_event(&task, due) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    tracing::warn!(task = task.uuid.as_str(), "Skipping task: {:#}", e);
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+fn to_event(task: &Task, due: &str) -> Result<Event> {
+    let due = NaiveDateTime::parse_from_str(due, "%Y%m%dT%H%M%SZ")
+        .with_context(|| format!("Invalid due date {}", due))?
+        .and_utc()
+        .with_timezone(&Local)
+        .naive_local();
+
+    // `due:tomorrow` and the like mean the whole day
+    let (start, end) = if due.time() == NaiveTime::MIN {
+        let date = due.date();
+        (
+            EventDateTime::from_date(date),
+            EventDateTime::from_date(date + chrono::Duration::days(1)),
+        )
+    } else {
+        let at = EventDateTime::from_local(due)?;
+        (at.clone(), at)
+    };
+
+    let mut details = Vec::new();
+    if let Some(ref project) = task.project {
+        details.push(format!("Project: {}", project));
+    }
+    if !task.tags.is_empty() {
+        details.push(format!("Tags: {}", task.tags.join(", ")));
+    }
+    if let Some(urgency) = task.urgency {
+        details.push(format!("Urgency: {:.1}", urgency));
+    }
+
+    Ok(Event {
+        id: task.uuid.clone(),
+        summary: Some(task.description.clone()),
+        description: (!details.is_empty()).then(|| details.join("\n")),
+        location: None,
+        start,
+        end,
+        status: None,
+        html_link: None,
+        hangout_link: None,
+        attendees: None,
+        calendar_id: None,
+        updated: None,
+        etag: None,
+        reminders: None,
+        attachments: None,
+        conference_data: None,
+        ical_uid: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Taskwarrior's UTC form of a local wall-clock time
+    fn due(local: &str) -> String {
+        let naive = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+        Local
+            .from_local_datetime(&naive)
+            .unwrap()
+            .with_timezone(&Utc)
+            .format("%Y%m%dT%H%M%SZ")
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let json = format!(
+            r#"[
+                {{"id": 1, "uuid": "a1", "description": "File taxes", "status": "pending",
+                  "due": "{}", "project": "home", "tags": ["money"], "urgency": 9.5}},
+                {{"id": 2, "uuid": "b2", "description": "Send slides", "status": "pending",
+                  "due": "{}"}},
+                {{"id": 3, "uuid": "c3", "description": "Someday", "status": "pending"}},
+                {{"id": 4, "uuid": "d4", "description": "Broken", "status": "pending",
+                  "due": "tomorrow"}}
+            ]"#,
+            due("2025-06-16 00:00"),
+            due("2025-06-16 17:00"),
+        );
+
+        let events = parse_export(&json).unwrap();
+
+        assert_eq!(events.len(), 2);
+        let taxes = &events[0];
+        assert_eq!(taxes.id, "a1");
+        assert_eq!(taxes.start.date.as_deref(), Some("2025-06-16"));
+        assert_eq!(taxes.end.date.as_deref(), Some("2025-06-17"));
+        assert_eq!(
+            taxes.description.as_deref(),
+            Some("Project: home\nTags: money\nUrgency: 9.5")
+        );
+
+        let slides = &events[1];
+        let start = slides.start.to_local().unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-06-16 17:00"
+        );
+        assert_eq!(slides.start, slides.end);
+        assert_eq!(slides.description, None);
+    }
+
+    #[test]
+    fn test_parse_export_rejects_other_output() {
+        assert!(parse_export("Configuration override rc.hooks=off").is_err());
+    }
+}
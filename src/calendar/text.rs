@@ -0,0 +1,266 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use std::fs;
+use std::path::PathBuf;
+
+use super::local::overlaps;
+use super::models::{Event, EventDateTime};
+
+/// A plain-text calendar (`--text-calendar`), one event per line:
+///
+/// ```text
+/// # Comments and blank lines are ignored
+/// 2025-06-16 Dentist                        all day
+/// 2025-06-16 09:30 Standup                  one hour
+/// 2025-06-16 09:30-10:15 Review @ Room 4    with a location
+/// 2025-07-01..2025-07-14 Vacation           several days
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextCalendar {
+    pub path: PathBuf,
+}
+
+impl TextCalendar {
+    /// The file name without extension
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// The file's events overlapping `time_min..time_max`; invalid lines are
+    /// skipped with a warning so one typo doesn't hide the whole file
+    pub fn load_events(
+        &self,
+        time_min: DateTime<Utc>,
+        time_max: DateTime<Utc>,
+    ) -> Result<Vec<Event>> {
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+
+        let mut events = Vec::new();
+        for (line, event) in parse_events(&contents) {
+            match event {
+                Ok(event) if overlaps(&event, time_min, time_max) => events.push(event),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    file = %self.path.display(),
+                    line,
+                    "Skipping invalid event: {:#}",
+                    e
+                ),
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Each event line (1-based) with its event or why it is invalid
+fn parse_events(contents: &str) -> Vec<(usize, Result<Event>)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, text)| (line, parse_line(text)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<Event> {
+    let (dates, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim_start();
+
+    let (first, last) = match dates.split_once("..") {
+        Some((first, last)) => (parse_date(first)?, parse_date(last)?),
+        None => (parse_date(dates)?, parse_date(dates)?),
+    };
+    if last < first {
+        bail!("{} ends before it starts", dates);
+    }
+
+    let (word, after_word) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let times = if first == last {
+        parse_times(word)?
+    } else {
+        None
+    };
+    let text = match times {
+        Some(_) => after_word.trim_start(),
+        None => rest,
+    };
+
+    let (summary, location) = match text.rsplit_once(" @ ") {
+        Some((summary, location)) => (summary.trim(), Some(location.trim().to_string())),
+        None => (text.trim(), None),
+    };
+    if summary.is_empty() {
+        bail!("Missing title");
+    }
+
+    let (start, end) = match times {
+        None => (
+            EventDateTime::from_date(first),
+            EventDateTime::from_date(last + Duration::days(1)),
+        ),
+        Some((from, to)) => {
+            let start = first.and_time(from);
+            let end = match to {
+                Some(to) if to <= from => bail!("{} ends before it starts", word),
+                Some(to) => first.and_time(to),
+                None => start + Duration::hours(1),
+            };
+            (
+                EventDateTime::from_local(start)?,
+                EventDateTime::from_local(end)?,
+            )
+        }
+    };
+
+    Ok(Event {
+        id: event_id(line),
+        summary: Some(summary.to_string()),
+        description: None,
+        location,
+        start,
+        end,
+        status: None,
+        html_link: None,
+        hangout_link: None,
+        attendees: None,
+        calendar_id: None,
+        updated: None,
+        etag: None,
+        reminders: None,
+        attachments: None,
+        conference_data: None,
+        ical_uid: None,
+    })
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Expected a YYYY-MM-DD date, got \"{}\"", value))
+}
+
+/// `09:30` or `09:30-10:15`; None when `word` is not a time, i.e. the
+/// title starts there
+fn parse_times(word: &str) -> Result<Option<(NaiveTime, Option<NaiveTime>)>> {
+    let time = |value: &str| NaiveTime::parse_from_str(value, "%H:%M");
+    let (from, to) = match word.split_once('-') {
+        Some((from, to)) => (from, Some(to)),
+        None => (word, None),
+    };
+
+    let Ok(from) = time(from) else {
+        return Ok(None);
+    };
+    let to = to
+        .map(|to| time(to).with_context(|| format!("Invalid end time \"{}\"", to)))
+        .transpose()?;
+    Ok(Some((from, to)))
+}
+
+/// Derived from the line's text (FNV-1a), so an event keeps its id when
+/// lines are added or moved around it
+fn event_id(line: &str) -> String {
+    let hash = line.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, NaiveDateTime, TimeZone};
+
+    fn local(date_time: &str) -> Option<String> {
+        let naive = NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap();
+        Some(Local.from_local_datetime(&naive).unwrap().to_rfc3339())
+    }
+
+    #[test]
+    fn test_parse_event_lines() {
+        let input = "\
+# Personal events
+2025-06-16 Dentist
+
+2025-06-16 09:30 Standup
+2025-06-16 09:30-10:15 Design review @ Room 4
+2025-07-01..2025-07-14 Vacation
+";
+
+        let events: Vec<(usize, Event)> = parse_events(input)
+            .into_iter()
+            .map(|(line, event)| (line, event.unwrap()))
+            .collect();
+
+        let lines: Vec<usize> = events.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![2, 4, 5, 6]);
+
+        let dentist = &events[0].1;
+        assert_eq!(dentist.summary.as_deref(), Some("Dentist"));
+        assert_eq!(dentist.start.date.as_deref(), Some("2025-06-16"));
+        assert_eq!(dentist.end.date.as_deref(), Some("2025-06-17"));
+
+        let standup = &events[1].1;
+        assert_eq!(standup.start.date_time, local("2025-06-16 09:30"));
+        assert_eq!(standup.end.date_time, local("2025-06-16 10:30"));
+
+        let review = &events[2].1;
+        assert_eq!(review.summary.as_deref(), Some("Design review"));
+        assert_eq!(review.location.as_deref(), Some("Room 4"));
+        assert_eq!(review.end.date_time, local("2025-06-16 10:15"));
+
+        let vacation = &events[3].1;
+        assert_eq!(vacation.end.date.as_deref(), Some("2025-07-15"));
+    }
+
+    #[test]
+    fn test_invalid_lines_are_reported() {
+        for line in [
+            "16.06.2025 Dentist",
+            "2025-06-16",
+            "2025-06-16 10:00-09:00 Backwards",
+            "2025-06-16 09:00-soon Meeting",
+            "2025-07-14..2025-07-01 Vacation",
+        ] {
+            assert!(parse_line(line).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_event_ids_follow_the_text() {
+        let a = parse_line("2025-06-16 Dentist").unwrap();
+        let b = parse_line("2025-06-16 Dentist").unwrap();
+        let c = parse_line("2025-06-17 Dentist").unwrap();
+
+        assert_eq!(a.id, b.id);
+        assert_ne!(a.id, c.id);
+    }
+
+    #[test]
+    fn test_load_events_in_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("personal.txt");
+        fs::write(
+            &path,
+            "2025-06-16 Dentist\nnot an event\n2025-09-01 Offsite\n",
+        )
+        .unwrap();
+        let calendar = TextCalendar { path };
+
+        let events = calendar
+            .load_events(
+                "2025-06-01T00:00:00Z".parse().unwrap(),
+                "2025-07-01T00:00:00Z".parse().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary.as_deref(), Some("Dentist"));
+        assert_eq!(calendar.name(), "personal");
+    }
+}
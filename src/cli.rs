@@ -1,4 +1,28 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::commands::bar::Bar;
+use crate::commands::edit::{parse_when, When};
+use crate::commands::find_slot::{parse_duration, parse_within};
+use crate::commands::output::OutputFormat;
+use crate::commands::rsvp::Response;
+use crate::commands::EventDefaults;
+use crate::config::Config;
+use crate::formats::Formats;
+use crate::hook::{Hook, Hooks, SyncHook};
+use crate::locale;
+use crate::settings::Settings;
+use crate::tui::color_utils::validate_color_overrides;
+use crate::tui::state::StartView;
+use crate::tui::zones::parse_zone;
+use crate::vdir::Vdir;
+use crate::working_hours::{parse_days, parse_hours, Days, Hours, WorkingHours};
+
+/// Minutes before an event's start to run `--hook` unless configured
+const DEFAULT_HOOK_LEAD: u32 = 5;
 
 #[derive(Parser, Debug)]
 #[command(name = "oxidate")]
@@ -10,12 +34,437 @@ pub struct Cli {
     /// Logout and delete stored credentials
     #[arg(long)]
     pub logout: bool,
+
+    /// What the TUI opens into [default: month]
+    #[arg(long, value_enum, env = "OXIDATE_VIEW")]
+    pub view: Option<StartView>,
+
+    /// Only show and fetch this calendar (id or name) in the TUI; repeatable
+    #[arg(long = "calendar")]
+    pub calendars: Vec<String>,
+
+    /// Desktop notification lead times in minutes while the TUI runs, e.g. 10,1
+    /// (default: each event's popup reminders); `N` mutes them for the
+    /// session, `z`/`Z` snooze the last one by 5/10 minutes
+    #[arg(long, value_delimiter = ',', env = "OXIDATE_NOTIFY")]
+    pub notify: Vec<u32>,
+
+    /// Also show times in these IANA zones in the TUI, e.g.
+    /// Europe/Berlin,Asia/Tokyo: in event details and the day's header
+    #[arg(long, value_delimiter = ',', env = "OXIDATE_OTHER_TIMEZONES", value_parser = parse_zone)]
+    pub other_timezones: Vec<Tz>,
+
+    /// Language of month and weekday names, e.g. de_DE; defaults to
+    /// LC_ALL/LC_TIME/LANG, falling back to English
+    #[arg(long, global = true, env = "OXIDATE_LOCALE")]
+    pub locale: Option<String>,
+
+    /// strftime pattern for times of day, e.g. "%I:%M %p" [default: %H:%M]
+    #[arg(long, global = true, env = "OXIDATE_TIME_FORMAT")]
+    pub time_format: Option<String>,
+
+    /// strftime pattern for short dates in command output, e.g. "%d.%m."
+    /// [default: "%a %b %-d"]
+    #[arg(long, global = true, env = "OXIDATE_DATE_FORMAT")]
+    pub date_format: Option<String>,
+
+    /// Shell command to run --hook-lead minutes before each timed event while
+    /// the TUI or `watch` runs; it gets the event as OXIDATE_EVENT_TITLE,
+    /// _START, _END, _LOCATION, _CALENDAR, _LINK, _VIDEO_CALL, _ID and _JSON
+    #[arg(long, global = true, env = "OXIDATE_HOOK")]
+    pub hook: Option<String>,
+
+    /// Minutes before the start to run --hook [default: 5]
+    #[arg(long, global = true, env = "OXIDATE_HOOK_LEAD")]
+    pub hook_lead: Option<u32>,
+
+    /// Shell command to run after each successful refresh of the TUI or
+    /// `watch`; OXIDATE_SYNC_ADDED, _CHANGED, _REMOVED and _INVITATIONS count
+    /// the changes since the previous one, OXIDATE_SYNC_JSON lists them
+    #[arg(long, global = true, env = "OXIDATE_SYNC_HOOK")]
+    pub sync_hook: Option<String>,
+
+    /// Directory to mirror fetched events into as .ics files (vdir layout,
+    /// one subdirectory per calendar) after each refresh of the TUI or
+    /// `watch`, for khal and vdirsyncer
+    #[arg(long, global = true, env = "OXIDATE_VDIR")]
+    pub vdir: Option<PathBuf>,
+
+    /// Keep fetched events in a local SQLite index for instant, offline search
+    /// beyond the loaded dates (by `search` and the TUI's `/` filter)
+    #[arg(long, global = true, env = "OXIDATE_INDEX")]
+    pub index: bool,
+
+    /// Also show the calendars of an existing khal/vdirsyncer setup (read
+    /// from khal's config) as read-only local calendars
+    #[arg(long, global = true, env = "OXIDATE_KHAL")]
+    pub khal: bool,
+
+    /// Plain-text file with one event per line ("2025-06-16 09:30-10:15
+    /// Review @ Room 4", "2025-06-16 Dentist") shown as a read-only calendar
+    /// named after the file; repeatable
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        env = "OXIDATE_TEXT_CALENDARS"
+    )]
+    pub text_calendar: Vec<PathBuf>,
+
+    /// Show pending Taskwarrior tasks with a due date (from `task export`)
+    /// on a read-only "Tasks" calendar
+    #[arg(long, global = true, env = "OXIDATE_TASKS")]
+    pub tasks: bool,
+
+    /// Calendar id or name that `add`, `quick`, `import` and the HTTP and MCP
+    /// servers create events on when not told otherwise [default: the primary calendar]
+    #[arg(long, global = true, env = "OXIDATE_DEFAULT_CALENDAR")]
+    pub default_calendar: Option<String>,
+
+    /// When you work, e.g. 9-17 or 08:30-16:30: find-slot searches these
+    /// hours, the TUI and `next` flag events outside them [default: 9-17]
+    #[arg(long, global = true, env = "OXIDATE_WORKING_HOURS", value_parser = parse_hours)]
+    pub working_hours: Option<Hours>,
+
+    /// Days you work, e.g. mon-fri, sun-thu or mon,tue,thu; the TUI shades
+    /// the others [default: mon-fri]
+    #[arg(long, global = true, env = "OXIDATE_WORKING_DAYS", value_parser = parse_days)]
+    pub working_days: Option<Days>,
+
+    /// Length in minutes of new timed events given only a start time, e.g. 25
+    /// [default: 60]
+    #[arg(long, global = true, env = "OXIDATE_EVENT_DURATION", value_parser = clap::value_parser!(u32).range(1..))]
+    pub event_duration: Option<u32>,
+
+    /// Minutes that proposed start times are rounded up to a multiple of,
+    /// e.g. by `find-slot` [default: 15]
+    #[arg(long, global = true, env = "OXIDATE_SNAP", value_parser = clap::value_parser!(u32).range(1..))]
+    pub snap: Option<u32>,
+
+    /// Months of events either side of the shown one that the TUI keeps in
+    /// memory; farther ones are dropped and reloaded (from the --index
+    /// right away) when navigated back to [default: 12]
+    #[arg(long, global = true, env = "OXIDATE_MEMORY_HORIZON", value_parser = clap::value_parser!(u32).range(2..))]
+    pub memory_horizon: Option<u32>,
+
+    /// Minutes between automatic refreshes of the TUI; 0 turns them off
+    /// [default: 5]
+    #[arg(long, global = true, env = "OXIDATE_REFRESH_INTERVAL")]
+    pub refresh_interval: Option<u32>,
+
+    /// Never change calendars; `login --read-only` asks only for read access.
+    /// Also enabled by `read_only = true` in config.toml.
+    #[arg(long, global = true, env = "OXIDATE_READ_ONLY")]
+    pub read_only: bool,
+
+    /// Show times in this IANA time zone (e.g. Europe/Berlin) instead of the
+    /// system's; also `timezone = "..."` in config.toml
+    #[arg(long, global = true, env = "OXIDATE_TIMEZONE")]
+    pub timezone: Option<String>,
+
+    /// Use the settings of [profile.<name>] in config.toml on top of the rest
+    #[arg(long, global = true, env = "OXIDATE_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Use the credentials and offline changes stored under this account name
+    /// (log in once per account with `login --account <name>`)
+    #[arg(long, global = true, env = "OXIDATE_ACCOUNT")]
+    pub account: Option<String>,
+
+    /// Output format of subcommands; defaults to table (plain for `search`)
+    #[arg(short, long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Authenticate with Google Calendar
-    Login,
+    Login {
+        /// Only print the authorization address instead of opening a browser,
+        /// e.g. over SSH; the address the browser lands on can be pasted back
+        #[arg(long)]
+        no_browser: bool,
+    },
+    /// Print the next upcoming event, e.g. for a status bar.
+    /// Exits 0 if an event was printed, 1 if none is upcoming, 2 on errors.
+    Next {
+        /// Output template; placeholders: {summary} {start} {end} {start_rel}
+        /// {date} {location} {calendar} {video_call} {off_hours}
+        #[arg(long, default_value = "{start_rel} {summary}")]
+        format: String,
+        /// How many days ahead to look
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Consider all-day events too
+        #[arg(long)]
+        all_day: bool,
+        /// Print a line for a status bar instead, with the template as its text
+        #[arg(long, value_enum)]
+        bar: Option<Bar>,
+    },
+    /// List events in a date range as a table or JSON
+    List {
+        /// First day to list (YYYY-MM-DD), defaults to today
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day to list (YYYY-MM-DD), defaults to a week after --from
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Shorthand for --output json
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create an event; without --date/--from/--to the title is parsed as free
+    /// text by Google (e.g. "Lunch with Ana tomorrow 1pm")
+    Add {
+        /// Event title, or the whole event as free text
+        #[arg(required_unless_present = "stdin")]
+        title: Option<String>,
+        /// Day of the event (YYYY-MM-DD), defaults to today when a time is given
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Start time (HH:MM); omit for an all-day event
+        #[arg(long, value_parser = parse_time)]
+        from: Option<NaiveTime>,
+        /// End time (HH:MM), defaults to an hour after --from
+        #[arg(long, value_parser = parse_time)]
+        to: Option<NaiveTime>,
+        /// Calendar id or name, defaults to --default-calendar
+        #[arg(long)]
+        calendar: Option<String>,
+        /// Read one JSON event per line from stdin and create them all, e.g.
+        /// {"title": "Standup", "date": "2025-07-02", "from": "09:30", "to": "09:45"}
+        #[arg(long, conflicts_with_all = ["title", "date", "from", "to"])]
+        stdin: bool,
+    },
+    /// Create an event from free text with Google's quick add, e.g.
+    /// "Coffee with Dana tomorrow 3pm"
+    Quick {
+        /// The whole event as a sentence
+        text: String,
+        /// Calendar id or name, defaults to --default-calendar
+        #[arg(long)]
+        calendar: Option<String>,
+    },
+    /// Delete events by id or title words
+    #[command(alias = "cancel")]
+    Delete {
+        /// Event id, or words that must all appear in the title
+        query: String,
+        /// Only consider events on this day (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Delete every matching event instead of requiring a unique match
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Change fields of an event
+    Edit {
+        /// Event id
+        event_id: String,
+        /// Calendar id or name to look in, defaults to searching all calendars
+        #[arg(long)]
+        calendar: Option<String>,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        location: Option<String>,
+        /// New start, "YYYY-MM-DD HH:MM" or "YYYY-MM-DD"; keeps the length without --end
+        #[arg(long, value_parser = parse_when)]
+        start: Option<When>,
+        /// New end, "YYYY-MM-DD HH:MM" or "YYYY-MM-DD"
+        #[arg(long, value_parser = parse_when)]
+        end: Option<When>,
+        /// Invite an attendee by email, or by name looked up in your contacts (repeatable)
+        #[arg(long = "add-attendee")]
+        add_attendees: Vec<String>,
+        /// Remove an attendee by email (repeatable)
+        #[arg(long = "remove-attendee")]
+        remove_attendees: Vec<String>,
+        /// Edit the event as TOML in $EDITOR instead
+        #[arg(long)]
+        editor: bool,
+    },
+    /// Find events by text and print them with their ids
+    Search {
+        /// Words that must all appear in the title, description, location or attendees
+        query: String,
+        /// First day to search (YYYY-MM-DD), defaults to 30 days ago
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day to search (YYYY-MM-DD), defaults to a year from today
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+    /// Print busy and free intervals of a day, e.g. to share availability
+    Busy {
+        /// Day to check: today, tomorrow or YYYY-MM-DD
+        #[arg(long, default_value = "today", value_parser = parse_day)]
+        date: NaiveDate,
+        /// Start of the window (HH:MM)
+        #[arg(long, default_value = "09:00", value_parser = parse_time)]
+        from: NaiveTime,
+        /// End of the window (HH:MM)
+        #[arg(long, default_value = "18:00", value_parser = parse_time)]
+        to: NaiveTime,
+        /// Emit JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Propose free meeting slots across calendars
+    FindSlot {
+        /// Meeting length: 45m, 1h, 1h30m; defaults to --event-duration, or 30m
+        #[arg(long, value_parser = parse_duration)]
+        duration: Option<Duration>,
+        /// How far to look: "next 5 days", "2 weeks", "today"
+        #[arg(long, default_value = "next 5 days", value_parser = parse_within)]
+        within: u32,
+        /// Hours of each day to search: 9-17 or 09:30-18:00; defaults to
+        /// --working-hours
+        #[arg(long, value_parser = parse_hours)]
+        hours: Option<Hours>,
+        /// Also propose slots on days outside --working-days
+        #[arg(long)]
+        weekends: bool,
+        /// Calendar id or name whose events block slots; repeatable,
+        /// defaults to the calendars visible in the TUI
+        #[arg(long)]
+        calendar: Vec<String>,
+        /// How many slots to propose
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// List calendars with their ids, names, access roles and colors
+    Calendars {
+        /// Shorthand for --output json
+        #[arg(long)]
+        json: bool,
+    },
+    /// List Google's public holiday calendars, or add the one of a country
+    /// so its holidays show in the month view
+    Holidays {
+        /// Country code or name, e.g. us or Brazil; omit to list countries
+        country: Option<String>,
+        /// Remove the country's holiday calendar instead
+        #[arg(long, requires = "country")]
+        remove: bool,
+    },
+    /// Answer JSON requests (list, next, create, refresh) on a Unix socket so
+    /// editors, bars and scripts can reuse this authenticated instance
+    Serve {
+        /// Socket path, defaults to oxidate.sock in the data directory
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+    /// Answer JSON-RPC requests (MCP tools list_events, search_events,
+    /// create_event, free_busy) on stdin/stdout for assistants and editor plugins
+    Mcp,
+    /// Open an event in the browser
+    Open {
+        /// Event id, or words that must all appear in the title; of several
+        /// matches the next one not over yet is opened
+        query: String,
+        /// Join the video call (Meet, Zoom, Teams or Webex) instead of
+        /// opening the calendar page
+        #[arg(long)]
+        join: bool,
+    },
+    /// Answer an invitation as the signed-in user
+    Rsvp {
+        /// Event id, e.g. the first column of `search`
+        event_id: String,
+        /// accept, decline or tentative
+        #[arg(value_enum)]
+        response: Response,
+        /// Note sent along with the response
+        #[arg(long)]
+        comment: Option<String>,
+        /// Calendar id or name holding the event, searched in all calendars by default
+        #[arg(long)]
+        calendar: Option<String>,
+    },
+    /// Print whether a meeting is in progress and what's next.
+    /// Exits 0 during a meeting, 1 otherwise, 2 on errors.
+    Status,
+    /// Check configuration, credentials, connectivity and the terminal
+    Doctor,
+    /// Print the week's events as a 7-column text grid
+    Week {
+        /// Any day of the week to show: today, tomorrow or YYYY-MM-DD
+        #[arg(long, default_value = "today", value_parser = parse_day)]
+        date: NaiveDate,
+        /// Total width in characters, defaults to the terminal width
+        #[arg(long)]
+        width: Option<usize>,
+    },
+    /// Print a day's schedule as a Markdown section for a daily note, e.g.
+    /// `oxidate note >> ~/notes/$(date +%F).md`
+    Note {
+        /// Day to print: today, tomorrow or YYYY-MM-DD
+        #[arg(long, default_value = "today", value_parser = parse_day)]
+        date: NaiveDate,
+        /// Line above the list; empty for none
+        #[arg(long, default_value = "## Schedule")]
+        heading: String,
+    },
+    /// Keep running and print a line shortly before each timed event starts
+    Watch {
+        /// Minutes before the start to print the event
+        #[arg(long, default_value_t = 5)]
+        lead: u32,
+        /// Output template; same placeholders as `next`
+        #[arg(long, default_value = "{start} {summary}")]
+        format: String,
+        /// Instead of announcing events, keep printing the next event as a
+        /// status bar line
+        #[arg(long, value_enum)]
+        bar: Option<Bar>,
+        /// Seconds between bar updates
+        #[arg(long, default_value_t = 60, requires = "bar", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Create the events of an .ics file, e.g. an emailed invite
+    Import {
+        /// Path to the .ics file, or to a .csv file exported from Outlook
+        file: PathBuf,
+        /// Calendar id or name, defaults to --default-calendar
+        #[arg(long)]
+        calendar: Option<String>,
+        /// Read the .csv file's dates as day/month/year instead of month/day/year
+        #[arg(long)]
+        day_first: bool,
+    },
+    /// Mirror events from a month back to a year ahead into --vdir once
+    Mirror,
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    resolve_day(value, Local::now().date_naive())
+}
+
+/// "today", "tomorrow", "yesterday" relative to `today`, or YYYY-MM-DD
+fn resolve_day(value: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today + Duration::days(1)),
+        "yesterday" => Ok(today - Duration::days(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+            format!(
+                "expected today, tomorrow, yesterday or YYYY-MM-DD, got \"{}\"",
+                value
+            )
+        }),
+    }
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| format!("expected HH:MM, got \"{}\"", value))
 }
 
 impl Cli {
@@ -23,12 +472,226 @@ impl Cli {
         Self::parse()
     }
 
+    /// Fill in what neither a flag nor its environment variable set from
+    /// config.toml, so the command line always wins over the file
+    pub fn apply_config(&mut self, config: &Config) -> Result<()> {
+        let display = &config.display;
+        let behavior = &config.behavior;
+        let providers = &config.providers;
+
+        if self.account.is_none() {
+            self.account = config.account.clone();
+        }
+        self.read_only |= config.read_only;
+        if self.timezone.is_none() {
+            self.timezone = config.timezone.clone();
+        }
+
+        if self.calendars.is_empty() {
+            self.calendars = display.calendars.clone();
+        }
+        if self.notify.is_empty() {
+            self.notify = display.notify.clone();
+        }
+        self.view = self.view.or(display.view);
+        if self.locale.is_none() {
+            self.locale = display.locale.clone();
+        }
+        if self.time_format.is_none() {
+            self.time_format = display.time_format.clone();
+        }
+        if self.date_format.is_none() {
+            self.date_format = display.date_format.clone();
+        }
+        if self.other_timezones.is_empty() {
+            self.other_timezones = display
+                .other_timezones
+                .iter()
+                .map(|name| parse_zone(name).map_err(|e| anyhow!("other_timezones: {}", e)))
+                .collect::<Result<_>>()?;
+        }
+
+        if self.hook.is_none() {
+            self.hook = behavior.hook.clone();
+        }
+        self.hook_lead = self.hook_lead.or(behavior.hook_lead);
+        if self.sync_hook.is_none() {
+            self.sync_hook = behavior.sync_hook.clone();
+        }
+        if self.vdir.is_none() {
+            self.vdir = behavior.vdir.clone();
+        }
+        if self.default_calendar.is_none() {
+            self.default_calendar = behavior.default_calendar.clone();
+        }
+        self.event_duration = self.event_duration.or(behavior.event_duration);
+        if self.working_hours.is_none() {
+            self.working_hours = behavior
+                .working_hours
+                .as_deref()
+                .map(|value| parse_hours(value).map_err(|e| anyhow!("working_hours: {}", e)))
+                .transpose()?;
+        }
+        if self.working_days.is_none() {
+            self.working_days = behavior
+                .working_days
+                .as_deref()
+                .map(|value| parse_days(value).map_err(|e| anyhow!("working_days: {}", e)))
+                .transpose()?;
+        }
+        self.snap = self.snap.or(behavior.snap);
+        if self.memory_horizon.is_none() {
+            self.memory_horizon = match behavior.memory_horizon {
+                // The fetched five months around the selection always stay
+                Some(months) if months < 2 => {
+                    bail!("memory_horizon: expected at least 2 months, got {}", months)
+                }
+                months => months,
+            };
+        }
+        self.refresh_interval = self.refresh_interval.or(behavior.refresh_interval);
+        self.index |= behavior.index;
+
+        self.khal |= providers.khal;
+        if self.text_calendar.is_empty() {
+            self.text_calendar = providers.text_calendars.clone();
+        }
+        self.tasks |= providers.tasks;
+        Ok(())
+    }
+
+    /// `--locale`, `--time-format` and `--date-format`, and the title
+    /// formats of `config`
+    pub fn formats(&self, config: &Config) -> Result<Formats> {
+        let defaults = Formats::default();
+        let display = &config.display;
+        Ok(Formats {
+            time: self.time_format.clone().unwrap_or(defaults.time),
+            date: self.date_format.clone().unwrap_or(defaults.date),
+            day_title: display
+                .day_title_format
+                .clone()
+                .unwrap_or(defaults.day_title),
+            month_title: display
+                .month_title_format
+                .clone()
+                .unwrap_or(defaults.month_title),
+            locale: locale::resolve(self.locale.as_deref())?,
+        })
+    }
+
+    /// `--working-hours` and `--working-days`
+    pub fn working_hours(&self) -> WorkingHours {
+        let defaults = WorkingHours::default();
+        WorkingHours {
+            hours: self.working_hours.unwrap_or(defaults.hours),
+            days: self.working_days.clone().unwrap_or(defaults.days),
+        }
+    }
+
+    /// `--default-calendar`, `--event-duration` and `--snap`
+    pub fn event_defaults(&self) -> EventDefaults {
+        let defaults = EventDefaults::default();
+        let minutes = |value: Option<u32>| value.map(|minutes| Duration::minutes(minutes.into()));
+        EventDefaults {
+            calendar: self.default_calendar.clone(),
+            duration: minutes(self.event_duration).unwrap_or(defaults.duration),
+            snap: minutes(self.snap).unwrap_or(defaults.snap),
+        }
+    }
+
+    /// Everything the TUI and the commands read from flags and `config`,
+    /// checked; local calendar sources are discovered by the caller
+    pub fn settings(&self, config: &Config) -> Result<Settings> {
+        let formats = self.formats(config)?;
+        formats.validate()?;
+        validate_color_overrides(&config.colors)?;
+        let defaults = Settings::default();
+        Ok(Settings {
+            formats,
+            working_hours: self.working_hours(),
+            event_defaults: self.event_defaults(),
+            calendar_colors: config.colors.clone(),
+            local_sources: Vec::new(),
+            index: self.index,
+            other_timezones: self.other_timezones.clone(),
+            memory_horizon: self.memory_horizon.unwrap_or(defaults.memory_horizon),
+            refresh_interval: match self.refresh_interval {
+                Some(0) => None,
+                Some(minutes) => Some(std::time::Duration::from_secs(u64::from(minutes) * 60)),
+                None => defaults.refresh_interval,
+            },
+        })
+    }
+
     pub fn is_logout(&self) -> bool {
         self.logout
     }
 
     pub fn is_login(&self) -> bool {
-        matches!(self.command, Some(Command::Login))
+        matches!(self.command, Some(Command::Login { .. }))
+    }
+
+    /// Whether a login may open a browser (`login --no-browser` says no)
+    pub fn opens_browser(&self) -> bool {
+        !matches!(self.command, Some(Command::Login { no_browser: true }))
+    }
+
+    pub fn is_doctor(&self) -> bool {
+        matches!(self.command, Some(Command::Doctor))
+    }
+
+    /// `--hook`, `--sync-hook` and `--vdir`; `quiet` for the TUI, which owns the terminal
+    pub fn hooks(&self, quiet: bool) -> Hooks {
+        Hooks {
+            event: self.hook.clone().map(|command| {
+                let lead = self.hook_lead.unwrap_or(DEFAULT_HOOK_LEAD);
+                Hook::new(command, lead, Local::now(), quiet)
+            }),
+            sync: self
+                .sync_hook
+                .clone()
+                .map(|command| SyncHook::new(command, quiet)),
+            vdir: self.vdir.clone().map(Vdir::new),
+            quiet,
+        }
+    }
+
+    /// The command creates, changes or deletes events, which `--read-only` forbids
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Command::Add { .. })
+                | Some(Command::Quick { .. })
+                | Some(Command::Delete { .. })
+                | Some(Command::Edit { .. })
+                | Some(Command::Import { .. })
+                | Some(Command::Rsvp { .. })
+                | Some(Command::Holidays {
+                    country: Some(_),
+                    ..
+                })
+        )
+    }
+
+    /// `--output`, with `--json` of a subcommand taking precedence
+    pub fn output_format(&self) -> OutputFormat {
+        match self.command {
+            Some(Command::List { json: true, .. })
+            | Some(Command::Busy { json: true, .. })
+            | Some(Command::Calendars { json: true }) => OutputFormat::Json,
+            Some(Command::Search { .. }) => self.output.unwrap_or(OutputFormat::Plain),
+            _ => self.output.unwrap_or(OutputFormat::Table),
+        }
+    }
+
+    /// Exit code for failures; `next` and `status` reserve 1 for a negative answer
+    pub fn error_exit_code(&self) -> i32 {
+        match self.command {
+            Some(Command::Next { .. }) => crate::commands::next::EXIT_ERROR,
+            Some(Command::Status) => crate::commands::status::EXIT_ERROR,
+            _ => 1,
+        }
     }
 }
 
@@ -72,4 +735,744 @@ mod tests {
         assert!(!cli.is_login());
         assert!(cli.command.is_none());
     }
+
+    #[test]
+    fn test_cli_next_command_defaults() {
+        let cli = Cli::parse_from(["oxidate", "next"]);
+        match cli.command {
+            Some(Command::Next {
+                ref format,
+                days,
+                all_day,
+                bar,
+            }) => {
+                assert_eq!(format, "{start_rel} {summary}");
+                assert_eq!(days, 7);
+                assert!(!all_day);
+                assert_eq!(bar, None);
+            }
+            _ => panic!("Expected next command"),
+        }
+        assert_eq!(cli.error_exit_code(), 2);
+    }
+
+    #[test]
+    fn test_cli_next_command_format() {
+        let cli = Cli::parse_from(["oxidate", "next", "--format", "{start} {summary}"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Next { ref format, .. }) if format == "{start} {summary}"
+        ));
+    }
+
+    #[test]
+    fn test_cli_list_command() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "list",
+            "--from",
+            "2025-06-01",
+            "--to",
+            "2025-06-30",
+            "--json",
+        ]);
+        match cli.command {
+            Some(Command::List { from, to, json }) => {
+                assert_eq!(from, NaiveDate::from_ymd_opt(2025, 6, 1));
+                assert_eq!(to, NaiveDate::from_ymd_opt(2025, 6, 30));
+                assert!(json);
+            }
+            _ => panic!("Expected list command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_list_rejects_bad_date() {
+        assert!(Cli::try_parse_from(["oxidate", "list", "--from", "June"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_add_command() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "add",
+            "Dentist",
+            "--date",
+            "2025-07-02",
+            "--from",
+            "14:00",
+            "--to",
+            "15:00",
+            "--calendar",
+            "personal",
+        ]);
+        match cli.command {
+            Some(Command::Add {
+                title,
+                date,
+                from,
+                to,
+                calendar,
+                stdin,
+            }) => {
+                assert_eq!(title.as_deref(), Some("Dentist"));
+                assert!(!stdin);
+                assert_eq!(date, NaiveDate::from_ymd_opt(2025, 7, 2));
+                assert_eq!(from, NaiveTime::from_hms_opt(14, 0, 0));
+                assert_eq!(to, NaiveTime::from_hms_opt(15, 0, 0));
+                assert_eq!(calendar.as_deref(), Some("personal"));
+            }
+            _ => panic!("Expected add command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_add_stdin() {
+        let cli = Cli::parse_from(["oxidate", "add", "--stdin", "--calendar", "work"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Add {
+                stdin: true,
+                title: None,
+                ..
+            })
+        ));
+        assert!(cli.is_mutating());
+
+        assert!(Cli::try_parse_from(["oxidate", "add"]).is_err());
+        assert!(Cli::try_parse_from(["oxidate", "add", "Dentist", "--stdin"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_add_rejects_bad_time() {
+        assert!(Cli::try_parse_from(["oxidate", "add", "Dentist", "--from", "2pm"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_delete_command_and_cancel_alias() {
+        for name in ["delete", "cancel"] {
+            let cli = Cli::parse_from(["oxidate", name, "load test", "--all", "-y"]);
+            match cli.command {
+                Some(Command::Delete {
+                    query,
+                    date,
+                    all,
+                    yes,
+                }) => {
+                    assert_eq!(query, "load test");
+                    assert!(date.is_none());
+                    assert!(all);
+                    assert!(yes);
+                }
+                _ => panic!("Expected delete command"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cli_edit_command() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "edit",
+            "abc123",
+            "--title",
+            "Daily standup",
+            "--start",
+            "2025-07-02 09:30",
+            "--add-attendee",
+            "ana@example.com",
+            "--add-attendee",
+            "bo@example.com",
+        ]);
+        match cli.command {
+            Some(Command::Edit {
+                event_id,
+                title,
+                start,
+                add_attendees,
+                editor,
+                ..
+            }) => {
+                assert_eq!(event_id, "abc123");
+                assert_eq!(title.as_deref(), Some("Daily standup"));
+                assert!(matches!(start, Some(When::DateTime(_))));
+                assert_eq!(add_attendees.len(), 2);
+                assert!(!editor);
+            }
+            _ => panic!("Expected edit command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_search_command() {
+        let cli = Cli::parse_from(["oxidate", "search", "1:1 with alex", "--from", "2025-06-01"]);
+        match cli.command {
+            Some(Command::Search { query, from, to }) => {
+                assert_eq!(query, "1:1 with alex");
+                assert_eq!(from, NaiveDate::from_ymd_opt(2025, 6, 1));
+                assert!(to.is_none());
+            }
+            _ => panic!("Expected search command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_import_command() {
+        let cli = Cli::parse_from(["oxidate", "import", "invite.ics", "--calendar", "Work"]);
+        match cli.command {
+            Some(Command::Import {
+                file,
+                calendar,
+                day_first,
+            }) => {
+                assert_eq!(file, PathBuf::from("invite.ics"));
+                assert_eq!(calendar.as_deref(), Some("Work"));
+                assert!(!day_first);
+            }
+            _ => panic!("Expected import command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_busy_command() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "busy",
+            "--from",
+            "9:00",
+            "--to",
+            "18:00",
+            "--date",
+            "2025-06-16",
+        ]);
+        match cli.command {
+            Some(Command::Busy {
+                date,
+                from,
+                to,
+                json,
+            }) => {
+                assert_eq!(date, NaiveDate::from_ymd_opt(2025, 6, 16).unwrap());
+                assert_eq!(from, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+                assert_eq!(to, NaiveTime::from_hms_opt(18, 0, 0).unwrap());
+                assert!(!json);
+            }
+            _ => panic!("Expected busy command"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_day() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+
+        assert_eq!(resolve_day("today", today), Ok(today));
+        assert_eq!(
+            resolve_day("Tomorrow", today),
+            Ok(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap())
+        );
+        assert_eq!(
+            resolve_day("2025-07-02", today),
+            Ok(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap())
+        );
+        assert!(resolve_day("someday", today).is_err());
+    }
+
+    #[test]
+    fn test_cli_calendars_command() {
+        let cli = Cli::parse_from(["oxidate", "calendars", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Calendars { json: true })
+        ));
+    }
+
+    #[test]
+    fn test_cli_calendar_filter_flag() {
+        let cli = Cli::parse_from(["oxidate", "--calendar", "Work", "--calendar", "Personal"]);
+        assert_eq!(cli.calendars, vec!["Work", "Personal"]);
+        assert!(cli.command.is_none());
+
+        let cli = Cli::parse_from(["oxidate"]);
+        assert!(cli.calendars.is_empty());
+    }
+
+    #[test]
+    fn test_cli_read_only_flag() {
+        let cli = Cli::parse_from(["oxidate", "--read-only"]);
+        assert!(cli.read_only);
+        assert!(!cli.is_mutating());
+
+        let cli = Cli::parse_from(["oxidate", "login", "--read-only"]);
+        assert!(cli.read_only);
+        assert!(cli.is_login());
+
+        let cli = Cli::parse_from(["oxidate", "delete", "Standup"]);
+        assert!(!cli.read_only);
+        assert!(cli.is_mutating());
+    }
+
+    #[test]
+    fn test_cli_watch_command() {
+        let cli = Cli::parse_from(["oxidate", "watch"]);
+        match cli.command {
+            Some(Command::Watch {
+                lead, ref format, ..
+            }) => {
+                assert_eq!(lead, 5);
+                assert_eq!(format, "{start} {summary}");
+            }
+            _ => panic!("Expected Watch command"),
+        }
+
+        let cli = Cli::parse_from(["oxidate", "watch", "--lead", "10"]);
+        assert!(matches!(cli.command, Some(Command::Watch { lead: 10, .. })));
+    }
+
+    #[test]
+    fn test_cli_output_format() {
+        let cli = Cli::parse_from(["oxidate", "list"]);
+        assert_eq!(cli.output_format(), OutputFormat::Table);
+
+        let cli = Cli::parse_from(["oxidate", "search", "standup"]);
+        assert_eq!(cli.output_format(), OutputFormat::Plain);
+
+        let cli = Cli::parse_from(["oxidate", "-o", "ndjson", "search", "standup"]);
+        assert_eq!(cli.output_format(), OutputFormat::Ndjson);
+
+        let cli = Cli::parse_from(["oxidate", "calendars", "--output", "plain"]);
+        assert_eq!(cli.output_format(), OutputFormat::Plain);
+
+        let cli = Cli::parse_from(["oxidate", "busy", "--json", "-o", "table"]);
+        assert_eq!(cli.output_format(), OutputFormat::Json);
+
+        assert!(Cli::try_parse_from(["oxidate", "-o", "yaml", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_doctor_command() {
+        let cli = Cli::parse_from(["oxidate", "doctor", "-o", "json"]);
+        assert!(cli.is_doctor());
+        assert!(!cli.is_login());
+        assert_eq!(cli.output_format(), OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_cli_timezone_flag() {
+        let cli = Cli::parse_from(["oxidate", "list", "--timezone", "Europe/Berlin"]);
+        assert_eq!(cli.timezone.as_deref(), Some("Europe/Berlin"));
+
+        let cli = Cli::parse_from(["oxidate"]);
+        assert_eq!(cli.timezone, None);
+    }
+
+    #[test]
+    fn test_cli_week_command() {
+        let cli = Cli::parse_from(["oxidate", "week", "--date", "2025-06-18", "--width", "100"]);
+        match cli.command {
+            Some(Command::Week { date, width }) => {
+                assert_eq!(date, NaiveDate::from_ymd_opt(2025, 6, 18).unwrap());
+                assert_eq!(width, Some(100));
+            }
+            _ => panic!("Expected Week command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_note_command() {
+        let cli = Cli::parse_from(["oxidate", "note", "--date", "2025-06-18", "--heading", ""]);
+        match cli.command {
+            Some(Command::Note { date, heading }) => {
+                assert_eq!(date, NaiveDate::from_ymd_opt(2025, 6, 18).unwrap());
+                assert_eq!(heading, "");
+            }
+            _ => panic!("Expected Note command"),
+        }
+
+        let cli = Cli::parse_from(["oxidate", "note"]);
+        assert!(
+            matches!(cli.command, Some(Command::Note { ref heading, .. }) if heading == "## Schedule")
+        );
+    }
+
+    #[test]
+    fn test_cli_quick_command() {
+        let cli = Cli::parse_from(["oxidate", "quick", "Coffee with Dana tomorrow 3pm"]);
+        match cli.command {
+            Some(Command::Quick {
+                ref text,
+                ref calendar,
+            }) => {
+                assert_eq!(text, "Coffee with Dana tomorrow 3pm");
+                assert_eq!(calendar, &None);
+            }
+            _ => panic!("Expected Quick command"),
+        }
+        assert!(cli.is_mutating());
+    }
+
+    #[test]
+    fn test_cli_rsvp_command() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "rsvp",
+            "abc123",
+            "decline",
+            "--comment",
+            "Focus time",
+        ]);
+        match cli.command {
+            Some(Command::Rsvp {
+                ref event_id,
+                response,
+                ref comment,
+                ..
+            }) => {
+                assert_eq!(event_id, "abc123");
+                assert_eq!(response, Response::Decline);
+                assert_eq!(comment.as_deref(), Some("Focus time"));
+            }
+            _ => panic!("Expected Rsvp command"),
+        }
+        assert!(cli.is_mutating());
+
+        assert!(Cli::try_parse_from(["oxidate", "rsvp", "abc123", "maybe"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_open_command() {
+        let cli = Cli::parse_from(["oxidate", "open", "standup", "--join"]);
+        match cli.command {
+            Some(Command::Open { ref query, join }) => {
+                assert_eq!(query, "standup");
+                assert!(join);
+            }
+            _ => panic!("Expected Open command"),
+        }
+        assert!(!cli.is_mutating());
+    }
+
+    #[test]
+    fn test_cli_status_command() {
+        let cli = Cli::parse_from(["oxidate", "status"]);
+        assert!(matches!(cli.command, Some(Command::Status)));
+        assert_eq!(cli.error_exit_code(), 2);
+    }
+
+    #[test]
+    fn test_cli_account_flag() {
+        let cli = Cli::parse_from(["oxidate", "--account", "work", "list"]);
+        assert_eq!(cli.account.as_deref(), Some("work"));
+
+        let cli = Cli::parse_from(["oxidate", "login", "--account", "personal"]);
+        assert_eq!(cli.account.as_deref(), Some("personal"));
+        assert!(cli.is_login());
+    }
+
+    #[test]
+    fn test_cli_login_no_browser() {
+        let cli = Cli::parse_from(["oxidate", "login", "--no-browser"]);
+        assert!(cli.is_login());
+        assert!(!cli.opens_browser());
+
+        let cli = Cli::parse_from(["oxidate", "login"]);
+        assert!(cli.opens_browser());
+        assert!(Cli::parse_from(["oxidate"]).opens_browser());
+    }
+
+    #[test]
+    fn test_cli_find_slot() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "find-slot",
+            "--duration",
+            "45m",
+            "--within",
+            "next 3 days",
+            "--hours",
+            "10-16",
+            "--calendar",
+            "work",
+        ]);
+        match cli.command {
+            Some(Command::FindSlot {
+                duration,
+                within,
+                hours,
+                weekends,
+                calendar,
+                limit,
+            }) => {
+                assert_eq!(duration, Some(Duration::minutes(45)));
+                assert_eq!(within, 3);
+                assert_eq!(
+                    hours.unwrap().start,
+                    NaiveTime::from_hms_opt(10, 0, 0).unwrap()
+                );
+                assert!(!weekends);
+                assert_eq!(calendar, vec!["work"]);
+                assert_eq!(limit, 10);
+            }
+            _ => panic!("Expected find-slot command"),
+        }
+
+        assert!(Cli::try_parse_from(["oxidate", "find-slot", "--duration", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_notify_lead_times() {
+        let cli = Cli::parse_from(["oxidate", "--notify", "10,1"]);
+        assert_eq!(cli.notify, vec![10, 1]);
+
+        assert!(Cli::try_parse_from(["oxidate", "--notify", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_hook() {
+        let cli = Cli::parse_from(["oxidate", "watch", "--hook", "dnd on", "--hook-lead", "2"]);
+        assert_eq!(cli.hook.as_deref(), Some("dnd on"));
+        assert_eq!(cli.hook_lead, Some(2));
+
+        let cli = Cli::parse_from(["oxidate"]);
+        assert_eq!(cli.hook, None);
+        assert_eq!(cli.hook_lead, None);
+        assert_eq!(cli.sync_hook, None);
+
+        let cli = Cli::parse_from(["oxidate", "--sync-hook", "notify-new"]);
+        assert_eq!(cli.sync_hook.as_deref(), Some("notify-new"));
+    }
+
+    #[test]
+    fn test_cli_other_timezones() {
+        let cli = Cli::parse_from(["oxidate", "--other-timezones", "Europe/Berlin,Asia/Tokyo"]);
+        assert_eq!(
+            cli.other_timezones,
+            vec![chrono_tz::Europe::Berlin, chrono_tz::Asia::Tokyo]
+        );
+
+        assert!(Cli::try_parse_from(["oxidate", "--other-timezones", "Mars/Olympus"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_index_flag() {
+        let cli = Cli::parse_from(["oxidate", "search", "standup", "--index"]);
+        assert!(cli.index);
+
+        assert!(!Cli::parse_from(["oxidate"]).index);
+    }
+
+    #[test]
+    fn test_cli_holidays() {
+        let cli = Cli::parse_from(["oxidate", "holidays"]);
+        assert!(!cli.is_mutating());
+
+        let cli = Cli::parse_from(["oxidate", "holidays", "br", "--remove"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Holidays {
+                country: Some(ref country),
+                remove: true,
+            }) if country == "br"
+        ));
+        assert!(cli.is_mutating());
+
+        assert!(Cli::try_parse_from(["oxidate", "holidays", "--remove"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_serve_socket() {
+        let cli = Cli::parse_from(["oxidate", "serve", "--socket", "/tmp/ox.sock"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Serve { socket: Some(ref socket) }) if socket == &PathBuf::from("/tmp/ox.sock")
+        ));
+        assert!(!cli.is_mutating());
+    }
+
+    #[test]
+    fn test_cli_vdir() {
+        let cli = Cli::parse_from(["oxidate", "mirror", "--vdir", "/tmp/calendars"]);
+        assert!(matches!(cli.command, Some(Command::Mirror)));
+        assert_eq!(cli.vdir, Some(PathBuf::from("/tmp/calendars")));
+        assert!(cli.hooks(false).vdir.is_some());
+
+        let cli = Cli::parse_from(["oxidate"]);
+        assert!(cli.hooks(true).vdir.is_none());
+    }
+
+    #[test]
+    fn test_cli_local_calendars() {
+        let cli = Cli::parse_from([
+            "oxidate",
+            "--khal",
+            "--text-calendar",
+            "personal.txt,birthdays.txt",
+            "list",
+        ]);
+        assert!(cli.khal);
+        assert!(!cli.tasks);
+        assert_eq!(
+            cli.text_calendar,
+            vec![
+                PathBuf::from("personal.txt"),
+                PathBuf::from("birthdays.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cli_apply_config() {
+        let config: Config = toml::from_str(
+            r#"
+read_only = true
+account = "work"
+
+[display]
+view = "day"
+notify = [10]
+other_timezones = ["Asia/Tokyo"]
+
+[behavior]
+hook = "dnd on"
+hook_lead = 2
+default_calendar = "Work"
+event_duration = 25
+working_days = "sun-thu"
+
+[providers]
+tasks = true
+text_calendars = ["/srv/team.txt"]
+"#,
+        )
+        .unwrap();
+
+        let mut cli = Cli::parse_from(["oxidate", "--notify", "1", "--hook-lead", "7"]);
+        cli.apply_config(&config).unwrap();
+
+        // Flags win, the file fills the rest
+        assert_eq!(cli.notify, vec![1]);
+        assert_eq!(cli.view, Some(StartView::Day));
+        assert_eq!(cli.hook_lead, Some(7));
+        assert_eq!(cli.hook.as_deref(), Some("dnd on"));
+        assert_eq!(cli.default_calendar.as_deref(), Some("Work"));
+        let defaults = cli.event_defaults();
+        assert_eq!(defaults.duration, Duration::minutes(25));
+        assert_eq!(defaults.snap, Duration::minutes(15));
+        let working_hours = cli.working_hours();
+        assert_eq!(working_hours.days[0], chrono::Weekday::Sun);
+        assert_eq!(working_hours.hours, WorkingHours::default().hours);
+        assert_eq!(cli.other_timezones, vec![chrono_tz::Asia::Tokyo]);
+        assert_eq!(cli.text_calendar, vec![PathBuf::from("/srv/team.txt")]);
+        assert!(cli.read_only);
+        assert_eq!(cli.account.as_deref(), Some("work"));
+        assert!(cli.tasks);
+        assert!(!cli.khal);
+
+        let mut config = Config::default();
+        config.display.other_timezones = vec!["Mars/Olympus".to_string()];
+        assert!(Cli::parse_from(["oxidate"]).apply_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_cli_formats() {
+        let mut config = Config::default();
+        config.display.time_format = Some("%H.%M".to_string());
+        config.display.month_title_format = Some("%m/%Y".to_string());
+
+        let mut cli = Cli::parse_from(["oxidate", "--time-format", "%I:%M %p"]);
+        cli.apply_config(&config).unwrap();
+        let formats = cli.formats(&config).unwrap();
+
+        assert_eq!(formats.time, "%I:%M %p");
+        assert_eq!(formats.date, Formats::default().date);
+        assert_eq!(formats.month_title, "%m/%Y");
+
+        let cli = Cli::parse_from(["oxidate", "--locale", "fr_FR.UTF-8"]);
+        assert_eq!(cli.formats(&config).unwrap().locale.code, "fr");
+        let cli = Cli::parse_from(["oxidate", "--locale", "tlh"]);
+        assert!(cli.formats(&config).is_err());
+    }
+
+    #[test]
+    fn test_cli_memory_horizon() {
+        let mut config = Config::default();
+        config.behavior.memory_horizon = Some(6);
+
+        let mut cli = Cli::parse_from(["oxidate"]);
+        cli.apply_config(&config).unwrap();
+        assert_eq!(cli.memory_horizon, Some(6));
+        assert_eq!(cli.settings(&config).unwrap().memory_horizon, 6);
+
+        config.behavior.memory_horizon = Some(1);
+        assert!(Cli::parse_from(["oxidate"]).apply_config(&config).is_err());
+        assert!(Cli::try_parse_from(["oxidate", "--memory-horizon", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_refresh_interval() {
+        let mut config = Config::default();
+        config.behavior.refresh_interval = Some(10);
+
+        let mut cli = Cli::parse_from(["oxidate"]);
+        cli.apply_config(&config).unwrap();
+        assert_eq!(cli.refresh_interval, Some(10));
+        let settings = cli.settings(&config).unwrap();
+        assert_eq!(
+            settings.refresh_interval,
+            Some(std::time::Duration::from_secs(600))
+        );
+
+        let mut cli = Cli::parse_from(["oxidate", "--refresh-interval", "0"]);
+        cli.apply_config(&config).unwrap();
+        assert_eq!(cli.refresh_interval, Some(0));
+        assert_eq!(cli.settings(&config).unwrap().refresh_interval, None);
+    }
+
+    #[test]
+    fn test_cli_view() {
+        let cli = Cli::parse_from(["oxidate", "--view", "day"]);
+        assert_eq!(cli.view, Some(StartView::Day));
+        let cli = Cli::parse_from(["oxidate", "--view", "timeline"]);
+        assert_eq!(cli.view, Some(StartView::Timeline));
+        let cli = Cli::parse_from(["oxidate", "--view", "week"]);
+        assert_eq!(cli.view, Some(StartView::Week));
+        let cli = Cli::parse_from(["oxidate", "--view", "agenda"]);
+        assert_eq!(cli.view, Some(StartView::Agenda));
+        assert!(Cli::try_parse_from(["oxidate", "--view", "year"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_mcp() {
+        let cli = Cli::parse_from(["oxidate", "--read-only", "mcp"]);
+        assert!(matches!(cli.command, Some(Command::Mcp)));
+        assert!(cli.read_only);
+        assert!(!cli.is_mutating());
+    }
+
+    #[test]
+    fn test_cli_bar_output() {
+        let cli = Cli::parse_from(["oxidate", "next", "--bar", "waybar"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Next {
+                bar: Some(Bar::Waybar),
+                ..
+            })
+        ));
+
+        let cli = Cli::parse_from(["oxidate", "watch", "--bar", "tmux", "--interval", "30"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Watch {
+                bar: Some(Bar::Tmux),
+                interval: 30,
+                ..
+            })
+        ));
+
+        assert!(Cli::try_parse_from(["oxidate", "watch", "--interval", "30"]).is_err());
+        assert!(
+            Cli::try_parse_from(["oxidate", "watch", "--bar", "tmux", "--interval", "0"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["oxidate", "next", "--bar", "i3"]).is_err());
+    }
 }
@@ -0,0 +1,210 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use super::resolve_calendar;
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::{Event, EventDateTime};
+use crate::formats::{Formats, Localized};
+use crate::settings::Settings;
+
+/// What the user asked for on the command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewEvent {
+    pub title: String,
+    pub date: Option<NaiveDate>,
+    pub from: Option<NaiveTime>,
+    pub to: Option<NaiveTime>,
+}
+
+impl NewEvent {
+    /// Without a date or time the title is free text for Google's quick add
+    fn is_free_text(&self) -> bool {
+        self.date.is_none() && self.from.is_none() && self.to.is_none()
+    }
+
+    /// Build the event: all-day when no time is given, otherwise timed in local
+    /// time, lasting `duration` unless `to` is given
+    pub fn to_event(&self, today: NaiveDate, duration: Duration) -> Result<Event> {
+        let date = self.date.unwrap_or(today);
+
+        let (start, end) = match (self.from, self.to) {
+            (None, None) => (
+                EventDateTime::from_date(date),
+                EventDateTime::from_date(date + Duration::days(1)),
+            ),
+            (Some(from), to) => {
+                let start = date.and_time(from);
+                // Without --to the event may run past midnight
+                let end = to.map_or(start + duration, |to| date.and_time(to));
+                if end <= start {
+                    bail!("--to ({}) must be after --from ({})", end.time(), from);
+                }
+                (
+                    EventDateTime::from_local(start)?,
+                    EventDateTime::from_local(end)?,
+                )
+            }
+            (None, Some(_)) => bail!("--to requires --from"),
+        };
+
+        Ok(Event {
+            id: String::new(),
+            summary: Some(self.title.clone()),
+            description: None,
+            location: None,
+            start,
+            end,
+            status: None,
+            html_link: None,
+            hangout_link: None,
+            attendees: None,
+            calendar_id: None,
+            updated: None,
+            etag: None,
+            reminders: None,
+            attachments: None,
+            conference_data: None,
+            ical_uid: None,
+        })
+    }
+}
+
+/// Create the event on `calendar` (id or name, primary by default) and print it
+pub async fn run(
+    mut client: CalendarClient,
+    new_event: NewEvent,
+    calendar: Option<&str>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let defaults = &settings.event_defaults;
+    let calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    let calendar = resolve_calendar(&calendars, calendar, defaults)?;
+
+    let created = if new_event.is_free_text() {
+        client
+            .quick_add_event(&calendar.id, &new_event.title)
+            .await
+            .context("Failed to quick-add event")?
+    } else {
+        let event = new_event.to_event(Local::now().date_naive(), defaults.duration)?;
+        client
+            .insert_event(&calendar.id, &event)
+            .await
+            .context("Failed to create event")?
+    };
+
+    if format.is_json() {
+        let record = ListedEvent::of(&created, &calendar.id, &calendar.summary);
+        return output::print(&[record], format, &settings.formats);
+    }
+
+    println!(
+        "{}",
+        describe(&created, &calendar.summary, &settings.formats)
+    );
+    if let Some(ref link) = created.html_link {
+        println!("{}", link);
+    }
+
+    Ok(())
+}
+
+/// e.g. `Created "Dentist" on Wed Jul 2 14:00-15:00 in Personal`
+fn describe(event: &Event, calendar: &str, formats: &Formats) -> String {
+    let summary = event.summary.as_deref().unwrap_or("(No title)");
+    let when = match (event.start.to_local(), event.end.to_local()) {
+        (Some(start), _) if event.start.is_all_day() => {
+            start.localized(&formats.date, formats.locale)
+        }
+        (Some(start), Some(end)) => format!(
+            "{}-{}",
+            start.localized(&formats.date_time(), formats.locale),
+            end.format(&formats.time)
+        ),
+        _ => "an unknown time".to_string(),
+    };
+
+    format!("Created \"{}\" on {} in {}", summary, when, calendar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_event(date: Option<&str>, from: Option<&str>, to: Option<&str>) -> NewEvent {
+        let time = |t: &str| NaiveTime::parse_from_str(t, "%H:%M").unwrap();
+        NewEvent {
+            title: "Dentist".to_string(),
+            date: date.map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()),
+            from: from.map(time),
+            to: to.map(time),
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
+    }
+
+    #[test]
+    fn test_free_text_without_date_or_time() {
+        assert!(new_event(None, None, None).is_free_text());
+        assert!(!new_event(Some("2025-07-02"), None, None).is_free_text());
+        assert!(!new_event(None, Some("14:00"), None).is_free_text());
+    }
+
+    #[test]
+    fn test_date_only_is_all_day() {
+        let event = new_event(Some("2025-07-02"), None, None)
+            .to_event(today(), Duration::hours(1))
+            .unwrap();
+
+        assert_eq!(event.start.date.as_deref(), Some("2025-07-02"));
+        assert_eq!(event.end.date.as_deref(), Some("2025-07-03"));
+        assert_eq!(event.summary.as_deref(), Some("Dentist"));
+    }
+
+    #[test]
+    fn test_timed_event_in_local_time() {
+        let event = new_event(Some("2025-07-02"), Some("14:00"), Some("15:00"))
+            .to_event(today(), Duration::hours(1))
+            .unwrap();
+
+        let start = event.start.to_local().unwrap();
+        let end = event.end.to_local().unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-07-02 14:00"
+        );
+        assert_eq!(end - start, Duration::hours(1));
+    }
+
+    #[test]
+    fn test_from_defaults_to_today_and_one_hour() {
+        let event = new_event(None, Some("09:30"), None)
+            .to_event(today(), Duration::hours(1))
+            .unwrap();
+
+        let start = event.start.to_local().unwrap();
+        assert_eq!(start.date_naive(), today());
+        assert_eq!(event.end.to_local().unwrap() - start, Duration::hours(1));
+    }
+
+    #[test]
+    fn test_invalid_times_are_rejected() {
+        assert!(new_event(None, Some("15:00"), Some("14:00"))
+            .to_event(today(), Duration::hours(1))
+            .is_err());
+        assert!(new_event(None, Some("23:30"), None)
+            .to_event(today(), Duration::hours(1))
+            .is_ok());
+        assert!(new_event(None, None, Some("14:00"))
+            .to_event(today(), Duration::hours(1))
+            .is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::{bail, Context, Result};
+
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Contact;
+
+/// Turn attendees given by name into email addresses using the user's
+/// contacts; values that already are addresses are kept as they are
+pub(super) async fn resolve(client: &mut CalendarClient, values: &[String]) -> Result<Vec<String>> {
+    let mut emails = Vec::with_capacity(values.len());
+
+    for value in values {
+        if value.contains('@') {
+            emails.push(value.clone());
+            continue;
+        }
+
+        let contacts = client
+            .search_contacts(value)
+            .await
+            .with_context(|| format!("Failed to look up \"{}\" in your contacts", value))?;
+        let contact = pick(value, &contacts)?;
+        eprintln!("{} → {}", value, describe(contact));
+        emails.push(contact.email.clone());
+    }
+
+    Ok(emails)
+}
+
+/// The only match, or the one whose full name is exactly `query` when the
+/// search is ambiguous
+fn pick<'a>(query: &str, contacts: &'a [Contact]) -> Result<&'a Contact> {
+    match contacts {
+        [] => bail!(
+            "No contact matches \"{}\"; use their email address instead",
+            query
+        ),
+        [contact] => Ok(contact),
+        _ => {
+            let exact: Vec<&Contact> = contacts
+                .iter()
+                .filter(|contact| {
+                    contact
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| name.eq_ignore_ascii_case(query.trim()))
+                })
+                .collect();
+            if let [contact] = exact[..] {
+                return Ok(contact);
+            }
+
+            let candidates: Vec<String> = contacts.iter().map(describe).collect();
+            bail!(
+                "\"{}\" matches several contacts, use one of:\n  {}",
+                query,
+                candidates.join("\n  ")
+            )
+        }
+    }
+}
+
+/// `Ana Lima <ana@example.com>`, or just the address
+fn describe(contact: &Contact) -> String {
+    match contact.name {
+        Some(ref name) => format!("{} <{}>", name, contact.email),
+        None => contact.email.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact(name: Option<&str>, email: &str) -> Contact {
+        Contact {
+            name: name.map(str::to_string),
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pick_single_match() {
+        let contacts = vec![contact(Some("Ana Lima"), "ana@example.com")];
+
+        assert_eq!(pick("ana", &contacts).unwrap().email, "ana@example.com");
+    }
+
+    #[test]
+    fn test_pick_prefers_exact_name() {
+        let contacts = vec![
+            contact(Some("Ana Lima"), "ana@example.com"),
+            contact(Some("Ana"), "ana@home.example"),
+            contact(None, "analytics@example.com"),
+        ];
+
+        assert_eq!(pick("ana", &contacts).unwrap().email, "ana@home.example");
+    }
+
+    #[test]
+    fn test_pick_ambiguous_lists_candidates() {
+        let contacts = vec![
+            contact(Some("Ana Lima"), "ana@example.com"),
+            contact(None, "analytics@example.com"),
+        ];
+
+        let message = pick("an", &contacts).unwrap_err().to_string();
+        assert!(message.contains("Ana Lima <ana@example.com>"));
+        assert!(message.contains("analytics@example.com"));
+        assert!(pick("bo", &[]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local};
+use clap::ValueEnum;
+use serde_json::json;
+
+use super::next::render;
+use crate::calendar::models::Event;
+use crate::tui::state::AppState;
+
+/// An event starting within this many minutes is shown as `soon`
+const SOON_MINUTES: i64 = 15;
+
+/// Status bars that `next --bar` and `watch --bar` can feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Bar {
+    /// One JSON object with text, tooltip and class
+    Waybar,
+    /// Text with `%{F}` color tags
+    Polybar,
+    /// Text with `#[fg=]` style tags for a status segment
+    Tmux,
+}
+
+/// How close the next event is; waybar gets it as the CSS class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Urgency {
+    None,
+    Later,
+    Soon,
+    Ongoing,
+}
+
+impl Urgency {
+    fn of(event: Option<&Event>, now: DateTime<Local>) -> Self {
+        let Some(start) = event.and_then(|event| event.start.to_local()) else {
+            return Urgency::None;
+        };
+        if start <= now {
+            Urgency::Ongoing
+        } else if start - now <= Duration::minutes(SOON_MINUTES) {
+            Urgency::Soon
+        } else {
+            Urgency::Later
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Urgency::None => "none",
+            Urgency::Later => "later",
+            Urgency::Soon => "soon",
+            Urgency::Ongoing => "ongoing",
+        }
+    }
+}
+
+/// The bar's line for `event` (the next one, if any) with its text from the
+/// `next` template `format`; empty text when nothing is upcoming
+pub(super) fn line(
+    bar: Bar,
+    format: &str,
+    event: Option<&Event>,
+    state: &AppState,
+    now: DateTime<Local>,
+) -> Result<String> {
+    let text = escape(
+        bar,
+        &event
+            .map(|event| render(format, event, state, now))
+            .unwrap_or_default(),
+    );
+    let urgency = Urgency::of(event, now);
+
+    Ok(match bar {
+        Bar::Waybar => {
+            let tooltip = event
+                .map(|event| escape(bar, &tooltip(event, state, now)))
+                .unwrap_or_else(|| "No upcoming events".to_string());
+            serde_json::to_string(&json!({
+                "text": text,
+                "tooltip": tooltip,
+                "class": urgency.class(),
+            }))
+            .context("Failed to serialize bar output")?
+        }
+        Bar::Polybar => match urgency {
+            Urgency::Soon | Urgency::Ongoing => format!("%{{F#f0c674}}{}%{{F-}}", text),
+            Urgency::None | Urgency::Later => text,
+        },
+        Bar::Tmux => match urgency {
+            Urgency::Soon | Urgency::Ongoing => format!("#[fg=yellow]{}#[default]", text),
+            Urgency::None | Urgency::Later => text,
+        },
+    })
+}
+
+/// Event text must not be read as markup: waybar renders Pango, and polybar
+/// and tmux tags can even run commands
+fn escape(bar: Bar, text: &str) -> String {
+    match bar {
+        Bar::Waybar => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        Bar::Polybar => text.replace('%', "%%"),
+        Bar::Tmux => text.replace('#', "##"),
+    }
+}
+
+/// Title, date and times, location and calendar, one per line
+fn tooltip(event: &Event, state: &AppState, now: DateTime<Local>) -> String {
+    let mut lines = vec![render(
+        "{summary}\n{date} {start}–{end} ({start_rel})",
+        event,
+        state,
+        now,
+    )];
+    if event.location.is_some() {
+        lines.push(render("{location}", event, state, now));
+    }
+    if event.calendar_id.is_some() {
+        lines.push(render("{calendar}", event, state, now));
+    }
+    lines.retain(|line| !line.is_empty());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn local_time(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn standup() -> Event {
+        let at = |time: &str| EventDateTime {
+            date_time: Some(local_time(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            id: "standup".to_string(),
+            location: Some("Room 4".to_string()),
+            ..Event::test("Standup", at("10:00"), at("10:15"))
+        }
+    }
+
+    #[test]
+    fn test_urgency() {
+        let event = standup();
+
+        assert_eq!(Urgency::of(None, local_time("09:00")), Urgency::None);
+        assert_eq!(
+            Urgency::of(Some(&event), local_time("09:00")),
+            Urgency::Later
+        );
+        assert_eq!(
+            Urgency::of(Some(&event), local_time("09:45")),
+            Urgency::Soon
+        );
+        assert_eq!(
+            Urgency::of(Some(&event), local_time("10:05")),
+            Urgency::Ongoing
+        );
+    }
+
+    #[test]
+    fn test_waybar_line() {
+        let event = standup();
+        let state = AppState::new();
+
+        let waybar = line(
+            Bar::Waybar,
+            "{start_rel} {summary}",
+            Some(&event),
+            &state,
+            local_time("09:50"),
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&waybar).unwrap();
+
+        assert_eq!(value["text"], "in 10m Standup");
+        assert_eq!(value["class"], "soon");
+        let tooltip = value["tooltip"].as_str().unwrap();
+        assert!(tooltip.starts_with("Standup\n"));
+        assert!(tooltip.ends_with("(in 10m)\nRoom 4"));
+
+        let empty = line(Bar::Waybar, "{summary}", None, &state, local_time("09:50")).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&empty).unwrap(),
+            json!({ "text": "", "tooltip": "No upcoming events", "class": "none" })
+        );
+    }
+
+    #[test]
+    fn test_text_bars_highlight_soon_events() {
+        let event = standup();
+        let state = AppState::new();
+        let text = |bar, time| line(bar, "{summary}", Some(&event), &state, local_time(time));
+
+        assert_eq!(text(Bar::Polybar, "09:00").unwrap(), "Standup");
+        assert_eq!(
+            text(Bar::Polybar, "09:50").unwrap(),
+            "%{F#f0c674}Standup%{F-}"
+        );
+        assert_eq!(
+            text(Bar::Tmux, "10:05").unwrap(),
+            "#[fg=yellow]Standup#[default]"
+        );
+        assert_eq!(
+            line(Bar::Tmux, "{summary}", None, &state, local_time("09:00")).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_event_text_is_escaped() {
+        assert_eq!(escape(Bar::Waybar, "R&D <sync>"), "R&amp;D &lt;sync&gt;");
+        assert_eq!(escape(Bar::Polybar, "%{A:reboot:}"), "%%{A:reboot:}");
+        assert_eq!(escape(Bar::Tmux, "#(reboot)"), "##(reboot)");
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+use super::add::NewEvent;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat, Record};
+use super::resolve_calendar;
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Event;
+use crate::formats::Formats;
+use crate::settings::Settings;
+
+/// One line of `add --stdin`, e.g.
+/// `{"title": "Standup", "date": "2025-07-02", "from": "09:30", "to": "09:45"}`.
+/// Same rules as `add`: no times means all day, `date` defaults to today.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct EventDraft {
+    title: String,
+    date: Option<NaiveDate>,
+    from: Option<NaiveTime>,
+    to: Option<NaiveTime>,
+    location: Option<String>,
+    description: Option<String>,
+}
+
+impl EventDraft {
+    /// The event, lasting `duration` unless `to` is given
+    pub(super) fn to_event(&self, today: NaiveDate, duration: Duration) -> Result<Event> {
+        let new_event = NewEvent {
+            title: self.title.clone(),
+            date: self.date,
+            from: self.from,
+            to: self.to,
+        };
+        let mut event = new_event.to_event(today, duration)?;
+        event.location = self.location.clone();
+        event.description = self.description.clone();
+        Ok(event)
+    }
+}
+
+/// The outcome for one input line
+#[derive(Debug, Serialize)]
+pub struct LineResult {
+    line: usize,
+    pub(super) created: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<ListedEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// LINE, STATUS, DETAIL
+impl Record for LineResult {
+    fn columns(&self, formats: &Formats) -> Vec<String> {
+        let detail = match (&self.event, &self.error) {
+            (Some(event), _) => {
+                let (date, time) = event.local_start(&formats.time);
+                format!("{} {} {}", date, time, event.summary)
+            }
+            (None, Some(error)) => error.clone(),
+            (None, None) => String::new(),
+        };
+        let status = if self.created { "created" } else { "failed" };
+
+        vec![self.line.to_string(), status.to_string(), detail]
+    }
+}
+
+/// Create every draft read from `input` on `calendar` (primary by default),
+/// reporting each line. Fails at the end if any line did.
+pub async fn run(
+    mut client: CalendarClient,
+    mut input: impl Read,
+    calendar: Option<&str>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let defaults = &settings.event_defaults;
+    let mut contents = String::new();
+    input
+        .read_to_string(&mut contents)
+        .context("Failed to read stdin")?;
+    let drafts = parse_drafts(&contents, Local::now().date_naive(), defaults.duration);
+    if drafts.is_empty() {
+        bail!("No events on stdin");
+    }
+
+    let calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    let calendar = resolve_calendar(&calendars, calendar, defaults)?;
+
+    let valid: Vec<Event> = drafts
+        .iter()
+        .filter_map(|(_, draft)| draft.as_ref().ok().cloned())
+        .collect();
+    let created = client
+        .insert_events(&calendar.id, &valid)
+        .await
+        .context("Failed to create events")?;
+
+    let results = line_results(drafts, created, &calendar.id, &calendar.summary);
+    output::print(&results, format, &settings.formats)?;
+
+    let failed = results.iter().filter(|result| !result.created).count();
+    if failed > 0 {
+        bail!("{} of {} events failed", failed, results.len());
+    }
+    Ok(())
+}
+
+/// Each non-blank line (1-based) with its event or why it is invalid
+fn parse_drafts(
+    contents: &str,
+    today: NaiveDate,
+    duration: Duration,
+) -> Vec<(usize, Result<Event>)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let event = serde_json::from_str::<EventDraft>(line)
+                .context("Invalid event JSON")
+                .and_then(|draft| draft.to_event(today, duration));
+            (i + 1, event)
+        })
+        .collect()
+}
+
+/// Pair the API results, which cover only the valid drafts, back up with their lines
+pub(super) fn line_results(
+    drafts: Vec<(usize, Result<Event>)>,
+    created: Vec<Result<Event>>,
+    calendar_id: &str,
+    calendar: &str,
+) -> Vec<LineResult> {
+    let mut created = created.into_iter();
+
+    drafts
+        .into_iter()
+        .map(|(line, draft)| {
+            let outcome = draft.and_then(|_| {
+                created
+                    .next()
+                    .unwrap_or_else(|| Err(anyhow::anyhow!("No response from the API")))
+            });
+            match outcome {
+                Ok(event) => LineResult {
+                    line,
+                    created: true,
+                    event: Some(ListedEvent::of(&event, calendar_id, calendar)),
+                    error: None,
+                },
+                Err(e) => LineResult {
+                    line,
+                    created: false,
+                    event: None,
+                    error: Some(format!("{:#}", e)),
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
+    }
+
+    #[test]
+    fn test_parse_drafts() {
+        let input = r#"{"title": "Standup", "date": "2025-07-02", "from": "09:30", "to": "09:45"}
+
+{"title": "Holiday", "date": "2025-07-04", "location": "Beach"}
+not json
+{"title": "Backwards", "from": "10:00", "to": "09:00"}
+"#;
+
+        let drafts = parse_drafts(input, today(), Duration::hours(1));
+
+        let lines: Vec<usize> = drafts.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![1, 3, 4, 5]);
+
+        let standup = drafts[0].1.as_ref().unwrap();
+        let start = standup.start.to_local().unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-07-02 09:30"
+        );
+
+        let holiday = drafts[1].1.as_ref().unwrap();
+        assert!(holiday.start.is_all_day());
+        assert_eq!(holiday.location.as_deref(), Some("Beach"));
+
+        assert!(drafts[2].1.is_err());
+        assert!(drafts[3].1.is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let drafts = parse_drafts(
+            r#"{"title": "Standup", "start": "09:30"}"#,
+            today(),
+            Duration::hours(1),
+        );
+
+        assert!(drafts[0].1.is_err());
+    }
+
+    #[test]
+    fn test_line_results_pair_api_results_with_lines() {
+        let input = "{\"title\": \"One\"}\nbroken\n{\"title\": \"Two\"}\n";
+        let drafts = parse_drafts(input, today(), Duration::hours(1));
+        let mut one = drafts[0].1.as_ref().unwrap().clone();
+        one.id = "evt1".to_string();
+        let created = vec![Ok(one), Err(anyhow::anyhow!("Invalid start time. (400)"))];
+
+        let results = line_results(drafts, created, "primary", "Me");
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].created);
+        assert_eq!(results[0].event.as_ref().unwrap().id, "evt1");
+        assert_eq!(results[1].line, 2);
+        assert!(!results[1].created);
+        assert_eq!(results[2].columns(&Formats::default())[1], "failed");
+        assert_eq!(
+            results[2].columns(&Formats::default())[2],
+            "Invalid start time. (400)"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::Serialize;
+
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::TimePeriod;
+use crate::formats::{Formats, Localized};
+use crate::settings::Settings;
+use crate::tui::calendar_prefs::CalendarPrefs;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Availability {
+    Busy,
+    Free,
+}
+
+/// A stretch of the requested window that is entirely busy or entirely free
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Interval {
+    pub status: Availability,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+/// STATUS, FROM, TO as local times of day
+impl Record for Interval {
+    fn columns(&self, formats: &Formats) -> Vec<String> {
+        let status = match self.status {
+            Availability::Busy => "busy",
+            Availability::Free => "free",
+        };
+        vec![
+            status.to_string(),
+            self.start.format(&formats.time).to_string(),
+            self.end.format(&formats.time).to_string(),
+        ]
+    }
+}
+
+/// Print busy/free intervals of the visible calendars between `from` and `to` on `date`
+pub async fn run(
+    mut client: CalendarClient,
+    date: NaiveDate,
+    from: NaiveTime,
+    to: NaiveTime,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    if to <= from {
+        bail!("--to ({}) must be after --from ({})", to, from);
+    }
+    let (start, end) = window(date, from, to)?;
+
+    let intervals = availability(&mut client, start, end).await?;
+    if format == OutputFormat::Table {
+        let formats = &settings.formats;
+        println!("{}", date.localized(&formats.date, formats.locale));
+    }
+    output::print(&intervals, format, &settings.formats)
+}
+
+/// `from` and `to` on `date` in the local time zone
+pub(super) fn window(
+    date: NaiveDate,
+    from: NaiveTime,
+    to: NaiveTime,
+) -> Result<(DateTime<Local>, DateTime<Local>)> {
+    let local = |time: NaiveTime| {
+        Local
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .with_context(|| format!("{} {} does not exist in the local time zone", date, time))
+    };
+    Ok((local(from)?, local(to)?))
+}
+
+/// Busy and free intervals of the visible calendars between `start` and `end`
+pub(super) async fn availability(
+    client: &mut CalendarClient,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+) -> Result<Vec<Interval>> {
+    let hidden = CalendarPrefs::load()
+        .map(|prefs| prefs.hidden)
+        .unwrap_or_default();
+    let calendar_ids: Vec<String> = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?
+        .into_iter()
+        .map(|calendar| calendar.id)
+        .filter(|id| !hidden.contains(id))
+        .collect();
+
+    let free_busy = client
+        .free_busy(
+            &calendar_ids,
+            start.with_timezone(&Utc),
+            end.with_timezone(&Utc),
+        )
+        .await
+        .context("Failed to query free/busy")?;
+    for id in &free_busy.unavailable {
+        eprintln!("Free/busy unavailable for {}", id);
+    }
+
+    Ok(intervals(&free_busy.busy, start, end))
+}
+
+/// Split `start..end` into alternating busy and free intervals, merging
+/// overlapping busy periods from different calendars
+pub(super) fn intervals(
+    busy: &[TimePeriod],
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+) -> Vec<Interval> {
+    let mut periods: Vec<(DateTime<Local>, DateTime<Local>)> = busy
+        .iter()
+        .map(|period| {
+            (
+                period.start.with_timezone(&Local).max(start),
+                period.end.with_timezone(&Local).min(end),
+            )
+        })
+        .filter(|(from, to)| from < to)
+        .collect();
+    periods.sort();
+
+    let mut result: Vec<Interval> = Vec::new();
+    let mut cursor = start;
+    for (from, to) in periods {
+        if from > cursor {
+            result.push(Interval {
+                status: Availability::Free,
+                start: cursor,
+                end: from,
+            });
+        }
+        match result.last_mut() {
+            Some(last) if last.status == Availability::Busy && last.end >= from => {
+                last.end = last.end.max(to);
+            }
+            _ => result.push(Interval {
+                status: Availability::Busy,
+                start: from,
+                end: to,
+            }),
+        }
+        cursor = cursor.max(to);
+    }
+    if cursor < end {
+        result.push(Interval {
+            status: Availability::Free,
+            start: cursor,
+            end,
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn period(from: &str, to: &str) -> TimePeriod {
+        TimePeriod {
+            start: at(from).with_timezone(&Utc),
+            end: at(to).with_timezone(&Utc),
+        }
+    }
+
+    fn summary(intervals: &[Interval]) -> Vec<(Availability, DateTime<Local>, DateTime<Local>)> {
+        intervals
+            .iter()
+            .map(|interval| (interval.status, interval.start, interval.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_intervals_merge_overlaps_and_fill_gaps() {
+        let busy = vec![
+            period("10:30", "11:00"),
+            period("09:30", "10:00"),
+            period("09:45", "10:15"),
+            period("10:15", "10:20"),
+        ];
+
+        let result = intervals(&busy, at("09:00"), at("12:00"));
+
+        assert_eq!(
+            summary(&result),
+            vec![
+                (Availability::Free, at("09:00"), at("09:30")),
+                (Availability::Busy, at("09:30"), at("10:20")),
+                (Availability::Free, at("10:20"), at("10:30")),
+                (Availability::Busy, at("10:30"), at("11:00")),
+                (Availability::Free, at("11:00"), at("12:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intervals_clip_to_window() {
+        let busy = vec![period("08:00", "09:30"), period("11:30", "13:00")];
+
+        let result = intervals(&busy, at("09:00"), at("12:00"));
+
+        assert_eq!(
+            summary(&result),
+            vec![
+                (Availability::Busy, at("09:00"), at("09:30")),
+                (Availability::Free, at("09:30"), at("11:30")),
+                (Availability::Busy, at("11:30"), at("12:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intervals_all_free() {
+        let result = intervals(&[], at("09:00"), at("12:00"));
+
+        assert_eq!(
+            summary(&result),
+            vec![(Availability::Free, at("09:00"), at("12:00"))]
+        );
+    }
+
+    #[test]
+    fn test_interval_columns() {
+        let interval = Interval {
+            status: Availability::Free,
+            start: at("09:00"),
+            end: at("10:00"),
+        };
+
+        let columns = interval.columns(&Formats::default());
+
+        assert_eq!(columns[0], "free");
+        assert_eq!(columns[1], at("09:00").format("%H:%M").to_string());
+    }
+
+    #[test]
+    fn test_interval_json() {
+        let interval = Interval {
+            status: Availability::Busy,
+            start: at("09:00"),
+            end: at("10:00"),
+        };
+
+        let json = serde_json::to_value(&interval).unwrap();
+
+        assert_eq!(json["status"], "busy");
+        assert_eq!(json["start"], serde_json::to_value(at("09:00")).unwrap());
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::formats::Formats;
+use crate::tui::calendar_prefs::CalendarPrefs;
+use crate::tui::state::AppState;
+
+/// One calendar as printed by `calendars`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ListedCalendar {
+    pub id: String,
+    pub name: String,
+    pub access_role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    pub primary: bool,
+    /// Hidden in the TUI (and skipped by other commands)
+    pub hidden: bool,
+}
+
+/// ID, NAME, ROLE, COLOR, FLAGS
+impl Record for ListedCalendar {
+    fn columns(&self, _formats: &Formats) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.primary {
+            flags.push("primary");
+        }
+        if self.hidden {
+            flags.push("hidden");
+        }
+
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.access_role.clone(),
+            self.color.clone().unwrap_or_else(|| "-".to_string()),
+            flags.join(","),
+        ]
+    }
+}
+
+/// Print the user's calendars in TUI order
+pub async fn run(
+    mut client: CalendarClient,
+    formats: &Formats,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut state = AppState::new();
+    if let Ok(prefs) = CalendarPrefs::load() {
+        state.apply_calendar_prefs(prefs);
+    }
+    state.calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+
+    output::print(&listed_calendars(&state), format, formats)
+}
+
+fn listed_calendars(state: &AppState) -> Vec<ListedCalendar> {
+    state
+        .ordered_calendars()
+        .into_iter()
+        .map(|calendar| ListedCalendar {
+            id: calendar.id.clone(),
+            name: calendar.summary.clone(),
+            access_role: calendar.access_role.clone(),
+            color: calendar.background_color.clone(),
+            primary: calendar.primary,
+            hidden: !state.is_calendar_visible(&calendar.id),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::Calendar;
+
+    fn calendar(id: &str, summary: &str, primary: bool) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary,
+            time_zone: "UTC".to_string(),
+            access_role: if primary { "owner" } else { "reader" }.to_string(),
+            background_color: primary.then(|| "#9fe1e7".to_string()),
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    fn state() -> AppState {
+        let mut state = AppState::new();
+        state.calendars = vec![
+            calendar("holidays", "Holidays", false),
+            calendar("me", "Me", true),
+        ];
+        state.filter.hidden_calendars.insert("holidays".to_string());
+        state
+    }
+
+    #[test]
+    fn test_listed_calendars_in_tui_order() {
+        let calendars = listed_calendars(&state());
+
+        assert_eq!(calendars[0].id, "me");
+        assert!(calendars[0].primary);
+        assert!(!calendars[0].hidden);
+        assert_eq!(calendars[1].id, "holidays");
+        assert!(calendars[1].hidden);
+    }
+
+    #[test]
+    fn test_columns() {
+        let calendars = listed_calendars(&state());
+
+        assert_eq!(
+            calendars[0].columns(&Formats::default()),
+            vec!["me", "Me", "owner", "#9fe1e7", "primary"]
+        );
+        assert_eq!(
+            calendars[1].columns(&Formats::default()),
+            vec!["holidays", "Holidays", "reader", "-", "hidden"]
+        );
+    }
+}
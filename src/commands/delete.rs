@@ -0,0 +1,237 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use super::{default_search_range, load_state};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Event;
+use crate::formats::{Formats, Localized};
+use crate::settings::Settings;
+use crate::tui::state::{AppState, DateRange};
+
+/// Delete events matching `query` (an event id or words of the title)
+pub async fn run(
+    mut client: CalendarClient,
+    query: &str,
+    date: Option<NaiveDate>,
+    all: bool,
+    yes: bool,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let range = match date {
+        Some(date) => DateRange {
+            start: date,
+            end: date + Duration::days(1),
+        },
+        None => default_search_range(Local::now().date_naive()),
+    };
+    let state = load_state(&mut client, range.clone(), settings).await?;
+
+    let formats = &settings.formats;
+    let matches = find_matches(&state, query, &range);
+    match matches.len() {
+        0 => bail!("No event matches \"{}\"", query),
+        1 => {}
+        _ if all => {}
+        _ => {
+            let candidates: Vec<String> = matches.iter().map(|e| describe(e, formats)).collect();
+            bail!(
+                "{} events match \"{}\"; pass an event id, --date or --all:\n  {}",
+                matches.len(),
+                query,
+                candidates.join("\n  ")
+            );
+        }
+    }
+
+    if !yes && !confirm(&matches, formats)? {
+        if format.is_json() {
+            return output::print::<ListedEvent>(&[], format, formats);
+        }
+        println!("Nothing deleted");
+        return Ok(());
+    }
+
+    let mut deleted = Vec::new();
+    for event in matches {
+        let calendar_id = event
+            .calendar_id
+            .as_deref()
+            .context("Event has no calendar")?;
+        client
+            .delete_event(calendar_id, &event.id)
+            .await
+            .with_context(|| format!("Failed to delete {}", describe(event, formats)))?;
+        if !format.is_json() {
+            println!("Deleted {}", describe(event, formats));
+        }
+        deleted.push(ListedEvent::from_state(event, &state));
+    }
+
+    if format.is_json() {
+        output::print(&deleted, format, formats)?;
+    }
+    Ok(())
+}
+
+/// An exact event id wins; otherwise every word of `query` must appear in the title
+pub(super) fn find_matches<'a>(
+    state: &'a AppState,
+    query: &str,
+    range: &DateRange,
+) -> Vec<&'a Event> {
+    let events: Vec<&Event> = range
+        .start
+        .iter_days()
+        .take_while(|date| *date < range.end)
+        .flat_map(|date| state.get_events_for_date(date))
+        .collect();
+
+    if let Some(event) = events.iter().find(|event| event.id == query) {
+        return vec![event];
+    }
+
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    events
+        .into_iter()
+        .filter(|event| {
+            let summary = event.summary.as_deref().unwrap_or_default().to_lowercase();
+            words.iter().all(|word| summary.contains(word.as_str()))
+        })
+        .collect()
+}
+
+/// Ask on the terminal; without one there is nobody to ask, so `--yes` is required.
+/// The prompt goes to stderr to keep stdout parseable.
+fn confirm(events: &[&Event], formats: &Formats) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        bail!("Refusing to delete without confirmation; pass --yes");
+    }
+
+    for event in events {
+        eprintln!("  {}", describe(event, formats));
+    }
+    eprint!("Delete {} event(s)? [y/N] ", events.len());
+    io::stderr().flush().context("Failed to write prompt")?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// e.g. `"Standup" on Wed Jul 2 09:00 (abc123)`
+fn describe(event: &Event, formats: &Formats) -> String {
+    let when = match event.start.to_local() {
+        Some(start) if event.start.is_all_day() => start.localized(&formats.date, formats.locale),
+        Some(start) => start.localized(&formats.date_time(), formats.locale),
+        None => "an unknown date".to_string(),
+    };
+
+    format!(
+        "\"{}\" on {} ({})",
+        event.summary.as_deref().unwrap_or("(No title)"),
+        when,
+        event.id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn create_event(id: &str, summary: &str, date: NaiveDate) -> Event {
+        let start = EventDateTime {
+            date_time: None,
+            date: Some(date.format("%Y-%m-%d").to_string()),
+            time_zone: None,
+        };
+        Event {
+            id: id.to_string(),
+            calendar_id: Some("work".to_string()),
+            ..Event::test(summary, start.clone(), start)
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, day).unwrap()
+    }
+
+    fn state_with(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        for event in events {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    fn june() -> DateRange {
+        DateRange {
+            start: date(1),
+            end: date(30),
+        }
+    }
+
+    #[test]
+    fn test_find_matches_by_id() {
+        let state = state_with(vec![
+            create_event("abc123", "Standup", date(10)),
+            create_event("def456", "abc123 review", date(11)),
+        ]);
+
+        let matches = find_matches(&state, "abc123", &june());
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, "abc123");
+    }
+
+    #[test]
+    fn test_find_matches_by_title_words() {
+        let state = state_with(vec![
+            create_event("1", "Generated: load test", date(10)),
+            create_event("2", "Load test retro", date(11)),
+            create_event("3", "Standup", date(12)),
+        ]);
+
+        let ids: Vec<&str> = find_matches(&state, "LOAD test", &june())
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["1", "2"]);
+        assert!(find_matches(&state, "   ", &june()).is_empty());
+    }
+
+    #[test]
+    fn test_find_matches_respects_range() {
+        let state = state_with(vec![create_event("1", "Standup", date(10))]);
+        let range = DateRange {
+            start: date(11),
+            end: date(12),
+        };
+
+        assert!(find_matches(&state, "standup", &range).is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        let event = create_event("abc123", "Standup", date(10));
+
+        assert_eq!(
+            describe(&event, &Formats::default()),
+            "\"Standup\" on Tue Jun 10 (abc123)"
+        );
+    }
+}
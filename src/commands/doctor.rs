@@ -0,0 +1,437 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::Duration;
+
+use super::output::{self, OutputFormat, Record};
+use crate::auth::Tokens;
+use crate::calendar::client::CalendarClient;
+use crate::calendar::khal;
+use crate::config::Config;
+use crate::formats::Formats;
+use crate::tui::app::{MIN_HEIGHT, MIN_PANE_WIDTH};
+use crate::tui::calendar_prefs::CalendarPrefs;
+
+/// Endpoints oxidate talks to: token exchange/refresh and the Calendar API
+const ENDPOINTS: &[&str] = &[
+    "https://oauth2.googleapis.com",
+    "https://www.googleapis.com",
+];
+const TOKEN_INFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+const RELOGIN: &str = "Run `oxidate --logout` and then `oxidate login`";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// The outcome of one check, with a suggested fix when it didn't pass
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: Status::Fail,
+            ..Self::warn(name, detail, fix)
+        }
+    }
+}
+
+/// STATUS, CHECK, DETAIL, FIX
+impl Record for Check {
+    fn columns(&self, _formats: &Formats) -> Vec<String> {
+        let status = match self.status {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        };
+        vec![
+            status.to_string(),
+            self.name.clone(),
+            self.detail.clone(),
+            self.fix.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// Run every check and print the results; returns false when any check failed.
+/// `khal` tells whether `--khal` is on.
+pub async fn run(
+    formats: &Formats,
+    format: OutputFormat,
+    khal: bool,
+    profile: Option<&str>,
+) -> Result<bool> {
+    let mut checks = vec![check_config(profile), check_calendar_prefs()];
+    checks.extend(check_khal(khal));
+
+    let tokens = match Tokens::load() {
+        Ok(tokens) => Some(tokens),
+        Err(_) if !Tokens::exists() => {
+            checks.push(Check::fail(
+                "credentials",
+                "not logged in",
+                "Run `oxidate login`",
+            ));
+            None
+        }
+        Err(e) => {
+            checks.push(Check::fail("credentials", format!("{:#}", e), RELOGIN));
+            None
+        }
+    };
+    if tokens.is_some() {
+        checks.push(Check::ok("credentials", "stored"));
+        if let Ok(path) = Tokens::get_storage_path() {
+            checks.extend(check_token_permissions(&path));
+        }
+    }
+
+    let http_client = reqwest::Client::builder()
+        .timeout(NETWORK_TIMEOUT)
+        .build()?;
+    let mut online = true;
+    for endpoint in ENDPOINTS {
+        let check = check_endpoint(&http_client, endpoint).await;
+        online &= check.status == Status::Ok;
+        checks.push(check);
+    }
+
+    if let (Some(tokens), true) = (tokens, online) {
+        checks.push(check_access_token(&http_client, &tokens).await);
+        checks.push(check_calendar_api(tokens).await);
+    }
+
+    checks.push(check_terminal(
+        std::io::stdout().is_terminal(),
+        std::env::var("TERM").ok().as_deref(),
+        crossterm::terminal::size().ok(),
+        std::env::var("COLORTERM").ok().as_deref(),
+    ));
+
+    output::print(&checks, format, formats)?;
+    Ok(checks.iter().all(|check| check.status != Status::Fail))
+}
+
+fn check_config(profile: Option<&str>) -> Check {
+    let path = Config::get_storage_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "config.toml".to_string());
+
+    match Config::load(profile) {
+        Ok(_) => match profile {
+            Some(profile) => Check::ok("config", format!("{} (profile {})", path, profile)),
+            None => Check::ok("config", path),
+        },
+        Err(e) => Check::fail(
+            "config",
+            format!("{:#}", e),
+            format!("Fix or remove {}", path),
+        ),
+    }
+}
+
+fn check_calendar_prefs() -> Check {
+    match CalendarPrefs::load() {
+        Ok(prefs) => Check::ok(
+            "calendar preferences",
+            format!(
+                "{} ordered, {} hidden",
+                prefs.order.len(),
+                prefs.hidden.len()
+            ),
+        ),
+        Err(e) => Check::warn(
+            "calendar preferences",
+            format!("{:#}", e),
+            "Remove calendars.json from the oxidate config directory to reset order and visibility",
+        ),
+    }
+}
+
+/// Offers an existing khal setup's calendars; no check without one
+fn check_khal(enabled: bool) -> Option<Check> {
+    let path = khal::config_path()?;
+    let collections = khal::discover().map(|collections| collections.len());
+    Some(khal_check(&path, collections, enabled))
+}
+
+fn khal_check(path: &Path, collections: Result<usize>, enabled: bool) -> Check {
+    const NAME: &str = "khal";
+
+    match collections {
+        Ok(count) if enabled => {
+            Check::ok(NAME, format!("{} calendars from {}", count, path.display()))
+        }
+        Ok(count) => Check::warn(
+            NAME,
+            format!("{} calendars in {} are not shown", count, path.display()),
+            "Pass --khal or set OXIDATE_KHAL=1 to show them",
+        ),
+        Err(e) => Check::warn(
+            NAME,
+            format!("{:#}", e),
+            format!("Fix {} to use its calendars", path.display()),
+        ),
+    }
+}
+
+#[cfg(unix)]
+fn check_token_permissions(path: &Path) -> Option<Check> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    Some(permissions_check(mode, path))
+}
+
+#[cfg(not(unix))]
+fn check_token_permissions(_path: &Path) -> Option<Check> {
+    None
+}
+
+/// Tokens grant access to the calendar; nobody but the owner should read them
+#[cfg_attr(not(unix), allow(dead_code))]
+fn permissions_check(mode: u32, path: &Path) -> Check {
+    let mode = mode & 0o777;
+    if mode & 0o077 == 0 {
+        Check::ok("token permissions", format!("{:o}", mode))
+    } else {
+        Check::warn(
+            "token permissions",
+            format!("{:o}, readable by other users", mode),
+            format!("chmod 600 {}", path.display()),
+        )
+    }
+}
+
+/// Any HTTP answer proves reachability; only transport errors fail
+async fn check_endpoint(client: &reqwest::Client, url: &str) -> Check {
+    let name = format!("reach {}", url.trim_start_matches("https://"));
+    match client.head(url).send().await {
+        Ok(_) => Check::ok(&name, "reachable"),
+        Err(e) => Check::fail(
+            &name,
+            e.to_string(),
+            "Check the network connection, proxy settings (HTTPS_PROXY) and firewall",
+        ),
+    }
+}
+
+async fn check_access_token(client: &reqwest::Client, tokens: &Tokens) -> Check {
+    let info: Option<serde_json::Value> = match client
+        .get(TOKEN_INFO_URL)
+        .query(&[("access_token", &tokens.access_token)])
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => response.json().await.ok(),
+        _ => None,
+    };
+
+    match info {
+        Some(info) => Check::ok("access token", token_detail(&info)),
+        // Access tokens live an hour; the client refreshes them when rejected
+        None => Check::ok("access token", "expired, renewed on the next request"),
+    }
+}
+
+/// e.g. "valid for 42m, read-only"
+fn token_detail(info: &serde_json::Value) -> String {
+    let expires_in = match &info["expires_in"] {
+        serde_json::Value::String(seconds) => seconds.parse().ok(),
+        value => value.as_i64(),
+    };
+    let access = match info["scope"].as_str() {
+        Some(scope) if scope.contains("calendar.readonly") => "read-only",
+        Some(_) => "read-write",
+        None => "unknown scope",
+    };
+
+    match expires_in {
+        Some(seconds) => format!("valid for {}m, {}", seconds / 60, access),
+        None => format!("valid, {}", access),
+    }
+}
+
+async fn check_calendar_api(tokens: Tokens) -> Check {
+    let mut client = match CalendarClient::new(tokens) {
+        Ok(client) => client,
+        Err(e) => {
+            return Check::fail(
+                "calendar api",
+                format!("{:#}", e),
+                "Rebuild with GOOGLE_CLIENT_ID and GOOGLE_CLIENT_SECRET set",
+            )
+        }
+    };
+
+    match client.list_calendars().await {
+        Ok(calendars) => Check::ok("calendar api", format!("{} calendars", calendars.len())),
+        Err(e) => Check::fail("calendar api", format!("{:#}", e), RELOGIN),
+    }
+}
+
+/// The TUI needs a terminal of at least the minimum size; colors depend on TERM/COLORTERM
+fn check_terminal(
+    is_terminal: bool,
+    term: Option<&str>,
+    size: Option<(u16, u16)>,
+    colorterm: Option<&str>,
+) -> Check {
+    const NAME: &str = "terminal";
+
+    if !is_terminal {
+        return Check::warn(
+            NAME,
+            "stdout is not a terminal",
+            "Run `oxidate` directly in a terminal to use the TUI",
+        );
+    }
+    let term = match term {
+        Some(term) if !term.is_empty() && term != "dumb" => term,
+        _ => {
+            return Check::warn(
+                NAME,
+                format!("TERM is {}", term.unwrap_or("unset")),
+                "Set TERM, e.g. TERM=xterm-256color",
+            )
+        }
+    };
+    let colors = match colorterm {
+        Some("truecolor") | Some("24bit") => "true color",
+        _ if term.contains("256color") => "256 colors",
+        _ => "basic colors",
+    };
+
+    match size {
+        Some((width, height)) if width < MIN_PANE_WIDTH || height < MIN_HEIGHT => Check::warn(
+            NAME,
+            format!("{}, {}x{}, {}", term, width, height, colors),
+            format!(
+                "Enlarge the terminal to at least {}x{}",
+                MIN_PANE_WIDTH, MIN_HEIGHT
+            ),
+        ),
+        Some((width, height)) => {
+            Check::ok(NAME, format!("{}, {}x{}, {}", term, width, height, colors))
+        }
+        None => Check::ok(NAME, format!("{}, {}", term, colors)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_khal_check() {
+        let path = Path::new("/home/me/.config/khal/config");
+
+        let check = khal_check(path, Ok(3), true);
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(
+            check.detail,
+            "3 calendars from /home/me/.config/khal/config"
+        );
+
+        let check = khal_check(path, Ok(3), false);
+        assert_eq!(check.status, Status::Warn);
+        assert_eq!(
+            check.fix.as_deref(),
+            Some("Pass --khal or set OXIDATE_KHAL=1 to show them")
+        );
+
+        let check = khal_check(path, Err(anyhow::anyhow!("no path")), true);
+        assert_eq!(check.status, Status::Warn);
+    }
+
+    #[test]
+    fn test_permissions_check() {
+        let path = Path::new("/home/me/.config/oxidate/token.json");
+
+        assert_eq!(permissions_check(0o100600, path).status, Status::Ok);
+
+        let check = permissions_check(0o100644, path);
+        assert_eq!(check.status, Status::Warn);
+        assert_eq!(check.detail, "644, readable by other users");
+        assert_eq!(
+            check.fix.as_deref(),
+            Some("chmod 600 /home/me/.config/oxidate/token.json")
+        );
+    }
+
+    #[test]
+    fn test_token_detail() {
+        let info = serde_json::json!({
+            "expires_in": "2520",
+            "scope": "https://www.googleapis.com/auth/calendar.readonly"
+        });
+        assert_eq!(token_detail(&info), "valid for 42m, read-only");
+
+        let info = serde_json::json!({
+            "expires_in": 600,
+            "scope": "https://www.googleapis.com/auth/calendar"
+        });
+        assert_eq!(token_detail(&info), "valid for 10m, read-write");
+    }
+
+    #[test]
+    fn test_check_terminal() {
+        let check = check_terminal(true, Some("xterm-256color"), Some((120, 40)), None);
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(check.detail, "xterm-256color, 120x40, 256 colors");
+
+        let check = check_terminal(true, Some("xterm"), Some((80, 10)), Some("truecolor"));
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.fix.unwrap().contains("at least 30x17"));
+
+        assert_eq!(
+            check_terminal(true, Some("dumb"), None, None).status,
+            Status::Warn
+        );
+        assert_eq!(
+            check_terminal(false, Some("xterm"), None, None).status,
+            Status::Warn
+        );
+    }
+
+    #[test]
+    fn test_check_columns() {
+        let check = Check::fail("config", "invalid", "Fix it");
+
+        assert_eq!(
+            check.columns(&Formats::default()),
+            vec!["FAIL", "config", "invalid", "Fix it"]
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::process;
+
+use super::attendees;
+use super::find_calendar;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::{Attendee, Calendar, Event, EventDateTime, EventPatch};
+use crate::settings::Settings;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A start or end given on the command line: a day for all-day events, or a local time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum When {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl When {
+    fn to_event_date_time(self) -> Result<EventDateTime> {
+        Ok(match self {
+            When::Date(date) => EventDateTime {
+                date_time: None,
+                date: Some(date.format(DATE_FORMAT).to_string()),
+                time_zone: None,
+            },
+            When::DateTime(local) => {
+                let date_time = Local
+                    .from_local_datetime(&local)
+                    .earliest()
+                    .with_context(|| format!("{} does not exist in the local time zone", local))?;
+                EventDateTime {
+                    date_time: Some(date_time.to_rfc3339()),
+                    date: None,
+                    time_zone: None,
+                }
+            }
+        })
+    }
+
+    fn of(event_date_time: &EventDateTime) -> Option<Self> {
+        let local = event_date_time.to_local()?;
+        Some(if event_date_time.is_all_day() {
+            When::Date(local.date_naive())
+        } else {
+            When::DateTime(local.naive_local())
+        })
+    }
+
+    fn format(self) -> String {
+        match self {
+            When::Date(date) => date.format(DATE_FORMAT).to_string(),
+            When::DateTime(local) => local.format(DATE_TIME_FORMAT).to_string(),
+        }
+    }
+}
+
+/// Parse "YYYY-MM-DD HH:MM" (local time) or "YYYY-MM-DD" (all day)
+pub fn parse_when(value: &str) -> Result<When, String> {
+    let value = value.trim();
+    NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+        .map(When::DateTime)
+        .or_else(|_| NaiveDate::parse_from_str(value, DATE_FORMAT).map(When::Date))
+        .map_err(|_| {
+            format!(
+                "expected \"YYYY-MM-DD HH:MM\" or \"YYYY-MM-DD\", got \"{}\"",
+                value
+            )
+        })
+}
+
+/// Requested changes; unset fields are left alone
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventEdits {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: Option<When>,
+    pub end: Option<When>,
+    pub add_attendees: Vec<String>,
+    pub remove_attendees: Vec<String>,
+}
+
+impl EventEdits {
+    /// Turn the edits into a patch for `event`. Moving only the start keeps the
+    /// event's length so `--start` alone reschedules it; a timed start on an
+    /// all-day event lasts `duration`.
+    fn to_patch(&self, event: &Event, duration: Duration) -> Result<EventPatch> {
+        let mut patch = EventPatch {
+            summary: self.title.clone(),
+            description: self.description.clone(),
+            location: self.location.clone(),
+            ..EventPatch::default()
+        };
+
+        let end = match (self.start, self.end) {
+            (Some(start), None) => Some(shifted_end(event, start, duration)?),
+            (_, end) => end,
+        };
+        if let (Some(start), Some(end)) = (self.start.or(When::of(&event.start)), end) {
+            let valid = match (start, end) {
+                (When::Date(start), When::Date(end)) => end > start,
+                (When::DateTime(start), When::DateTime(end)) => end > start,
+                _ => bail!("Start and end must both be dates or both be date-times"),
+            };
+            if !valid {
+                bail!(
+                    "The end ({}) must be after the start ({})",
+                    end.format(),
+                    start.format()
+                );
+            }
+        }
+        patch.start = self.start.map(When::to_event_date_time).transpose()?;
+        patch.end = end.map(When::to_event_date_time).transpose()?;
+
+        if !self.add_attendees.is_empty() || !self.remove_attendees.is_empty() {
+            patch.attendees = Some(self.attendees(event));
+        }
+
+        Ok(patch)
+    }
+
+    fn attendees(&self, event: &Event) -> Vec<Attendee> {
+        let is_removed = |email: &str| {
+            self.remove_attendees
+                .iter()
+                .any(|removed| removed.eq_ignore_ascii_case(email))
+        };
+
+        let mut attendees: Vec<Attendee> = event
+            .attendees
+            .iter()
+            .flatten()
+            .filter(|attendee| !is_removed(&attendee.email))
+            .cloned()
+            .collect();
+
+        for email in &self.add_attendees {
+            if !attendees
+                .iter()
+                .any(|attendee| attendee.email.eq_ignore_ascii_case(email))
+            {
+                attendees.push(Attendee {
+                    email: email.clone(),
+                    display_name: None,
+                    response_status: None,
+                    optional: None,
+                    is_self: None,
+                    comment: None,
+                });
+            }
+        }
+
+        attendees
+    }
+}
+
+/// The end after moving the start to `start`, keeping the original length
+fn shifted_end(event: &Event, start: When, duration: Duration) -> Result<When> {
+    let (Some(old_start), Some(old_end)) = (When::of(&event.start), When::of(&event.end)) else {
+        bail!("The event has no valid start and end; pass --end too");
+    };
+
+    Ok(match (start, old_start, old_end) {
+        (When::Date(new), When::Date(old_start), When::Date(old_end)) => {
+            When::Date(new + (old_end - old_start))
+        }
+        (When::DateTime(new), When::DateTime(old_start), When::DateTime(old_end)) => {
+            When::DateTime(new + (old_end - old_start))
+        }
+        // Switching between all-day and timed: fall back to a day or the
+        // default event length
+        (When::Date(new), _, _) => When::Date(new + Duration::days(1)),
+        (When::DateTime(new), _, _) => When::DateTime(new + duration),
+    })
+}
+
+/// The editable fields as written to the `--editor` file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+struct EditableEvent {
+    title: String,
+    /// "YYYY-MM-DD HH:MM" or "YYYY-MM-DD" for all-day events
+    start: String,
+    end: String,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    attendees: Vec<String>,
+}
+
+impl EditableEvent {
+    fn of(event: &Event) -> Self {
+        let when = |dt: &EventDateTime| When::of(dt).map(When::format).unwrap_or_default();
+
+        Self {
+            title: event.summary.clone().unwrap_or_default(),
+            start: when(&event.start),
+            end: when(&event.end),
+            location: event.location.clone().unwrap_or_default(),
+            description: event.description.clone().unwrap_or_default(),
+            attendees: event
+                .attendees
+                .iter()
+                .flatten()
+                .map(|attendee| attendee.email.clone())
+                .collect(),
+        }
+    }
+
+    /// The edits that turn `self` into `edited`
+    fn diff(&self, edited: &EditableEvent) -> Result<EventEdits> {
+        let changed = |old: &String, new: &String| (old != new).then(|| new.clone());
+        let changed_when = |old: &String, new: &String| -> Result<Option<When>> {
+            changed(old, new)
+                .map(|value| parse_when(&value).map_err(anyhow::Error::msg))
+                .transpose()
+        };
+
+        Ok(EventEdits {
+            title: changed(&self.title, &edited.title),
+            description: changed(&self.description, &edited.description),
+            location: changed(&self.location, &edited.location),
+            start: changed_when(&self.start, &edited.start)?,
+            end: changed_when(&self.end, &edited.end)?,
+            add_attendees: edited
+                .attendees
+                .iter()
+                .filter(|email| !self.attendees.contains(email))
+                .cloned()
+                .collect(),
+            remove_attendees: self
+                .attendees
+                .iter()
+                .filter(|email| !edited.attendees.contains(email))
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+/// Apply `edits` (or the result of editing in `$EDITOR`) to the event
+pub async fn run(
+    mut client: CalendarClient,
+    event_id: &str,
+    calendar: Option<&str>,
+    edits: EventEdits,
+    editor: bool,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    if editor && edits != EventEdits::default() {
+        bail!("--editor cannot be combined with field flags");
+    }
+
+    let (calendar, event) = find_event(&mut client, event_id, calendar).await?;
+    let mut edits = if editor {
+        edit_in_editor(&event, true)?
+    } else {
+        edits
+    };
+    edits.add_attendees = attendees::resolve(&mut client, &edits.add_attendees).await?;
+
+    let patch = edits.to_patch(&event, settings.event_defaults.duration)?;
+    if patch == EventPatch::default() {
+        if format.is_json() {
+            return output::print::<ListedEvent>(&[], format, &settings.formats);
+        }
+        println!("Nothing to change");
+        return Ok(());
+    }
+
+    let updated = client
+        .patch_event(&calendar.id, &event.id, &patch)
+        .await
+        .context("Failed to update event")?;
+    if format.is_json() {
+        let record = ListedEvent::of(&updated, &calendar.id, &calendar.summary);
+        return output::print(&[record], format, &settings.formats);
+    }
+    println!(
+        "Updated \"{}\"",
+        updated.summary.as_deref().unwrap_or("(No title)")
+    );
+
+    Ok(())
+}
+
+/// Look the event up in `calendar`, or in every calendar (primary first) when not given
+pub(super) async fn find_event(
+    client: &mut CalendarClient,
+    event_id: &str,
+    calendar: Option<&str>,
+) -> Result<(Calendar, Event)> {
+    let mut calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    if let Some(name) = calendar {
+        calendars = vec![find_calendar(&calendars, Some(name))?.clone()];
+    }
+    calendars.sort_by_key(|calendar| !calendar.primary);
+
+    for calendar in calendars {
+        if let Some(event) = client.get_event(&calendar.id, event_id).await? {
+            return Ok((calendar, event));
+        }
+    }
+
+    bail!("No event with id {}", event_id)
+}
+
+/// Edit `event` in `$EDITOR` from the TUI, which cannot look contacts up:
+/// added attendees must be email addresses there
+pub fn patch_in_editor(event: &Event, duration: Duration) -> Result<EventPatch> {
+    let edits = edit_in_editor(event, false)?;
+    if let Some(name) = edits
+        .add_attendees
+        .iter()
+        .find(|attendee| !attendee.contains('@'))
+    {
+        bail!(
+            "\"{}\" is not an email address; add contacts by name with `oxidate edit`",
+            name
+        );
+    }
+    edits.to_patch(event, duration)
+}
+
+/// Open the event as TOML in `$EDITOR` (falling back to `vi`) and diff the
+/// result; `contacts` tells the user that attendees may be contact names
+fn edit_in_editor(event: &Event, contacts: bool) -> Result<EventEdits> {
+    let original = EditableEvent::of(event);
+    let path = env::temp_dir().join(format!("oxidate-edit-{}.toml", process::id()));
+    let mut contents = "# Edit the event and save; leave it unchanged to abort\n".to_string();
+    if contacts {
+        contents.push_str("# Attendees may also be names from your contacts\n");
+    }
+    contents.push_str(&toml::to_string(&original).context("Failed to serialize event")?);
+    fs::write(&path, contents).context("Failed to write temporary file")?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Editors are often configured with arguments, e.g. "code --wait"
+    let mut words = editor.split_whitespace();
+    let program = words.next().context("$EDITOR is empty")?;
+    let status = process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to run {}", editor));
+
+    let edited = fs::read_to_string(&path).context("Failed to read temporary file");
+    let _ = fs::remove_file(&path);
+
+    if !status?.success() {
+        bail!("{} exited with an error; nothing changed", editor);
+    }
+    let edited: EditableEvent = toml::from_str(&edited?).context("Invalid event file")?;
+
+    original.diff(&edited)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn when(value: &str) -> When {
+        parse_when(value).unwrap()
+    }
+
+    fn create_event(start: &str, end: &str) -> Event {
+        Event {
+            id: "abc123".to_string(),
+            attendees: Some(vec![Attendee {
+                email: "ana@example.com".to_string(),
+                display_name: Some("Ana".to_string()),
+                response_status: Some("accepted".to_string()),
+                optional: None,
+                is_self: None,
+                comment: None,
+            }]),
+            calendar_id: Some("work".to_string()),
+            ..Event::test(
+                "Standup",
+                when(start).to_event_date_time().unwrap(),
+                when(end).to_event_date_time().unwrap(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_parse_when() {
+        assert_eq!(
+            when("2025-07-02 14:00"),
+            When::DateTime(
+                NaiveDate::from_ymd_opt(2025, 7, 2)
+                    .unwrap()
+                    .and_hms_opt(14, 0, 0)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            when("2025-07-02"),
+            When::Date(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap())
+        );
+        assert!(parse_when("tomorrow").is_err());
+    }
+
+    #[test]
+    fn test_patch_only_contains_edited_fields() {
+        let event = create_event("2025-07-02 09:00", "2025-07-02 09:15");
+        let edits = EventEdits {
+            title: Some("Daily standup".to_string()),
+            ..EventEdits::default()
+        };
+
+        let patch = edits.to_patch(&event, Duration::hours(1)).unwrap();
+
+        assert_eq!(
+            patch,
+            EventPatch {
+                summary: Some("Daily standup".to_string()),
+                ..EventPatch::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_moving_start_keeps_duration() {
+        let event = create_event("2025-07-02 09:00", "2025-07-02 09:15");
+        let edits = EventEdits {
+            start: Some(when("2025-07-03 10:00")),
+            ..EventEdits::default()
+        };
+
+        let patch = edits.to_patch(&event, Duration::hours(1)).unwrap();
+
+        assert_eq!(
+            When::of(&patch.end.unwrap()),
+            Some(when("2025-07-03 10:15"))
+        );
+    }
+
+    #[test]
+    fn test_end_before_start_is_rejected() {
+        let event = create_event("2025-07-02 09:00", "2025-07-02 09:15");
+        let edits = EventEdits {
+            end: Some(when("2025-07-02 08:00")),
+            ..EventEdits::default()
+        };
+
+        assert!(edits.to_patch(&event, Duration::hours(1)).is_err());
+    }
+
+    #[test]
+    fn test_attendee_changes_keep_existing_entries() {
+        let event = create_event("2025-07-02 09:00", "2025-07-02 09:15");
+        let edits = EventEdits {
+            add_attendees: vec!["bo@example.com".to_string(), "ANA@example.com".to_string()],
+            ..EventEdits::default()
+        };
+
+        let attendees = edits
+            .to_patch(&event, Duration::hours(1))
+            .unwrap()
+            .attendees
+            .unwrap();
+
+        assert_eq!(attendees.len(), 2);
+        assert_eq!(attendees[0].response_status.as_deref(), Some("accepted"));
+        assert_eq!(attendees[1].email, "bo@example.com");
+
+        let edits = EventEdits {
+            remove_attendees: vec!["ana@example.com".to_string()],
+            ..EventEdits::default()
+        };
+        assert!(edits
+            .to_patch(&event, Duration::hours(1))
+            .unwrap()
+            .attendees
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_editable_event_roundtrip_and_diff() {
+        let event = create_event("2025-07-02 09:00", "2025-07-02 09:15");
+        let original = EditableEvent::of(&event);
+        assert_eq!(original.start, "2025-07-02 09:00");
+
+        let toml = toml::to_string(&original).unwrap();
+        let mut edited: EditableEvent = toml::from_str(&toml).unwrap();
+        assert_eq!(original.diff(&edited).unwrap(), EventEdits::default());
+
+        edited.location = "Room 4".to_string();
+        edited.end = "2025-07-02 09:30".to_string();
+        edited.attendees = vec!["bo@example.com".to_string()];
+        let edits = original.diff(&edited).unwrap();
+
+        assert_eq!(edits.location.as_deref(), Some("Room 4"));
+        assert_eq!(edits.end, Some(when("2025-07-02 09:30")));
+        assert_eq!(edits.add_attendees, vec!["bo@example.com".to_string()]);
+        assert_eq!(edits.remove_attendees, vec!["ana@example.com".to_string()]);
+        assert!(edits.title.is_none());
+    }
+}
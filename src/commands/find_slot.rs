@@ -0,0 +1,375 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc, Weekday};
+use serde::Serialize;
+
+use super::busy::{intervals, Availability};
+use super::find_calendar;
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::TimePeriod;
+use crate::formats::{Formats, Localized};
+use crate::settings::Settings;
+use crate::tui::calendar_prefs::CalendarPrefs;
+use crate::working_hours::Hours;
+
+/// Meeting length without `--duration` or `--event-duration`
+const DEFAULT_MEETING: Duration = Duration::minutes(30);
+
+/// `--duration`, else the configured length of new events, else half an hour
+pub fn meeting_duration(requested: Option<Duration>, event_duration: Option<u32>) -> Duration {
+    requested
+        .or(event_duration.map(|minutes| Duration::minutes(minutes.into())))
+        .unwrap_or(DEFAULT_MEETING)
+}
+
+/// What `find-slot` should look for
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotQuery {
+    pub duration: Duration,
+    /// Days to search, today included
+    pub days: u32,
+    pub hours: Hours,
+    pub working_days: Vec<Weekday>,
+    /// Also search days outside `working_days`
+    pub weekends: bool,
+    pub limit: usize,
+    /// Slots start on a multiple of this (`--snap`)
+    pub snap: Duration,
+}
+
+/// A free meeting slot, and how long the gap it starts lasts
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Slot {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub free_until: DateTime<Local>,
+}
+
+/// DATE, FROM, TO, FREE UNTIL
+impl Record for Slot {
+    fn columns(&self, formats: &Formats) -> Vec<String> {
+        vec![
+            self.start.localized(&formats.date, formats.locale),
+            self.start.format(&formats.time).to_string(),
+            self.end.format(&formats.time).to_string(),
+            self.free_until.format(&formats.time).to_string(),
+        ]
+    }
+}
+
+/// Propose free slots across `calendars` (ids or names; the calendars visible
+/// in the TUI by default)
+pub async fn run(
+    mut client: CalendarClient,
+    calendars: &[String],
+    query: SlotQuery,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let windows = windows(Local::now(), &query);
+    let (Some(first), Some(last)) = (windows.first(), windows.last()) else {
+        bail!("No working hours left in the next {} day(s)", query.days);
+    };
+    let (start, end) = (first.0, last.1);
+
+    let available = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    let calendar_ids: Vec<String> = if calendars.is_empty() {
+        let hidden = CalendarPrefs::load()
+            .map(|prefs| prefs.hidden)
+            .unwrap_or_default();
+        available
+            .iter()
+            .map(|calendar| calendar.id.clone())
+            .filter(|id| !hidden.contains(id))
+            .collect()
+    } else {
+        calendars
+            .iter()
+            .map(|name| find_calendar(&available, Some(name)).map(|c| c.id.clone()))
+            .collect::<Result<_>>()?
+    };
+
+    let free_busy = client
+        .free_busy(
+            &calendar_ids,
+            start.with_timezone(&Utc),
+            end.with_timezone(&Utc),
+        )
+        .await
+        .context("Failed to query free/busy")?;
+    for id in &free_busy.unavailable {
+        eprintln!("Free/busy unavailable for {}", id);
+    }
+
+    let slots = slots(
+        &free_busy.busy,
+        &windows,
+        query.duration,
+        query.snap,
+        query.limit,
+    );
+    if slots.is_empty() && !format.is_json() {
+        println!(
+            "No free slot of {} minutes found",
+            query.duration.num_minutes()
+        );
+        return Ok(());
+    }
+    output::print(&slots, format, &settings.formats)
+}
+
+/// The working hours of each searched day, starting no earlier than `now`
+fn windows(now: DateTime<Local>, query: &SlotQuery) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    let today = now.date_naive();
+    let local = |date: chrono::NaiveDate, time: NaiveTime| {
+        Local.from_local_datetime(&date.and_time(time)).earliest()
+    };
+
+    (0..query.days)
+        .map(|offset| today + Duration::days(offset.into()))
+        .filter(|date| query.weekends || query.working_days.contains(&date.weekday()))
+        .filter_map(|date| {
+            let start = local(date, query.hours.start)?;
+            let end = local(date, query.hours.end)?;
+            let start = start.max(round_up(now, query.snap));
+            (start < end).then_some((start, end))
+        })
+        .collect()
+}
+
+/// The first `limit` slots of `duration`, one per free gap
+fn slots(
+    busy: &[TimePeriod],
+    windows: &[(DateTime<Local>, DateTime<Local>)],
+    duration: Duration,
+    snap: Duration,
+    limit: usize,
+) -> Vec<Slot> {
+    windows
+        .iter()
+        .flat_map(|&(start, end)| intervals(busy, start, end))
+        .filter(|interval| interval.status == Availability::Free)
+        .filter_map(|interval| {
+            let start = round_up(interval.start, snap);
+            let end = start + duration;
+            (end <= interval.end).then_some(Slot {
+                start,
+                end,
+                free_until: interval.end,
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Proposed slots start on a multiple of `snap`
+fn round_up(time: DateTime<Local>, snap: Duration) -> DateTime<Local> {
+    let step = snap.num_seconds().max(1);
+    let seconds = time.timestamp() + i64::from(time.timestamp_subsec_nanos() > 0);
+    let rounded = (seconds + step - 1).div_euclid(step) * step;
+    Local.timestamp_opt(rounded, 0).single().unwrap_or(time)
+}
+
+/// `45m`, `1h`, `1h30m` or plain minutes
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let error = || {
+        format!(
+            "expected a duration like 45m, 1h or 1h30m, got \"{}\"",
+            value
+        )
+    };
+    let value = value.trim().to_ascii_lowercase();
+
+    let minutes = match value.parse::<i64>() {
+        Ok(minutes) => minutes,
+        Err(_) => {
+            let mut total = 0;
+            let mut number = String::new();
+            for c in value.chars() {
+                if c.is_ascii_digit() {
+                    number.push(c);
+                    continue;
+                }
+                let n: i64 = number.parse().map_err(|_| error())?;
+                number.clear();
+                total += match c {
+                    'h' => n * 60,
+                    'm' => n,
+                    _ => return Err(error()),
+                };
+            }
+            if !number.is_empty() {
+                return Err(error());
+            }
+            total
+        }
+    };
+
+    if minutes <= 0 {
+        return Err(error());
+    }
+    Ok(Duration::minutes(minutes))
+}
+
+/// `next 5 days`, `3 days`, `2w`, `today`: the number of days to search
+pub fn parse_within(value: &str) -> Result<u32, String> {
+    let error = || {
+        format!(
+            "expected e.g. \"next 5 days\", \"2 weeks\" or \"today\", got \"{}\"",
+            value
+        )
+    };
+    let value = value.trim().to_ascii_lowercase();
+    let value = value.strip_prefix("next ").unwrap_or(&value).trim();
+
+    match value {
+        "today" | "day" => return Ok(1),
+        "week" => return Ok(7),
+        _ => {}
+    }
+
+    let digits = value.chars().take_while(char::is_ascii_digit).count();
+    let count: u32 = value[..digits].parse().map_err(|_| error())?;
+    let days = match value[digits..].trim() {
+        "d" | "day" | "days" => count,
+        "w" | "week" | "weeks" => count * 7,
+        _ => return Err(error()),
+    };
+
+    if days == 0 {
+        return Err(error());
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::working_hours::{parse_hours, WorkingHours};
+
+    fn at(date_time: &str) -> DateTime<Local> {
+        Local
+            .from_local_datetime(
+                &chrono::NaiveDateTime::parse_from_str(date_time, "%Y-%m-%d %H:%M").unwrap(),
+            )
+            .unwrap()
+    }
+
+    fn query(days: u32, weekends: bool) -> SlotQuery {
+        SlotQuery {
+            duration: Duration::minutes(45),
+            days,
+            hours: parse_hours("9-17").unwrap(),
+            working_days: WorkingHours::default().days,
+            weekends,
+            limit: 10,
+            snap: Duration::minutes(15),
+        }
+    }
+
+    fn period(from: &str, to: &str) -> TimePeriod {
+        TimePeriod {
+            start: at(from).with_timezone(&Utc),
+            end: at(to).with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn test_windows_skip_weekends_and_past_hours() {
+        // Friday afternoon
+        let now = at("2025-06-20 14:07");
+
+        assert_eq!(
+            windows(now, &query(4, false)),
+            vec![
+                (at("2025-06-20 14:15"), at("2025-06-20 17:00")),
+                (at("2025-06-23 09:00"), at("2025-06-23 17:00")),
+            ]
+        );
+        assert_eq!(windows(now, &query(4, true)).len(), 4);
+        assert!(windows(at("2025-06-20 18:00"), &query(1, false)).is_empty());
+    }
+
+    #[test]
+    fn test_slots_fit_the_duration() {
+        let windows = vec![(at("2025-06-16 09:00"), at("2025-06-16 12:00"))];
+        let busy = vec![
+            period("2025-06-16 09:00", "2025-06-16 09:30"),
+            period("2025-06-16 10:00", "2025-06-16 10:50"),
+            period("2025-06-16 11:20", "2025-06-16 12:00"),
+        ];
+
+        let slots = slots(
+            &busy,
+            &windows,
+            Duration::minutes(30),
+            Duration::minutes(15),
+            10,
+        );
+
+        // 10:50-11:20 is long enough, but not once the start is rounded to 11:00
+        assert_eq!(
+            slots,
+            vec![Slot {
+                start: at("2025-06-16 09:30"),
+                end: at("2025-06-16 10:00"),
+                free_until: at("2025-06-16 10:00"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_slots_limit() {
+        let windows = vec![
+            (at("2025-06-16 09:00"), at("2025-06-16 17:00")),
+            (at("2025-06-17 09:00"), at("2025-06-17 17:00")),
+        ];
+
+        let slots = slots(
+            &[],
+            &windows,
+            Duration::minutes(45),
+            Duration::minutes(15),
+            1,
+        );
+
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].end, at("2025-06-16 09:45"));
+        assert_eq!(slots[0].free_until, at("2025-06-16 17:00"));
+    }
+
+    #[test]
+    fn test_meeting_duration() {
+        assert_eq!(meeting_duration(None, None), Duration::minutes(30));
+        assert_eq!(meeting_duration(None, Some(25)), Duration::minutes(25));
+        assert_eq!(
+            meeting_duration(Some(Duration::hours(1)), Some(25)),
+            Duration::hours(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("45m"), Ok(Duration::minutes(45)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::hours(1)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration("20"), Ok(Duration::minutes(20)));
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("1x").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn test_parse_within() {
+        assert_eq!(parse_within("next 5 days"), Ok(5));
+        assert_eq!(parse_within("3d"), Ok(3));
+        assert_eq!(parse_within("Next 2 weeks"), Ok(14));
+        assert_eq!(parse_within("today"), Ok(1));
+        assert_eq!(parse_within("next week"), Ok(7));
+        assert!(parse_within("0 days").is_err());
+        assert!(parse_within("soon").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::list_cache;
+use crate::formats::Formats;
+
+/// Country code, country name and the region part of Google's public holiday
+/// calendar id (`en.<region>#holiday@group.v.calendar.google.com`)
+const COUNTRIES: &[(&str, &str, &str)] = &[
+    ("ar", "Argentina", "ar"),
+    ("au", "Australia", "australian"),
+    ("at", "Austria", "austrian"),
+    ("be", "Belgium", "be"),
+    ("br", "Brazil", "brazilian"),
+    ("ca", "Canada", "canadian"),
+    ("cl", "Chile", "cl"),
+    ("cn", "China", "china"),
+    ("co", "Colombia", "co"),
+    ("cz", "Czechia", "czech"),
+    ("dk", "Denmark", "danish"),
+    ("fi", "Finland", "finnish"),
+    ("fr", "France", "french"),
+    ("de", "Germany", "german"),
+    ("gr", "Greece", "greek"),
+    ("hk", "Hong Kong", "hong_kong"),
+    ("in", "India", "indian"),
+    ("id", "Indonesia", "indonesian"),
+    ("ie", "Ireland", "irish"),
+    ("il", "Israel", "jewish"),
+    ("it", "Italy", "italian"),
+    ("jp", "Japan", "japanese"),
+    ("kr", "South Korea", "south_korea"),
+    ("my", "Malaysia", "malaysia"),
+    ("mx", "Mexico", "mexican"),
+    ("nl", "Netherlands", "dutch"),
+    ("nz", "New Zealand", "new_zealand"),
+    ("no", "Norway", "norwegian"),
+    ("ph", "Philippines", "philippines"),
+    ("pl", "Poland", "polish"),
+    ("pt", "Portugal", "portuguese"),
+    ("ru", "Russia", "russian"),
+    ("sg", "Singapore", "singapore"),
+    ("za", "South Africa", "sa"),
+    ("es", "Spain", "spain"),
+    ("se", "Sweden", "swedish"),
+    ("ch", "Switzerland", "ch"),
+    ("tw", "Taiwan", "taiwan"),
+    ("th", "Thailand", "th"),
+    ("tr", "Turkey", "turkish"),
+    ("ua", "Ukraine", "ukrainian"),
+    ("gb", "United Kingdom", "uk"),
+    ("us", "United States", "usa"),
+    ("uy", "Uruguay", "uy"),
+    ("vn", "Vietnam", "vietnamese"),
+];
+
+/// A public holiday calendar as printed by `holidays`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct HolidayCalendar {
+    pub code: String,
+    pub country: String,
+    pub id: String,
+    /// Already in the user's calendar list
+    pub subscribed: bool,
+}
+
+/// CODE, COUNTRY, FLAGS
+impl Record for HolidayCalendar {
+    fn columns(&self, _formats: &Formats) -> Vec<String> {
+        vec![
+            self.code.clone(),
+            self.country.clone(),
+            if self.subscribed {
+                "subscribed".to_string()
+            } else {
+                String::new()
+            },
+        ]
+    }
+}
+
+/// List the holiday calendars, or (un)subscribe to the one of `country`
+/// (a code like `us` or a name like `Brazil`)
+pub async fn run(
+    mut client: CalendarClient,
+    country: Option<&str>,
+    remove: bool,
+    formats: &Formats,
+    format: OutputFormat,
+) -> Result<()> {
+    let subscribed: Vec<String> = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?
+        .into_iter()
+        .map(|calendar| calendar.id)
+        .collect();
+    let holidays = holiday_calendars(&subscribed);
+
+    let Some(country) = country else {
+        return output::print(&holidays, format, formats);
+    };
+    let holiday = find(&holidays, country)?;
+
+    if remove {
+        if !holiday.subscribed {
+            println!("Not subscribed to holidays in {}", holiday.country);
+            return Ok(());
+        }
+        client
+            .unsubscribe_calendar(&holiday.id)
+            .await
+            .context("Failed to remove holiday calendar")?;
+        list_cache::invalidate();
+        println!("Removed holidays in {}", holiday.country);
+        return Ok(());
+    }
+
+    if holiday.subscribed {
+        println!("Already subscribed to holidays in {}", holiday.country);
+        return Ok(());
+    }
+    let calendar = client
+        .subscribe_calendar(&holiday.id)
+        .await
+        .context("Failed to add holiday calendar")?;
+    list_cache::invalidate();
+    println!("Added \"{}\"", calendar.summary);
+
+    Ok(())
+}
+
+fn holiday_calendars(subscribed: &[String]) -> Vec<HolidayCalendar> {
+    COUNTRIES
+        .iter()
+        .map(|&(code, country, region)| {
+            let id = format!("en.{}#holiday@group.v.calendar.google.com", region);
+            HolidayCalendar {
+                code: code.to_string(),
+                country: country.to_string(),
+                subscribed: subscribed.contains(&id),
+                id,
+            }
+        })
+        .collect()
+}
+
+/// By code or name, ignoring case; a unique name prefix also matches
+fn find<'a>(holidays: &'a [HolidayCalendar], country: &str) -> Result<&'a HolidayCalendar> {
+    let country = country.trim().to_lowercase();
+    let exact = holidays
+        .iter()
+        .find(|holiday| holiday.code == country || holiday.country.to_lowercase() == country);
+    if let Some(holiday) = exact {
+        return Ok(holiday);
+    }
+
+    let matches: Vec<&HolidayCalendar> = holidays
+        .iter()
+        .filter(|holiday| holiday.country.to_lowercase().starts_with(&country))
+        .collect();
+    match matches[..] {
+        [holiday] => Ok(holiday),
+        [] => bail!(
+            "No holiday calendar for \"{}\"; run `oxidate holidays` to list countries",
+            country
+        ),
+        _ => bail!(
+            "\"{}\" matches {}",
+            country,
+            matches
+                .iter()
+                .map(|holiday| holiday.country.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holiday_calendars_mark_subscriptions() {
+        let holidays =
+            holiday_calendars(&["en.usa#holiday@group.v.calendar.google.com".to_string()]);
+
+        let us = holidays
+            .iter()
+            .find(|holiday| holiday.code == "us")
+            .unwrap();
+        assert!(us.subscribed);
+        assert_eq!(
+            holidays.iter().filter(|holiday| holiday.subscribed).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_find_by_code_name_or_prefix() {
+        let holidays = holiday_calendars(&[]);
+
+        assert_eq!(find(&holidays, "BR").unwrap().country, "Brazil");
+        assert_eq!(find(&holidays, "united kingdom").unwrap().code, "gb");
+        assert_eq!(find(&holidays, "Ger").unwrap().code, "de");
+        assert!(find(&holidays, "united").is_err());
+        assert!(find(&holidays, "Atlantis").is_err());
+    }
+
+    #[test]
+    fn test_country_codes_are_unique() {
+        let holidays = holiday_calendars(&[]);
+
+        for holiday in &holidays {
+            assert_eq!(
+                holidays
+                    .iter()
+                    .filter(|other| other.code == holiday.code || other.id == holiday.id)
+                    .count(),
+                1,
+                "{} is listed twice",
+                holiday.country
+            );
+        }
+    }
+}
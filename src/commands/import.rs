@@ -0,0 +1,118 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::bulk;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use super::resolve_calendar;
+use crate::calendar::client::CalendarClient;
+use crate::calendar::{ics, outlook};
+use crate::settings::Settings;
+
+/// Create every VEVENT of the .ics file, or every row of an Outlook .csv
+/// export, on `calendar` (primary by default)
+pub async fn run(
+    client: CalendarClient,
+    path: &Path,
+    calendar: Option<&str>,
+    day_first: bool,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_csv = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+    if is_csv {
+        run_csv(
+            client, path, &contents, calendar, day_first, settings, format,
+        )
+        .await
+    } else {
+        run_ics(client, path, &contents, calendar, settings, format).await
+    }
+}
+
+async fn run_ics(
+    mut client: CalendarClient,
+    path: &Path,
+    contents: &str,
+    calendar: Option<&str>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let events = ics::parse_events(contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    if events.is_empty() {
+        bail!("{} contains no events", path.display());
+    }
+
+    let calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    let calendar = resolve_calendar(&calendars, calendar, &settings.event_defaults)?;
+
+    let mut imported = Vec::new();
+    for event in &events {
+        let summary = event.summary.as_deref().unwrap_or("(No title)");
+        let created = client
+            .insert_event(&calendar.id, event)
+            .await
+            .with_context(|| format!("Failed to import \"{}\"", summary))?;
+        if !format.is_json() {
+            println!("Imported \"{}\" into {}", summary, calendar.summary);
+        }
+        imported.push(ListedEvent::of(&created, &calendar.id, &calendar.summary));
+    }
+
+    if format.is_json() {
+        output::print(&imported, format, &settings.formats)?;
+    }
+    Ok(())
+}
+
+/// Create the rows in batches, reporting each row like `add --stdin`.
+/// Fails at the end if any row did.
+async fn run_csv(
+    mut client: CalendarClient,
+    path: &Path,
+    contents: &str,
+    calendar: Option<&str>,
+    day_first: bool,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let rows = outlook::parse_events(contents, day_first)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    if rows.is_empty() {
+        bail!("{} contains no events", path.display());
+    }
+
+    let calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+    let calendar = resolve_calendar(&calendars, calendar, &settings.event_defaults)?;
+
+    let valid: Vec<_> = rows
+        .iter()
+        .filter_map(|(_, event)| event.as_ref().ok().cloned())
+        .collect();
+    let created = client
+        .insert_events(&calendar.id, &valid)
+        .await
+        .context("Failed to import events")?;
+
+    let results = bulk::line_results(rows, created, &calendar.id, &calendar.summary);
+    output::print(&results, format, &settings.formats)?;
+
+    let failed = results.iter().filter(|result| !result.created).count();
+    if failed > 0 {
+        bail!("{} of {} events failed", failed, results.len());
+    }
+    Ok(())
+}
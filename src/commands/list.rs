@@ -0,0 +1,270 @@
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::load_state;
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::conference;
+use crate::calendar::models::{Event, EventDateTime};
+use crate::formats::Formats;
+use crate::settings::Settings;
+use crate::tui::state::{AppState, DateRange};
+
+/// One event as emitted by `list` and the other event commands' JSON output;
+/// flattened so consumers need no knowledge of the API shape
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ListedEvent {
+    pub id: String,
+    pub calendar_id: String,
+    pub calendar: String,
+    pub summary: String,
+    /// RFC3339 for timed events, YYYY-MM-DD for all-day events
+    pub start: String,
+    pub end: String,
+    pub all_day: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Link to join the event's Meet, Zoom, Teams or Webex call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_call: Option<String>,
+}
+
+impl ListedEvent {
+    pub fn of(event: &Event, calendar_id: &str, calendar: &str) -> Self {
+        let date_or_time = |dt: &EventDateTime| {
+            dt.date_time
+                .clone()
+                .or_else(|| dt.date.clone())
+                .unwrap_or_default()
+        };
+
+        Self {
+            id: event.id.clone(),
+            calendar_id: calendar_id.to_string(),
+            calendar: calendar.to_string(),
+            summary: event
+                .summary
+                .clone()
+                .unwrap_or_else(|| "(No title)".to_string()),
+            start: date_or_time(&event.start),
+            end: date_or_time(&event.end),
+            all_day: event.start.is_all_day(),
+            location: event.location.clone(),
+            video_call: conference::video_call(event).map(|call| call.url),
+        }
+    }
+
+    /// `event` as loaded into `state`, which knows its calendar's name
+    pub fn from_state(event: &Event, state: &AppState) -> Self {
+        let calendar_id = event.calendar_id.as_deref().unwrap_or_default();
+        let calendar = state
+            .get_calendar_by_id(calendar_id)
+            .map(|calendar| calendar.summary.as_str())
+            .unwrap_or_default();
+        Self::of(event, calendar_id, calendar)
+    }
+
+    /// Local start date and its time in `time_format` (or "all day")
+    pub fn local_start(&self, time_format: &str) -> (String, String) {
+        if self.all_day {
+            return (self.start.clone(), "all day".to_string());
+        }
+
+        match chrono::DateTime::parse_from_rfc3339(&self.start) {
+            Ok(start) => {
+                let start = start.with_timezone(&chrono::Local);
+                (
+                    start.format("%Y-%m-%d").to_string(),
+                    start.format(time_format).to_string(),
+                )
+            }
+            Err(_) => (self.start.clone(), String::new()),
+        }
+    }
+}
+
+/// DATE, TIME, SUMMARY, CALENDAR
+impl Record for ListedEvent {
+    fn columns(&self, formats: &Formats) -> Vec<String> {
+        let (date, time) = self.local_start(&formats.time);
+        vec![date, time, self.summary.clone(), self.calendar.clone()]
+    }
+}
+
+/// Print visible events between `from` and `to` (inclusive)
+pub async fn run(
+    mut client: CalendarClient,
+    from: NaiveDate,
+    to: NaiveDate,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    if to < from {
+        bail!("--to ({}) is before --from ({})", to, from);
+    }
+
+    // The fetch already runs through the end of `to`
+    let range = DateRange {
+        start: from,
+        end: to,
+    };
+    let state = load_state(&mut client, range, settings).await?;
+    let events = listed_events(&state, from, to);
+
+    if events.is_empty() && format == OutputFormat::Table {
+        println!("No events");
+        return Ok(());
+    }
+    output::print(&events, format, &settings.formats)
+}
+
+/// Visible events from `from` through `to`, ordered by start
+pub(super) fn listed_events(state: &AppState, from: NaiveDate, to: NaiveDate) -> Vec<ListedEvent> {
+    let mut events = Vec::new();
+
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let mut day_events: Vec<&Event> = state.get_events_for_date(date).collect();
+        day_events.sort_by_key(|event| event.start.to_local());
+
+        events.extend(
+            day_events
+                .into_iter()
+                .map(|event| ListedEvent::from_state(event, state)),
+        );
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::Calendar;
+
+    fn create_event(id: &str, start: EventDateTime, calendar_id: &str) -> Event {
+        Event {
+            calendar_id: Some(calendar_id.to_string()),
+            ..Event::test(id, start.clone(), start)
+        }
+    }
+
+    fn timed(date_time: &str) -> EventDateTime {
+        EventDateTime {
+            date_time: Some(date_time.to_string()),
+            date: None,
+            time_zone: None,
+        }
+    }
+
+    fn all_day(date: &str) -> EventDateTime {
+        EventDateTime {
+            date_time: None,
+            date: Some(date.to_string()),
+            time_zone: None,
+        }
+    }
+
+    fn state_with(events: Vec<(NaiveDate, Event)>) -> AppState {
+        let mut state = AppState::new();
+        state.calendars = vec![Calendar {
+            id: "work".to_string(),
+            summary: "Work".to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }];
+        for (date, event) in events {
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, day).unwrap()
+    }
+
+    #[test]
+    fn test_listed_events_in_range_and_order() {
+        let state = state_with(vec![
+            (
+                date(10),
+                create_event("late", timed("2025-06-10T15:00:00Z"), "work"),
+            ),
+            (
+                date(10),
+                create_event("early", timed("2025-06-10T09:00:00Z"), "work"),
+            ),
+            (
+                date(11),
+                create_event("holiday", all_day("2025-06-11"), "work"),
+            ),
+            (
+                date(20),
+                create_event("outside", timed("2025-06-20T09:00:00Z"), "work"),
+            ),
+        ]);
+
+        let events = listed_events(&state, date(10), date(11));
+
+        let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["early", "late", "holiday"]);
+        assert_eq!(events[0].calendar, "Work");
+        assert_eq!(events[0].start, "2025-06-10T09:00:00Z");
+        assert!(events[2].all_day);
+        assert_eq!(events[2].start, "2025-06-11");
+    }
+
+    #[test]
+    fn test_listed_events_skip_hidden_calendars() {
+        let mut state = state_with(vec![(
+            date(10),
+            create_event("hidden", timed("2025-06-10T09:00:00Z"), "holidays"),
+        )]);
+        state.filter.hidden_calendars.insert("holidays".to_string());
+
+        assert!(listed_events(&state, date(10), date(10)).is_empty());
+    }
+
+    #[test]
+    fn test_json_output_shape() {
+        let state = state_with(vec![(
+            date(11),
+            create_event("holiday", all_day("2025-06-11"), "work"),
+        )]);
+
+        let json = serde_json::to_value(listed_events(&state, date(11), date(11))).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "id": "holiday",
+                "calendar_id": "work",
+                "calendar": "Work",
+                "summary": "holiday",
+                "start": "2025-06-11",
+                "end": "2025-06-11",
+                "all_day": true
+            }])
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let state = state_with(vec![(
+            date(11),
+            create_event("holiday", all_day("2025-06-11"), "work"),
+        )]);
+
+        let events = listed_events(&state, date(11), date(11));
+
+        assert_eq!(
+            events[0].columns(&Formats::default()),
+            vec!["2025-06-11", "all day", "holiday", "Work"]
+        );
+    }
+}
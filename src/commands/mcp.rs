@@ -0,0 +1,429 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use super::bulk::EventDraft;
+use super::list::{listed_events, ListedEvent};
+use super::{busy, default_search_range, load_state, resolve_calendar, search};
+use crate::calendar::client::CalendarClient;
+use crate::settings::Settings;
+use crate::tui::state::DateRange;
+
+/// The MCP revision whose handshake and tool calls are implemented
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// A calendar operation, called as an MCP tool (`tools/call`) or directly as
+/// a JSON-RPC method of the same name
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(
+    tag = "name",
+    content = "arguments",
+    rename_all = "snake_case",
+    deny_unknown_fields
+)]
+enum Tool {
+    /// Visible events from `from` through `to`, both defaulting to today
+    ListEvents {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    /// Events whose title, description, location or attendees hold every word
+    SearchEvents {
+        query: String,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    /// Create an event; `event` takes the fields of an `add --stdin` line
+    CreateEvent {
+        event: EventDraft,
+        calendar: Option<String>,
+    },
+    /// Busy and free intervals of the visible calendars on one day
+    FreeBusy {
+        date: Option<NaiveDate>,
+        from: Option<NaiveTime>,
+        to: Option<NaiveTime>,
+    },
+}
+
+impl Tool {
+    /// `arguments` may be omitted when every argument is optional
+    fn parse(name: &str, arguments: Option<Value>) -> Result<Self> {
+        let arguments = arguments.unwrap_or_else(|| json!({}));
+        serde_json::from_value(json!({ "name": name, "arguments": arguments }))
+            .with_context(|| format!("Invalid arguments for {}", name))
+    }
+
+    fn is_known(name: &str) -> bool {
+        tool_definitions(false)
+            .iter()
+            .any(|tool| tool["name"] == name)
+    }
+}
+
+/// The tools advertised by `tools/list`, without `create_event` when read-only
+fn tool_definitions(read_only: bool) -> Vec<Value> {
+    let date = json!({ "type": "string", "description": "YYYY-MM-DD" });
+    let time = json!({ "type": "string", "description": "HH:MM" });
+
+    let mut tools = vec![
+        json!({
+            "name": "list_events",
+            "description": "List calendar events from one day through another (both default to today)",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "from": date, "to": date },
+            },
+        }),
+        json!({
+            "name": "search_events",
+            "description": "Find events whose title, description, location or attendees contain every word of the query",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "query": { "type": "string" }, "from": date, "to": date },
+                "required": ["query"],
+            },
+        }),
+        json!({
+            "name": "free_busy",
+            "description": "Busy and free intervals across the user's calendars on one day (default today, 09:00-18:00)",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "date": date, "from": time, "to": time },
+            },
+        }),
+    ];
+    if !read_only {
+        tools.push(json!({
+            "name": "create_event",
+            "description": "Create an event; without times it lasts all day, without a date it is today",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "event": {
+                        "type": "object",
+                        "properties": {
+                            "title": { "type": "string" },
+                            "date": date,
+                            "from": time,
+                            "to": time,
+                            "location": { "type": "string" },
+                            "description": { "type": "string" },
+                        },
+                        "required": ["title"],
+                    },
+                    "calendar": {
+                        "type": "string",
+                        "description": "Calendar id or name, defaults to the configured default calendar (normally the primary one)",
+                    },
+                },
+                "required": ["event"],
+            },
+        }));
+    }
+    tools
+}
+
+/// A JSON-RPC request, or a notification when `id` is absent
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    id: Option<Value>,
+    method: String,
+    params: Option<Value>,
+}
+
+/// A failure carrying its JSON-RPC error code
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+struct Server {
+    client: CalendarClient,
+    read_only: bool,
+    settings: Settings,
+}
+
+impl Server {
+    /// The response line for one request line, None for notifications
+    async fn handle_line(&mut self, line: &str) -> Option<Value> {
+        let request = match serde_json::from_str::<Value>(line) {
+            Ok(value) => value,
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                return Some(error_response(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    "Only JSON-RPC 2.0 is supported",
+                ))
+            }
+            Err(e) => return Some(error_response(Value::Null, INVALID_REQUEST, e.to_string())),
+        };
+
+        let result = self.dispatch(&request.method, request.params).await;
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e.code, e.message),
+        })
+    }
+
+    async fn dispatch(&mut self, method: &str, params: Option<Value>) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "oxidate", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions(self.read_only) })),
+            "tools/call" => {
+                let params = params.unwrap_or_default();
+                let name = params["name"]
+                    .as_str()
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+                if !Tool::is_known(name) {
+                    return Err(RpcError::new(
+                        INVALID_PARAMS,
+                        format!("Unknown tool {}", name),
+                    ));
+                }
+                let result = match Tool::parse(name, params.get("arguments").cloned()) {
+                    Ok(tool) => self.call(tool).await,
+                    Err(e) => Err(e),
+                };
+                Ok(tool_result(result))
+            }
+            // MCP notifications such as notifications/initialized need no answer
+            _ if method.starts_with("notifications/") => Ok(Value::Null),
+            _ if Tool::is_known(method) => {
+                let tool = Tool::parse(method, params)
+                    .map_err(|e| RpcError::new(INVALID_PARAMS, format!("{:#}", e)))?;
+                self.call(tool)
+                    .await
+                    .map_err(|e| RpcError::new(SERVER_ERROR, format!("{:#}", e)))
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method {}", method),
+            )),
+        }
+    }
+
+    async fn call(&mut self, tool: Tool) -> Result<Value> {
+        let today = Local::now().date_naive();
+
+        match tool {
+            Tool::ListEvents { from, to } => {
+                let from = from.unwrap_or(today);
+                let to = to.unwrap_or(from);
+                if to < from {
+                    bail!("\"to\" ({}) is before \"from\" ({})", to, from);
+                }
+                let range = DateRange {
+                    start: from,
+                    end: to + Duration::days(1),
+                };
+                let state = load_state(&mut self.client, range, &self.settings).await?;
+                Ok(serde_json::to_value(listed_events(&state, from, to))?)
+            }
+            Tool::SearchEvents { query, from, to } => {
+                let default_range = default_search_range(today);
+                let range = DateRange {
+                    start: from.unwrap_or(default_range.start),
+                    end: to.map_or(default_range.end, |to| to + Duration::days(1)),
+                };
+                if range.end <= range.start {
+                    bail!("\"to\" is before \"from\"");
+                }
+                let events =
+                    search::find(&mut self.client, &query, &range, self.settings.index).await?;
+                Ok(serde_json::to_value(events)?)
+            }
+            Tool::CreateEvent { event, calendar } => {
+                if self.read_only {
+                    bail!("Creating events is disabled in read-only mode");
+                }
+                let calendars = self
+                    .client
+                    .list_calendars()
+                    .await
+                    .context("Failed to fetch calendars")?;
+                let calendar = resolve_calendar(
+                    &calendars,
+                    calendar.as_deref(),
+                    &self.settings.event_defaults,
+                )?;
+                let event = event.to_event(today, self.settings.event_defaults.duration)?;
+                let created = self
+                    .client
+                    .insert_event(&calendar.id, &event)
+                    .await
+                    .context("Failed to create event")?;
+                Ok(serde_json::to_value(ListedEvent::of(
+                    &created,
+                    &calendar.id,
+                    &calendar.summary,
+                ))?)
+            }
+            Tool::FreeBusy { date, from, to } => {
+                let from = from.unwrap_or(NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+                let to = to.unwrap_or(NaiveTime::from_hms_opt(18, 0, 0).unwrap());
+                if to <= from {
+                    bail!("\"to\" ({}) must be after \"from\" ({})", to, from);
+                }
+                let (start, end) = busy::window(date.unwrap_or(today), from, to)?;
+                let intervals = busy::availability(&mut self.client, start, end).await?;
+                Ok(serde_json::to_value(intervals)?)
+            }
+        }
+    }
+}
+
+/// MCP reports tool failures in the result so the model can read them
+fn tool_result(result: Result<Value>) -> Value {
+    let (text, is_error) = match result {
+        Ok(value) => (value.to_string(), false),
+        Err(e) => (format!("{:#}", e), true),
+    };
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+/// Answer JSON-RPC 2.0 requests, one per line on stdin, with one response
+/// line each on stdout until stdin closes. Speaks enough of the Model
+/// Context Protocol (`initialize`, `tools/list`, `tools/call`) for assistants
+/// to use the calendar as a tool.
+pub async fn run(client: CalendarClient, read_only: bool, settings: &Settings) -> Result<()> {
+    let mut server = Server {
+        client,
+        read_only,
+        settings: settings.clone(),
+    };
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await.context("Failed to read stdin")? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_line(&line).await {
+            let mut response = response.to_string();
+            response.push('\n');
+            stdout.write_all(response.as_bytes()).await?;
+            stdout.flush().await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tools() {
+        assert_eq!(
+            Tool::parse("list_events", None).unwrap(),
+            Tool::ListEvents {
+                from: None,
+                to: None
+            }
+        );
+        assert_eq!(
+            Tool::parse(
+                "free_busy",
+                Some(json!({ "date": "2025-06-16", "from": "10:00" }))
+            )
+            .unwrap(),
+            Tool::FreeBusy {
+                date: NaiveDate::from_ymd_opt(2025, 6, 16),
+                from: NaiveTime::from_hms_opt(10, 0, 0),
+                to: None,
+            }
+        );
+
+        let create = Tool::parse(
+            "create_event",
+            Some(json!({ "event": { "title": "Standup", "from": "09:30" }, "calendar": "Work" })),
+        )
+        .unwrap();
+        assert!(matches!(
+            create,
+            Tool::CreateEvent { calendar: Some(ref calendar), .. } if calendar == "Work"
+        ));
+
+        assert!(Tool::parse("search_events", None).is_err());
+        assert!(Tool::parse("list_events", Some(json!({ "soon": 1 }))).is_err());
+    }
+
+    #[test]
+    fn test_tool_definitions_match_tools() {
+        let names: Vec<String> = tool_definitions(false)
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["list_events", "search_events", "free_busy", "create_event"]
+        );
+        assert!(!tool_definitions(true)
+            .iter()
+            .any(|tool| tool["name"] == "create_event"));
+        assert!(Tool::is_known("free_busy"));
+        assert!(!Tool::is_known("delete_event"));
+    }
+
+    #[test]
+    fn test_tool_result_and_error_envelopes() {
+        assert_eq!(
+            tool_result(Ok(json!([]))),
+            json!({ "content": [{ "type": "text", "text": "[]" }], "isError": false })
+        );
+        assert_eq!(
+            tool_result(Err(anyhow::anyhow!("boom").context("Failed"))),
+            json!({ "content": [{ "type": "text", "text": "Failed: boom" }], "isError": true })
+        );
+        assert_eq!(
+            error_response(json!(7), METHOD_NOT_FOUND, "Unknown method x"),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "error": { "code": -32601, "message": "Unknown method x" },
+            })
+        );
+    }
+}
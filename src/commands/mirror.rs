@@ -0,0 +1,26 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::path::Path;
+
+use super::{default_search_range, load_state};
+use crate::calendar::client::CalendarClient;
+use crate::settings::Settings;
+use crate::vdir::Vdir;
+
+/// Mirror the events from a month back to a year ahead into the vdir at
+/// `dir` once, e.g. from cron when neither the TUI nor `watch` runs
+pub async fn run(mut client: CalendarClient, dir: &Path, settings: &Settings) -> Result<()> {
+    let range = default_search_range(Local::now().date_naive());
+    let state = load_state(&mut client, range.clone(), settings).await?;
+
+    let stats = Vdir::new(dir.to_path_buf())
+        .mirror(&state, &range)
+        .with_context(|| format!("Failed to mirror into {}", dir.display()))?;
+    println!(
+        "Wrote {} and removed {} event files in {}",
+        stats.written,
+        stats.removed,
+        dir.display()
+    );
+    Ok(())
+}
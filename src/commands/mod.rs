@@ -0,0 +1,173 @@
+pub mod add;
+mod attendees;
+pub mod bar;
+pub mod bulk;
+pub mod busy;
+pub mod calendars;
+pub mod delete;
+pub mod doctor;
+pub mod edit;
+pub mod find_slot;
+pub mod holidays;
+pub mod import;
+pub mod list;
+pub mod mcp;
+pub mod mirror;
+pub mod next;
+pub mod note;
+pub mod open;
+pub mod output;
+pub mod rsvp;
+pub mod search;
+#[cfg(unix)]
+pub mod serve;
+pub mod status;
+pub mod watch;
+pub mod week;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use std::sync::Arc;
+
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Calendar;
+use crate::settings::Settings;
+use crate::tui::calendar_prefs::CalendarPrefs;
+use crate::tui::fetcher::fetch_calendar_data;
+use crate::tui::state::{AppState, DateRange};
+
+/// How new events are made when the command doesn't say, from flags and config.toml
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventDefaults {
+    /// Calendar id or name new events go to (`--default-calendar`); None for
+    /// the primary calendar
+    pub calendar: Option<String>,
+    /// Length of a timed event given only its start (`--event-duration`)
+    pub duration: Duration,
+    /// Proposed start times fall on multiples of this (`--snap`)
+    pub snap: Duration,
+}
+
+impl Default for EventDefaults {
+    fn default() -> Self {
+        Self {
+            calendar: None,
+            duration: Duration::hours(1),
+            snap: Duration::minutes(15),
+        }
+    }
+}
+
+/// Without explicit dates, lookups cover this far back and ahead of today
+const SEARCH_DAYS_BACK: i64 = 30;
+const SEARCH_DAYS_AHEAD: i64 = 365;
+
+fn default_search_range(today: NaiveDate) -> DateRange {
+    DateRange {
+        start: today - Duration::days(SEARCH_DAYS_BACK),
+        end: today + Duration::days(SEARCH_DAYS_AHEAD),
+    }
+}
+
+/// Fetch calendars and events for `range` into a fresh state, honoring the
+/// calendars hidden in the TUI so commands show what the TUI would
+async fn load_state(
+    client: &mut CalendarClient,
+    range: DateRange,
+    settings: &Settings,
+) -> Result<AppState> {
+    let mut state = AppState::new();
+    state.settings = Arc::new(settings.clone());
+    // A broken prefs file shouldn't make commands unusable
+    if let Ok(prefs) = CalendarPrefs::load() {
+        state.apply_calendar_prefs(prefs);
+    }
+
+    let data = fetch_calendar_data(
+        client,
+        range.clone(),
+        &[],
+        settings,
+        |calendar, events, _, _| {
+            if let Some(events) = events {
+                state.merge_calendar_events(&range, &calendar.id, events);
+            }
+        },
+    )
+    .await?;
+    state.calendars = data.calendars;
+
+    Ok(state)
+}
+
+/// Find a calendar by id or case-insensitive name; `None` picks the
+/// `--default-calendar` of `defaults`, or else the primary calendar
+pub fn resolve_calendar<'a>(
+    calendars: &'a [Calendar],
+    name: Option<&str>,
+    defaults: &EventDefaults,
+) -> Result<&'a Calendar> {
+    find_calendar(calendars, name.or(defaults.calendar.as_deref()))
+}
+
+/// Find a calendar by id or case-insensitive name; `None` picks the primary one
+pub(super) fn find_calendar<'a>(
+    calendars: &'a [Calendar],
+    name: Option<&str>,
+) -> Result<&'a Calendar> {
+    match name {
+        Some(name) => calendars
+            .iter()
+            .find(|calendar| calendar.id == name)
+            .or_else(|| {
+                calendars
+                    .iter()
+                    .find(|calendar| calendar.summary.eq_ignore_ascii_case(name))
+            })
+            .with_context(|| format!("No calendar named \"{}\"", name)),
+        None => calendars
+            .iter()
+            .find(|calendar| calendar.primary)
+            .context("No primary calendar found"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(id: &str, summary: &str, primary: bool) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_resolve_calendar() {
+        let calendars = vec![
+            calendar("me@example.com", "Me", true),
+            calendar("abc@group.calendar.google.com", "Personal", false),
+        ];
+
+        assert_eq!(find_calendar(&calendars, None).unwrap().summary, "Me");
+        assert_eq!(
+            find_calendar(&calendars, Some("personal")).unwrap().summary,
+            "Personal"
+        );
+        assert_eq!(
+            find_calendar(&calendars, Some("abc@group.calendar.google.com"))
+                .unwrap()
+                .summary,
+            "Personal"
+        );
+        assert!(find_calendar(&calendars, Some("work")).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local};
+
+use super::bar::{self, Bar};
+use super::list::ListedEvent;
+use super::load_state;
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::conference;
+use crate::calendar::models::Event;
+use crate::formats::Localized;
+use crate::settings::Settings;
+use crate::tui::state::{relative_start, AppState, DateRange};
+
+/// An event was printed
+pub const EXIT_EVENT: i32 = 0;
+/// Nothing is upcoming within the lookahead
+pub const EXIT_NO_EVENT: i32 = 1;
+/// Fetching failed
+pub const EXIT_ERROR: i32 = 2;
+
+/// Print the next upcoming event using `format` (or as a JSON record, or as
+/// a status bar line) and return the exit code
+pub async fn run(
+    mut client: CalendarClient,
+    format: &str,
+    days: u32,
+    all_day: bool,
+    bar: Option<Bar>,
+    settings: &Settings,
+    output: OutputFormat,
+) -> i32 {
+    let now = Local::now();
+    let state = match load_upcoming(&mut client, now, days, settings).await {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Failed to fetch events: {:#}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    let next = next_event(&state, now, all_day);
+
+    if let Some(bar) = bar {
+        // Bars show a failing command as broken, so an empty slot is a success
+        return match bar::line(bar, format, next, &state, now) {
+            Ok(line) => {
+                println!("{}", line);
+                EXIT_EVENT
+            }
+            Err(e) => {
+                eprintln!("{:#}", e);
+                EXIT_ERROR
+            }
+        };
+    }
+
+    if output.is_json() {
+        let records: Vec<ListedEvent> = next
+            .iter()
+            .map(|event| ListedEvent::from_state(event, &state))
+            .collect();
+        if let Err(e) = output::print(&records, output, &settings.formats) {
+            eprintln!("{:#}", e);
+            return EXIT_ERROR;
+        }
+    } else if let Some(event) = next {
+        println!("{}", render(format, event, &state, now));
+    }
+
+    match next {
+        Some(_) => EXIT_EVENT,
+        None => EXIT_NO_EVENT,
+    }
+}
+
+/// The first event not over yet, skipping all-day events unless `all_day`
+pub(super) fn next_event(state: &AppState, now: DateTime<Local>, all_day: bool) -> Option<&Event> {
+    state
+        .upcoming_from(now, state.event_count())
+        .into_iter()
+        .find(|event| all_day || !event.start.is_all_day())
+}
+
+pub(super) async fn load_upcoming(
+    client: &mut CalendarClient,
+    now: DateTime<Local>,
+    days: u32,
+    settings: &Settings,
+) -> Result<AppState> {
+    let range = DateRange {
+        start: now.date_naive(),
+        end: now.date_naive() + Duration::days(i64::from(days) + 1),
+    };
+    load_state(client, range, settings).await
+}
+
+/// Substitute `{placeholder}`s in `format`; unknown placeholders are kept verbatim
+pub(super) fn render(
+    format: &str,
+    event: &Event,
+    state: &AppState,
+    now: DateTime<Local>,
+) -> String {
+    let mut output = String::new();
+    let mut rest = format;
+
+    while let Some(open) = rest.find('{') {
+        output.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+
+        let name = &rest[open + 1..open + close];
+        match placeholder(name, event, state, now) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn placeholder(
+    name: &str,
+    event: &Event,
+    state: &AppState,
+    now: DateTime<Local>,
+) -> Option<String> {
+    let formats = &state.settings.formats;
+    let start = event.start.to_local();
+    let time = |dt: Option<DateTime<Local>>, all_day: bool| match dt {
+        Some(_) if all_day => "all day".to_string(),
+        Some(dt) => dt.format(&formats.time).to_string(),
+        None => String::new(),
+    };
+
+    let value = match name {
+        "summary" => event
+            .summary
+            .clone()
+            .unwrap_or_else(|| "(No title)".to_string()),
+        "start" => time(start, event.start.is_all_day()),
+        "end" => time(event.end.to_local(), event.end.is_all_day()),
+        "start_rel" => start
+            .map(|start| relative_start(start, now))
+            .unwrap_or_default(),
+        "date" => start
+            .map(|start| start.localized(&formats.date, formats.locale))
+            .unwrap_or_default(),
+        "location" => event.location.clone().unwrap_or_default(),
+        "video_call" => conference::video_call(event)
+            .map(|call| call.url)
+            .unwrap_or_default(),
+        "calendar" => event
+            .calendar_id
+            .as_deref()
+            .and_then(|id| state.get_calendar_by_id(id))
+            .map(|calendar| calendar.summary.clone())
+            .unwrap_or_default(),
+        "off_hours" => {
+            let off_hours = state.settings.working_hours.is_off_hours(event);
+            if off_hours { "off hours" } else { "" }.to_string()
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+    use chrono::TimeZone;
+
+    fn local_time(date_time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(date_time)
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn create_event(start: &str, end: &str) -> Event {
+        Event {
+            id: "standup".to_string(),
+            location: Some("Room 4".to_string()),
+            calendar_id: Some("work".to_string()),
+            ..Event::test(
+                "Standup",
+                EventDateTime {
+                    date_time: Some(start.to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some(end.to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_render_default_format() {
+        let event = create_event("2025-06-15T10:12:00Z", "2025-06-15T10:30:00Z");
+        let now = local_time("2025-06-15T10:00:00Z");
+
+        let line = render("{start_rel} {summary}", &event, &AppState::new(), now);
+
+        assert_eq!(line, "in 12m Standup");
+    }
+
+    #[test]
+    fn test_render_times_and_location() {
+        let event = create_event("2025-06-15T10:12:00Z", "2025-06-15T10:30:00Z");
+        let now = local_time("2025-06-15T10:00:00Z");
+        let start = local_time("2025-06-15T10:12:00Z").format("%H:%M");
+        let end = local_time("2025-06-15T10:30:00Z").format("%H:%M");
+
+        let line = render("{start}-{end} @ {location}", &event, &AppState::new(), now);
+
+        assert_eq!(line, format!("{}-{} @ Room 4", start, end));
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_and_unclosed_placeholders() {
+        let event = create_event("2025-06-15T10:12:00Z", "2025-06-15T10:30:00Z");
+        let now = local_time("2025-06-15T10:00:00Z");
+
+        let line = render("{nope} {summary} {oops", &event, &AppState::new(), now);
+
+        assert_eq!(line, "{nope} Standup {oops");
+    }
+
+    #[test]
+    fn test_render_all_day_times() {
+        let mut event = create_event("2025-06-15T10:12:00Z", "2025-06-15T10:30:00Z");
+        event.start = EventDateTime {
+            date_time: None,
+            date: Some("2025-06-16".to_string()),
+            time_zone: None,
+        };
+        let now = local_time("2025-06-15T10:00:00Z");
+
+        let line = render("{start} {summary}", &event, &AppState::new(), now);
+
+        assert_eq!(line, "all day Standup");
+    }
+
+    #[test]
+    fn test_render_off_hours() {
+        // The default working hours are 9-17 on weekdays; June 16 is a Monday
+        let at = |hour: u32| {
+            Local
+                .with_ymd_and_hms(2025, 6, 16, hour, 0, 0)
+                .unwrap()
+                .to_rfc3339()
+        };
+        let now = Local.with_ymd_and_hms(2025, 6, 16, 1, 0, 0).unwrap();
+
+        let hold = create_event(&at(2), &at(3));
+        let standup = create_event(&at(10), &at(11));
+
+        assert_eq!(
+            render("{summary} {off_hours}", &hold, &AppState::new(), now),
+            "Standup off hours"
+        );
+        assert_eq!(
+            render("{summary}{off_hours}", &standup, &AppState::new(), now),
+            "Standup"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+
+use super::list::listed_events;
+use super::load_state;
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::conference;
+use crate::calendar::models::Event;
+use crate::settings::Settings;
+use crate::tui::state::{AppState, DateRange};
+
+/// Print the schedule of `date` as a Markdown section for a daily note (or
+/// its events in `format` when that isn't a table)
+pub async fn run(
+    mut client: CalendarClient,
+    date: NaiveDate,
+    heading: &str,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let range = DateRange {
+        start: date,
+        end: date + Duration::days(1),
+    };
+    let state = load_state(&mut client, range, settings).await?;
+
+    if format != OutputFormat::Table {
+        return output::print(
+            &listed_events(&state, date, date),
+            format,
+            &settings.formats,
+        );
+    }
+
+    print!("{}", markdown(&state, date, heading));
+    Ok(())
+}
+
+/// `heading`, then one list item per event: all-day events first, then timed
+/// ones by start, each with its links
+fn markdown(state: &AppState, date: NaiveDate, heading: &str) -> String {
+    let mut events: Vec<&Event> = state.get_events_for_date(date).collect();
+    events.sort_by_key(|event| (!event.start.is_all_day(), event.start.to_local()));
+
+    let mut text = String::new();
+    if !heading.is_empty() {
+        text.push_str(heading);
+        text.push_str("\n\n");
+    }
+    if events.is_empty() {
+        text.push_str("- No events\n");
+    }
+    for event in events {
+        text.push_str(&item(event, &state.settings.formats.time));
+        text.push('\n');
+    }
+    text
+}
+
+/// `- 09:00–09:30 [Standup](link) · Room 4 · [Join Google Meet](call link)`
+fn item(event: &Event, time_format: &str) -> String {
+    let time = match (event.start.to_local(), event.end.to_local()) {
+        (Some(start), Some(end)) if !event.start.is_all_day() => {
+            format!("{}–{}", start.format(time_format), end.format(time_format))
+        }
+        _ => "All day".to_string(),
+    };
+    let title = escape(event.summary.as_deref().unwrap_or("(No title)"));
+
+    let mut parts = vec![match &event.html_link {
+        Some(link) => format!("{} [{}](<{}>)", time, title, link),
+        None => format!("{} {}", time, title),
+    }];
+    if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+        parts.push(escape(location));
+    }
+    if let Some(call) = conference::video_call(event) {
+        parts.push(format!("[Join {}](<{}>)", call.name(), call.url));
+    }
+    format!("- {}", parts.join(" · "))
+}
+
+/// Titles must not turn into links, emphasis or tasks in the note
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '[' | ']' | '(' | ')' | '*' | '_' | '`' | '#' | '<' | '>' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(if c == '\n' { ' ' } else { c });
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn event(id: &str, start: EventDateTime, end: EventDateTime) -> Event {
+        Event::test(id, start, end)
+    }
+
+    fn local(time: &str) -> EventDateTime {
+        let date_time = chrono::NaiveDateTime::parse_from_str(
+            &format!("2025-06-16 {}", time),
+            "%Y-%m-%d %H:%M",
+        )
+        .unwrap()
+        .and_local_timezone(chrono::Local)
+        .unwrap();
+        EventDateTime {
+            date_time: Some(date_time.to_rfc3339()),
+            date: None,
+            time_zone: None,
+        }
+    }
+
+    fn day(date: &str) -> EventDateTime {
+        EventDateTime {
+            date_time: None,
+            date: Some(date.to_string()),
+            time_zone: None,
+        }
+    }
+
+    #[test]
+    fn test_item_with_links() {
+        let mut standup = event("Standup", local("09:00"), local("09:30"));
+        standup.html_link = Some("https://calendar.google.com/event?eid=abc".to_string());
+        standup.location = Some("Room 4".to_string());
+        standup.hangout_link = Some("https://meet.google.com/abc-defg-hij".to_string());
+
+        assert_eq!(
+            item(&standup, "%H:%M"),
+            "- 09:00–09:30 [Standup](<https://calendar.google.com/event?eid=abc>) · Room 4 · \
+             [Join Google Meet](<https://meet.google.com/abc-defg-hij>)"
+        );
+        assert_eq!(
+            item(
+                &event("Holiday", day("2025-06-16"), day("2025-06-17")),
+                "%H:%M"
+            ),
+            "- All day Holiday"
+        );
+    }
+
+    #[test]
+    fn test_markdown_orders_all_day_first() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        let mut state = AppState::new();
+        state.events.insert(
+            date,
+            vec![
+                event("Review", local("15:00"), local("16:00")),
+                event("Standup", local("09:00"), local("09:30")),
+                event("Holiday", day("2025-06-16"), day("2025-06-17")),
+            ],
+        );
+
+        assert_eq!(
+            markdown(&state, date, "## Schedule"),
+            "## Schedule\n\n\
+             - All day Holiday\n\
+             - 09:00–09:30 Standup\n\
+             - 15:00–16:00 Review\n"
+        );
+        assert_eq!(markdown(&AppState::new(), date, ""), "- No events\n");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("[WIP] *urgent* #1"), "\\[WIP\\] \\*urgent\\* \\#1");
+        assert_eq!(escape("line\nbreak"), "line break");
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+
+use super::delete::find_matches;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use super::{default_search_range, load_state};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::conference;
+use crate::calendar::models::Event;
+use crate::settings::Settings;
+
+/// Open the event matching `query` (an id or title words) in the browser;
+/// `join` opens its video call instead of the calendar page
+pub async fn run(
+    mut client: CalendarClient,
+    query: &str,
+    join: bool,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let range = default_search_range(Local::now().date_naive());
+    let state = load_state(&mut client, range.clone(), settings).await?;
+
+    let matches = find_matches(&state, query, &range);
+    let event =
+        pick(&matches, Local::now()).with_context(|| format!("No event matches \"{}\"", query))?;
+    let url = link(event, join)?;
+
+    webbrowser::open(&url).context("Failed to open browser")?;
+    if format.is_json() {
+        output::print(
+            &[ListedEvent::from_state(event, &state)],
+            format,
+            &settings.formats,
+        )
+    } else {
+        println!("Opened {}", url);
+        Ok(())
+    }
+}
+
+/// Of several matches the next one not over yet is meant, else the latest
+fn pick<'a>(matches: &[&'a Event], now: DateTime<Local>) -> Option<&'a Event> {
+    let mut matches = matches.to_vec();
+    matches.sort_by_key(|event| event.start.to_local());
+
+    matches
+        .iter()
+        .find(|event| event.end.to_local().is_some_and(|end| end > now))
+        .or(matches.last())
+        .copied()
+}
+
+fn link(event: &Event, join: bool) -> Result<String> {
+    let summary = event.summary.as_deref().unwrap_or("(No title)");
+    if join {
+        conference::video_call(event)
+            .map(|call| call.url)
+            .with_context(|| format!("\"{}\" has no video call link", summary))
+    } else {
+        event
+            .html_link
+            .clone()
+            .with_context(|| format!("\"{}\" has no link", summary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn create_event(id: &str, start: &str, end: &str) -> Event {
+        let at = |time: &str| EventDateTime {
+            date_time: Some(format!("2025-06-16T{}:00Z", time)),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            id: id.to_string(),
+            html_link: Some(format!("https://calendar.google.com/event?eid={}", id)),
+            ..Event::test("Standup", at(start), at(end))
+        }
+    }
+
+    fn at(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    #[test]
+    fn test_pick_prefers_next_unfinished() {
+        let early = create_event("early", "09:00", "09:15");
+        let late = create_event("late", "15:00", "15:15");
+        let matches = vec![&late, &early];
+
+        assert_eq!(pick(&matches, at("08:00")).unwrap().id, "early");
+        assert_eq!(pick(&matches, at("09:10")).unwrap().id, "early");
+        assert_eq!(pick(&matches, at("10:00")).unwrap().id, "late");
+        assert_eq!(pick(&matches, at("16:00")).unwrap().id, "late");
+        assert!(pick(&[], at("16:00")).is_none());
+    }
+
+    #[test]
+    fn test_link() {
+        let mut event = create_event("abc", "09:00", "09:15");
+
+        assert_eq!(
+            link(&event, false).unwrap(),
+            "https://calendar.google.com/event?eid=abc"
+        );
+        assert!(link(&event, true).is_err());
+
+        event.location = Some("https://acme.zoom.us/j/123".to_string());
+        assert_eq!(link(&event, true).unwrap(), "https://acme.zoom.us/j/123");
+
+        event.hangout_link = Some("https://meet.google.com/abc-defg-hij".to_string());
+        assert_eq!(
+            link(&event, true).unwrap(),
+            "https://meet.google.com/abc-defg-hij"
+        );
+    }
+}
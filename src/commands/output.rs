@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::formats::Formats;
+
+/// How non-interactive commands print their results (`-o/--output`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Tab-separated columns, one record per line
+    Plain,
+    /// Aligned columns for reading
+    Table,
+    /// A pretty-printed JSON array
+    Json,
+    /// One compact JSON object per line
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// JSON or NDJSON: records instead of human-readable messages
+    pub fn is_json(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Ndjson)
+    }
+}
+
+/// A result row. Its serialized form is the stable JSON schema of a command;
+/// `columns` is what plain and table output show, with dates and times in `formats`.
+pub trait Record: Serialize {
+    fn columns(&self, formats: &Formats) -> Vec<String>;
+}
+
+/// Print `records` in `format`; an empty JSON result is still printed as `[]`
+pub fn print<T: Record>(records: &[T], format: OutputFormat, formats: &Formats) -> Result<()> {
+    for line in lines(records, format, formats)? {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn lines<T: Record>(records: &[T], format: OutputFormat, formats: &Formats) -> Result<Vec<String>> {
+    Ok(match format {
+        OutputFormat::Plain => records
+            .iter()
+            .map(|record| record.columns(formats).join("\t"))
+            .collect(),
+        OutputFormat::Table => table(
+            records
+                .iter()
+                .map(|record| record.columns(formats))
+                .collect(),
+        ),
+        OutputFormat::Json => {
+            vec![serde_json::to_string_pretty(records).context("Failed to serialize output")?]
+        }
+        OutputFormat::Ndjson => records
+            .iter()
+            .map(|record| serde_json::to_string(record).context("Failed to serialize output"))
+            .collect::<Result<_>>()?,
+    })
+}
+
+/// Pad every column but the last to its widest value
+fn table(rows: Vec<Vec<String>>) -> Vec<String> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &rows {
+        for (index, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(index) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            row.iter()
+                .enumerate()
+                .map(|(index, cell)| match index {
+                    index if index == last => cell.clone(),
+                    index => format!("{:<width$}", cell, width = widths[index]),
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        count: u32,
+    }
+
+    impl Record for Row {
+        fn columns(&self, _formats: &Formats) -> Vec<String> {
+            vec![self.name.clone(), self.count.to_string()]
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                name: "standup".to_string(),
+                count: 5,
+            },
+            Row {
+                name: "1:1".to_string(),
+                count: 12,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_plain_and_table_lines() {
+        assert_eq!(
+            lines(&rows(), OutputFormat::Plain, &Formats::default()).unwrap(),
+            vec!["standup\t5", "1:1\t12"]
+        );
+        assert_eq!(
+            lines(&rows(), OutputFormat::Table, &Formats::default()).unwrap(),
+            vec!["standup  5", "1:1      12"]
+        );
+    }
+
+    #[test]
+    fn test_json_and_ndjson_lines() {
+        assert_eq!(
+            lines(&rows(), OutputFormat::Ndjson, &Formats::default()).unwrap(),
+            vec![
+                r#"{"name":"standup","count":5}"#,
+                r#"{"name":"1:1","count":12}"#
+            ]
+        );
+
+        let json = lines(&rows(), OutputFormat::Json, &Formats::default())
+            .unwrap()
+            .join("\n");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[1]["count"], 12);
+    }
+
+    #[test]
+    fn test_empty_output() {
+        let empty: Vec<Row> = Vec::new();
+
+        assert!(lines(&empty, OutputFormat::Table, &Formats::default())
+            .unwrap()
+            .is_empty());
+        assert!(lines(&empty, OutputFormat::Ndjson, &Formats::default())
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            lines(&empty, OutputFormat::Json, &Formats::default()).unwrap(),
+            vec!["[]"]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+use super::edit::find_event;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::{Attendee, Event, EventPatch};
+use crate::settings::Settings;
+
+/// An answer to an invitation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Response {
+    Accept,
+    Decline,
+    Tentative,
+}
+
+impl Response {
+    /// The attendee `responseStatus` the API expects
+    pub fn status(self) -> &'static str {
+        match self {
+            Response::Accept => "accepted",
+            Response::Decline => "declined",
+            Response::Tentative => "tentative",
+        }
+    }
+}
+
+/// Answer the invitation to `event_id` as the authenticated user
+pub async fn run(
+    mut client: CalendarClient,
+    event_id: &str,
+    response: Response,
+    comment: Option<String>,
+    calendar: Option<&str>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let (calendar, event) = find_event(&mut client, event_id, calendar).await?;
+    let patch = EventPatch {
+        attendees: Some(respond(&event, response, comment)?),
+        ..EventPatch::default()
+    };
+
+    let updated = client
+        .patch_event(&calendar.id, &event.id, &patch)
+        .await
+        .context("Failed to send response")?;
+    if format.is_json() {
+        let record = ListedEvent::of(&updated, &calendar.id, &calendar.summary);
+        return output::print(&[record], format, &settings.formats);
+    }
+    println!(
+        "Responded {} to \"{}\"",
+        response.status(),
+        updated.summary.as_deref().unwrap_or("(No title)")
+    );
+
+    Ok(())
+}
+
+/// The event's attendees with the user's own entry answered. Attendees must be
+/// sent as a whole list, so everybody else is kept unchanged. Without `comment`
+/// a previous comment stays.
+pub fn respond(
+    event: &Event,
+    response: Response,
+    comment: Option<String>,
+) -> Result<Vec<Attendee>> {
+    let mut attendees = event.attendees.clone().unwrap_or_default();
+    let own = attendees
+        .iter_mut()
+        .find(|attendee| attendee.is_self == Some(true))
+        .context("You are not invited to this event")?;
+
+    own.response_status = Some(response.status().to_string());
+    if comment.is_some() {
+        own.comment = comment;
+    }
+
+    Ok(attendees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn attendee(email: &str, is_self: bool) -> Attendee {
+        Attendee {
+            email: email.to_string(),
+            display_name: None,
+            response_status: Some("needsAction".to_string()),
+            optional: None,
+            is_self: is_self.then_some(true),
+            comment: Some("Maybe".to_string()),
+        }
+    }
+
+    fn invitation(attendees: Option<Vec<Attendee>>) -> Event {
+        let start = EventDateTime {
+            date_time: Some("2025-07-02T09:00:00Z".to_string()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            id: "abc123".to_string(),
+            attendees,
+            ..Event::test("Planning", start.clone(), start)
+        }
+    }
+
+    #[test]
+    fn test_respond_updates_only_own_entry() {
+        let event = invitation(Some(vec![
+            attendee("boss@example.com", false),
+            attendee("me@example.com", true),
+        ]));
+
+        let attendees = respond(&event, Response::Decline, Some("Focus time".to_string())).unwrap();
+
+        assert_eq!(attendees.len(), 2);
+        assert_eq!(attendees[0], attendee("boss@example.com", false));
+        assert_eq!(attendees[1].response_status.as_deref(), Some("declined"));
+        assert_eq!(attendees[1].comment.as_deref(), Some("Focus time"));
+    }
+
+    #[test]
+    fn test_respond_keeps_comment_when_not_given() {
+        let event = invitation(Some(vec![attendee("me@example.com", true)]));
+
+        let attendees = respond(&event, Response::Tentative, None).unwrap();
+
+        assert_eq!(attendees[0].response_status.as_deref(), Some("tentative"));
+        assert_eq!(attendees[0].comment.as_deref(), Some("Maybe"));
+    }
+
+    #[test]
+    fn test_respond_requires_an_invitation() {
+        assert!(respond(&invitation(None), Response::Accept, None).is_err());
+
+        let event = invitation(Some(vec![attendee("boss@example.com", false)]));
+        assert!(respond(&event, Response::Accept, None).is_err());
+    }
+}
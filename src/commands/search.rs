@@ -0,0 +1,228 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate};
+use serde::Serialize;
+
+use super::default_search_range;
+use super::list::ListedEvent;
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::index::EventIndex;
+use crate::calendar::models::{Calendar, Event};
+use crate::formats::Formats;
+use crate::settings::Settings;
+use crate::tui::calendar_prefs::CalendarPrefs;
+use crate::tui::fetcher::local_midnight;
+use crate::tui::state::DateRange;
+
+/// Most events read from the local index for one search
+const INDEX_SEARCH_LIMIT: usize = 500;
+
+/// A search hit; serialized like a `list` event, but shown id first
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+struct SearchResult(ListedEvent);
+
+/// ID, WHEN, SUMMARY, CALENDAR so `cut -f1` of plain output yields ids for `edit`/`delete`
+impl Record for SearchResult {
+    fn columns(&self, formats: &Formats) -> Vec<String> {
+        let event = &self.0;
+        let when = match event.local_start(&formats.time) {
+            (date, _) if event.all_day => date,
+            (date, time) => format!("{} {}", date, time),
+        };
+
+        vec![
+            event.id.clone(),
+            when,
+            event.summary.clone(),
+            event.calendar.clone(),
+        ]
+    }
+}
+
+/// Print events matching `query` in visible calendars, from the local index
+/// when it is enabled and from the API otherwise
+pub async fn run(
+    mut client: CalendarClient,
+    query: &str,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let default_range = default_search_range(Local::now().date_naive());
+    let range = DateRange {
+        start: from.unwrap_or(default_range.start),
+        end: to.map_or(default_range.end, |to| to + Duration::days(1)),
+    };
+    if range.end <= range.start {
+        bail!("--to is before --from");
+    }
+
+    let results = find(&mut client, query, &range, settings.index).await?;
+    if results.is_empty() && !format.is_json() {
+        bail!("No events match \"{}\"", query);
+    }
+    let results: Vec<SearchResult> = results.into_iter().map(SearchResult).collect();
+
+    output::print(&results, format, &settings.formats)
+}
+
+/// Events matching `query` in visible calendars within `range`, by start;
+/// read from the local index when `use_index` (`--index`)
+pub(super) async fn find(
+    client: &mut CalendarClient,
+    query: &str,
+    range: &DateRange,
+    use_index: bool,
+) -> Result<Vec<ListedEvent>> {
+    let hidden = CalendarPrefs::load()
+        .map(|prefs| prefs.hidden)
+        .unwrap_or_default();
+    let calendars = client
+        .list_calendars()
+        .await
+        .context("Failed to fetch calendars")?;
+
+    let visible: Vec<&Calendar> = calendars
+        .iter()
+        .filter(|c| !hidden.contains(&c.id))
+        .collect();
+
+    let mut results: Vec<(Event, &Calendar)> = match EventIndex::open(use_index)? {
+        Some(index) => index
+            .search(
+                query,
+                local_midnight(range.start),
+                local_midnight(range.end),
+                INDEX_SEARCH_LIMIT,
+            )?
+            .into_iter()
+            .filter(|event| matches_words(event, query))
+            .filter_map(|event| {
+                let calendar = visible
+                    .iter()
+                    .find(|calendar| event.calendar_id.as_deref() == Some(calendar.id.as_str()))?;
+                Some((event, *calendar))
+            })
+            .collect(),
+        None => search_api(client, &visible, query, range).await,
+    };
+    results.sort_by_key(|(event, _)| event.start.to_local());
+
+    Ok(results
+        .iter()
+        .map(|(event, calendar)| ListedEvent::of(event, &calendar.id, &calendar.summary))
+        .collect())
+}
+
+/// Ask the API calendar by calendar, skipping calendars that fail
+async fn search_api<'a>(
+    client: &mut CalendarClient,
+    calendars: &[&'a Calendar],
+    query: &str,
+    range: &DateRange,
+) -> Vec<(Event, &'a Calendar)> {
+    let mut results = Vec::new();
+    for &calendar in calendars {
+        let events = match client
+            .search_events(
+                &calendar.id,
+                query,
+                local_midnight(range.start),
+                local_midnight(range.end),
+            )
+            .await
+        {
+            Ok(events) => events,
+            Err(e) => {
+                eprintln!("Skipping {}: {:#}", calendar.summary, e);
+                continue;
+            }
+        };
+
+        results.extend(
+            events
+                .into_iter()
+                .filter(|event| matches_words(event, query))
+                .map(|event| (event, calendar)),
+        );
+    }
+    results
+}
+
+/// The API matches loosely (any field, stemmed words); keep only events where
+/// every query word appears in the title, description, location or attendees
+fn matches_words(event: &Event, query: &str) -> bool {
+    let mut haystack: Vec<String> = [&event.summary, &event.description, &event.location]
+        .into_iter()
+        .flatten()
+        .map(|text| text.to_lowercase())
+        .collect();
+    for attendee in event.attendees.iter().flatten() {
+        haystack.push(attendee.email.to_lowercase());
+        if let Some(ref name) = attendee.display_name {
+            haystack.push(name.to_lowercase());
+        }
+    }
+
+    query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .all(|word| haystack.iter().any(|text| text.contains(&word)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::{Attendee, EventDateTime};
+
+    fn create_event(summary: &str) -> Event {
+        Event {
+            id: "abc123".to_string(),
+            description: Some("Weekly sync".to_string()),
+            attendees: Some(vec![Attendee {
+                email: "alex@example.com".to_string(),
+                display_name: Some("Alex Doe".to_string()),
+                response_status: None,
+                optional: None,
+                is_self: None,
+                comment: None,
+            }]),
+            ..Event::test(
+                summary,
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-07-02".to_string()),
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-07-03".to_string()),
+                    time_zone: None,
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_matches_words_across_fields() {
+        let event = create_event("1:1");
+
+        assert!(matches_words(&event, "1:1 alex"));
+        assert!(matches_words(&event, "WEEKLY doe"));
+        assert!(!matches_words(&event, "1:1 sam"));
+    }
+
+    #[test]
+    fn test_result_columns_start_with_id() {
+        let event = create_event("1:1 with Alex");
+
+        let result = SearchResult(ListedEvent::of(&event, "work", "Work"));
+
+        assert_eq!(
+            result.columns(&Formats::default()),
+            vec!["abc123", "2025-07-02", "1:1 with Alex", "Work"]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Mutex;
+
+use super::bulk::EventDraft;
+use super::list::{listed_events, ListedEvent};
+use super::next;
+use super::{load_state, resolve_calendar};
+use crate::auth::account;
+use crate::calendar::client::CalendarClient;
+use crate::settings::Settings;
+use crate::tui::state::{AppState, DateRange};
+
+/// Cached events older than this are refetched before answering
+const MAX_CACHE_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// One request per line, e.g. `{"method": "list", "from": "2025-06-16"}`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case", deny_unknown_fields)]
+enum Request {
+    /// Visible events from `from` through `to`, both defaulting to today
+    List {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    /// The next timed (or, with `all_day`, any) event, or null
+    Next {
+        #[serde(default)]
+        all_day: bool,
+    },
+    /// Create an event; `event` takes the fields of an `add --stdin` line
+    Create {
+        event: EventDraft,
+        calendar: Option<String>,
+    },
+    /// Refetch calendars and events now
+    Refresh,
+}
+
+/// The calendar data shared by all connections
+struct Server {
+    client: CalendarClient,
+    read_only: bool,
+    settings: Settings,
+    range: DateRange,
+    state: AppState,
+    /// None until the first fetch and after a change
+    loaded_at: Option<Instant>,
+}
+
+impl Server {
+    async fn handle(&mut self, request: Request) -> Result<Value> {
+        let now = Local::now();
+        if request == Request::Refresh
+            || self
+                .loaded_at
+                .is_none_or(|loaded_at| loaded_at.elapsed() >= MAX_CACHE_AGE)
+        {
+            self.reload(now.date_naive()).await?;
+        }
+
+        match request {
+            Request::List { from, to } => {
+                let from = from.unwrap_or(now.date_naive());
+                let to = to.unwrap_or(from);
+                if to < from {
+                    bail!("\"to\" ({}) is before \"from\" ({})", to, from);
+                }
+                let events = if from >= self.range.start && to < self.range.end {
+                    listed_events(&self.state, from, to)
+                } else {
+                    let range = DateRange {
+                        start: from,
+                        end: to + chrono::Duration::days(1),
+                    };
+                    let state = load_state(&mut self.client, range, &self.settings).await?;
+                    listed_events(&state, from, to)
+                };
+                Ok(serde_json::to_value(events)?)
+            }
+            Request::Next { all_day } => {
+                Ok(serde_json::to_value(next_event(&self.state, now, all_day))?)
+            }
+            Request::Create { event, calendar } => {
+                if self.read_only {
+                    bail!("Creating events is disabled in read-only mode");
+                }
+                let calendar = resolve_calendar(
+                    &self.state.calendars,
+                    calendar.as_deref(),
+                    &self.settings.event_defaults,
+                )?;
+                let (calendar_id, calendar_name) = (calendar.id.clone(), calendar.summary.clone());
+                let event =
+                    event.to_event(now.date_naive(), self.settings.event_defaults.duration)?;
+                let created = self
+                    .client
+                    .insert_event(&calendar_id, &event)
+                    .await
+                    .context("Failed to create event")?;
+                self.loaded_at = None;
+                Ok(serde_json::to_value(ListedEvent::of(
+                    &created,
+                    &calendar_id,
+                    &calendar_name,
+                ))?)
+            }
+            Request::Refresh => Ok(json!({ "events": self.state.event_count() })),
+        }
+    }
+
+    async fn reload(&mut self, today: NaiveDate) -> Result<()> {
+        self.range = DateRange::five_month_span(today);
+        self.state = load_state(&mut self.client, self.range.clone(), &self.settings).await?;
+        self.loaded_at = Some(Instant::now());
+        Ok(())
+    }
+}
+
+/// Answer JSON requests on a Unix socket (by default `oxidate.sock` in the
+/// account's data directory) until interrupted
+pub async fn run(
+    client: CalendarClient,
+    socket: Option<PathBuf>,
+    read_only: bool,
+    settings: &Settings,
+) -> Result<()> {
+    let path = match socket {
+        Some(path) => path,
+        None => account::data_dir()?.join("oxidate.sock"),
+    };
+    let listener = bind(&path).await?;
+    eprintln!("Listening on {}", path.display());
+
+    let server = Arc::new(Mutex::new(Server {
+        client,
+        read_only,
+        settings: settings.clone(),
+        range: DateRange::five_month_span(Local::now().date_naive()),
+        state: AppState::new(),
+        loaded_at: None,
+    }));
+
+    let result = tokio::select! {
+        result = accept_loop(&listener, server) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    };
+    // Leave no stale socket behind for the next start to trip over
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// Listen on `path`, replacing a socket left behind by an instance that is gone
+async fn bind(path: &Path) -> Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).await.is_ok() {
+            bail!("Another oxidate is already serving on {}", path.display());
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create socket directory")?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))?;
+    // Only the user may talk to their calendar
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .context("Failed to restrict socket permissions")?;
+    Ok(listener)
+}
+
+async fn accept_loop(listener: &UnixListener, server: Arc<Mutex<Server>>) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await.context("Failed to accept")?;
+        let server = Arc::clone(&server);
+        tokio::spawn(async move {
+            if let Err(e) = serve_connection(stream, server).await {
+                tracing::warn!("Connection failed: {:#}", e);
+            }
+        });
+    }
+}
+
+/// Answer each request line with one response line until the client hangs up
+async fn serve_connection(stream: UnixStream, server: Arc<Mutex<Server>>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => server.lock().await.handle(request).await,
+            Err(e) => Err(anyhow::Error::new(e).context("Invalid request")),
+        };
+
+        let mut response = response(result).to_string();
+        response.push('\n');
+        writer.write_all(response.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+/// `{"ok": true, "result": ...}` or `{"ok": false, "error": "..."}`
+fn response(result: Result<Value>) -> Value {
+    match result {
+        Ok(result) => json!({ "ok": true, "result": result }),
+        Err(e) => json!({ "ok": false, "error": format!("{:#}", e) }),
+    }
+}
+
+fn next_event(state: &AppState, now: DateTime<Local>, all_day: bool) -> Option<ListedEvent> {
+    next::next_event(state, now, all_day).map(|event| ListedEvent::from_state(event, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::{Event, EventDateTime};
+
+    fn event(id: &str, start: EventDateTime) -> Event {
+        Event {
+            calendar_id: Some("me".to_string()),
+            ..Event::test(id, start.clone(), start)
+        }
+    }
+
+    #[test]
+    fn test_parse_requests() {
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"method": "list", "from": "2025-06-16"}"#).unwrap(),
+            Request::List {
+                from: NaiveDate::from_ymd_opt(2025, 6, 16),
+                to: None,
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"method": "next"}"#).unwrap(),
+            Request::Next { all_day: false }
+        );
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"method": "refresh"}"#).unwrap(),
+            Request::Refresh
+        );
+
+        let create = serde_json::from_str::<Request>(
+            r#"{"method": "create", "event": {"title": "Standup", "from": "09:30"}, "calendar": "Work"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            create,
+            Request::Create { calendar: Some(ref calendar), .. } if calendar == "Work"
+        ));
+
+        assert!(serde_json::from_str::<Request>(r#"{"method": "delete"}"#).is_err());
+        assert!(serde_json::from_str::<Request>(r#"{"method": "next", "soon": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_response_envelope() {
+        assert_eq!(
+            response(Ok(json!([1]))),
+            json!({ "ok": true, "result": [1] })
+        );
+        assert_eq!(
+            response(Err(anyhow::anyhow!("boom").context("Failed"))),
+            json!({ "ok": false, "error": "Failed: boom" })
+        );
+    }
+
+    #[test]
+    fn test_next_event_skips_all_day_unless_asked() {
+        let now = Local::now();
+        let later = (now + chrono::Duration::hours(2)).to_rfc3339();
+        let tomorrow = (now.date_naive() + chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let mut state = AppState::new();
+        for event in [
+            event(
+                "holiday",
+                EventDateTime {
+                    date_time: None,
+                    date: Some(tomorrow),
+                    time_zone: None,
+                },
+            ),
+            event(
+                "meeting",
+                EventDateTime {
+                    date_time: Some(later),
+                    date: None,
+                    time_zone: None,
+                },
+            ),
+        ] {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+
+        assert_eq!(next_event(&state, now, false).unwrap().id, "meeting");
+        assert!(next_event(&AppState::new(), now, false).is_none());
+    }
+}
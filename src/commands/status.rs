@@ -0,0 +1,247 @@
+use chrono::{DateTime, Duration, Local};
+use serde::Serialize;
+
+use super::list::ListedEvent;
+use super::load_state;
+use super::output::{self, OutputFormat, Record};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Event;
+use crate::formats::Formats;
+use crate::settings::Settings;
+use crate::tui::state::{relative_start, AppState, DateRange};
+
+/// A meeting is in progress
+pub const EXIT_IN_MEETING: i32 = 0;
+/// No meeting right now
+pub const EXIT_FREE: i32 = 1;
+/// Fetching failed
+pub const EXIT_ERROR: i32 = 2;
+
+/// Whether a meeting is running now and what comes next. Any timed, visible,
+/// not declined event counts as a meeting; all-day events never do.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MeetingStatus {
+    pub in_meeting: bool,
+    /// Meetings in progress, earliest start first
+    pub current: Vec<ListedEvent>,
+    /// The next meeting starting today or tomorrow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<ListedEvent>,
+}
+
+/// STATE, CURRENT, NEXT
+impl Record for MeetingStatus {
+    fn columns(&self, _formats: &Formats) -> Vec<String> {
+        let state = if self.in_meeting { "busy" } else { "free" };
+        let current: Vec<&str> = self.current.iter().map(|e| e.summary.as_str()).collect();
+        vec![
+            state.to_string(),
+            current.join(", "),
+            self.next
+                .as_ref()
+                .map(|event| event.summary.clone())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+/// Print the meeting status and return the exit code
+pub async fn run(mut client: CalendarClient, settings: &Settings, format: OutputFormat) -> i32 {
+    let now = Local::now();
+    let range = DateRange {
+        start: now.date_naive(),
+        end: now.date_naive() + Duration::days(2),
+    };
+    let state = match load_state(&mut client, range, settings).await {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Failed to fetch events: {:#}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    let status = meeting_status(&state, now);
+    let printed = match format {
+        OutputFormat::Table => {
+            for line in status_lines(&status, now, &settings.formats.time) {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+        format => output::print(std::slice::from_ref(&status), format, &settings.formats),
+    };
+    if let Err(e) = printed {
+        eprintln!("{:#}", e);
+        return EXIT_ERROR;
+    }
+
+    if status.in_meeting {
+        EXIT_IN_MEETING
+    } else {
+        EXIT_FREE
+    }
+}
+
+fn meeting_status(state: &AppState, now: DateTime<Local>) -> MeetingStatus {
+    let (current, upcoming): (Vec<&Event>, Vec<&Event>) = state
+        .upcoming_from(now, state.event_count())
+        .into_iter()
+        .filter(|event| !event.start.is_all_day())
+        .partition(|event| event.start.to_local().is_some_and(|start| start <= now));
+
+    let current: Vec<ListedEvent> = current
+        .into_iter()
+        .map(|event| ListedEvent::from_state(event, state))
+        .collect();
+    let next = upcoming
+        .first()
+        .map(|event| ListedEvent::from_state(event, state));
+
+    MeetingStatus {
+        in_meeting: !current.is_empty(),
+        current,
+        next,
+    }
+}
+
+/// e.g. "In a meeting: Standup (until 09:45)" and "Next: 1:1 at 10:30 (in 45m)"
+fn status_lines(status: &MeetingStatus, now: DateTime<Local>, time_format: &str) -> Vec<String> {
+    let time = |rfc3339: &str| DateTime::parse_from_rfc3339(rfc3339).ok();
+
+    let mut lines: Vec<String> = status
+        .current
+        .iter()
+        .map(|event| match time(&event.end) {
+            Some(end) => format!(
+                "In a meeting: {} (until {})",
+                event.summary,
+                end.with_timezone(&Local).format(time_format)
+            ),
+            None => format!("In a meeting: {}", event.summary),
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push("Free".to_string());
+    }
+
+    if let Some(ref next) = status.next {
+        lines.push(match time(&next.start) {
+            Some(start) => {
+                let start = start.with_timezone(&Local);
+                format!(
+                    "Next: {} at {} ({})",
+                    next.summary,
+                    start.format(time_format),
+                    relative_start(start, now)
+                )
+            }
+            None => format!("Next: {}", next.summary),
+        });
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn at(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn create_event(summary: &str, start: EventDateTime, end: EventDateTime) -> Event {
+        Event {
+            id: summary.to_lowercase(),
+            ..Event::test(summary, start, end)
+        }
+    }
+
+    fn timed(summary: &str, start: &str, end: &str) -> Event {
+        let date_time = |time: &str| EventDateTime {
+            date_time: Some(at(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        create_event(summary, date_time(start), date_time(end))
+    }
+
+    fn state(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        for event in events {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    #[test]
+    fn test_in_meeting_with_next() {
+        let state = state(vec![
+            timed("Lunch", "12:00", "13:00"),
+            timed("Standup", "09:30", "09:45"),
+        ]);
+
+        let status = meeting_status(&state, at("09:40"));
+
+        assert!(status.in_meeting);
+        assert_eq!(status.current.len(), 1);
+        assert_eq!(status.current[0].summary, "Standup");
+        assert_eq!(status.next.as_ref().unwrap().summary, "Lunch");
+        assert_eq!(
+            status.columns(&Formats::default()),
+            vec!["busy", "Standup", "Lunch"]
+        );
+    }
+
+    #[test]
+    fn test_free_ignores_all_day_and_finished_events() {
+        let all_day = EventDateTime {
+            date_time: None,
+            date: Some(at("09:40").date_naive().format("%Y-%m-%d").to_string()),
+            time_zone: None,
+        };
+        let mut end = all_day.clone();
+        end.date = Some(
+            (at("09:40").date_naive() + Duration::days(1))
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+        let state = state(vec![
+            create_event("Holiday", all_day, end),
+            timed("Standup", "09:00", "09:15"),
+        ]);
+
+        let status = meeting_status(&state, at("09:40"));
+
+        assert!(!status.in_meeting);
+        assert!(status.current.is_empty());
+        assert!(status.next.is_none());
+        assert_eq!(status_lines(&status, at("09:40"), "%H:%M"), vec!["Free"]);
+    }
+
+    #[test]
+    fn test_status_lines() {
+        let state = state(vec![
+            timed("Standup", "09:30", "09:45"),
+            timed("1:1", "10:30", "11:00"),
+        ]);
+        let now = at("09:40");
+
+        let lines = status_lines(&meeting_status(&state, now), now, "%H:%M");
+
+        assert_eq!(
+            lines,
+            vec![
+                format!(
+                    "In a meeting: Standup (until {})",
+                    at("09:45").format("%H:%M")
+                ),
+                format!("Next: 1:1 at {} (in 50m)", at("10:30").format("%H:%M")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local};
+use std::collections::HashSet;
+
+use super::bar::{self, Bar};
+use super::list::ListedEvent;
+use super::load_state;
+use super::next::{load_upcoming, next_event, render};
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::calendar::models::Event;
+use crate::hook::Hooks;
+use crate::settings::Settings;
+use crate::tui::state::{AppState, DateRange};
+
+/// How often events are refetched from the server
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+/// How often fetched events are checked for a start within the lead time
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+/// How far ahead `watch --bar` looks for the next event, like `next`
+const BAR_LOOKAHEAD_DAYS: u32 = 7;
+
+/// Run until interrupted, printing `format` for each timed event `lead_minutes`
+/// before it starts. Fetch failures are reported and retried; events keep
+/// being announced from the last successful fetch. JSON output is streamed
+/// as one object per line for either JSON format.
+pub async fn run(
+    mut client: CalendarClient,
+    format: &str,
+    lead_minutes: u32,
+    settings: &Settings,
+    output: OutputFormat,
+    mut hooks: Hooks,
+) -> Result<()> {
+    let lead = Duration::minutes(i64::from(lead_minutes));
+    // Events already running at launch are not announced
+    let since = Local::now();
+    let mut announced = HashSet::new();
+    let mut state = AppState::new();
+    let mut last_fetch: Option<std::time::Instant> = None;
+
+    let mut check = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        check.tick().await;
+
+        if last_fetch.is_none_or(|fetched| fetched.elapsed() >= REFRESH_INTERVAL) {
+            let today = Local::now().date_naive();
+            let range = DateRange {
+                start: today,
+                end: today + Duration::days(2),
+            };
+            match load_state(&mut client, range.clone(), settings).await {
+                Ok(fetched) => {
+                    state = fetched;
+                    hooks.refreshed(&state, &range);
+                }
+                Err(e) => eprintln!("Failed to fetch events: {:#}", e),
+            }
+            last_fetch = Some(std::time::Instant::now());
+        }
+
+        let now = Local::now();
+        hooks.run_due(&state, now);
+        let due = take_due(&state, since, now, lead, &mut announced);
+        if output.is_json() {
+            let records: Vec<ListedEvent> = due
+                .iter()
+                .map(|event| ListedEvent::from_state(event, &state))
+                .collect();
+            output::print(&records, OutputFormat::Ndjson, &settings.formats)?;
+        } else {
+            for event in due {
+                println!("{}", render(format, event, &state, now));
+            }
+        }
+    }
+}
+
+/// Run until interrupted, printing the bar line for the next event every
+/// `interval` (when it changed), for bars that read a command's output
+/// continuously, e.g. waybar without `interval` or polybar with `tail = true`
+pub async fn run_bar(
+    mut client: CalendarClient,
+    bar: Bar,
+    format: &str,
+    interval: std::time::Duration,
+    settings: &Settings,
+    mut hooks: Hooks,
+) -> Result<()> {
+    let mut state = AppState::new();
+    let mut last_fetch: Option<std::time::Instant> = None;
+    let mut last_line = None;
+
+    let mut tick = tokio::time::interval(interval);
+    loop {
+        tick.tick().await;
+
+        let now = Local::now();
+        if last_fetch.is_none_or(|fetched| fetched.elapsed() >= REFRESH_INTERVAL) {
+            match load_upcoming(&mut client, now, BAR_LOOKAHEAD_DAYS, settings).await {
+                Ok(fetched) => {
+                    state = fetched;
+                    let range = DateRange {
+                        start: now.date_naive(),
+                        end: now.date_naive() + Duration::days(BAR_LOOKAHEAD_DAYS.into()),
+                    };
+                    hooks.refreshed(&state, &range);
+                }
+                Err(e) => eprintln!("Failed to fetch events: {:#}", e),
+            }
+            last_fetch = Some(std::time::Instant::now());
+        }
+
+        hooks.run_due(&state, now);
+        let line = bar::line(bar, format, next_event(&state, now, false), &state, now)?;
+        if last_line.as_ref() != Some(&line) {
+            println!("{}", line);
+            last_line = Some(line);
+        }
+    }
+}
+
+/// Timed events starting after `since` and within `lead` of `now` that weren't
+/// announced yet; they are recorded in `announced`. A rescheduled event is
+/// announced again for its new start.
+fn take_due<'a>(
+    state: &'a AppState,
+    since: DateTime<Local>,
+    now: DateTime<Local>,
+    lead: Duration,
+    announced: &mut HashSet<String>,
+) -> Vec<&'a Event> {
+    state
+        .upcoming_from(now, state.event_count())
+        .into_iter()
+        .filter(|event| !event.start.is_all_day())
+        .filter(|event| {
+            event
+                .start
+                .to_local()
+                .is_some_and(|start| start > since && start - lead <= now)
+        })
+        .filter(|event| announced.insert(format!("{}@{:?}", event.id, event.start.date_time)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn local_time(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn timed(id: &str, start: &str, end: &str) -> Event {
+        let at = |time: &str| EventDateTime {
+            date_time: Some(local_time(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            calendar_id: Some("me".to_string()),
+            ..Event::test(id, at(start), at(end))
+        }
+    }
+
+    fn state(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        for event in events {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    fn ids(events: &[&Event]) -> Vec<String> {
+        events.iter().map(|event| event.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_take_due_within_lead_time_once() {
+        let state = state(vec![
+            timed("standup", "09:30", "09:45"),
+            timed("lunch", "12:00", "13:00"),
+        ]);
+        let mut announced = HashSet::new();
+        let lead = Duration::minutes(5);
+
+        let due = take_due(
+            &state,
+            local_time("08:00"),
+            local_time("09:20"),
+            lead,
+            &mut announced,
+        );
+        assert!(due.is_empty());
+
+        let due = take_due(
+            &state,
+            local_time("08:00"),
+            local_time("09:26"),
+            lead,
+            &mut announced,
+        );
+        assert_eq!(ids(&due), vec!["standup"]);
+
+        let due = take_due(
+            &state,
+            local_time("08:00"),
+            local_time("09:27"),
+            lead,
+            &mut announced,
+        );
+        assert!(due.is_empty());
+    }
+
+    #[test]
+    fn test_take_due_skips_events_running_at_launch() {
+        let state = state(vec![timed("standup", "09:30", "09:45")]);
+        let mut announced = HashSet::new();
+
+        let due = take_due(
+            &state,
+            local_time("09:35"),
+            local_time("09:36"),
+            Duration::minutes(5),
+            &mut announced,
+        );
+
+        assert!(due.is_empty());
+    }
+
+    #[test]
+    fn test_take_due_announces_rescheduled_event_again() {
+        let mut announced = HashSet::new();
+        let lead = Duration::minutes(5);
+        let since = local_time("08:00");
+
+        let before = state(vec![timed("standup", "09:30", "09:45")]);
+        let due = take_due(&before, since, local_time("09:26"), lead, &mut announced);
+        assert_eq!(ids(&due), vec!["standup"]);
+
+        let after = state(vec![timed("standup", "09:40", "09:55")]);
+        let due = take_due(&after, since, local_time("09:36"), lead, &mut announced);
+        assert_eq!(ids(&due), vec!["standup"]);
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::io::IsTerminal;
+
+use super::list::{listed_events, ListedEvent};
+use super::load_state;
+use super::output::{self, OutputFormat};
+use crate::calendar::client::CalendarClient;
+use crate::formats::{Formats, Localized};
+use crate::settings::Settings;
+use crate::tui::state::DateRange;
+
+/// Grid width when stdout is not a terminal, e.g. when pasting into chat
+const DEFAULT_WIDTH: usize = 112;
+const MIN_COLUMN_WIDTH: usize = 8;
+const SEPARATOR: &str = " | ";
+
+/// Print the Sunday-to-Saturday week containing `date` as a 7-column grid
+/// (or its events in `format` when that isn't a table)
+pub async fn run(
+    mut client: CalendarClient,
+    date: NaiveDate,
+    width: Option<usize>,
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<()> {
+    let start = week_start(date);
+    let end = start + Duration::days(6);
+    let range = DateRange {
+        start,
+        end: end + Duration::days(1),
+    };
+    let state = load_state(&mut client, range, settings).await?;
+    let formats = &settings.formats;
+
+    if format != OutputFormat::Table {
+        return output::print(&listed_events(&state, start, end), format, formats);
+    }
+
+    let days: Vec<(NaiveDate, Vec<ListedEvent>)> = start
+        .iter_days()
+        .take(7)
+        .map(|day| (day, listed_events(&state, day, day)))
+        .collect();
+    let width = width.unwrap_or_else(|| {
+        std::io::stdout()
+            .is_terminal()
+            .then(|| crossterm::terminal::size().ok())
+            .flatten()
+            .map_or(DEFAULT_WIDTH, |(columns, _)| usize::from(columns))
+    });
+
+    for line in grid(&days, width, formats) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Weeks start on Sunday, like the TUI's month grid
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_sunday()))
+}
+
+/// Header, rule and one row per event slot; all-day events come first in a
+/// day and show no time. Cells are cut to fit `width`.
+fn grid(days: &[(NaiveDate, Vec<ListedEvent>)], width: usize, formats: &Formats) -> Vec<String> {
+    let separators = SEPARATOR.len() * days.len().saturating_sub(1);
+    let column = (width.saturating_sub(separators) / days.len().max(1)).max(MIN_COLUMN_WIDTH);
+
+    let cells: Vec<Vec<String>> = days
+        .iter()
+        .map(|(_, events)| {
+            let (all_day, timed): (Vec<&ListedEvent>, Vec<&ListedEvent>) =
+                events.iter().partition(|event| event.all_day);
+            all_day
+                .into_iter()
+                .map(|event| event.summary.clone())
+                .chain(timed.into_iter().map(|event| {
+                    let (_, time) = event.local_start(&formats.time);
+                    format!("{} {}", time, event.summary)
+                }))
+                .collect()
+        })
+        .collect();
+    let rows = cells.iter().map(Vec::len).max().unwrap_or(0);
+
+    let line = |values: Vec<String>| {
+        values
+            .iter()
+            .map(|value| format!("{:<column$}", fit(value, column), column = column))
+            .collect::<Vec<_>>()
+            .join(SEPARATOR)
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        line(
+            days.iter()
+                .map(|(day, _)| day.localized(&formats.date, formats.locale))
+                .collect(),
+        ),
+        vec!["-".repeat(column); days.len()].join("-+-"),
+    ];
+    for row in 0..rows {
+        lines.push(line(
+            cells
+                .iter()
+                .map(|day| day.get(row).cloned().unwrap_or_default())
+                .collect(),
+        ));
+    }
+
+    lines
+}
+
+/// Cut `value` to `width` characters, marking the cut with an ellipsis
+fn fit(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+    let mut cut: String = value.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(summary: &str, start: &str, all_day: bool) -> ListedEvent {
+        ListedEvent {
+            id: summary.to_lowercase(),
+            calendar_id: "me".to_string(),
+            calendar: "Me".to_string(),
+            summary: summary.to_string(),
+            start: start.to_string(),
+            end: start.to_string(),
+            all_day,
+            location: None,
+            video_call: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, day).unwrap()
+    }
+
+    #[test]
+    fn test_week_start_is_sunday() {
+        assert_eq!(week_start(date(18)), date(15));
+        assert_eq!(week_start(date(15)), date(15));
+        assert_eq!(week_start(date(21)), date(15));
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let mut days: Vec<(NaiveDate, Vec<ListedEvent>)> =
+            (15..=21).map(|day| (date(day), Vec::new())).collect();
+        days[1].1 = vec![
+            event("Holiday", "2025-06-16", true),
+            event("Standup", &local_rfc3339("2025-06-16T09:30:00"), false),
+        ];
+        days[3].1 = vec![event("Quarterly planning", "2025-06-18", true)];
+
+        let lines = grid(&days, 7 * 10 + 6 * 3, &Formats::default());
+
+        assert_eq!(
+            lines[0],
+            "Sun Jun 15 | Mon Jun 16 | Tue Jun 17 | Wed Jun 18 | Thu Jun 19 | Fri Jun 20 | Sat Jun 21"
+        );
+        assert_eq!(lines[1], vec!["-".repeat(10); 7].join("-+-"));
+        assert_eq!(
+            lines[2],
+            "           | Holiday    |            | Quarterly… |            |            |"
+        );
+        assert_eq!(
+            lines[3],
+            "           | 09:30 Sta… |            |            |            |            |"
+        );
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("Standup", 10), "Standup");
+        assert_eq!(fit("Quarterly planning", 10), "Quarterly…");
+        assert_eq!(fit("Quarterly planning", 10).chars().count(), 10);
+    }
+
+    fn local_rfc3339(naive: &str) -> String {
+        use chrono::{Local, NaiveDateTime, TimeZone};
+        let naive = NaiveDateTime::parse_from_str(naive, "%Y-%m-%dT%H:%M:%S").unwrap();
+        Local.from_local_datetime(&naive).unwrap().to_rfc3339()
+    }
+}
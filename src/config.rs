@@ -0,0 +1,431 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::tui::state::StartView;
+
+/// Settings from `config.toml` in the oxidate config directory (or the file
+/// named by `OXIDATE_CONFIG`). Every key is optional; a command-line flag or
+/// its `OXIDATE_*` environment variable wins over the file. A
+/// `[profile.<name>]` table holds the same keys and, selected with
+/// `--profile <name>`, takes precedence over the rest of the file.
+///
+/// ```toml
+/// account = "personal"
+/// read_only = false
+/// timezone = "Europe/Berlin"
+///
+/// [display]
+/// view = "day"
+/// locale = "de_DE"
+/// time_format = "%I:%M %p"
+/// date_format = "%d.%m."
+/// day_title_format = "%A, %-d %B"
+/// month_title_format = "%m/%Y"
+/// calendars = ["Work"]
+/// notify = [10, 1]
+/// other_timezones = ["Asia/Tokyo"]
+///
+/// [behavior]
+/// default_calendar = "Work"
+/// event_duration = 25
+/// working_hours = "08:30-16:30"
+/// working_days = "mon-thu"
+/// snap = 5
+/// hook = "notify-send \"$OXIDATE_EVENT_TITLE\""
+/// hook_lead = 5
+/// sync_hook = "~/bin/on-sync"
+/// vdir = "~/.local/share/oxidate/vdir"
+/// index = true
+/// memory_horizon = 6
+/// refresh_interval = 10
+///
+/// [colors]
+/// Work = "#ff8800"
+/// "team@group.calendar.google.com" = "214"
+///
+/// [providers]
+/// khal = true
+/// text_calendars = ["~/notes/calendar.txt"]
+/// tasks = true
+///
+/// [profile.work]
+/// account = "work"
+/// display.calendars = ["Work", "Team"]
+/// colors.Team = "214"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Stored account to use (`--account`)
+    pub account: Option<String>,
+    /// Never change calendars and only ask for read access when logging in (`--read-only`)
+    pub read_only: bool,
+    /// IANA time zone (e.g. "Europe/Berlin") used instead of the system's (`--timezone`)
+    pub timezone: Option<String>,
+    pub display: Display,
+    pub behavior: Behavior,
+    pub providers: Providers,
+    /// `[colors]`: calendar id or name to `#RRGGBB`, a terminal color name or
+    /// a 256-color index, instead of Google's color
+    pub colors: HashMap<String, String>,
+}
+
+/// `[display]`: what the TUI shows
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Display {
+    /// What the TUI opens into, "month", "week", "day", "agenda" or
+    /// "timeline" (`--view`, default month)
+    pub view: Option<StartView>,
+    /// Language of month and weekday names, e.g. "fr_FR" (`--locale`,
+    /// default from LC_TIME or LANG)
+    pub locale: Option<String>,
+    /// strftime pattern for times of day (`--time-format`, default `%H:%M`)
+    pub time_format: Option<String>,
+    /// strftime pattern for short dates in CLI output (`--date-format`,
+    /// default `%a %b %-d`)
+    pub date_format: Option<String>,
+    /// strftime pattern for the day above the TUI's event list (default `%B %d, %Y`)
+    pub day_title_format: Option<String>,
+    /// strftime pattern for the month above the TUI's calendar (default `%B %Y`)
+    pub month_title_format: Option<String>,
+    /// Only show and fetch these calendars, by id or name (`--calendar`)
+    pub calendars: Vec<String>,
+    /// Desktop notification lead times in minutes (`--notify`)
+    pub notify: Vec<u32>,
+    /// Extra IANA zones shown beside local times (`--other-timezones`)
+    pub other_timezones: Vec<String>,
+}
+
+/// `[behavior]`: what runs alongside the TUI and `watch`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Behavior {
+    /// Calendar id or name new events go to instead of the primary calendar
+    /// (`--default-calendar`)
+    pub default_calendar: Option<String>,
+    /// Minutes a new timed event lasts when only its start is given
+    /// (`--event-duration`, default 60)
+    pub event_duration: Option<u32>,
+    /// Minutes proposed start times are rounded to (`--snap`, default 15)
+    pub snap: Option<u32>,
+    /// e.g. "9-17" or "08:30-16:30" (`--working-hours`, default 9-17)
+    pub working_hours: Option<String>,
+    /// e.g. "mon-fri" or "sun-thu" (`--working-days`, default mon-fri)
+    pub working_days: Option<String>,
+    /// Shell command run before each timed event (`--hook`)
+    pub hook: Option<String>,
+    /// Minutes before the start to run `hook` (`--hook-lead`, default 5)
+    pub hook_lead: Option<u32>,
+    /// Shell command run after each refresh (`--sync-hook`)
+    pub sync_hook: Option<String>,
+    /// Directory to mirror events into as a vdir (`--vdir`)
+    pub vdir: Option<PathBuf>,
+    /// Keep a local search index of fetched events (`--index`)
+    pub index: bool,
+    /// Months of events kept in memory either side of the shown one
+    /// (`--memory-horizon`, default 12, at least 2)
+    pub memory_horizon: Option<u32>,
+    /// Minutes between automatic refreshes of the TUI, 0 for none
+    /// (`--refresh-interval`, default 5)
+    pub refresh_interval: Option<u32>,
+}
+
+/// `[providers]`: read-only local calendars shown beside Google's
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Providers {
+    /// Calendars of khal's configuration (`--khal`)
+    pub khal: bool,
+    /// Plain-text calendar files (`--text-calendar`)
+    pub text_calendars: Vec<PathBuf>,
+    /// Taskwarrior's pending tasks by due date (`--tasks`)
+    pub tasks: bool,
+}
+
+impl Config {
+    /// The settings of the file, with those of `profile` on top
+    pub fn load(profile: Option<&str>) -> Result<Self> {
+        Self::load_from(&Self::get_storage_path()?, profile)
+    }
+
+    fn load_from(path: &Path, profile: Option<&str>) -> Result<Self> {
+        if !path.exists() {
+            if let Some(profile) = profile {
+                bail!(
+                    "No profile \"{}\": {} does not exist",
+                    profile,
+                    path.display()
+                );
+            }
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path).context("Failed to read config file")?;
+        let mut config = Self::parse(&contents, profile)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        let home = dirs::home_dir().unwrap_or_default();
+        config.expand_home(&home);
+        Ok(config)
+    }
+
+    fn parse(contents: &str, profile: Option<&str>) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(contents)?;
+        let mut profiles = match table.remove("profile") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => bail!("`profile` must be a table of [profile.<name>] sections"),
+            None => toml::Table::new(),
+        };
+
+        if let Some(name) = profile {
+            match profiles.remove(name) {
+                Some(toml::Value::Table(overrides)) => merge(&mut table, overrides),
+                Some(_) => bail!("[profile.{}] must be a table", name),
+                None => {
+                    let mut known: Vec<&String> = profiles.keys().collect();
+                    known.sort();
+                    bail!(
+                        "No profile \"{}\" (known: {})",
+                        name,
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known
+                                .iter()
+                                .map(|name| name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        }
+                    );
+                }
+            }
+        }
+
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
+    /// Paths in the file may start with `~/`, which no shell expands for them
+    fn expand_home(&mut self, home: &Path) {
+        let expand = |path: &mut PathBuf| {
+            if let Ok(rest) = path.strip_prefix("~") {
+                *path = home.join(rest);
+            }
+        };
+        if let Some(ref mut vdir) = self.behavior.vdir {
+            expand(vdir);
+        }
+        self.providers.text_calendars.iter_mut().for_each(expand);
+    }
+
+    pub fn get_storage_path() -> Result<PathBuf> {
+        if let Some(path) = std::env::var_os("OXIDATE_CONFIG") {
+            return Ok(PathBuf::from(path));
+        }
+        let config_dir = dirs::config_dir().context("Failed to get config directory")?;
+
+        Ok(config_dir.join("oxidate").join("config.toml"))
+    }
+}
+
+/// Lay `overrides` over `base`: tables merge key by key, anything else is replaced
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge(base, overrides)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Show and bucket all times in the IANA zone `name` instead of the system zone.
+/// Everything uses `chrono::Local`, which reads `TZ`, so this must run before
+/// any local time is computed, and before any other thread exists since
+/// changing the environment races with threads reading it.
+/// Not supported on Windows, where `TZ` is ignored.
+pub fn override_timezone(name: &str) -> Result<()> {
+    validate_timezone(name)?;
+    std::env::set_var("TZ", name);
+    Ok(())
+}
+
+/// An unknown `TZ` silently falls back to UTC, so check the name first
+fn validate_timezone(name: &str) -> Result<()> {
+    name.parse::<chrono_tz::Tz>().map(|_| ()).map_err(|_| {
+        anyhow!(
+            "Unknown time zone \"{}\", expected e.g. Europe/Berlin",
+            name
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_missing_file_returns_defaults() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let config = Config::load_from(&temp_dir.path().join("config.toml"), None).unwrap();
+
+        assert_eq!(config, Config::default());
+        assert!(!config.read_only);
+    }
+
+    #[test]
+    fn test_load_read_only() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "read_only = true\n").unwrap();
+
+        let config = Config::load_from(&path, None).unwrap();
+
+        assert!(config.read_only);
+        assert_eq!(config.timezone, None);
+    }
+
+    #[test]
+    fn test_load_timezone() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "timezone = \"America/New_York\"\n").unwrap();
+
+        let config = Config::load_from(&path, None).unwrap();
+
+        assert_eq!(config.timezone.as_deref(), Some("America/New_York"));
+    }
+
+    #[test]
+    fn test_validate_timezone() {
+        assert!(validate_timezone("Europe/Berlin").is_ok());
+        assert!(validate_timezone("UTC").is_ok());
+        assert!(validate_timezone("Europe/Atlantis").is_err());
+        assert!(validate_timezone("").is_err());
+    }
+
+    #[test]
+    fn test_load_sections() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            r##"
+[display]
+view = "day"
+notify = [10, 1]
+other_timezones = ["Asia/Tokyo"]
+
+[behavior]
+hook_lead = 2
+
+[providers]
+text_calendars = ["~/cal.txt", "/srv/team.txt"]
+tasks = true
+
+[colors]
+Work = "#ff8800"
+"##,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path, None).unwrap();
+
+        assert_eq!(config.display.view, Some(StartView::Day));
+        assert_eq!(config.display.notify, vec![10, 1]);
+        assert_eq!(config.display.other_timezones, vec!["Asia/Tokyo"]);
+        assert!(config.display.calendars.is_empty());
+        assert_eq!(config.behavior.hook_lead, Some(2));
+        assert!(!config.behavior.index);
+        assert!(config.providers.tasks);
+        assert_eq!(config.colors["Work"], "#ff8800");
+        assert!(!config.providers.khal);
+        assert_eq!(
+            config.providers.text_calendars[1],
+            PathBuf::from("/srv/team.txt")
+        );
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let mut config = Config::default();
+        config.behavior.vdir = Some(PathBuf::from("~/vdir"));
+        config.providers.text_calendars =
+            vec![PathBuf::from("~/cal.txt"), PathBuf::from("cal.txt")];
+
+        config.expand_home(Path::new("/home/me"));
+
+        assert_eq!(config.behavior.vdir, Some(PathBuf::from("/home/me/vdir")));
+        assert_eq!(
+            config.providers.text_calendars,
+            vec![PathBuf::from("/home/me/cal.txt"), PathBuf::from("cal.txt")]
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        let contents = r#"
+account = "personal"
+read_only = true
+
+[display]
+calendars = ["Me"]
+notify = [10]
+
+[profile.work]
+account = "work"
+display.calendars = ["Work", "Team"]
+
+[profile.work.colors]
+Team = "214"
+"#;
+
+        let base = Config::parse(contents, None).unwrap();
+        assert_eq!(base.account.as_deref(), Some("personal"));
+        assert_eq!(base.display.calendars, vec!["Me"]);
+        assert!(base.colors.is_empty());
+
+        let work = Config::parse(contents, Some("work")).unwrap();
+        assert_eq!(work.account.as_deref(), Some("work"));
+        assert_eq!(work.display.calendars, vec!["Work", "Team"]);
+        // Keys the profile leaves out keep the file's values
+        assert_eq!(work.display.notify, vec![10]);
+        assert!(work.read_only);
+        assert_eq!(work.colors["Team"], "214");
+
+        let error = Config::parse(contents, Some("home")).unwrap_err();
+        assert_eq!(error.to_string(), "No profile \"home\" (known: work)");
+    }
+
+    #[test]
+    fn test_load_missing_profile_fails() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let path = temp_dir.path().join("config.toml");
+        assert!(Config::load_from(&path, Some("work")).is_err());
+    }
+
+    #[test]
+    fn test_load_invalid_file_fails() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "read_only = \"sometimes\"\n").unwrap();
+
+        assert!(Config::load_from(&path, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        let error = Config::parse("[display]\ntime_fromat = \"%I:%M %p\"\n", None).unwrap_err();
+        assert!(error.to_string().contains("unknown field `time_fromat`"));
+
+        assert!(Config::parse("readonly = true\n", None).is_err());
+        assert!(Config::parse("[profile.work]\nreadonly = true\n", Some("work")).is_err());
+    }
+}
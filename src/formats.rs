@@ -0,0 +1,149 @@
+use anyhow::{bail, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Weekday};
+use std::fmt::Display;
+
+use crate::locale::{self, Locale};
+
+/// strftime-style patterns for the dates and times shown to people, e.g.
+/// `%I:%M %p` for 12-hour times. Machine-readable output (JSON, ids, .ics)
+/// keeps its fixed formats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formats {
+    /// Times of day in the TUI and CLI output (`--time-format`)
+    pub time: String,
+    /// Short dates in CLI output, e.g. `next`'s {date} (`--date-format`)
+    pub date: String,
+    /// The selected day above the TUI's event list
+    pub day_title: String,
+    /// The month above the TUI's calendar
+    pub month_title: String,
+    /// Month and weekday names (`--locale`)
+    pub locale: &'static Locale,
+}
+
+impl Default for Formats {
+    fn default() -> Self {
+        Self {
+            time: "%H:%M".to_string(),
+            date: "%a %b %-d".to_string(),
+            day_title: "%B %d, %Y".to_string(),
+            month_title: "%B %Y".to_string(),
+            locale: locale::english(),
+        }
+    }
+}
+
+impl Formats {
+    /// Check each pattern before use, since chrono panics when displaying an
+    /// invalid one
+    pub fn validate(&self) -> Result<()> {
+        for pattern in [&self.time, &self.date, &self.day_title, &self.month_title] {
+            validate(pattern)?;
+        }
+        Ok(())
+    }
+
+    /// A short date followed by a time of day
+    pub fn date_time(&self) -> String {
+        format!("{} {}", self.date, self.time)
+    }
+}
+
+/// Formatting with the month and weekday names of `locale`
+pub trait Localized {
+    fn localized(&self, pattern: &str, locale: &Locale) -> String;
+}
+
+impl Localized for NaiveDate {
+    fn localized(&self, pattern: &str, locale: &Locale) -> String {
+        let pattern = with_names(pattern, locale, self.weekday(), self.month());
+        self.format(&pattern).to_string()
+    }
+}
+
+impl<Tz: TimeZone> Localized for DateTime<Tz>
+where
+    Tz::Offset: Display,
+{
+    fn localized(&self, pattern: &str, locale: &Locale) -> String {
+        let pattern = with_names(pattern, locale, self.weekday(), self.month());
+        self.format(&pattern).to_string()
+    }
+}
+
+/// `pattern` with `%B`, `%b`/`%h`, `%A` and `%a` replaced by the names of
+/// `weekday` and `month` in `locale`, since chrono only knows English ones
+fn with_names(pattern: &str, locale: &Locale, weekday: Weekday, month: u32) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('B') => result.push_str(locale.month(month)),
+            Some('b') | Some('h') => result.push_str(locale.month_abbr(month)),
+            Some('A') => result.push_str(locale.weekday(weekday)),
+            Some('a') => result.push_str(locale.weekday_abbr(weekday)),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+    result
+}
+
+fn validate(pattern: &str) -> Result<()> {
+    if pattern.is_empty() {
+        bail!("Empty date/time format");
+    }
+    if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        bail!("Invalid date/time format \"{}\"", pattern);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("%I:%M %p").is_ok());
+        assert!(validate("%d.%m.%Y").is_ok());
+        assert!(validate("%Q").is_err());
+        assert!(validate("%").is_err());
+        assert!(validate("").is_err());
+    }
+
+    #[test]
+    fn test_with_names() {
+        let german = locale::find("de").unwrap();
+        assert_eq!(
+            with_names("%A, %-d. %B %Y", german, Weekday::Sun, 6),
+            "Sonntag, %-d. Juni %Y"
+        );
+        assert_eq!(
+            with_names("%a %h 100%%", german, Weekday::Mon, 3),
+            "Mo Mär 100%%"
+        );
+    }
+
+    #[test]
+    fn test_localized_defaults_match_chrono() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        assert_eq!(
+            date.localized("%a %b %-d, %A %B", locale::english()),
+            "Sun Jun 15, Sunday June"
+        );
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(Formats::default().validate().is_ok());
+    }
+}
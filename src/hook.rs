@@ -0,0 +1,481 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Stdio};
+
+use crate::calendar::conference;
+use crate::calendar::models::Event;
+use crate::commands::list::ListedEvent;
+use crate::tui::state::{AppState, DateRange};
+use crate::vdir::Vdir;
+
+/// What runs alongside the TUI or `watch`: `--hook` before each event, and
+/// `--sync-hook` and the `--vdir` mirror after each refresh
+#[derive(Debug, Default)]
+pub struct Hooks {
+    pub event: Option<Hook>,
+    pub sync: Option<SyncHook>,
+    pub vdir: Option<Vdir>,
+    /// Report failures to the log instead of stderr, for the TUI
+    pub quiet: bool,
+}
+
+impl Hooks {
+    pub fn run_due(&mut self, state: &AppState, now: DateTime<Local>) {
+        if let Some(hook) = self.event.as_mut() {
+            hook.run_due(state, now);
+        }
+    }
+
+    /// After a successful refresh of `range` that produced `state`
+    pub fn refreshed(&mut self, state: &AppState, range: &DateRange) {
+        if let Some(sync) = self.sync.as_mut() {
+            sync.run(state, range);
+        }
+        if let Some(ref vdir) = self.vdir {
+            if let Err(e) = vdir.mirror(state, range) {
+                report(self.quiet, &format!("Could not mirror events: {:#}", e));
+            }
+        }
+    }
+}
+
+/// A user command run shortly before each timed event (`--hook`), e.g. to
+/// turn on do-not-disturb or open a notes template
+#[derive(Debug)]
+pub struct Hook {
+    command: String,
+    lead: Duration,
+    /// Events that had started when oxidate launched are skipped
+    since: DateTime<Local>,
+    ran: HashSet<String>,
+    /// Capture the command's output instead of sharing the terminal, for the TUI
+    quiet: bool,
+}
+
+impl Hook {
+    pub fn new(command: String, lead_minutes: u32, since: DateTime<Local>, quiet: bool) -> Self {
+        Self {
+            command,
+            lead: Duration::minutes(i64::from(lead_minutes)),
+            since,
+            ran: HashSet::new(),
+            quiet,
+        }
+    }
+
+    /// Start the command for every timed event now within the lead time; each
+    /// start runs it once, so a rescheduled event runs it again
+    pub fn run_due(&mut self, state: &AppState, now: DateTime<Local>) {
+        for event in self.take_due(state, now) {
+            if let Err(e) = self.spawn(&event, state) {
+                self.report(&format!("Could not run hook: {:#}", e));
+            }
+        }
+    }
+
+    fn take_due(&mut self, state: &AppState, now: DateTime<Local>) -> Vec<Event> {
+        state
+            .upcoming_from(now, state.event_count())
+            .into_iter()
+            .filter(|event| !event.start.is_all_day())
+            .filter(|event| {
+                event
+                    .start
+                    .to_local()
+                    .is_some_and(|start| start > self.since && start - self.lead <= now)
+            })
+            .filter(|event| {
+                self.ran
+                    .insert(format!("{}@{:?}", event.id, event.start.date_time))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn spawn(&self, event: &Event, state: &AppState) -> Result<()> {
+        let title = event.summary.clone().unwrap_or_default();
+        spawn(
+            &self.command,
+            env(event, state)?,
+            self.quiet,
+            format!("Hook for \"{}\"", title),
+        )
+    }
+
+    fn report(&self, message: &str) {
+        report(self.quiet, message);
+    }
+}
+
+/// Fetched events by calendar and event id
+type Snapshot = HashMap<(String, String), Event>;
+
+/// A user command run after each successful refresh (`--sync-hook`) with what
+/// changed since the previous one, e.g. to react to new invitations
+#[derive(Debug)]
+pub struct SyncHook {
+    command: String,
+    quiet: bool,
+    /// The events of the previous refresh and the days they were fetched
+    /// for; None before the first refresh
+    previous: Option<(DateRange, Snapshot)>,
+}
+
+/// What a refresh changed, as passed to the sync hook in `OXIDATE_SYNC_JSON`
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct SyncChanges {
+    pub added: Vec<ListedEvent>,
+    pub changed: Vec<ListedEvent>,
+    pub removed: Vec<ListedEvent>,
+    /// New events the user has not responded to yet, also listed in `added`
+    pub invitations: Vec<ListedEvent>,
+}
+
+impl SyncHook {
+    pub fn new(command: String, quiet: bool) -> Self {
+        Self {
+            command,
+            quiet,
+            previous: None,
+        }
+    }
+
+    /// Run the command for a refresh of `range` that produced `state`. Only
+    /// days fetched both times are compared; the first refresh reports nothing.
+    pub fn run(&mut self, state: &AppState, range: &DateRange) {
+        let events = snapshot(state, range);
+        let changes = match self.previous.take() {
+            Some((previous_range, previous)) => {
+                let overlap = DateRange {
+                    start: previous_range.start.max(range.start),
+                    end: previous_range.end.min(range.end),
+                };
+                changes(&previous, &events, &overlap, state)
+            }
+            None => SyncChanges::default(),
+        };
+        self.previous = Some((range.clone(), events));
+
+        let result = sync_env(&changes)
+            .and_then(|env| spawn(&self.command, env, self.quiet, "Sync hook".to_string()));
+        if let Err(e) = result {
+            report(self.quiet, &format!("Could not run sync hook: {:#}", e));
+        }
+    }
+}
+
+fn snapshot(state: &AppState, range: &DateRange) -> Snapshot {
+    state
+        .events
+        .iter()
+        .filter(|(date, _)| in_range(**date, range))
+        .flat_map(|(_, events)| events)
+        .map(|event| {
+            let calendar_id = event.calendar_id.clone().unwrap_or_default();
+            ((calendar_id, event.id.clone()), event.clone())
+        })
+        .collect()
+}
+
+fn in_range(date: NaiveDate, range: &DateRange) -> bool {
+    date >= range.start && date <= range.end
+}
+
+/// Events starting within `overlap` that appeared, changed or disappeared
+fn changes(
+    previous: &Snapshot,
+    current: &Snapshot,
+    overlap: &DateRange,
+    state: &AppState,
+) -> SyncChanges {
+    let in_overlap = |event: &Event| start_date(event).is_some_and(|date| in_range(date, overlap));
+    let mut changes = SyncChanges::default();
+
+    for (key, event) in current.iter().filter(|(_, event)| in_overlap(event)) {
+        match previous.get(key) {
+            None => {
+                let listed = ListedEvent::from_state(event, state);
+                if is_invitation(event) {
+                    changes.invitations.push(listed.clone());
+                }
+                changes.added.push(listed);
+            }
+            Some(old) if is_modified(old, event) => {
+                changes.changed.push(ListedEvent::from_state(event, state))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, event) in previous.iter().filter(|(_, event)| in_overlap(event)) {
+        if !current.contains_key(key) {
+            changes.removed.push(ListedEvent::from_state(event, state));
+        }
+    }
+
+    for events in [
+        &mut changes.added,
+        &mut changes.changed,
+        &mut changes.removed,
+        &mut changes.invitations,
+    ] {
+        events.sort_by(|a, b| (&a.start, &a.id).cmp(&(&b.start, &b.id)));
+    }
+    changes
+}
+
+fn start_date(event: &Event) -> Option<NaiveDate> {
+    match event.start.to_local() {
+        Some(start) => Some(start.date_naive()),
+        None => event.start.date.as_deref()?.parse().ok(),
+    }
+}
+
+/// Prefers the etag, like offline edits do, and falls back to the content
+fn is_modified(old: &Event, new: &Event) -> bool {
+    match (&old.etag, &new.etag) {
+        (Some(old), Some(new)) => old != new,
+        _ => old != new,
+    }
+}
+
+fn is_invitation(event: &Event) -> bool {
+    event.attendees.iter().flatten().any(|attendee| {
+        attendee.is_self == Some(true) && attendee.response_status.as_deref() == Some("needsAction")
+    })
+}
+
+/// Counts in `OXIDATE_SYNC_ADDED`, `_CHANGED`, `_REMOVED` and
+/// `_INVITATIONS`, and the events themselves in `OXIDATE_SYNC_JSON`
+fn sync_env(changes: &SyncChanges) -> Result<Vec<(&'static str, String)>> {
+    Ok(vec![
+        ("OXIDATE_SYNC_ADDED", changes.added.len().to_string()),
+        ("OXIDATE_SYNC_CHANGED", changes.changed.len().to_string()),
+        ("OXIDATE_SYNC_REMOVED", changes.removed.len().to_string()),
+        (
+            "OXIDATE_SYNC_INVITATIONS",
+            changes.invitations.len().to_string(),
+        ),
+        (
+            "OXIDATE_SYNC_JSON",
+            serde_json::to_string(changes).context("Failed to serialize changes")?,
+        ),
+    ])
+}
+
+/// Run `command` through the shell with `env` without waiting for it; a
+/// thread reaps it and reports a failure as `what`
+fn spawn(command: &str, env: Vec<(&'static str, String)>, quiet: bool, what: String) -> Result<()> {
+    let mut shell = shell(command);
+    shell.envs(env).stdin(Stdio::null());
+    if quiet {
+        shell.stdout(Stdio::null()).stderr(Stdio::piped());
+    }
+    let child = shell
+        .spawn()
+        .with_context(|| format!("Failed to start \"{}\"", command))?;
+
+    std::thread::spawn(move || match child.wait_with_output() {
+        Ok(output) if !output.status.success() => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = format!("{} exited with {}: {}", what, output.status, stderr.trim());
+            report(quiet, message.trim_end_matches(": "));
+        }
+        Ok(_) => {}
+        Err(e) => report(quiet, &format!("{} failed: {}", what, e)),
+    });
+    Ok(())
+}
+
+/// The TUI owns the terminal, so quiet hooks report to its log pane
+fn report(quiet: bool, message: &str) {
+    if quiet {
+        tracing::warn!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// `OXIDATE_EVENT_*` variables for the common fields, and the whole event as
+/// the API returned it in `OXIDATE_EVENT_JSON`
+fn env(event: &Event, state: &AppState) -> Result<Vec<(&'static str, String)>> {
+    let time = |at: Option<DateTime<Local>>| at.map(|at| at.to_rfc3339()).unwrap_or_default();
+    let calendar = event
+        .calendar_id
+        .as_deref()
+        .and_then(|id| state.get_calendar_by_id(id))
+        .map(|calendar| calendar.summary.clone())
+        .unwrap_or_default();
+
+    Ok(vec![
+        ("OXIDATE_EVENT_ID", event.id.clone()),
+        (
+            "OXIDATE_EVENT_TITLE",
+            event.summary.clone().unwrap_or_default(),
+        ),
+        ("OXIDATE_EVENT_START", time(event.start.to_local())),
+        ("OXIDATE_EVENT_END", time(event.end.to_local())),
+        (
+            "OXIDATE_EVENT_LOCATION",
+            event.location.clone().unwrap_or_default(),
+        ),
+        ("OXIDATE_EVENT_CALENDAR", calendar),
+        (
+            "OXIDATE_EVENT_LINK",
+            event.html_link.clone().unwrap_or_default(),
+        ),
+        (
+            "OXIDATE_EVENT_VIDEO_CALL",
+            conference::video_call(event)
+                .map(|call| call.url)
+                .unwrap_or_default(),
+        ),
+        (
+            "OXIDATE_EVENT_JSON",
+            serde_json::to_string(event).context("Failed to serialize event")?,
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+
+    fn local_time(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn timed(id: &str, start: &str) -> Event {
+        let at = |time: &str| EventDateTime {
+            date_time: Some(local_time(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            hangout_link: Some("https://meet.google.com/abc".to_string()),
+            ..Event::test(id, at(start), at(start))
+        }
+    }
+
+    fn state_with(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        for event in events {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    #[test]
+    fn test_take_due_runs_once_per_start() {
+        let state = state_with(vec![
+            timed("running", "08:00"),
+            timed("standup", "09:00"),
+            timed("review", "11:00"),
+        ]);
+        let mut hook = Hook::new("true".to_string(), 5, local_time("08:30"), true);
+
+        let due = |hook: &mut Hook, time| -> Vec<String> {
+            hook.take_due(&state, local_time(time))
+                .into_iter()
+                .map(|event| event.id)
+                .collect()
+        };
+        assert!(due(&mut hook, "08:50").is_empty());
+        assert_eq!(due(&mut hook, "08:55"), vec!["standup"]);
+        assert!(due(&mut hook, "08:57").is_empty());
+    }
+
+    #[test]
+    fn test_env_carries_event_fields() {
+        let event = timed("standup", "09:00");
+
+        let env = env(&event, &AppState::new()).unwrap();
+        let value = |name| {
+            env.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap()
+        };
+
+        assert_eq!(value("OXIDATE_EVENT_TITLE"), "standup");
+        assert_eq!(
+            value("OXIDATE_EVENT_VIDEO_CALL"),
+            "https://meet.google.com/abc"
+        );
+        assert_eq!(value("OXIDATE_EVENT_LOCATION"), "");
+        let json: serde_json::Value = serde_json::from_str(value("OXIDATE_EVENT_JSON")).unwrap();
+        assert_eq!(json["id"], "standup");
+    }
+
+    #[test]
+    fn test_changes_between_refreshes() {
+        use crate::calendar::models::Attendee;
+
+        let mut invite = timed("invite", "14:00");
+        invite.attendees = Some(vec![Attendee {
+            email: "me@example.com".to_string(),
+            display_name: None,
+            response_status: Some("needsAction".to_string()),
+            optional: None,
+            is_self: Some(true),
+            comment: None,
+        }]);
+        let mut moved = timed("review", "11:00");
+        moved.etag = Some("2".to_string());
+        let mut review = timed("review", "10:00");
+        review.etag = Some("1".to_string());
+
+        let range = |first: u32, last: u32| DateRange {
+            start: NaiveDate::from_ymd_opt(2025, 6, first).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 6, last).unwrap(),
+        };
+        let before = state_with(vec![
+            timed("standup", "09:00"),
+            review,
+            timed("gone", "12:00"),
+        ]);
+        let after = state_with(vec![timed("standup", "09:00"), moved, invite]);
+
+        let refreshed = changes(
+            &snapshot(&before, &range(16, 16)),
+            &snapshot(&after, &range(16, 16)),
+            &range(16, 16),
+            &after,
+        );
+        let ids = |events: &[ListedEvent]| -> Vec<String> {
+            events.iter().map(|event| event.id.clone()).collect()
+        };
+        assert_eq!(ids(&refreshed.added), vec!["invite"]);
+        assert_eq!(ids(&refreshed.invitations), vec!["invite"]);
+        assert_eq!(ids(&refreshed.changed), vec!["review"]);
+        assert_eq!(ids(&refreshed.removed), vec!["gone"]);
+
+        // Days fetched only once are not compared
+        let outside = changes(
+            &snapshot(&before, &range(16, 17)),
+            &snapshot(&after, &range(16, 17)),
+            &range(17, 17),
+            &after,
+        );
+        assert_eq!(outside, SyncChanges::default());
+    }
+}
@@ -0,0 +1,342 @@
+use anyhow::{bail, Result};
+use chrono::Weekday;
+
+/// Month and weekday names of one language, as `%B`, `%b`, `%A` and `%a`
+/// print them
+#[derive(Debug, PartialEq)]
+pub struct Locale {
+    pub code: &'static str,
+    months: [&'static str; 12],
+    months_abbr: [&'static str; 12],
+    /// Monday first
+    weekdays: [&'static str; 7],
+    weekdays_abbr: [&'static str; 7],
+}
+
+impl Locale {
+    /// `month` counts from 1
+    pub fn month(&self, month: u32) -> &'static str {
+        self.months[month as usize - 1]
+    }
+
+    pub fn month_abbr(&self, month: u32) -> &'static str {
+        self.months_abbr[month as usize - 1]
+    }
+
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        self.weekdays[weekday.num_days_from_monday() as usize]
+    }
+
+    pub fn weekday_abbr(&self, weekday: Weekday) -> &'static str {
+        self.weekdays_abbr[weekday.num_days_from_monday() as usize]
+    }
+}
+
+const ENGLISH: Locale = Locale {
+    code: "en",
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    months_abbr: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+    weekdays: [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+    weekdays_abbr: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+};
+
+static LOCALES: [Locale; 8] = [
+    ENGLISH,
+    Locale {
+        code: "de",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        weekdays_abbr: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    },
+    Locale {
+        code: "fr",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_abbr: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        weekdays_abbr: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    },
+    Locale {
+        code: "es",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_abbr: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        weekdays_abbr: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    },
+    Locale {
+        code: "it",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_abbr: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        weekdays_abbr: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+    },
+    Locale {
+        code: "nl",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_abbr: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+            "zondag",
+        ],
+        weekdays_abbr: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+    },
+    Locale {
+        code: "pt",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_abbr: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        weekdays_abbr: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+    },
+    Locale {
+        code: "sv",
+        months: [
+            "januari",
+            "februari",
+            "mars",
+            "april",
+            "maj",
+            "juni",
+            "juli",
+            "augusti",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_abbr: [
+            "jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag",
+        ],
+        weekdays_abbr: ["mån", "tis", "ons", "tor", "fre", "lör", "sön"],
+    },
+];
+
+/// What times read like unless told otherwise, as chrono prints them
+pub fn english() -> &'static Locale {
+    &LOCALES[0]
+}
+
+/// A POSIX-style name such as `de`, `de_AT` or `fr_FR.UTF-8`; `C` and
+/// `POSIX` are English
+pub fn find(name: &str) -> Option<&'static Locale> {
+    let language = name
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let language = match language.as_str() {
+        "c" | "posix" => "en",
+        language => language,
+    };
+    LOCALES.iter().find(|locale| locale.code == language)
+}
+
+/// The configured locale (`--locale`), or else the one `LC_ALL`, `LC_TIME`
+/// or `LANG` names, falling back to English for languages without names here
+pub fn resolve(configured: Option<&str>) -> Result<&'static Locale> {
+    if let Some(name) = configured {
+        return match find(name) {
+            Some(locale) => Ok(locale),
+            None => bail!(
+                "Unsupported locale \"{}\", expected one of {}",
+                name,
+                LOCALES
+                    .iter()
+                    .map(|locale| locale.code)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+    }
+
+    // The first variable that is set wins, as for the C library
+    let name = ["LC_ALL", "LC_TIME", "LANG"]
+        .into_iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|value| !value.is_empty());
+    Ok(name.as_deref().and_then(find).unwrap_or_else(english))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_locale() {
+        assert_eq!(find("de_DE.UTF-8").unwrap().code, "de");
+        assert_eq!(find("fr").unwrap().code, "fr");
+        assert_eq!(find("pt-BR").unwrap().code, "pt");
+        assert_eq!(find("C.UTF-8").unwrap().code, "en");
+        assert!(find("tlh").is_none());
+    }
+
+    #[test]
+    fn test_names() {
+        let german = find("de").unwrap();
+        assert_eq!(german.month(3), "März");
+        assert_eq!(german.month_abbr(12), "Dez");
+        assert_eq!(german.weekday(Weekday::Sun), "Sonntag");
+        assert_eq!(german.weekday_abbr(Weekday::Mon), "Mo");
+    }
+
+    #[test]
+    fn test_resolve_configured_locale() {
+        assert_eq!(resolve(Some("es_ES")).unwrap().code, "es");
+        assert!(resolve(Some("tlh")).is_err());
+    }
+}
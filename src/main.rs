@@ -1,27 +1,129 @@
 mod auth;
 mod calendar;
 mod cli;
+mod commands;
+mod config;
+mod formats;
+mod hook;
+mod locale;
+mod settings;
 mod tui;
+mod vdir;
+mod working_hours;
 
 use auth::Tokens;
 use calendar::client::CalendarClient;
-use cli::Cli;
+use calendar::local::Source;
+use calendar::text::TextCalendar;
+use chrono::{Duration, Local};
+use cli::{Cli, Command};
+use config::Config;
 
-#[tokio::main]
-async fn main() {
-    let args = Cli::parse_args();
+fn main() {
+    let mut args = Cli::parse_args();
+
+    let config = match Config::load(args.profile.as_deref()) {
+        Ok(config) => config,
+        // Falling back to defaults would silently drop e.g. `read_only`
+        Err(e) => {
+            eprintln!("{:#}", e);
+            std::process::exit(args.error_exit_code());
+        }
+    };
+    if let Some(timezone) = args.timezone.clone().or(config.timezone.clone()) {
+        if let Err(e) = config::override_timezone(&timezone) {
+            eprintln!("{:#}", e);
+            std::process::exit(args.error_exit_code());
+        }
+        // Days like "tomorrow" were resolved in the system zone while parsing
+        args = Cli::parse_args();
+    }
+
+    // Built only now so `TZ` is set before any worker thread starts
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            std::process::exit(args.error_exit_code());
+        }
+    };
+    runtime.block_on(run(args, config));
+}
+
+async fn run(mut args: Cli, config: Config) {
+    if let Err(e) = args.apply_config(&config) {
+        eprintln!("Invalid config: {:#}", e);
+        std::process::exit(args.error_exit_code());
+    }
+
+    // Every stored file below depends on the account, so select it first
+    if let Some(account) = &args.account {
+        if let Err(e) = auth::account::select(account) {
+            eprintln!("{:#}", e);
+            std::process::exit(args.error_exit_code());
+        }
+    }
 
     if args.is_logout() {
         handle_logout();
         return;
     }
 
+    let mut settings = match args.settings(&config) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Invalid config: {:#}", e);
+            std::process::exit(args.error_exit_code());
+        }
+    };
+    settings.local_sources = args
+        .text_calendar
+        .iter()
+        .map(|path| Source::Text(TextCalendar { path: path.clone() }))
+        .collect();
+    if args.khal {
+        match calendar::khal::discover() {
+            Ok(collections) => settings
+                .local_sources
+                .extend(collections.into_iter().map(Source::Vdir)),
+            Err(e) => eprintln!("Ignoring khal calendars: {:#}", e),
+        }
+    }
+    if args.tasks {
+        settings.local_sources.push(Source::Tasks);
+    }
+
+    let read_only = args.read_only;
+    if read_only && args.is_mutating() {
+        eprintln!("This command changes calendars and is disabled in read-only mode");
+        std::process::exit(args.error_exit_code());
+    }
+
+    // Runs without credentials, so before authentication
+    if args.is_doctor() {
+        match commands::doctor::run(
+            &settings.formats,
+            args.output_format(),
+            args.khal,
+            args.profile.as_deref(),
+        )
+        .await
+        {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Doctor failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Authenticate first
-    let tokens = match auth::authenticate().await {
+    let tokens = match auth::authenticate(read_only, args.opens_browser()).await {
         Ok(tokens) => tokens,
         Err(e) => {
             eprintln!("Authentication failed: {}", e);
-            std::process::exit(1);
+            std::process::exit(args.error_exit_code());
         }
     };
 
@@ -35,22 +137,327 @@ async fn main() {
         return;
     }
 
-    // Default: Launch TUI
-    match CalendarClient::new(tokens) {
-        Ok(client) => {
-            if let Err(e) = tui::run_tui(client) {
-                eprintln!("TUI error: {}", e);
+    let client = match CalendarClient::new(tokens) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to create calendar client: {}", e);
+            std::process::exit(args.error_exit_code());
+        }
+    };
+
+    let output = args.output_format();
+    match args.command {
+        Some(Command::Next {
+            ref format,
+            days,
+            all_day,
+            bar,
+        }) => {
+            let code =
+                commands::next::run(client, format, days, all_day, bar, &settings, output).await;
+            std::process::exit(code);
+        }
+        Some(Command::Status) => {
+            let code = commands::status::run(client, &settings, output).await;
+            std::process::exit(code);
+        }
+        Some(Command::List { from, to, .. }) => {
+            let from = from.unwrap_or_else(|| Local::now().date_naive());
+            let to = to.unwrap_or(from + Duration::days(7));
+            if let Err(e) = commands::list::run(client, from, to, &settings, output).await {
+                eprintln!("Failed to list events: {:#}", e);
                 std::process::exit(1);
             }
         }
-        Err(e) => {
-            eprintln!("Failed to create calendar client: {}", e);
+        Some(Command::Add {
+            stdin: true,
+            calendar,
+            ..
+        }) => {
+            let input = std::io::stdin().lock();
+            if let Err(e) =
+                commands::bulk::run(client, input, calendar.as_deref(), &settings, output).await
+            {
+                eprintln!("Failed to add events: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Add {
+            title: Some(title),
+            date,
+            from,
+            to,
+            calendar,
+            ..
+        }) => {
+            let new_event = commands::add::NewEvent {
+                title,
+                date,
+                from,
+                to,
+            };
+            if let Err(e) =
+                commands::add::run(client, new_event, calendar.as_deref(), &settings, output).await
+            {
+                eprintln!("Failed to add event: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Quick { text, calendar }) => {
+            // Without date or times `add` hands the title to quick add
+            let new_event = commands::add::NewEvent {
+                title: text,
+                date: None,
+                from: None,
+                to: None,
+            };
+            if let Err(e) =
+                commands::add::run(client, new_event, calendar.as_deref(), &settings, output).await
+            {
+                eprintln!("Failed to add event: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Delete {
+            query,
+            date,
+            all,
+            yes,
+        }) => {
+            if let Err(e) =
+                commands::delete::run(client, &query, date, all, yes, &settings, output).await
+            {
+                eprintln!("Failed to delete event: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Edit {
+            event_id,
+            calendar,
+            title,
+            description,
+            location,
+            start,
+            end,
+            add_attendees,
+            remove_attendees,
+            editor,
+        }) => {
+            let edits = commands::edit::EventEdits {
+                title,
+                description,
+                location,
+                start,
+                end,
+                add_attendees,
+                remove_attendees,
+            };
+            let result = commands::edit::run(
+                client,
+                &event_id,
+                calendar.as_deref(),
+                edits,
+                editor,
+                &settings,
+                output,
+            )
+            .await;
+            if let Err(e) = result {
+                eprintln!("Failed to edit event: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Open { query, join }) => {
+            if let Err(e) = commands::open::run(client, &query, join, &settings, output).await {
+                eprintln!("Failed to open event: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Rsvp {
+            event_id,
+            response,
+            comment,
+            calendar,
+        }) => {
+            let result = commands::rsvp::run(
+                client,
+                &event_id,
+                response,
+                comment,
+                calendar.as_deref(),
+                &settings,
+                output,
+            )
+            .await;
+            if let Err(e) = result {
+                eprintln!("Failed to respond: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Search { query, from, to }) => {
+            if let Err(e) = commands::search::run(client, &query, from, to, &settings, output).await
+            {
+                eprintln!("Search failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Import {
+            file,
+            calendar,
+            day_first,
+        }) => {
+            if let Err(e) = commands::import::run(
+                client,
+                &file,
+                calendar.as_deref(),
+                day_first,
+                &settings,
+                output,
+            )
+            .await
+            {
+                eprintln!("Import failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Busy { date, from, to, .. }) => {
+            if let Err(e) = commands::busy::run(client, date, from, to, &settings, output).await {
+                eprintln!("Failed to check availability: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::FindSlot {
+            duration,
+            within,
+            hours,
+            weekends,
+            calendar,
+            limit,
+        }) => {
+            let query = commands::find_slot::SlotQuery {
+                duration: commands::find_slot::meeting_duration(duration, args.event_duration),
+                days: within,
+                hours: hours.unwrap_or(settings.working_hours.hours),
+                working_days: settings.working_hours.days.clone(),
+                weekends,
+                limit,
+                snap: settings.event_defaults.snap,
+            };
+            if let Err(e) =
+                commands::find_slot::run(client, &calendar, query, &settings, output).await
+            {
+                eprintln!("Failed to find a slot: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Calendars { .. }) => {
+            if let Err(e) = commands::calendars::run(client, &settings.formats, output).await {
+                eprintln!("Failed to list calendars: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        #[cfg(unix)]
+        Some(Command::Serve { socket }) => {
+            if let Err(e) = commands::serve::run(client, socket, read_only, &settings).await {
+                eprintln!("Server failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        #[cfg(not(unix))]
+        Some(Command::Serve { .. }) => {
+            eprintln!("serve needs Unix sockets, which this platform lacks");
             std::process::exit(1);
         }
+        Some(Command::Mirror) => {
+            let Some(ref dir) = args.vdir else {
+                eprintln!("mirror needs --vdir (or OXIDATE_VDIR) to know where to write");
+                std::process::exit(1);
+            };
+            if let Err(e) = commands::mirror::run(client, dir, &settings).await {
+                eprintln!("Mirror failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Mcp) => {
+            if let Err(e) = commands::mcp::run(client, read_only, &settings).await {
+                eprintln!("Server failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Holidays { country, remove }) => {
+            if let Err(e) = commands::holidays::run(
+                client,
+                country.as_deref(),
+                remove,
+                &settings.formats,
+                output,
+            )
+            .await
+            {
+                eprintln!("Failed to manage holiday calendars: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Week { date, width }) => {
+            if let Err(e) = commands::week::run(client, date, width, &settings, output).await {
+                eprintln!("Failed to show week: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Note { date, ref heading }) => {
+            if let Err(e) = commands::note::run(client, date, heading, &settings, output).await {
+                eprintln!("Failed to print note: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Watch {
+            ref format,
+            bar: Some(bar),
+            interval,
+            ..
+        }) => {
+            let interval = std::time::Duration::from_secs(interval);
+            let hooks = args.hooks(false);
+            if let Err(e) =
+                commands::watch::run_bar(client, bar, format, interval, &settings, hooks).await
+            {
+                eprintln!("Watch failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Watch {
+            lead, ref format, ..
+        }) => {
+            let hooks = args.hooks(false);
+            if let Err(e) =
+                commands::watch::run(client, format, lead, &settings, output, hooks).await
+            {
+                eprintln!("Watch failed: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        // Default: Launch TUI
+        _ => {
+            let hooks = args.hooks(true);
+            if let Err(e) = tui::run_tui(
+                client,
+                settings,
+                args.calendars,
+                read_only,
+                &args.notify,
+                hooks,
+                args.view.unwrap_or_default(),
+            ) {
+                eprintln!("TUI error: {}", e);
+                std::process::exit(1);
+            }
+        }
     }
 }
 
 fn handle_logout() {
+    // The cached calendar list goes with the credentials
+    calendar::list_cache::invalidate();
     match Tokens::delete() {
         Ok(_) => println!("Successfully logged out. Credentials deleted."),
         Err(e) => eprintln!("Failed to delete credentials: {}", e),
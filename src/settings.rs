@@ -0,0 +1,53 @@
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::calendar::local::Source;
+use crate::commands::EventDefaults;
+use crate::formats::Formats;
+use crate::working_hours::WorkingHours;
+
+/// Months either side of the selected one whose events stay in memory
+/// unless `--memory-horizon` says otherwise
+const DEFAULT_MEMORY_HORIZON: u32 = 12;
+
+/// How often the TUI fetches the loaded range again without the user asking
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Options resolved from flags, `OXIDATE_*` variables and config.toml that
+/// the TUI and the commands share
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub formats: Formats,
+    pub working_hours: WorkingHours,
+    pub event_defaults: EventDefaults,
+    /// Calendar colors from config.toml's `[colors]`, by calendar id or name
+    pub calendar_colors: HashMap<String, String>,
+    /// Calendars kept in local files (`--khal`, `--text-calendar`, `--tasks`),
+    /// shown beside Google's
+    pub local_sources: Vec<Source>,
+    /// Keep fetched events in the local index (`--index`)
+    pub index: bool,
+    /// Zones whose times are shown next to local ones (`--other-timezones`)
+    pub other_timezones: Vec<Tz>,
+    /// Months of events kept in memory either side of the selected date
+    pub memory_horizon: u32,
+    /// How often the TUI refreshes on its own; None turns it off
+    pub refresh_interval: Option<Duration>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            formats: Formats::default(),
+            working_hours: WorkingHours::default(),
+            event_defaults: EventDefaults::default(),
+            calendar_colors: HashMap::new(),
+            local_sources: Vec::new(),
+            index: false,
+            other_timezones: Vec::new(),
+            memory_horizon: DEFAULT_MEMORY_HORIZON,
+            refresh_interval: Some(DEFAULT_REFRESH_INTERVAL),
+        }
+    }
+}
@@ -0,0 +1,902 @@
+use chrono::NaiveDate;
+use std::time::Instant;
+
+use super::export;
+use super::state::{AppState, EventsViewMode, ListLayout, ViewFocus};
+use crate::calendar::conference;
+use crate::calendar::models::EventPatch;
+use crate::commands::rsvp::{self, Response};
+
+/// Semantic user intents, independent of the key that triggered them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Refresh,
+    JumpToToday,
+    FocusNext,
+    FocusPrev,
+    MoveDay(i64),
+    MoveWeek(i64),
+    SelectNextEvent,
+    SelectPrevEvent,
+    OpenDetails,
+    CloseDetails,
+    ScrollDetails(i64),
+    /// Save the event shown in the details view as an .ics file
+    ExportEvent,
+    /// Edit the event shown in the details view in the event form
+    EditEvent,
+    /// Edit the event shown in the details view in `$EDITOR`, e.g. to change
+    /// its attendees
+    EditEventInEditor,
+    /// Answer the invitation to the event shown in the details view
+    Respond(Response),
+    /// Ask to delete the selected event
+    DeleteEvent,
+    ConfirmDelete,
+    CancelDelete,
+    /// Select the next attachment of the event in the details view
+    NextAttachment,
+    /// Open the selected attachment in the browser
+    OpenAttachment,
+    /// Join the selected event's video call
+    JoinCall,
+    /// Write to the selected event's attendees in the mail client
+    MailAttendees,
+    /// Periodic clock tick carrying the current local date
+    Tick(NaiveDate),
+    /// The event loop resumed after a long gap (e.g. system sleep) on this date
+    Wake(NaiveDate),
+    StartKeywordFilter,
+    KeywordInput(char),
+    KeywordBackspace,
+    /// Stop editing and keep the keyword
+    FinishKeywordFilter,
+    /// Stop editing and clear the keyword
+    CancelKeywordFilter,
+    ToggleDeclined,
+    HideSelectedCalendar,
+    ShowAllCalendars,
+    /// Quit despite unsaved changes
+    ConfirmQuit,
+    CancelQuit,
+    ToggleStats,
+    ToggleLog,
+    /// Mute or unmute desktop notifications for the session
+    ToggleNotifications,
+    /// Show the latest notification again in this many minutes
+    SnoozeNotification(u32),
+    ToggleSidebar,
+    /// Switch the events pane between the list and the day timeline
+    ToggleTimeline,
+    /// Switch the events pane between the list and the selected week
+    ToggleWeek,
+    /// Switch the events pane between the list and the agenda
+    ToggleAgenda,
+    /// A digit typed in the calendar pane
+    DayDigit(u32),
+    /// Jump to the typed day of the selected month
+    JumpToTypedDay,
+    ClearTypedDay,
+    MoveSidebarSelection(i64),
+    /// Show or hide the calendar under the sidebar cursor
+    ToggleSidebarCalendar,
+    /// Move the calendar under the sidebar cursor within the ordering
+    MoveSidebarCalendar(i64),
+    /// Repeat the last navigation or mutating action
+    RepeatLast,
+    /// Open the new event form for the selected day
+    NewEvent,
+    /// A character typed into the focused form field
+    FormInput(char),
+    FormBackspace,
+    /// Move the form's focus by this many fields
+    MoveFormFocus(i64),
+    /// Pick another calendar in the form
+    CycleFormCalendar(i64),
+    /// Save the event typed into the form
+    SubmitForm,
+    CancelForm,
+}
+
+impl Action {
+    /// Actions worth repeating with `.`: navigation and changes, not toggles of
+    /// overlays or prompts whose repetition would just undo them
+    fn is_repeatable(self) -> bool {
+        matches!(
+            self,
+            Action::MoveDay(_)
+                | Action::MoveWeek(_)
+                | Action::SelectNextEvent
+                | Action::SelectPrevEvent
+                | Action::ScrollDetails(_)
+                | Action::HideSelectedCalendar
+                | Action::MoveSidebarSelection(_)
+                | Action::ToggleSidebarCalendar
+                | Action::MoveSidebarCalendar(_)
+                | Action::Respond(_)
+        )
+    }
+}
+
+/// Side effects the event loop has to perform after an action was reduced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    None,
+    Quit,
+    Refresh,
+    /// Calendar order or visibility changed and should be persisted
+    SaveCalendarPrefs,
+    /// Write the selected event to an .ics file
+    ExportSelectedEvent,
+    /// Suspend the TUI and edit the selected event in `$EDITOR`
+    EditSelectedEvent,
+    /// Queue the event typed into the form for creation
+    CreateFormEvent,
+    /// Queue the changes made in the form to the edited event
+    UpdateFormEvent,
+    /// Queue the deletion of the selected event
+    DeleteSelectedEvent,
+    /// Queue the user's answer to the selected invitation
+    RespondToSelectedEvent(Response),
+    /// Open the selected attachment's link in the browser
+    OpenSelectedAttachment,
+    /// Open the selected event's video call in the browser
+    JoinSelectedCall,
+    /// Open a mail draft to the selected event's attendees
+    MailSelectedAttendees,
+    /// Look the keyword up in the local event index
+    SearchIndex,
+    /// Repeat the latest notification after this many minutes
+    SnoozeNotification(u32),
+}
+
+impl AppState {
+    /// The single place where actions turn into state transitions
+    pub fn reduce(&mut self, action: Action) -> Effect {
+        if action == Action::RepeatLast {
+            return match self.last_repeatable {
+                Some(last) => self.reduce(last),
+                None => Effect::None,
+            };
+        }
+
+        if action.is_repeatable() {
+            self.last_repeatable = Some(action);
+        }
+
+        // A typed day number only survives further digits
+        if !matches!(
+            action,
+            Action::DayDigit(_) | Action::JumpToTypedDay | Action::Tick(_) | Action::Wake(_)
+        ) {
+            self.typed_day.clear();
+        }
+
+        match action {
+            Action::Quit if self.unsaved_changes().is_empty() => return Effect::Quit,
+            Action::Quit => self.confirming_quit = true,
+            Action::ConfirmQuit => return Effect::Quit,
+            Action::CancelQuit => self.confirming_quit = false,
+            Action::ToggleStats => self.show_stats = !self.show_stats,
+            Action::ToggleLog => self.show_log = !self.show_log,
+            Action::ToggleNotifications => {
+                self.notifications_muted = !self.notifications_muted;
+                if self.notifications_muted {
+                    tracing::info!("Notifications muted for this session");
+                } else {
+                    tracing::info!("Notifications unmuted");
+                }
+            }
+            Action::SnoozeNotification(minutes) => return Effect::SnoozeNotification(minutes),
+            Action::ToggleSidebar => {
+                self.show_sidebar = !self.show_sidebar;
+                if !self.show_sidebar && self.view_focus == ViewFocus::Sidebar {
+                    self.view_focus = ViewFocus::Calendar;
+                }
+            }
+            Action::ToggleTimeline => self.toggle_list_layout(ListLayout::Timeline),
+            Action::ToggleWeek => self.toggle_list_layout(ListLayout::Week),
+            Action::ToggleAgenda => self.toggle_list_layout(ListLayout::Agenda),
+            Action::DayDigit(digit) => {
+                // Days have at most two digits; keep the latest ones
+                if self.typed_day.len() == 2 {
+                    self.typed_day.remove(0);
+                }
+                self.typed_day.push_str(&digit.to_string());
+            }
+            Action::MoveSidebarSelection(delta) => self.move_sidebar_selection(delta),
+            Action::ToggleSidebarCalendar => {
+                self.toggle_sidebar_calendar();
+                return Effect::SaveCalendarPrefs;
+            }
+            Action::MoveSidebarCalendar(delta) => {
+                self.move_sidebar_calendar(delta);
+                return Effect::SaveCalendarPrefs;
+            }
+            // Already cleared above, like by any other action
+            Action::ClearTypedDay => {}
+            Action::RepeatLast => unreachable!("handled above"),
+            Action::JumpToTypedDay => {
+                if let Ok(day) = std::mem::take(&mut self.typed_day).parse() {
+                    self.jump_to_day_of_month(day);
+                    self.reset_event_selection();
+                    self.last_navigation = Some(Instant::now());
+                }
+            }
+            Action::Refresh => return Effect::Refresh,
+            Action::JumpToToday => {
+                self.jump_to_today();
+                self.last_navigation = Some(Instant::now());
+            }
+            Action::FocusNext => self.focus_next(),
+            Action::FocusPrev => self.focus_prev(),
+            Action::MoveDay(days) => {
+                self.move_selected_date(days);
+                self.reset_event_selection();
+                self.last_navigation = Some(Instant::now());
+            }
+            Action::MoveWeek(weeks) => {
+                self.move_selected_date(weeks * 7);
+                self.reset_event_selection();
+                self.last_navigation = Some(Instant::now());
+            }
+            Action::SelectNextEvent => self.move_event_selection_down(),
+            Action::SelectPrevEvent => self.move_event_selection_up(),
+            Action::OpenDetails => self.select_event(),
+            Action::CloseDetails => self.exit_event_details(),
+            Action::ExportEvent if self.selected_event().is_some() => {
+                return Effect::ExportSelectedEvent
+            }
+            Action::ExportEvent => {}
+            Action::EditEvent | Action::EditEventInEditor if self.read_only => {
+                self.show_toast("Read-only mode: editing is disabled")
+            }
+            Action::EditEvent | Action::EditEventInEditor if self.selected_event_is_read_only() => {
+                self.show_toast("This calendar is read-only")
+            }
+            Action::EditEvent => self.open_edit_form(),
+            Action::EditEventInEditor if self.selected_event().is_some() => {
+                return Effect::EditSelectedEvent
+            }
+            Action::EditEventInEditor => {}
+            Action::Respond(_) if self.read_only => {
+                self.show_toast("Read-only mode: responding is disabled")
+            }
+            Action::Respond(response) => {
+                if let Some(event) = self.selected_event() {
+                    match rsvp::respond(event, response, None) {
+                        Ok(_) => return Effect::RespondToSelectedEvent(response),
+                        Err(e) => self.show_toast(format!("{:#}", e)),
+                    }
+                }
+            }
+            Action::DeleteEvent if self.read_only => {
+                self.show_toast("Read-only mode: deleting is disabled")
+            }
+            Action::DeleteEvent if self.selected_event_is_read_only() => {
+                self.show_toast("This calendar is read-only")
+            }
+            Action::DeleteEvent => self.confirming_delete = self.selected_event().is_some(),
+            Action::ConfirmDelete => {
+                self.confirming_delete = false;
+                return Effect::DeleteSelectedEvent;
+            }
+            Action::CancelDelete => self.confirming_delete = false,
+            Action::NextAttachment => self.select_next_attachment(),
+            Action::OpenAttachment if self.selected_attachment().is_some() => {
+                return Effect::OpenSelectedAttachment
+            }
+            Action::OpenAttachment => self.show_toast("No attachments"),
+            Action::JoinCall => match self.selected_event().map(conference::video_call) {
+                Some(Some(_)) => return Effect::JoinSelectedCall,
+                Some(None) => self.show_toast("No video call link"),
+                None => {}
+            },
+            Action::MailAttendees => match self.selected_event().map(export::mailto) {
+                Some(Some(_)) => return Effect::MailSelectedAttendees,
+                Some(None) => self.show_toast("No other attendees to write to"),
+                None => {}
+            },
+            Action::ScrollDetails(lines) if lines < 0 => {
+                for _ in 0..lines.unsigned_abs() {
+                    self.scroll_event_details_up();
+                }
+            }
+            Action::ScrollDetails(lines) => {
+                for _ in 0..lines {
+                    self.scroll_event_details_down();
+                }
+            }
+            Action::Tick(today) => {
+                self.roll_over_to(today);
+                self.expire_toast(Instant::now());
+            }
+            Action::Wake(today) => {
+                tracing::info!("Resumed after sleep, refreshing events");
+                self.roll_over_to(today);
+                return Effect::Refresh;
+            }
+            Action::StartKeywordFilter => self.editing_keyword = true,
+            Action::KeywordInput(c) => self.update_filter(|filter| filter.keyword.push(c)),
+            Action::KeywordBackspace => self.update_filter(|filter| {
+                filter.keyword.pop();
+            }),
+            Action::FinishKeywordFilter => {
+                self.editing_keyword = false;
+                if !self.filter.keyword.is_empty() {
+                    return Effect::SearchIndex;
+                }
+            }
+            Action::CancelKeywordFilter => {
+                self.editing_keyword = false;
+                self.update_filter(|filter| filter.keyword.clear());
+            }
+            Action::ToggleDeclined => {
+                self.update_filter(|filter| filter.show_declined = !filter.show_declined)
+            }
+            Action::HideSelectedCalendar => {
+                if let Some(calendar_id) = self.selected_event_calendar() {
+                    self.update_filter(|filter| {
+                        filter.hidden_calendars.insert(calendar_id);
+                    });
+                    return Effect::SaveCalendarPrefs;
+                }
+            }
+            Action::ShowAllCalendars => {
+                self.update_filter(|filter| filter.hidden_calendars.clear());
+                return Effect::SaveCalendarPrefs;
+            }
+            Action::NewEvent if self.read_only => {
+                self.show_toast("Read-only mode: creating events is disabled")
+            }
+            Action::NewEvent if self.writable_calendars().is_empty() => {
+                self.show_toast("No calendar to add events to")
+            }
+            Action::NewEvent => self.open_event_form(),
+            Action::FormInput(c) => {
+                if let Some(ref mut form) = self.event_form {
+                    form.input(c);
+                }
+            }
+            Action::FormBackspace => {
+                if let Some(ref mut form) = self.event_form {
+                    form.backspace();
+                }
+            }
+            Action::MoveFormFocus(delta) => {
+                if let Some(ref mut form) = self.event_form {
+                    form.move_focus(delta);
+                }
+            }
+            Action::CycleFormCalendar(delta) => {
+                let count = self.writable_calendars().len();
+                if let Some(ref mut form) = self.event_form {
+                    form.cycle_calendar(delta, count);
+                }
+            }
+            Action::SubmitForm if matches!(self.events_view_mode, EventsViewMode::Edit { .. }) => {
+                match self.form_patch() {
+                    Ok((_, patch)) if patch == EventPatch::default() => {
+                        self.close_event_form();
+                        self.show_toast("Nothing to change");
+                    }
+                    Ok(_) => return Effect::UpdateFormEvent,
+                    Err(e) => self.show_toast(format!("{:#}", e)),
+                }
+            }
+            Action::SubmitForm => match self.form_event() {
+                Ok(_) => return Effect::CreateFormEvent,
+                Err(e) => self.show_toast(format!("{:#}", e)),
+            },
+            Action::CancelForm => self.close_event_form(),
+        }
+
+        Effect::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::form::EventForm;
+    use chrono::Duration;
+
+    #[test]
+    fn test_quit_and_refresh_produce_effects() {
+        let mut state = AppState::new();
+
+        assert_eq!(state.reduce(Action::Quit), Effect::Quit);
+        assert_eq!(state.reduce(Action::Refresh), Effect::Refresh);
+        assert_eq!(state.reduce(Action::JumpToToday), Effect::None);
+    }
+
+    #[test]
+    fn test_move_day_resets_event_selection() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_event_index = Some(1);
+        state.events_view_mode = EventsViewMode::Details {
+            event_index: 1,
+            scroll_offset: 0,
+            max_scroll: 0,
+        };
+
+        state.reduce(Action::MoveDay(-1));
+
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 14).unwrap()
+        );
+        assert_eq!(state.selected_event_index, None);
+        assert!(matches!(state.events_view_mode, EventsViewMode::List));
+        assert!(state.last_navigation.is_some());
+    }
+
+    #[test]
+    fn test_move_week() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+
+        state.reduce(Action::MoveWeek(1));
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 22).unwrap()
+        );
+
+        state.reduce(Action::MoveWeek(-2));
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 8).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toggle_stats() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::ToggleStats);
+        assert!(state.show_stats);
+
+        state.reduce(Action::ToggleStats);
+        assert!(!state.show_stats);
+    }
+
+    #[test]
+    fn test_toggle_log() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::ToggleLog);
+        assert!(state.show_log);
+    }
+
+    #[test]
+    fn test_finishing_a_keyword_searches_the_index() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::StartKeywordFilter);
+        assert_eq!(state.reduce(Action::FinishKeywordFilter), Effect::None);
+
+        state.reduce(Action::StartKeywordFilter);
+        state.reduce(Action::KeywordInput('x'));
+        assert_eq!(
+            state.reduce(Action::FinishKeywordFilter),
+            Effect::SearchIndex
+        );
+        assert!(!state.editing_keyword);
+    }
+
+    #[test]
+    fn test_export_event_needs_a_selection() {
+        let mut state = AppState::new();
+
+        assert_eq!(state.reduce(Action::ExportEvent), Effect::None);
+    }
+
+    #[test]
+    fn test_join_call_needs_a_link() {
+        let mut state = AppState::new();
+        assert_eq!(state.reduce(Action::JoinCall), Effect::None);
+        assert!(state.toast.is_none());
+    }
+
+    #[test]
+    fn test_edit_event_is_refused_in_read_only_mode() {
+        let mut state = AppState::new();
+        assert_eq!(state.reduce(Action::EditEventInEditor), Effect::None);
+
+        state.read_only = true;
+        assert_eq!(state.reduce(Action::EditEventInEditor), Effect::None);
+        assert!(state.toast.is_some());
+        state.reduce(Action::EditEvent);
+        assert!(state.event_form.is_none());
+    }
+
+    #[test]
+    fn test_respond_needs_an_invitation() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        let mut form = EventForm::new(date, 0);
+        form.title = "Planning".to_string();
+        let mut state = AppState::new();
+        state.selected_date = date;
+        state
+            .events
+            .insert(date, vec![form.to_event(Duration::hours(1)).unwrap()]);
+        state.selected_event_index = Some(0);
+
+        assert_eq!(
+            state.reduce(Action::Respond(Response::Accept)),
+            Effect::None
+        );
+        assert!(state.toast.is_some());
+
+        state.events.get_mut(&date).unwrap()[0].attendees =
+            Some(vec![crate::calendar::models::Attendee {
+                email: "me@example.com".to_string(),
+                display_name: None,
+                response_status: Some("needsAction".to_string()),
+                optional: None,
+                is_self: Some(true),
+                comment: None,
+            }]);
+        assert_eq!(
+            state.reduce(Action::Respond(Response::Decline)),
+            Effect::RespondToSelectedEvent(Response::Decline)
+        );
+    }
+
+    #[test]
+    fn test_delete_event_asks_first() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        let mut form = EventForm::new(date, 0);
+        form.title = "Standup".to_string();
+        let mut state = AppState::new();
+        state.selected_date = date;
+
+        state.reduce(Action::DeleteEvent);
+        assert!(!state.confirming_delete);
+
+        state
+            .events
+            .insert(date, vec![form.to_event(Duration::hours(1)).unwrap()]);
+        state.selected_event_index = Some(0);
+        state.reduce(Action::DeleteEvent);
+        assert!(state.confirming_delete);
+        state.reduce(Action::CancelDelete);
+        assert!(!state.confirming_delete);
+
+        state.reduce(Action::DeleteEvent);
+        assert_eq!(
+            state.reduce(Action::ConfirmDelete),
+            Effect::DeleteSelectedEvent
+        );
+        assert!(!state.confirming_delete);
+
+        state.read_only = true;
+        state.reduce(Action::DeleteEvent);
+        assert!(!state.confirming_delete);
+        assert!(state.toast.is_some());
+    }
+
+    #[test]
+    fn test_edit_event_form() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        let mut form = EventForm::new(date, 0);
+        form.title = "Standup".to_string();
+        form.start = "09:30".to_string();
+        let mut event = form.to_event(Duration::hours(1)).unwrap();
+        event.id = "standup".to_string();
+
+        let mut state = AppState::new();
+        state.selected_date = date;
+        state.events.insert(date, vec![event]);
+        state.view_focus = ViewFocus::Events;
+        state.selected_event_index = Some(0);
+        state.reduce(Action::OpenDetails);
+        state.reduce(Action::EditEvent);
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Edit { event_index: 0 }
+        ));
+
+        // Unchanged, the form just closes
+        assert_eq!(state.reduce(Action::SubmitForm), Effect::None);
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details { event_index: 0, .. }
+        ));
+
+        state.reduce(Action::EditEvent);
+        state.reduce(Action::FormInput('!'));
+        assert_eq!(state.reduce(Action::SubmitForm), Effect::UpdateFormEvent);
+        let (event, patch) = state.form_patch().unwrap();
+        assert_eq!(event.id, "standup");
+        assert_eq!(patch.summary.as_deref(), Some("Standup!"));
+
+        state.reduce(Action::CancelForm);
+        assert!(state.event_form.is_none());
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details { event_index: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_new_event_form() {
+        let mut state = AppState::new();
+        state.reduce(Action::NewEvent);
+        assert_eq!(state.events_view_mode, EventsViewMode::List);
+        assert!(state.toast.is_some());
+
+        state.calendars = vec![crate::calendar::models::Calendar {
+            id: "me".to_string(),
+            summary: "Me".to_string(),
+            primary: true,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }];
+        state.reduce(Action::NewEvent);
+        assert_eq!(state.events_view_mode, EventsViewMode::Create);
+        assert_eq!(state.view_focus, ViewFocus::Events);
+
+        // Without a title the form stays open
+        assert_eq!(state.reduce(Action::SubmitForm), Effect::None);
+        state.reduce(Action::FormInput('Q'));
+        assert_eq!(state.reduce(Action::SubmitForm), Effect::CreateFormEvent);
+        assert_eq!(state.form_event().unwrap().0, "me");
+
+        state.reduce(Action::CancelForm);
+        assert_eq!(state.events_view_mode, EventsViewMode::List);
+        assert!(state.event_form.is_none());
+    }
+
+    #[test]
+    fn test_new_event_is_refused_in_read_only_mode() {
+        let mut state = AppState::new();
+        state.read_only = true;
+
+        state.reduce(Action::NewEvent);
+
+        assert!(state.event_form.is_none());
+        assert!(state.toast.is_some());
+    }
+
+    #[test]
+    fn test_toggle_notifications() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::ToggleNotifications);
+        assert!(state.notifications_muted);
+
+        state.reduce(Action::ToggleNotifications);
+        assert!(!state.notifications_muted);
+    }
+
+    #[test]
+    fn test_snooze_notification() {
+        let mut state = AppState::new();
+
+        assert_eq!(
+            state.reduce(Action::SnoozeNotification(10)),
+            Effect::SnoozeNotification(10)
+        );
+    }
+
+    #[test]
+    fn test_toggle_focus() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::FocusNext);
+
+        assert_eq!(state.view_focus, ViewFocus::Events);
+    }
+
+    #[test]
+    fn test_open_and_close_details() {
+        let mut state = AppState::new();
+        state.selected_event_index = Some(0);
+
+        state.reduce(Action::OpenDetails);
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details { event_index: 0, .. }
+        ));
+
+        state.reduce(Action::CloseDetails);
+        assert!(matches!(state.events_view_mode, EventsViewMode::List));
+    }
+
+    #[test]
+    fn test_scroll_details_in_both_directions() {
+        let mut state = AppState::new();
+        state.events_view_mode = EventsViewMode::Details {
+            event_index: 0,
+            scroll_offset: 2,
+            max_scroll: 10,
+        };
+
+        state.reduce(Action::ScrollDetails(3));
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details {
+                scroll_offset: 5,
+                ..
+            }
+        ));
+
+        state.reduce(Action::ScrollDetails(-10));
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details {
+                scroll_offset: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_tick_rolls_today_forward() {
+        let mut state = AppState::new();
+        state.today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = state.today;
+
+        let effect = state.reduce(Action::Tick(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap()));
+
+        assert_eq!(effect, Effect::None);
+        assert_eq!(state.today, NaiveDate::from_ymd_opt(2025, 6, 16).unwrap());
+    }
+
+    #[test]
+    fn test_wake_rolls_over_and_refreshes() {
+        let mut state = AppState::new();
+        state.today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = state.today;
+
+        let effect = state.reduce(Action::Wake(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap()));
+
+        assert_eq!(effect, Effect::Refresh);
+        assert_eq!(state.today, NaiveDate::from_ymd_opt(2025, 6, 16).unwrap());
+        assert_eq!(state.selected_date, state.today);
+    }
+
+    #[test]
+    fn test_keyword_filter_editing() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::StartKeywordFilter);
+        assert!(state.editing_keyword);
+
+        state.reduce(Action::KeywordInput('s'));
+        state.reduce(Action::KeywordInput('x'));
+        state.reduce(Action::KeywordBackspace);
+        state.reduce(Action::KeywordInput('t'));
+        assert_eq!(state.filter.keyword, "st");
+
+        state.reduce(Action::FinishKeywordFilter);
+        assert!(!state.editing_keyword);
+        assert_eq!(state.filter.keyword, "st");
+
+        state.reduce(Action::StartKeywordFilter);
+        state.reduce(Action::CancelKeywordFilter);
+        assert!(!state.editing_keyword);
+        assert!(state.filter.keyword.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_declined_and_show_all_calendars() {
+        let mut state = AppState::new();
+        state.filter.hidden_calendars.insert("work".to_string());
+
+        state.reduce(Action::ToggleDeclined);
+        assert!(state.filter.show_declined);
+
+        assert_eq!(
+            state.reduce(Action::ShowAllCalendars),
+            Effect::SaveCalendarPrefs
+        );
+        assert!(state.filter.hidden_calendars.is_empty());
+    }
+
+    #[test]
+    fn test_quit_with_pending_changes_asks_for_confirmation() {
+        let mut state = AppState::new();
+        state.pending_mutations = 1;
+
+        assert_eq!(state.reduce(Action::Quit), Effect::None);
+        assert!(state.confirming_quit);
+
+        assert_eq!(state.reduce(Action::CancelQuit), Effect::None);
+        assert!(!state.confirming_quit);
+
+        state.reduce(Action::Quit);
+        assert_eq!(state.reduce(Action::ConfirmQuit), Effect::Quit);
+    }
+
+    #[test]
+    fn test_typed_day_jumps_within_month() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 3).unwrap();
+
+        state.reduce(Action::DayDigit(2));
+        state.reduce(Action::DayDigit(4));
+        assert_eq!(state.typed_day, "24");
+
+        state.reduce(Action::JumpToTypedDay);
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 24).unwrap()
+        );
+        assert!(state.typed_day.is_empty());
+    }
+
+    #[test]
+    fn test_typed_day_cleared_by_other_actions() {
+        let mut state = AppState::new();
+
+        state.reduce(Action::DayDigit(1));
+        state.reduce(Action::MoveDay(1));
+
+        assert!(state.typed_day.is_empty());
+    }
+
+    #[test]
+    fn test_repeat_last_navigation() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+
+        state.reduce(Action::MoveDay(1));
+        state.reduce(Action::ToggleStats);
+        state.reduce(Action::RepeatLast);
+        state.reduce(Action::RepeatLast);
+
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 18).unwrap()
+        );
+        assert_eq!(state.last_repeatable, Some(Action::MoveDay(1)));
+
+        let mut form = EventForm::new(state.selected_date, 0);
+        form.title = "Planning".to_string();
+        let mut invitation = form.to_event(Duration::hours(1)).unwrap();
+        invitation.attendees = Some(vec![crate::calendar::models::Attendee {
+            email: "me@example.com".to_string(),
+            display_name: None,
+            response_status: Some("needsAction".to_string()),
+            optional: None,
+            is_self: Some(true),
+            comment: None,
+        }]);
+        state.events.insert(state.selected_date, vec![invitation]);
+        state.selected_event_index = Some(0);
+
+        state.reduce(Action::Respond(Response::Tentative));
+        assert_eq!(
+            state.reduce(Action::RepeatLast),
+            Effect::RespondToSelectedEvent(Response::Tentative)
+        );
+    }
+
+    #[test]
+    fn test_repeat_without_history_does_nothing() {
+        let mut state = AppState::new();
+        let date = state.selected_date;
+
+        assert_eq!(state.reduce(Action::RepeatLast), Effect::None);
+        assert_eq!(state.selected_date, date);
+    }
+
+    #[test]
+    fn test_hiding_sidebar_moves_focus_away() {
+        let mut state = AppState::new();
+        state.view_focus = ViewFocus::Sidebar;
+
+        state.reduce(Action::ToggleSidebar);
+
+        assert!(!state.show_sidebar);
+        assert_eq!(state.view_focus, ViewFocus::Calendar);
+    }
+}
@@ -1,239 +1,1341 @@
-use anyhow::Result;
-use chrono::Local;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
 use crossterm::{
+    cursor,
     event::{self, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Terminal,
 };
 use std::io;
-use std::time::Duration;
+use std::panic;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::Level;
 
 use super::{
-    input::{handle_key_event, InputAction},
-    loader::{DataLoader, DataMessage},
-    state::{AppState, DateRange, EventsViewMode, ViewFocus},
-    widgets::{CalendarWidget, EventDetailsWidget, EventListWidget},
+    action::{Action, Effect},
+    calendar_prefs::CalendarPrefs,
+    fetcher::local_midnight,
+    input::{display_keys, handle_key_event},
+    loader::{self, DataLoader, DataMessage},
+    log::LogBuffer,
+    notify::{self, Notifier},
+    state::{
+        next_event_countdown, AppState, DateRange, EventsViewMode, ListLayout, LoadProgress,
+        StartView, ViewFocus,
+    },
+    widgets::{
+        AgendaWidget, CalendarSidebarWidget, CalendarWidget, EventDetailsWidget, EventFormWidget,
+        EventListWidget, TimelineWidget, WeekWidget,
+    },
 };
 use crate::calendar::client::CalendarClient;
+use crate::calendar::conference;
+use crate::calendar::index::EventIndex;
+use crate::calendar::list_cache;
+use crate::calendar::models::{self, EventPatch};
+use crate::calendar::queue::{Mutation, MutationQueue, Revision};
+use crate::commands::edit;
+use crate::commands::rsvp::{self, Response};
+use crate::hook::Hooks;
+use crate::settings::Settings;
 
-pub fn run_tui(client: CalendarClient) -> Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
+/// How often the clock is checked for a date change
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// Most events merged from the local index for one keyword
+const INDEX_SEARCH_LIMIT: usize = 500;
+/// A wall-clock gap between ticks this long means the machine was asleep
+const WAKE_GAP: chrono::Duration = chrono::Duration::minutes(1);
+
+/// Run the TUI; `only_calendars` (ids or names) limits the calendars shown and fetched,
+/// `read_only` keeps the TUI from sending any changes, desktop notifications
+/// fire `notify_minutes` before events start, and `hooks` run before events
+/// and after each refresh
+pub fn run_tui(
+    client: CalendarClient,
+    settings: Settings,
+    only_calendars: Vec<String>,
+    read_only: bool,
+    notify_minutes: &[u32],
+    mut hooks: Hooks,
+    view: StartView,
+) -> Result<()> {
+    install_panic_hook();
+    let log = LogBuffer::install();
+
+    // Setup terminal; the guard restores it on every exit path, including errors
+    let _guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Initialize app state
     let mut app_state = AppState::new();
+    app_state.pending_mutations = match MutationQueue::load() {
+        Ok(queue) => queue.len(),
+        Err(e) => {
+            tracing::warn!("Could not read offline changes: {:#}", e);
+            0
+        }
+    };
+
+    match CalendarPrefs::load() {
+        Ok(prefs) => app_state.apply_calendar_prefs(prefs),
+        Err(e) => tracing::warn!("Could not read calendar preferences: {:#}", e),
+    }
 
     // Start data loader
     let date_range = DateRange::five_month_span(Local::now().date_naive());
     app_state.current_date_range = date_range.clone();
-    let mut data_loader = Some(DataLoader::new(client, date_range));
+    app_state.only_calendars = only_calendars;
+    app_state.read_only = read_only;
+    app_state.settings = Arc::new(settings);
+    app_state.open_view(view);
+    let mut data_loader = Some(DataLoader::new(
+        client,
+        date_range,
+        app_state.only_calendars.clone(),
+        app_state.read_only,
+        app_state.settings.clone(),
+    ));
+
+    let mut notifier = Notifier::new(notify_minutes, Local::now());
 
     // Main event loop
-    let result = run_app(&mut terminal, &mut app_state, &mut data_loader);
+    run_app(
+        &mut terminal,
+        &mut app_state,
+        &mut data_loader,
+        &mut notifier,
+        &mut hooks,
+        &log,
+    )
+}
+
+/// Raw mode and the alternate screen for as long as the guard lives
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        enable_raw_mode()?;
+        // Construct the guard before entering the alternate screen so a failure
+        // there still disables raw mode
+        let guard = Self;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(guard)
+    }
+}
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
 
-    result
+/// Best effort: errors are ignored because this also runs while panicking
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+}
+
+/// Restore the terminal before the default hook prints the panic, otherwise the
+/// message is written to the alternate screen and lost with it
+fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
 }
 
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app_state: &mut AppState,
     data_loader: &mut Option<DataLoader>,
+    notifier: &mut Notifier,
+    hooks: &mut Hooks,
+    log: &LogBuffer,
 ) -> Result<()> {
     let mut available_client: Option<CalendarClient> = None;
+    let event_index = EventIndex::open(app_state.settings.index).unwrap_or_else(|e| {
+        tracing::warn!("Event index unavailable: {:#}", e);
+        None
+    });
+    let mut last_tick = Instant::now();
+    // `Instant` may not advance while suspended, so sleep is detected on the wall clock
+    let mut last_tick_at = Local::now();
+    // Drawing is the costly part of an iteration, so it only happens after
+    // input, loader messages or ticks that change what is on screen
+    let mut needs_redraw = true;
+    // When the last fetch ended, for the periodic refresh
+    let mut last_loaded: Option<Instant> = None;
 
     loop {
-        // Check for data updates from loader
-        if let Some(loader) = data_loader {
-            if let Some(message) = loader.try_recv() {
-                match message {
-                    DataMessage::Loading => {
-                        app_state.loading = true;
-                        app_state.error = None;
-                    }
-                    DataMessage::Success {
-                        calendars,
-                        events,
-                        client,
-                    } => {
-                        app_state.calendars = calendars;
-                        // Merge new events into existing cache
-                        app_state.events.extend(events);
-                        // Trim to 25-month span to prevent unlimited growth
-                        app_state.trim_events_to_25_month_span();
-                        app_state.loading = false;
-                        app_state.error = None;
-                        // Store client for reuse in future refreshes
-                        available_client = Some(client);
-                        *data_loader = None; // Drop loader after success
+        if last_tick.elapsed() >= TICK_INTERVAL {
+            let now = Local::now();
+            let before = (app_state.today, app_state.toast.is_some());
+            let action = if resumed_from_sleep(last_tick_at, now) {
+                Action::Wake(now.date_naive())
+            } else {
+                Action::Tick(now.date_naive())
+            };
+            let effect = app_state.reduce(action);
+            apply_effect(
+                effect,
+                terminal,
+                app_state,
+                data_loader,
+                &mut available_client,
+                event_index.as_ref(),
+                notifier,
+            );
+            notify_due_events(notifier, app_state, now);
+            hooks.run_due(app_state, now);
+            // The log pane shows whatever was logged meanwhile
+            needs_redraw |= minute_changed(last_tick_at, now)
+                || before != (app_state.today, app_state.toast.is_some())
+                || app_state.show_log;
+            last_tick = Instant::now();
+            last_tick_at = now;
+        }
+
+        // Drain all pending loader messages so progress updates don't lag behind
+        let mut loader_done = false;
+        while let Some(message) = data_loader.as_mut().and_then(|loader| loader.try_recv()) {
+            needs_redraw = true;
+            match message {
+                DataMessage::Loading => {
+                    app_state.loading = true;
+                    app_state.load_progress = None;
+                    app_state.error = None;
+                }
+                DataMessage::Progress {
+                    calendar,
+                    calendar_id,
+                    events,
+                    done,
+                    total,
+                } => {
+                    // Show each calendar's events as soon as they arrive
+                    if let Some(events) = events {
+                        let fetched_range = app_state.current_date_range.clone();
+                        app_state.merge_calendar_events(&fetched_range, &calendar_id, events);
                     }
-                    DataMessage::Error { error, client } => {
-                        app_state.loading = false;
-                        app_state.error = Some(error);
-                        // Store client even on error to allow retry
-                        available_client = Some(client);
-                        *data_loader = None; // Drop loader after error
+                    app_state.load_progress = Some(LoadProgress {
+                        calendar,
+                        done,
+                        total,
+                    });
+                }
+                DataMessage::Success {
+                    calendars,
+                    failed,
+                    sync,
+                    client,
+                } => {
+                    app_state.calendars = calendars;
+                    app_state.retain_known_calendars();
+                    if app_state.adopt_calendar_selection() {
+                        if let Err(e) = app_state.calendar_prefs().save() {
+                            tracing::warn!("Could not save calendar preferences: {:#}", e);
+                        }
                     }
+                    app_state.failed_calendars = failed;
+                    // Keep memory bounded however far the user browsed
+                    app_state.evict_distant_events();
+                    app_state.loading = false;
+                    app_state.load_progress = None;
+                    app_state.pending_mutations = sync.pending;
+                    app_state.restore_dropped_deletes(&sync.dropped, sync.pending);
+                    app_state.error = if sync.discarded.is_empty() {
+                        None
+                    } else {
+                        Some(format!(
+                            "Offline changes dropped: {}",
+                            sync.discarded.join("; ")
+                        ))
+                    };
+                    app_state.last_sync = Some(Local::now());
+                    app_state.api_stats = client.stats();
+                    let range = app_state.current_date_range.clone();
+                    hooks.refreshed(app_state, &range);
+                    // Store client for reuse in future refreshes
+                    available_client = Some(client);
+                    *data_loader = None; // Drop loader after success
+                    loader_done = true;
+                    last_loaded = Some(Instant::now());
+                }
+                DataMessage::Cancelled { client } => {
+                    app_state.loading = false;
+                    app_state.load_progress = None;
+                    app_state.api_stats = client.stats();
+                    available_client = Some(client);
+                    *data_loader = None;
+                    loader_done = true;
+                }
+                DataMessage::Error { error, client } => {
+                    app_state.loading = false;
+                    app_state.load_progress = None;
+                    app_state.error = Some(error);
+                    app_state.api_stats = client.stats();
+                    // Store client even on error to allow retry
+                    available_client = Some(client);
+                    *data_loader = None; // Drop loader after error
+                    loader_done = true;
+                    last_loaded = Some(Instant::now());
                 }
             }
         }
 
-        // Render UI
-        terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(33), Constraint::Percentage(67)])
-                .split(f.area());
+        // A save made while the previous fetch ran still has to be sent, once
+        // that fetch hands the client back
+        if loader_done && app_state.refresh_pending {
+            apply_effect(
+                Effect::Refresh,
+                terminal,
+                app_state,
+                data_loader,
+                &mut available_client,
+                event_index.as_ref(),
+                notifier,
+            );
+        }
 
-            // Render calendar widget
-            let calendar_widget = CalendarWidget::new(app_state);
-            f.render_widget(calendar_widget, chunks[0]);
+        if needs_redraw {
+            terminal.draw(|f| render_ui(f, app_state, log))?;
+            needs_redraw = false;
+        }
 
-            // Render events widget based on mode
-            match app_state.events_view_mode {
-                EventsViewMode::List => {
-                    let events_widget = EventListWidget::new(app_state);
-                    f.render_widget(events_widget, chunks[1]);
+        // Handle input (non-blocking with timeout)
+        if event::poll(Duration::from_millis(100))? {
+            needs_redraw = true;
+            match event::read()? {
+                Event::Key(key) => {
+                    let effect = handle_key_event(key, app_state);
+                    if apply_effect(
+                        effect,
+                        terminal,
+                        app_state,
+                        data_loader,
+                        &mut available_client,
+                        event_index.as_ref(),
+                        notifier,
+                    ) {
+                        break;
+                    }
                 }
-                EventsViewMode::Details {
-                    event_index,
-                    scroll_offset,
-                    ..
-                } => {
-                    let details_widget = EventDetailsWidget::new(&mut *app_state, event_index, scroll_offset);
-                    f.render_widget(details_widget, chunks[1]);
+                // Pick up the new size right away; the layout is recomputed on the
+                // next draw at the top of the loop instead of after the next keypress
+                Event::Resize(..) => terminal.autoresize()?,
+                _ => {}
+            }
+        }
+
+        // Auto-refresh date range once navigation has settled. A fetch still running
+        // for a range the user already left is cancelled; the next iteration
+        // starts the replacement once its client is handed back.
+        if app_state.range_refresh_due(Instant::now()) {
+            if let Some(loader) = data_loader.as_mut() {
+                loader.cancel();
+            } else if let Some(client) = available_client.take() {
+                let new_range = DateRange::five_month_span(app_state.selected_date);
+                // Months evicted earlier show their indexed events while the
+                // fetch runs, which then replaces them
+                if let Some(index) = event_index.as_ref() {
+                    restore_from_index(index, app_state, &new_range);
                 }
+                app_state.update_date_range(new_range.clone());
+                app_state.evict_distant_events();
+
+                *data_loader = Some(DataLoader::new(
+                    client,
+                    new_range,
+                    app_state.only_calendars.clone(),
+                    app_state.read_only,
+                    app_state.settings.clone(),
+                ));
+                needs_redraw = true;
             }
+        }
 
-            // Render status bar at the bottom
-            render_status_bar(f, app_state);
-        })?;
+        // Periodic refresh (`--refresh-interval`) of the range already loaded,
+        // which also sends changes queued while offline
+        if data_loader.is_none()
+            && loader::refresh_due(
+                app_state.settings.refresh_interval,
+                last_loaded,
+                Instant::now(),
+            )
+        {
+            if let Some(client) = available_client.take() {
+                tracing::debug!("Refreshing events periodically");
+                *data_loader = Some(DataLoader::new(
+                    client,
+                    app_state.current_date_range.clone(),
+                    app_state.only_calendars.clone(),
+                    app_state.read_only,
+                    app_state.settings.clone(),
+                ));
+                needs_redraw = true;
+            }
+        }
+    }
 
-        // Handle input (non-blocking with timeout)
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match handle_key_event(key, app_state) {
-                    InputAction::Quit => break,
-                    InputAction::Refresh => {
-                        // Manual refresh: refetch current date range
-                        if data_loader.is_none() && available_client.is_some() {
-                            let new_range = DateRange::five_month_span(app_state.selected_date);
-                            app_state.update_date_range(new_range.clone());
-
-                            let client = available_client.take().unwrap();
-                            *data_loader = Some(DataLoader::new(client, new_range));
-                        }
+    Ok(())
+}
+
+/// Bring keyword matches from the local index into the cache, so the filtered
+/// calendar also shows them on days that were never fetched this session
+fn search_index(index: &EventIndex, app_state: &mut AppState) {
+    let keyword = app_state.filter.keyword.clone();
+    match index.search(
+        &keyword,
+        chrono::DateTime::<chrono::Utc>::MIN_UTC,
+        chrono::DateTime::<chrono::Utc>::MAX_UTC,
+        INDEX_SEARCH_LIMIT,
+    ) {
+        Ok(events) => {
+            let found = events.len();
+            let added = app_state.merge_indexed_events(events);
+            app_state.show_toast(format!(
+                "{} indexed matches, {} outside the loaded dates",
+                found, added
+            ));
+        }
+        Err(e) => tracing::warn!("Index search failed: {:#}", e),
+    }
+}
+
+/// Reload the indexed events of `range` that are not in memory, e.g. of
+/// months evicted since they were last shown
+fn restore_from_index(index: &EventIndex, app_state: &mut AppState, range: &DateRange) {
+    match index.events_between(
+        local_midnight(range.start),
+        local_midnight(range.end + chrono::Duration::days(1)),
+    ) {
+        Ok(events) => {
+            app_state.merge_indexed_events(events);
+        }
+        Err(e) => tracing::warn!("Could not read events from the index: {:#}", e),
+    }
+}
+
+/// Due notifications are consumed even while muted so unmuting doesn't replay them
+fn notify_due_events(notifier: &mut Notifier, app_state: &AppState, now: DateTime<Local>) {
+    for notification in notifier.take_due(app_state, now) {
+        if app_state.notifications_muted {
+            continue;
+        }
+        if let Err(e) = notify::send(&notification) {
+            tracing::warn!("Could not show notification: {:#}", e);
+        }
+    }
+}
+
+/// Narrowest pane that fits the calendar grid plus borders
+pub const MIN_PANE_WIDTH: u16 = 30;
+/// Calendar grid, its borders and the status bar
+pub const MIN_HEIGHT: u16 = 17;
+/// Below this width the calendar and events are not shown side by side
+const SIDE_BY_SIDE_MIN_WIDTH: u16 = 92;
+const STATUS_BAR_HEIGHT: u16 = 3;
+/// Month grid plus borders
+const CALENDAR_HEIGHT: u16 = 14;
+/// Borders plus at least one calendar
+const MIN_SIDEBAR_HEIGHT: u16 = 3;
+const LOG_PANE_HEIGHT: u16 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayoutMode {
+    /// Calendar and events next to each other
+    SideBySide,
+    /// Only the focused pane, using the full width
+    FocusedPane,
+    /// Nothing fits; ask the user to enlarge the terminal
+    TooSmall,
+}
+
+impl LayoutMode {
+    fn for_area(area: Rect) -> Self {
+        if area.width < MIN_PANE_WIDTH || area.height < MIN_HEIGHT {
+            LayoutMode::TooSmall
+        } else if area.width < SIDE_BY_SIDE_MIN_WIDTH {
+            LayoutMode::FocusedPane
+        } else {
+            LayoutMode::SideBySide
+        }
+    }
+}
+
+/// Perform the side effect of a reduced action; returns true when the app should quit
+fn apply_effect(
+    effect: Effect,
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app_state: &mut AppState,
+    data_loader: &mut Option<DataLoader>,
+    available_client: &mut Option<CalendarClient>,
+    event_index: Option<&EventIndex>,
+    notifier: &mut Notifier,
+) -> bool {
+    match effect {
+        Effect::Quit => return true,
+        Effect::EditSelectedEvent => {
+            if let Err(e) = edit_selected_event(terminal, app_state) {
+                tracing::warn!("Could not edit event: {:#}", e);
+                app_state.show_toast(format!("Not saved: {:#}", e));
+                return false;
+            }
+            return apply_effect(
+                Effect::Refresh,
+                terminal,
+                app_state,
+                data_loader,
+                available_client,
+                event_index,
+                notifier,
+            );
+        }
+        Effect::CreateFormEvent
+        | Effect::UpdateFormEvent
+        | Effect::DeleteSelectedEvent
+        | Effect::RespondToSelectedEvent(_) => {
+            let saved = match effect {
+                Effect::CreateFormEvent => create_form_event(app_state),
+                Effect::UpdateFormEvent => update_form_event(app_state),
+                Effect::RespondToSelectedEvent(response) => {
+                    respond_to_selected_event(app_state, response)
+                }
+                _ => delete_selected_event(app_state),
+            };
+            if let Err(e) = saved {
+                tracing::warn!("Could not save event: {:#}", e);
+                app_state.show_toast(format!("Not saved: {:#}", e));
+                return false;
+            }
+            return apply_effect(
+                Effect::Refresh,
+                terminal,
+                app_state,
+                data_loader,
+                available_client,
+                event_index,
+                notifier,
+            );
+        }
+        Effect::Refresh => {
+            // Manual refresh: refetch current date range, and the calendar list with it.
+            // The running fetch may have replayed the queue before the latest
+            // save, so another one follows it.
+            if data_loader.is_some() || available_client.is_none() {
+                app_state.refresh_pending = true;
+            } else {
+                app_state.refresh_pending = false;
+                list_cache::invalidate();
+                let new_range = DateRange::five_month_span(app_state.selected_date);
+                app_state.update_date_range(new_range.clone());
+
+                let client = available_client.take().unwrap();
+                *data_loader = Some(DataLoader::new(
+                    client,
+                    new_range,
+                    app_state.only_calendars.clone(),
+                    app_state.read_only,
+                    app_state.settings.clone(),
+                ));
+            }
+        }
+        Effect::SaveCalendarPrefs => {
+            if let Err(e) = app_state.calendar_prefs().save() {
+                tracing::warn!("Could not save calendar preferences: {:#}", e);
+            }
+        }
+        Effect::ExportSelectedEvent => {
+            let exported = app_state.selected_event().map(super::export::export_event);
+            match exported {
+                Some(Ok(path)) => app_state.show_toast(format!("Saved {}", path.display())),
+                Some(Err(e)) => {
+                    tracing::warn!("Could not export event: {:#}", e);
+                    app_state.show_toast("Export failed, see log (L)");
+                }
+                None => {}
+            }
+        }
+        Effect::OpenSelectedAttachment => {
+            if let Some(attachment) = app_state.selected_attachment() {
+                let name = attachment.name().to_string();
+                match webbrowser::open(&attachment.file_url) {
+                    Ok(()) => app_state.show_toast(format!("Opened {}", name)),
+                    Err(e) => {
+                        tracing::warn!("Could not open attachment: {:#}", e);
+                        app_state.show_toast("Opening failed, see log (L)");
+                    }
+                }
+            }
+        }
+        Effect::JoinSelectedCall => {
+            if let Some(call) = app_state.selected_event().and_then(conference::video_call) {
+                match webbrowser::open(&call.url) {
+                    Ok(()) => app_state.show_toast(format!("Joining {}", call.name())),
+                    Err(e) => {
+                        tracing::warn!("Could not open video call: {:#}", e);
+                        app_state.show_toast("Opening failed, see log (L)");
+                    }
+                }
+            }
+        }
+        Effect::MailSelectedAttendees => {
+            if let Some(link) = app_state.selected_event().and_then(super::export::mailto) {
+                match webbrowser::open(&link) {
+                    Ok(()) => app_state.show_toast("Opened mail draft to attendees"),
+                    Err(e) => {
+                        tracing::warn!("Could not open mail client: {:#}", e);
+                        app_state.show_toast("Opening failed, see log (L)");
                     }
-                    InputAction::None => {}
                 }
             }
         }
+        Effect::SearchIndex => {
+            if let Some(index) = event_index {
+                search_index(index, app_state);
+            }
+        }
+        Effect::SnoozeNotification(minutes) => match notifier.snooze(minutes, Local::now()) {
+            Some(title) => {
+                app_state.show_toast(format!("Snoozed \"{}\" for {} min", title, minutes))
+            }
+            None => app_state.show_toast("No reminder to snooze"),
+        },
+        Effect::None => {}
+    }
 
-        // Auto-refresh date range if needed after navigation
-        if app_state.needs_date_range_refresh()
-            && data_loader.is_none()
-            && available_client.is_some()
-        {
-            let new_range = DateRange::five_month_span(app_state.selected_date);
-            app_state.update_date_range(new_range.clone());
+    false
+}
 
-            let client = available_client.take().unwrap();
-            *data_loader = Some(DataLoader::new(client, new_range));
-        }
+/// Hand the terminal to `$EDITOR` for the selected event and queue the change;
+/// the refresh that follows sends it, or keeps it queued while offline
+fn edit_selected_event(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app_state: &mut AppState,
+) -> Result<()> {
+    let Some(event) = app_state.selected_event().cloned() else {
+        return Ok(());
+    };
+    let calendar_id = event
+        .calendar_id
+        .clone()
+        .context("The event has no calendar")?;
+
+    restore_terminal();
+    let patch = edit::patch_in_editor(&event, app_state.settings.event_defaults.duration);
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    let patch = patch?;
+    if patch == EventPatch::default() {
+        app_state.show_toast("Nothing to change");
+        return Ok(());
     }
+    queue_update(app_state, calendar_id, &event, patch)
+}
+
+/// Queue the changes made in the form and close it, back to the details
+fn update_form_event(app_state: &mut AppState) -> Result<()> {
+    let (event, patch) = app_state.form_patch()?;
+    let calendar_id = event
+        .calendar_id
+        .clone()
+        .context("The event has no calendar")?;
+
+    queue_update(app_state, calendar_id, &event, patch)?;
+    app_state.close_event_form();
+    Ok(())
+}
+
+/// Queue the deletion of the selected event and drop it from the view right
+/// away; the refresh that follows sends it, or keeps it queued while offline
+fn delete_selected_event(app_state: &mut AppState) -> Result<()> {
+    let Some(event) = app_state.selected_event().cloned() else {
+        return Ok(());
+    };
+    let calendar_id = event
+        .calendar_id
+        .clone()
+        .context("The event has no calendar")?;
+
+    let mut queue = MutationQueue::load()?;
+    queue.push(Mutation::Delete {
+        calendar_id: calendar_id.clone(),
+        event_id: event.id.clone(),
+        base: Revision::of(&event),
+    })?;
+    app_state.pending_mutations = queue.len();
+    app_state.remove_event(&calendar_id, &event.id);
+    app_state.unconfirmed_deletes.push(event.clone());
+    app_state.show_toast(format!(
+        "Deleted \"{}\"",
+        event.summary.as_deref().unwrap_or("(No title)")
+    ));
+    Ok(())
+}
+
+/// Queue the user's answer to the selected invitation and show it right away
+fn respond_to_selected_event(app_state: &mut AppState, response: Response) -> Result<()> {
+    let Some(event) = app_state.selected_event().cloned() else {
+        return Ok(());
+    };
+    let calendar_id = event
+        .calendar_id
+        .clone()
+        .context("The event has no calendar")?;
+    let attendees = rsvp::respond(&event, response, None)?;
+
+    let patch = EventPatch {
+        attendees: Some(attendees.clone()),
+        ..EventPatch::default()
+    };
+    queue_update(app_state, calendar_id.clone(), &event, patch)?;
+    app_state.update_attendees(&calendar_id, &event.id, &attendees);
+    app_state.show_toast(format!(
+        "Responded {} to \"{}\"",
+        response.status(),
+        event.summary.as_deref().unwrap_or("(No title)")
+    ));
+    Ok(())
+}
+
+/// Queue `patch` to `event`; the refresh that follows sends it, or keeps it
+/// queued while offline
+fn queue_update(
+    app_state: &mut AppState,
+    calendar_id: String,
+    event: &models::Event,
+    patch: EventPatch,
+) -> Result<()> {
+    let title = patch
+        .summary
+        .clone()
+        .or_else(|| event.summary.clone())
+        .unwrap_or_else(|| "(No title)".to_string());
+
+    let mut queue = MutationQueue::load()?;
+    queue.push(Mutation::Update {
+        calendar_id,
+        event_id: event.id.clone(),
+        patch,
+        base: Revision::of(event),
+    })?;
+    app_state.pending_mutations = queue.len();
+    app_state.show_toast(format!("Saving \"{}\"", title));
+    Ok(())
+}
+
+/// Queue the event typed into the form and close it; the refresh that
+/// follows sends it, or keeps it queued while offline
+fn create_form_event(app_state: &mut AppState) -> Result<()> {
+    let (calendar_id, event) = app_state.form_event()?;
+    let title = event.summary.clone().unwrap_or_default();
 
+    let mut queue = MutationQueue::load()?;
+    queue.push(Mutation::Create { calendar_id, event })?;
+    app_state.pending_mutations = queue.len();
+    app_state.close_event_form();
+    app_state.show_toast(format!("Saving \"{}\"", title));
     Ok(())
 }
 
-fn render_status_bar(f: &mut ratatui::Frame, app_state: &AppState) {
-    let status_area = Rect {
-        x: 0,
-        y: f.area().height.saturating_sub(3),
-        width: f.area().width,
-        height: 3,
+/// Whether the gap since the previous tick is too long to be normal scheduling
+fn resumed_from_sleep(last_tick_at: DateTime<Local>, now: DateTime<Local>) -> bool {
+    now - last_tick_at >= WAKE_GAP
+}
+
+/// Clocks, countdowns and "in 5m" labels only change with the minute
+fn minute_changed(last_tick_at: DateTime<Local>, now: DateTime<Local>) -> bool {
+    last_tick_at.timestamp().div_euclid(60) != now.timestamp().div_euclid(60)
+}
+
+fn render_ui(f: &mut ratatui::Frame, app_state: &mut AppState, log: &LogBuffer) {
+    let area = f.area();
+
+    let mode = LayoutMode::for_area(area);
+    if mode == LayoutMode::TooSmall {
+        render_too_small(f, area);
+        return;
+    }
+
+    let log_height = if app_state.show_log {
+        LOG_PANE_HEIGHT
+    } else {
+        0
+    };
+    let [main_area, log_area, status_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(log_height),
+            Constraint::Length(STATUS_BAR_HEIGHT),
+        ])
+        .areas(area);
+
+    match mode {
+        LayoutMode::SideBySide => {
+            let [calendar_area, events_area] = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(33), Constraint::Percentage(67)])
+                .areas(main_area);
+
+            render_calendar_column(f, app_state, calendar_area);
+            render_events_pane(f, app_state, events_area);
+        }
+        LayoutMode::FocusedPane => match app_state.view_focus {
+            ViewFocus::Calendar => f.render_widget(CalendarWidget::new(app_state), main_area),
+            ViewFocus::Events => render_events_pane(f, app_state, main_area),
+            ViewFocus::Sidebar => f.render_widget(CalendarSidebarWidget::new(app_state), main_area),
+        },
+        LayoutMode::TooSmall => unreachable!("handled above"),
+    }
+
+    if app_state.show_log {
+        render_log_pane(f, log, log_area);
+    }
+
+    render_status_bar(f, app_state, status_area);
+
+    if app_state.show_stats {
+        render_stats(f, app_state, area);
+    }
+
+    if let Some(ref toast) = app_state.toast {
+        render_toast(f, &toast.message, main_area);
+    }
+
+    if app_state.confirming_quit {
+        render_quit_confirmation(f, app_state, area);
+    }
+
+    if app_state.confirming_delete {
+        render_delete_confirmation(f, app_state, area);
+    }
+}
+
+/// A one-line box in the bottom right corner of `area`
+fn render_toast(f: &mut ratatui::Frame, message: &str, area: Rect) {
+    let width = (message.chars().count() as u16 + 4).min(area.width);
+    let height = 3.min(area.height);
+    let popup = Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height - height,
+        width,
+        height,
     };
+    let toast = Paragraph::new(message).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(toast, popup);
+}
+
+fn render_stats(f: &mut ratatui::Frame, app_state: &AppState, area: Rect) {
+    let lines: Vec<Line> = app_state
+        .stats_lines()
+        .into_iter()
+        .map(Line::from)
+        .collect();
+
+    let popup = centered_rect(area, 40, lines.len() as u16 + 2);
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Sync statistics (i to close) "),
+    );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(panel, popup);
+}
+
+fn render_quit_confirmation(f: &mut ratatui::Frame, app_state: &AppState, area: Rect) {
+    let mut lines: Vec<Line> = app_state
+        .unsaved_changes()
+        .into_iter()
+        .map(Line::from)
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("y", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Quit anyway | "),
+        Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Cancel"),
+    ]));
+
+    let popup = centered_rect(area, 44, lines.len() as u16 + 2);
+    let dialog = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(" Discard changes / Quit anyway? "),
+        );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(dialog, popup);
+}
+
+fn render_delete_confirmation(f: &mut ratatui::Frame, app_state: &AppState, area: Rect) {
+    let title = app_state
+        .selected_event()
+        .and_then(|event| event.summary.as_deref())
+        .unwrap_or("(No title)");
+    let lines = vec![
+        Line::from(format!("\"{}\"", title)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Delete | "),
+            Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let popup = centered_rect(area, 44, lines.len() as u16 + 2);
+    let dialog = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" Delete event? "),
+        );
+
+    f.render_widget(Clear, popup);
+    f.render_widget(dialog, popup);
+}
+
+/// A `width` x `height` rectangle centered in `area`, clamped to fit
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Month calendar with the calendar list below it when there is room
+fn render_calendar_column(f: &mut ratatui::Frame, app_state: &AppState, area: Rect) {
+    let sidebar_height = area.height.saturating_sub(CALENDAR_HEIGHT);
+    if !app_state.show_sidebar
+        || app_state.calendars.is_empty()
+        || sidebar_height < MIN_SIDEBAR_HEIGHT
+    {
+        f.render_widget(CalendarWidget::new(app_state), area);
+        return;
+    }
+
+    let [calendar_area, sidebar_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(CALENDAR_HEIGHT), Constraint::Min(0)])
+        .areas(area);
+
+    f.render_widget(CalendarWidget::new(app_state), calendar_area);
+    f.render_widget(CalendarSidebarWidget::new(app_state), sidebar_area);
+}
+
+fn render_log_pane(f: &mut ratatui::Frame, log: &LogBuffer, area: Rect) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = log
+        .recent(visible)
+        .into_iter()
+        .map(|entry| {
+            let level_style = match entry.level {
+                Level::ERROR => Style::default().fg(Color::Red),
+                Level::WARN => Style::default().fg(Color::Yellow),
+                _ => Style::default().fg(Color::DarkGray),
+            };
+            Line::from(vec![
+                Span::raw(entry.time.format("%H:%M:%S ").to_string()),
+                Span::styled(format!("{:<5} ", entry.level), level_style),
+                Span::raw(entry.message),
+            ])
+        })
+        .collect();
+
+    let pane = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Log (L to close) "),
+    );
+
+    f.render_widget(pane, area);
+}
+
+fn render_events_pane(f: &mut ratatui::Frame, app_state: &mut AppState, area: Rect) {
+    match app_state.events_view_mode {
+        EventsViewMode::List => match app_state.list_layout {
+            ListLayout::Day => {
+                let events_widget = EventListWidget::new(app_state);
+                f.render_widget(events_widget, area);
+            }
+            ListLayout::Timeline => f.render_widget(TimelineWidget::new(app_state), area),
+            ListLayout::Week => f.render_widget(WeekWidget::new(app_state), area),
+            ListLayout::Agenda => f.render_widget(AgendaWidget::new(app_state), area),
+        },
+        EventsViewMode::Details {
+            event_index,
+            scroll_offset,
+            ..
+        } => {
+            let details_widget =
+                EventDetailsWidget::new(&mut *app_state, event_index, scroll_offset);
+            f.render_widget(details_widget, area);
+        }
+        EventsViewMode::Create | EventsViewMode::Edit { .. } => {
+            f.render_widget(EventFormWidget::new(app_state), area)
+        }
+    }
+}
+
+fn render_too_small(f: &mut ratatui::Frame, area: Rect) {
+    let message = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "{}x{}, need {}x{}",
+            area.width, area.height, MIN_PANE_WIDTH, MIN_HEIGHT
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+
+    f.render_widget(message, area);
+}
 
-    let status_text = if app_state.loading {
-        vec![Line::from(Span::styled(
-            "Loading calendars and events...",
+fn render_status_bar(f: &mut ratatui::Frame, app_state: &AppState, status_area: Rect) {
+    let mut status_line = if app_state.editing_keyword {
+        Line::from(vec![
+            Span::raw("Filter: "),
+            Span::styled(
+                format!("{}_", app_state.filter.keyword),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(" | "),
+            Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Apply | "),
+            Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Clear"),
+        ])
+    } else if app_state.loading {
+        Line::from(Span::styled(
+            app_state.loading_message(),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        ))]
+        ))
     } else if let Some(ref error) = app_state.error {
-        vec![Line::from(Span::styled(
-            format!("Error: {}", error),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ))]
+        Line::from(vec![
+            Span::styled(
+                format!("Error: {}", error),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                "R",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Retry | "),
+            Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Quit"),
+        ])
     } else {
         // Show different hints based on focus and mode
         match (app_state.view_focus, app_state.events_view_mode) {
-            (ViewFocus::Calendar, _) => {
-                vec![Line::from(vec![
-                    Span::raw("Keys: "),
-                    Span::styled("←→↑↓", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Navigate | "),
-                    Span::styled("t", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Today | "),
-                    Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Switch View | "),
-                    Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Refresh | "),
-                    Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Quit"),
-                ])]
-            }
-            (ViewFocus::Events, EventsViewMode::List) => {
-                vec![Line::from(vec![
-                    Span::raw("Keys: "),
-                    Span::styled(
-                        "\u{2191}\u{2193}",
-                        Style::default().add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(" Select | "),
-                    Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Details | "),
-                    Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Switch View | "),
-                    Span::styled("t", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Today | "),
-                    Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Quit"),
-                ])]
-            }
-            (ViewFocus::Events, EventsViewMode::Details { .. }) => {
-                vec![Line::from(vec![
-                    Span::raw("Keys: "),
-                    Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Back to List | "),
-                    Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Switch View | "),
-                    Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Quit"),
-                ])]
-            }
+            (ViewFocus::Calendar, _) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled("←→↑↓", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Navigate | "),
+                Span::styled("t", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Today | "),
+                Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" New | "),
+                Span::styled("v", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Timeline | "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Switch View | "),
+                Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Refresh | "),
+                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Quit"),
+            ]),
+            (ViewFocus::Events, EventsViewMode::Create) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Next Field | "),
+                Span::styled("←→", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Calendar | "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Save | "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Cancel"),
+            ]),
+            (ViewFocus::Events, EventsViewMode::Edit { .. }) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Next Field | "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Save | "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Cancel"),
+            ]),
+            (ViewFocus::Events, EventsViewMode::List) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled(
+                    "\u{2191}\u{2193}",
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Select | "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Details | "),
+                Span::styled("J", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Join Call | "),
+                Span::styled("m", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Mail Attendees | "),
+                Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" New | "),
+                Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Delete | "),
+                Span::styled("v", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Timeline | "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Switch View | "),
+                Span::styled("t", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Today | "),
+                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Quit"),
+            ]),
+            (ViewFocus::Sidebar, _) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled("↑↓", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Select | "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Show/Hide | "),
+                Span::styled("J/K", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Reorder | "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Switch View | "),
+                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Quit"),
+            ]),
+            (ViewFocus::Events, EventsViewMode::Details { .. }) => Line::from(vec![
+                Span::raw("Keys: "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Back to List | "),
+                Span::styled("x", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Export .ics | "),
+                Span::styled("e", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Edit | "),
+                Span::styled("E", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Edit in $EDITOR | "),
+                Span::styled("a/t/d", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Accept/Maybe/Decline | "),
+                Span::styled("n/o", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Attachment | "),
+                Span::styled("J", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Join Call | "),
+                Span::styled("m", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Mail Attendees | "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Switch View | "),
+                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Quit"),
+            ]),
         }
     };
 
+    if !app_state.loading && app_state.error.is_none() {
+        if let Some(countdown) = next_event_countdown(&app_state.upcoming(10), Local::now()) {
+            status_line.spans.push(Span::styled(
+                format!(" | Next: {}", countdown),
+                Style::default().fg(Color::Green),
+            ));
+        }
+    }
+
+    if !app_state.loading {
+        if let Some(last_sync) = app_state.last_sync {
+            status_line.spans.push(Span::styled(
+                format!(
+                    " | Last updated {}",
+                    last_sync.format(&app_state.settings.formats.time)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+
+    if let Some(account) = crate::auth::account::selected() {
+        status_line.spans.push(Span::styled(
+            format!(" | Account: {}", account),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    if app_state.notifications_muted {
+        status_line
+            .spans
+            .push(Span::styled(" | Muted", Style::default().fg(Color::Yellow)));
+    }
+
+    if app_state.read_only {
+        status_line.spans.push(Span::styled(
+            " | Read-only",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    if !app_state.typed_day.is_empty() {
+        status_line.spans.push(Span::styled(
+            format!(" | Go to day {}_ (Enter)", app_state.typed_day),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if !app_state.pending_keys.is_empty() {
+        status_line.spans.push(Span::styled(
+            format!(" | {} …", display_keys(&app_state.pending_keys)),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if !app_state.editing_keyword {
+        if let Some(summary) = app_state.filter.summary() {
+            status_line.spans.push(Span::styled(
+                format!(" | Filter: {}", summary),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+    }
+
+    if app_state.pending_mutations > 0 {
+        status_line.spans.push(Span::styled(
+            format!(" | {} pending offline", app_state.pending_mutations),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
     let status_block = Block::default().borders(Borders::TOP).title(" Status ");
 
-    let status_paragraph = Paragraph::new(status_text).block(status_block);
+    let mut status_lines = vec![status_line];
+
+    if !app_state.loading {
+        if let Some(summary) = app_state.failure_summary() {
+            status_lines.push(Line::from(vec![
+                Span::styled(summary, Style::default().fg(Color::Yellow)),
+                Span::raw(" | "),
+                Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" Retry"),
+            ]));
+        }
+    }
+
+    let status_paragraph = Paragraph::new(status_lines).block(status_block);
 
     f.render_widget(status_paragraph, status_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resumed_from_sleep() {
+        let last = Local::now();
+
+        assert!(!resumed_from_sleep(
+            last,
+            last + chrono::Duration::seconds(2)
+        ));
+        assert!(resumed_from_sleep(last, last + chrono::Duration::hours(8)));
+    }
+
+    #[test]
+    fn test_minute_changed() {
+        let at = |time: &str| {
+            DateTime::parse_from_rfc3339(time)
+                .unwrap()
+                .with_timezone(&Local)
+        };
+
+        assert!(!minute_changed(
+            at("2025-06-16T09:30:00Z"),
+            at("2025-06-16T09:30:59Z")
+        ));
+        assert!(minute_changed(
+            at("2025-06-16T09:30:59Z"),
+            at("2025-06-16T09:31:00Z")
+        ));
+    }
+
+    #[test]
+    fn test_centered_rect_clamps_to_area() {
+        let area = Rect::new(0, 0, 100, 30);
+        assert_eq!(centered_rect(area, 40, 10), Rect::new(30, 10, 40, 10));
+
+        let small = Rect::new(0, 0, 20, 5);
+        assert_eq!(centered_rect(small, 40, 10), small);
+    }
+
+    #[test]
+    fn test_layout_mode_side_by_side_on_wide_terminal() {
+        assert_eq!(
+            LayoutMode::for_area(Rect::new(0, 0, 120, 40)),
+            LayoutMode::SideBySide
+        );
+    }
+
+    #[test]
+    fn test_layout_mode_focused_pane_on_narrow_terminal() {
+        assert_eq!(
+            LayoutMode::for_area(Rect::new(0, 0, 60, 40)),
+            LayoutMode::FocusedPane
+        );
+    }
+
+    #[test]
+    fn test_layout_mode_too_small() {
+        assert_eq!(
+            LayoutMode::for_area(Rect::new(0, 0, 29, 40)),
+            LayoutMode::TooSmall
+        );
+        assert_eq!(
+            LayoutMode::for_area(Rect::new(0, 0, 120, 16)),
+            LayoutMode::TooSmall
+        );
+    }
+}
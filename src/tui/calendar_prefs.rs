@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::auth::account;
+
+/// User-defined calendar ordering and visibility, persisted between sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CalendarPrefs {
+    /// Calendar ids in display order; calendars not listed follow alphabetically
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Calendar ids whose events are hidden
+    #[serde(default)]
+    pub hidden: Vec<String>,
+    /// Calendar ids already seen, whose visibility is up to `hidden` rather
+    /// than Google's `selected` flag
+    #[serde(default)]
+    pub seen: Vec<String>,
+}
+
+impl CalendarPrefs {
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::get_storage_path()?)
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(path).context("Failed to read calendar preferences")?;
+        serde_json::from_str(&json).context("Failed to deserialize calendar preferences")
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::get_storage_path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize calendar preferences")?;
+        fs::write(path, json).context("Failed to write calendar preferences")?;
+
+        Ok(())
+    }
+
+    /// Calendar ids differ per account, so each has its own preferences
+    fn get_storage_path() -> Result<PathBuf> {
+        Ok(account::data_dir()?.join("calendars.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_missing_file_returns_defaults() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let prefs = CalendarPrefs::load_from(&temp_dir.path().join("calendars.json")).unwrap();
+
+        assert_eq!(prefs, CalendarPrefs::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("oxidate").join("calendars.json");
+        let prefs = CalendarPrefs {
+            order: vec!["work".to_string(), "family".to_string()],
+            hidden: vec!["holidays".to_string()],
+            seen: vec!["holidays".to_string(), "work".to_string()],
+        };
+
+        prefs.save_to(&path).unwrap();
+        let loaded = CalendarPrefs::load_from(&path).unwrap();
+
+        assert_eq!(loaded, prefs);
+    }
+
+    #[test]
+    fn test_load_tolerates_missing_fields() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("calendars.json");
+        fs::write(&path, r#"{"hidden": ["holidays"]}"#).unwrap();
+
+        let prefs = CalendarPrefs::load_from(&path).unwrap();
+
+        assert!(prefs.order.is_empty());
+        assert!(prefs.seen.is_empty());
+        assert_eq!(prefs.hidden, vec!["holidays".to_string()]);
+    }
+}
@@ -1,4 +1,45 @@
+use anyhow::{bail, Result};
 use ratatui::style::Color;
+use std::collections::HashMap;
+
+use crate::calendar::models::Calendar;
+
+/// Check calendar color overrides, which must each be something `parse_color`
+/// understands
+pub fn validate_color_overrides(colors: &HashMap<String, String>) -> Result<()> {
+    for (calendar, color) in colors {
+        if parse_color(color).is_none() {
+            bail!(
+                "Invalid color \"{}\" for {}, expected e.g. #ff8800, red or 214",
+                color,
+                calendar
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Replace the colors of calendars named in `overrides`; an id match wins
+/// over a case-insensitive name match
+pub fn apply_color_overrides(calendars: &mut [Calendar], overrides: &HashMap<String, String>) {
+    for calendar in calendars {
+        let color = overrides.get(&calendar.id).or_else(|| {
+            overrides
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&calendar.summary))
+                .map(|(_, color)| color)
+        });
+        if let Some(color) = color {
+            calendar.background_color = Some(color.clone());
+        }
+    }
+}
+
+/// A calendar color: `#RRGGBB` as Google sends it, or a terminal color name
+/// or 256-color index (`red`, `lightblue`, `214`) from the overrides
+pub fn parse_color(value: &str) -> Option<Color> {
+    parse_hex_color(value).or_else(|| value.parse().ok())
+}
 
 /// Parse hex color string (#RRGGBB) to ratatui Color
 /// Returns None if invalid format
@@ -52,6 +93,49 @@ mod tests {
         assert_eq!(parse_hex_color(""), None);
     }
 
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#0088aa"), Some(Color::Rgb(0, 136, 170)));
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color("214"), Some(Color::Indexed(214)));
+        assert_eq!(parse_color("mauve-ish"), None);
+    }
+
+    #[test]
+    fn test_recolor_by_id_or_name() {
+        let calendar = |id: &str, summary: &str| Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: Some("#9fe1e7".to_string()),
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        };
+        let mut calendars = vec![
+            calendar("team@group.calendar.google.com", "Team"),
+            calendar("me@example.com", "Me"),
+            calendar("other", "Other"),
+        ];
+        let overrides = HashMap::from([
+            (
+                "team@group.calendar.google.com".to_string(),
+                "214".to_string(),
+            ),
+            ("me".to_string(), "#ff8800".to_string()),
+        ]);
+
+        apply_color_overrides(&mut calendars, &overrides);
+
+        let colors: Vec<Option<&str>> = calendars
+            .iter()
+            .map(|calendar| calendar.background_color.as_deref())
+            .collect();
+        assert_eq!(colors, vec![Some("214"), Some("#ff8800"), Some("#9fe1e7")]);
+    }
+
     #[test]
     fn test_default_event_color_is_gray() {
         assert_eq!(default_event_color(), Color::Gray);
@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::calendar::ics;
+use crate::calendar::models::Event;
+
+/// Write `event` as an .ics file to the downloads folder (home folder as a
+/// fallback) without overwriting anything; returns the path written
+pub fn export_event(event: &Event) -> Result<PathBuf> {
+    let dir = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .context("Failed to find a downloads or home directory")?;
+    let contents = ics::write_event(event, Utc::now())?;
+
+    let path = unused_path(&dir, &file_stem(event.summary.as_deref()));
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The title reduced to characters that are safe in file names everywhere
+fn file_stem(summary: Option<&str>) -> String {
+    let stem: String = summary
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if stem.is_empty() {
+        "event".to_string()
+    } else {
+        stem
+    }
+}
+
+/// A `mailto:` link to everyone else invited to `event` who hasn't declined,
+/// with its title as the subject; None when there is nobody to write to
+pub fn mailto(event: &Event) -> Option<String> {
+    let recipients: Vec<String> = event
+        .attendees
+        .iter()
+        .flatten()
+        .filter(|attendee| attendee.is_self != Some(true))
+        .filter(|attendee| attendee.response_status.as_deref() != Some("declined"))
+        // Rooms are invited like people but have no one reading their mail
+        .filter(|attendee| !attendee.email.ends_with("@resource.calendar.google.com"))
+        .map(|attendee| percent_encode(&attendee.email))
+        .collect();
+    if recipients.is_empty() {
+        return None;
+    }
+
+    let subject = event.summary.as_deref().unwrap_or_default();
+    Some(format!(
+        "mailto:{}?subject={}",
+        recipients.join(","),
+        percent_encode(subject)
+    ))
+}
+
+/// Mail clients expect `%20` rather than `+` for spaces in mailto links
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// `stem.ics`, or `stem-2.ics` and so on when taken
+fn unused_path(dir: &Path, stem: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.ics", stem));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.ics", stem, n));
+        n += 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem(Some("Dentist: check-up")), "Dentist-check-up");
+        assert_eq!(file_stem(Some("1:1 w/ Ana")), "1-1-w-Ana");
+        assert_eq!(file_stem(Some("  ")), "event");
+        assert_eq!(file_stem(None), "event");
+    }
+
+    #[test]
+    fn test_mailto_skips_self_declined_and_rooms() {
+        use crate::calendar::models::{Attendee, EventDateTime};
+
+        let attendee = |email: &str, status: &str, is_self: bool| Attendee {
+            email: email.to_string(),
+            display_name: None,
+            response_status: Some(status.to_string()),
+            optional: None,
+            is_self: is_self.then_some(true),
+            comment: None,
+        };
+        let at = EventDateTime {
+            date_time: Some("2025-06-16T09:00:00Z".to_string()),
+            date: None,
+            time_zone: None,
+        };
+        let mut event = Event {
+            id: "sync".to_string(),
+            attendees: Some(vec![
+                attendee("me@example.com", "accepted", true),
+                attendee("ana+team@example.com", "accepted", false),
+                attendee("bo@example.com", "needsAction", false),
+                attendee("cy@example.com", "declined", false),
+                attendee("c_123@resource.calendar.google.com", "accepted", false),
+            ]),
+            ..Event::test("Sync & plan: Q3", at.clone(), at)
+        };
+
+        assert_eq!(
+            mailto(&event).unwrap(),
+            "mailto:ana%2Bteam@example.com,bo@example.com?subject=Sync%20%26%20plan%3A%20Q3"
+        );
+
+        event.attendees = Some(vec![attendee("me@example.com", "accepted", true)]);
+        assert_eq!(mailto(&event), None);
+    }
+
+    #[test]
+    fn test_unused_path_does_not_overwrite() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let first = unused_path(temp_dir.path(), "Standup");
+        assert_eq!(first, temp_dir.path().join("Standup.ics"));
+
+        fs::write(&first, "").unwrap();
+        assert_eq!(
+            unused_path(temp_dir.path(), "Standup"),
+            temp_dir.path().join("Standup-2.ics")
+        );
+    }
+}
@@ -1,61 +1,230 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 #[allow(unused_imports)]
 use chrono::Timelike;
-use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
 
+use super::color_utils::apply_color_overrides;
 use super::state::DateRange;
 use crate::calendar::client::CalendarClient;
+use crate::calendar::index::EventIndex;
+use crate::calendar::list_cache;
+use crate::calendar::local::Source;
 use crate::calendar::models::{Calendar, Event};
+use crate::settings::Settings;
 
+/// Calendars whose events are requested at once; more mostly trips the
+/// API's rate limits
+const MAX_CONCURRENT_FETCHES: usize = 5;
+
+/// A calendar whose events could not be fetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedCalendar {
+    pub id: String,
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Debug)]
+pub struct CalendarData {
+    pub calendars: Vec<Calendar>,
+    pub failed: Vec<FailedCalendar>,
+}
+
+/// Fetch calendars and their events for the date range, limited to the calendars
+/// named in `only` (by id or name) unless it is empty. The local calendars,
+/// color overrides and index come from `settings`.
+/// Up to `MAX_CONCURRENT_FETCHES` calendars are fetched at a time, and
+/// `on_calendar` is called as soon as each calendar's request completes with
+/// (calendar, events, done, total); `events` is None if that calendar failed, in
+/// which case it is also reported in `failed` instead of failing the whole fetch.
 pub async fn fetch_calendar_data(
     client: &mut CalendarClient,
     date_range: DateRange,
-) -> Result<(Vec<Calendar>, HashMap<NaiveDate, Vec<Event>>)> {
-    // Fetch all calendars
-    let calendars = client
-        .list_calendars()
+    only: &[String],
+    settings: &Settings,
+    mut on_calendar: impl FnMut(&Calendar, Option<HashMap<NaiveDate, Vec<Event>>>, usize, usize),
+) -> Result<CalendarData> {
+    // Fetch all calendars (or reuse the cached list), followed by the local ones from files
+    let mut calendars = list_cache::calendars(client)
         .await
         .context("Failed to fetch calendars")?;
+    let local_sources = &settings.local_sources;
+    calendars.extend(local_sources.iter().map(Source::calendar));
+    apply_color_overrides(&mut calendars, &settings.calendar_colors);
+    let calendars = select_calendars(calendars, only)?;
 
-    // Convert date range to DateTime<Utc>
-    let time_min = date_to_utc(date_range.start);
-    let time_max = date_to_utc(date_range.end);
+    // The range covers whole local days, its last day included
+    let time_min = local_midnight(date_range.start);
+    let time_max = local_midnight(date_range.end + chrono::Duration::days(1));
 
-    // Fetch events from all calendars
-    let mut all_events_by_date: HashMap<NaiveDate, Vec<Event>> = HashMap::new();
+    // The local index is a cache: failing to use it never fails the fetch
+    let mut event_index = EventIndex::open(settings.index).unwrap_or_else(|e| {
+        tracing::warn!("Event index unavailable: {:#}", e);
+        None
+    });
 
-    for calendar in &calendars {
-        match client.list_events(&calendar.id, time_min, time_max).await {
+    // Fetch events from all calendars, each request on its own fork of the client
+    let mut failed = Vec::new();
+    // (owned, so the requests' futures stay Send for tokio::spawn)
+    let forks: Vec<_> = calendars
+        .iter()
+        .enumerate()
+        .map(|(index, calendar)| (index, calendar.id.clone(), client.fork()))
+        .collect();
+    let mut fetches = stream::iter(forks)
+        .map(|(index, id, mut fork)| async move {
+            // Google returns events in start order; local files are in any order
+            let result = match local_sources.iter().find(|source| source.id() == id) {
+                Some(source) => source.load_events(time_min, time_max).map(|mut events| {
+                    events.sort_by_cached_key(Event::start_order);
+                    events
+                }),
+                None => fork.list_events(&id, time_min, time_max).await,
+            };
+            (index, result, fork)
+        })
+        .buffer_unordered(MAX_CONCURRENT_FETCHES);
+
+    let mut done = 0;
+    while let Some((index, result, fork)) = fetches.next().await {
+        client.join(fork);
+        done += 1;
+        let calendar = &calendars[index];
+        let events = match result {
             Ok(events) => {
-                for mut event in events {
-                    event.calendar_id = Some(calendar.id.clone());
-                    if let Some(date) = extract_date_from_event(&event) {
-                        all_events_by_date.entry(date).or_default().push(event);
+                if let Some(ref mut event_index) = event_index {
+                    if let Err(e) =
+                        event_index.store_range(&calendar.id, time_min, time_max, &events)
+                    {
+                        tracing::warn!(
+                            calendar = calendar.summary.as_str(),
+                            "Failed to index events: {:#}",
+                            e
+                        );
                     }
                 }
+                Some(group_by_date(calendar, events))
             }
-            Err(_) => {
-                // TODO: Log error
+            Err(e) => {
+                tracing::warn!(
+                    calendar = calendar.summary.as_str(),
+                    "Failed to load events: {:#}",
+                    e
+                );
+                failed.push(FailedCalendar {
+                    id: calendar.id.clone(),
+                    name: calendar.summary.clone(),
+                    error: format!("{:#}", e),
+                });
+                None
             }
+        };
+
+        on_calendar(calendar, events, done, calendars.len());
+    }
+
+    Ok(CalendarData { calendars, failed })
+}
+
+/// Keep the calendars whose id or name (case-insensitive) is in `only`; all when empty
+fn select_calendars(calendars: Vec<Calendar>, only: &[String]) -> Result<Vec<Calendar>> {
+    if only.is_empty() {
+        return Ok(calendars);
+    }
+
+    let is_selected = |calendar: &Calendar, name: &String| {
+        calendar.id == *name || calendar.summary.eq_ignore_ascii_case(name)
+    };
+    for name in only {
+        if !calendars.iter().any(|calendar| is_selected(calendar, name)) {
+            tracing::warn!(calendar = name.as_str(), "No such calendar");
+        }
+    }
+
+    let selected: Vec<Calendar> = calendars
+        .into_iter()
+        .filter(|calendar| only.iter().any(|name| is_selected(calendar, name)))
+        .collect();
+    if selected.is_empty() {
+        bail!("None of the calendars {} exist", only.join(", "));
+    }
+
+    Ok(selected)
+}
+
+fn group_by_date(calendar: &Calendar, events: Vec<Event>) -> HashMap<NaiveDate, Vec<Event>> {
+    let mut events_by_date: HashMap<NaiveDate, Vec<Event>> = HashMap::new();
+
+    for mut event in events {
+        event.calendar_id = Some(calendar.id.clone());
+        let dates = event_dates(&event);
+        if dates.is_empty() {
+            tracing::warn!(
+                calendar = calendar.summary.as_str(),
+                event = event.id.as_str(),
+                "Skipping event with unparseable start time"
+            );
+        }
+        for date in dates {
+            events_by_date.entry(date).or_default().push(event.clone());
         }
     }
 
-    Ok((calendars, all_events_by_date))
+    events_by_date
+}
+
+/// The start of `date` in the local time zone, as a UTC bound for the API;
+/// where DST skips midnight, the first hour that exists
+pub(crate) fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| {
+            Local
+                .from_local_datetime(&(midnight + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map_or_else(|| midnight.and_utc(), |start| start.with_timezone(&Utc))
 }
 
-fn date_to_utc(date: NaiveDate) -> DateTime<Utc> {
-    Utc.with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
-        .single()
-        .expect("Invalid date")
+/// The days `event` is listed on: its start date, or every day an all-day
+/// event covers. All-day end dates are exclusive, so an event from Monday
+/// through Wednesday ends on Thursday.
+pub(crate) fn event_dates(event: &Event) -> Vec<NaiveDate> {
+    let Some(start) = extract_date_from_event(event) else {
+        return Vec::new();
+    };
+    if !event.start.is_all_day() {
+        return vec![start];
+    }
+
+    let end = event
+        .end
+        .date
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+    match end {
+        Some(end) if end > start => start.iter_days().take_while(|date| *date < end).collect(),
+        // A missing or non-exclusive end still lists the start day
+        _ => vec![start],
+    }
 }
 
-fn extract_date_from_event(event: &Event) -> Option<NaiveDate> {
+pub(crate) fn extract_date_from_event(event: &Event) -> Option<NaiveDate> {
+    extract_date_in(event, &Local)
+}
+
+/// Timed events belong to the day they start on in `tz`, not in whatever
+/// offset the API sent them with
+fn extract_date_in<Tz: TimeZone>(event: &Event, tz: &Tz) -> Option<NaiveDate> {
     // Try to extract date from event start time
     if let Some(ref date_time_str) = event.start.date_time {
         // Parse RFC3339 format
         if let Ok(dt) = DateTime::parse_from_rfc3339(date_time_str) {
-            return Some(dt.date_naive());
+            return Some(dt.with_timezone(tz).date_naive());
         }
     }
 
@@ -75,96 +244,184 @@ mod tests {
     use crate::calendar::models::EventDateTime;
 
     #[test]
-    fn test_date_to_utc() {
+    fn test_local_midnight() {
         let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
-        let utc = date_to_utc(date);
+        let local = local_midnight(date).with_timezone(&Local);
 
-        assert_eq!(utc.year(), 2025);
-        assert_eq!(utc.month(), 6);
-        assert_eq!(utc.day(), 15);
-        assert_eq!(utc.hour(), 0);
-        assert_eq!(utc.minute(), 0);
-        assert_eq!(utc.second(), 0);
+        assert_eq!(local.date_naive(), date);
+        assert_eq!(local.hour(), 0);
+        assert_eq!(local.minute(), 0);
+        assert_eq!(local.second(), 0);
     }
 
     #[test]
     fn test_extract_date_from_event_with_datetime() {
         let event = Event {
             id: "test".to_string(),
-            summary: Some("Test Event".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:30:00-05:00".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:30:00-05:00".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Test Event",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:30:00-05:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:30:00-05:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
 
-        let date = extract_date_from_event(&event);
+        let date = extract_date_in(&event, &Utc);
         assert_eq!(date, Some(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()));
     }
 
     #[test]
-    fn test_extract_date_from_event_with_date_only() {
+    fn test_extract_date_from_event_uses_local_day() {
         let event = Event {
             id: "test".to_string(),
-            summary: Some("All-day Event".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Late Call",
+                EventDateTime {
+                    date_time: Some("2025-06-15T23:30:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-16T00:30:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
 
-        let date = extract_date_from_event(&event);
+        // Already the next morning in Tokyo
+        let date = extract_date_in(&event, &chrono_tz::Asia::Tokyo);
+        assert_eq!(date, Some(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap()));
+
+        let date = extract_date_in(&event, &Utc);
         assert_eq!(date, Some(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()));
     }
 
     #[test]
-    fn test_extract_date_from_event_with_invalid_format() {
+    fn test_extract_date_from_event_with_date_only() {
         let event = Event {
             id: "test".to_string(),
-            summary: Some("Invalid Event".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("invalid_date".to_string()),
+            ..Event::test(
+                "All-day Event",
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+            )
+        };
+
+        let date = extract_date_from_event(&event);
+        assert_eq!(date, Some(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()));
+    }
+
+    #[test]
+    fn test_event_dates_end_exclusive() {
+        let event = |start: EventDateTime, end: EventDateTime| Event {
+            summary: None,
+            ..Event::test("test", start, end)
+        };
+        let all_day = |start: &str, end: &str| {
+            event(
+                EventDateTime::from_date(start.parse().unwrap()),
+                EventDateTime::from_date(end.parse().unwrap()),
+            )
+        };
+        let timed = event(
+            EventDateTime {
+                date_time: Some("2025-06-16T10:00:00Z".to_string()),
                 date: None,
                 time_zone: None,
             },
-            end: EventDateTime {
-                date_time: Some("invalid_date".to_string()),
+            EventDateTime {
+                date_time: Some("2025-06-16T11:00:00Z".to_string()),
                 date: None,
                 time_zone: None,
             },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+        );
+        let date = |day: u32| NaiveDate::from_ymd_opt(2025, 6, day).unwrap();
+
+        // Monday through Wednesday
+        assert_eq!(
+            event_dates(&all_day("2025-06-16", "2025-06-19")),
+            vec![date(16), date(17), date(18)]
+        );
+        assert_eq!(
+            event_dates(&all_day("2025-06-16", "2025-06-17")),
+            vec![date(16)]
+        );
+        assert_eq!(
+            event_dates(&all_day("2025-06-16", "2025-06-16")),
+            vec![date(16)]
+        );
+        assert_eq!(event_dates(&timed), vec![date(16)]);
+    }
+
+    #[test]
+    fn test_extract_date_from_event_with_invalid_format() {
+        let event = Event {
+            id: "test".to_string(),
+            ..Event::test(
+                "Invalid Event",
+                EventDateTime {
+                    date_time: Some("invalid_date".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("invalid_date".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
 
         let date = extract_date_from_event(&event);
         assert_eq!(date, None);
     }
+
+    fn calendar(id: &str, summary: &str) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_select_calendars_by_id_or_name() {
+        let calendars = vec![
+            calendar("work-id", "Work"),
+            calendar("personal-id", "Personal"),
+            calendar("holidays-id", "Holidays"),
+        ];
+
+        let all = select_calendars(calendars.clone(), &[]).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let only = ["work".to_string(), "personal-id".to_string()];
+        let selected = select_calendars(calendars.clone(), &only).unwrap();
+        let ids: Vec<&str> = selected.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["work-id", "personal-id"]);
+
+        assert!(select_calendars(calendars, &["nope".to_string()]).is_err());
+    }
 }
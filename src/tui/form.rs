@@ -0,0 +1,414 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use crate::calendar::models::{Event, EventDateTime, EventPatch};
+use crate::commands::add::NewEvent;
+
+/// A field of the event form, in the order Tab moves through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Title,
+    Date,
+    Start,
+    End,
+    Location,
+    Calendar,
+    Description,
+}
+
+impl FormField {
+    pub const ALL: [FormField; 7] = [
+        FormField::Title,
+        FormField::Date,
+        FormField::Start,
+        FormField::End,
+        FormField::Location,
+        FormField::Calendar,
+        FormField::Description,
+    ];
+
+    /// An edited event stays on its calendar, so there is nothing to pick
+    pub const EDIT: [FormField; 6] = [
+        FormField::Title,
+        FormField::Date,
+        FormField::Start,
+        FormField::End,
+        FormField::Location,
+        FormField::Description,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FormField::Title => "Title",
+            FormField::Date => "Date",
+            FormField::Start => "Start",
+            FormField::End => "End",
+            FormField::Location => "Location",
+            FormField::Calendar => "Calendar",
+            FormField::Description => "Description",
+        }
+    }
+
+    /// What the field expects while it is empty
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            FormField::Title => "(required)",
+            FormField::Date => "YYYY-MM-DD",
+            FormField::Start => "HH:MM, empty for all day",
+            FormField::End => "HH:MM, default length if empty",
+            FormField::Location => "(optional)",
+            FormField::Calendar => "",
+            FormField::Description => "(optional)",
+        }
+    }
+}
+
+/// What has been typed into the event form, for a new event or an edited
+/// one. The calendar is picked from the writable calendars rather than typed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventForm {
+    pub title: String,
+    pub date: String,
+    pub start: String,
+    pub end: String,
+    pub location: String,
+    /// Index into `AppState::writable_calendars`
+    pub calendar: usize,
+    pub description: String,
+    pub focused: FormField,
+    /// The event being edited; None for a new one
+    pub original: Option<Event>,
+}
+
+impl EventForm {
+    /// An empty form for an event on `date`
+    pub fn new(date: NaiveDate, calendar: usize) -> Self {
+        Self {
+            title: String::new(),
+            date: date.format("%Y-%m-%d").to_string(),
+            start: String::new(),
+            end: String::new(),
+            location: String::new(),
+            calendar,
+            description: String::new(),
+            focused: FormField::Title,
+            original: None,
+        }
+    }
+
+    /// A form filled in with `event`, to edit it
+    pub fn for_event(event: &Event) -> Self {
+        let start = event.start.to_local();
+        let time = |date_time: &EventDateTime| {
+            if date_time.is_all_day() {
+                return String::new();
+            }
+            date_time
+                .to_local()
+                .map_or_else(String::new, |at| at.format("%H:%M").to_string())
+        };
+        Self {
+            title: event.summary.clone().unwrap_or_default(),
+            date: start.map_or_else(String::new, |at| at.format("%Y-%m-%d").to_string()),
+            start: time(&event.start),
+            end: time(&event.end),
+            location: event.location.clone().unwrap_or_default(),
+            calendar: 0,
+            description: event.description.clone().unwrap_or_default(),
+            focused: FormField::Title,
+            original: Some(event.clone()),
+        }
+    }
+
+    /// The fields Tab moves through
+    pub fn fields(&self) -> &'static [FormField] {
+        match self.original {
+            Some(_) => &FormField::EDIT,
+            None => &FormField::ALL,
+        }
+    }
+
+    /// The text of `field`; None for the calendar, which isn't typed
+    pub fn text(&self, field: FormField) -> Option<&str> {
+        match field {
+            FormField::Title => Some(&self.title),
+            FormField::Date => Some(&self.date),
+            FormField::Start => Some(&self.start),
+            FormField::End => Some(&self.end),
+            FormField::Location => Some(&self.location),
+            FormField::Description => Some(&self.description),
+            FormField::Calendar => None,
+        }
+    }
+
+    fn focused_text_mut(&mut self) -> Option<&mut String> {
+        match self.focused {
+            FormField::Title => Some(&mut self.title),
+            FormField::Date => Some(&mut self.date),
+            FormField::Start => Some(&mut self.start),
+            FormField::End => Some(&mut self.end),
+            FormField::Location => Some(&mut self.location),
+            FormField::Description => Some(&mut self.description),
+            FormField::Calendar => None,
+        }
+    }
+
+    pub fn input(&mut self, c: char) {
+        if let Some(text) = self.focused_text_mut() {
+            text.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(text) = self.focused_text_mut() {
+            text.pop();
+        }
+    }
+
+    /// Move the focus by `delta` fields, wrapping around
+    pub fn move_focus(&mut self, delta: i64) {
+        let fields = self.fields();
+        let count = fields.len() as i64;
+        let current = fields
+            .iter()
+            .position(|field| *field == self.focused)
+            .unwrap_or(0) as i64;
+        self.focused = fields[(current + delta).rem_euclid(count) as usize];
+    }
+
+    /// Step through `count` calendars by `delta`, wrapping around
+    pub fn cycle_calendar(&mut self, delta: i64, count: usize) {
+        if count > 0 {
+            self.calendar = (self.calendar as i64 + delta).rem_euclid(count as i64) as usize;
+        }
+    }
+
+    /// The event as typed: all-day without a start time, timed in local time
+    /// otherwise, lasting `duration` (`--event-duration`) without an end time
+    pub fn to_event(&self, duration: Duration) -> Result<Event> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            bail!("The title is empty");
+        }
+        let date = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d")
+            .with_context(|| format!("Invalid date \"{}\", expected YYYY-MM-DD", self.date))?;
+        let time = |value: &str| -> Result<Option<NaiveTime>> {
+            match value.trim() {
+                "" => Ok(None),
+                value => NaiveTime::parse_from_str(value, "%H:%M")
+                    .map(Some)
+                    .with_context(|| format!("Invalid time \"{}\", expected HH:MM", value)),
+            }
+        };
+        let (from, to) = (time(&self.start)?, time(&self.end)?);
+        match (from, to) {
+            (None, Some(_)) => bail!("An end time needs a start time"),
+            (Some(from), Some(to)) if to <= from => bail!("The end must be after the start"),
+            _ => {}
+        }
+
+        let mut event = NewEvent {
+            title: title.to_string(),
+            date: Some(date),
+            from,
+            to,
+        }
+        .to_event(date, duration)?;
+        let location = self.location.trim();
+        if !location.is_empty() {
+            event.location = Some(location.to_string());
+        }
+        let description = self.description.trim();
+        if !description.is_empty() {
+            event.description = Some(description.to_string());
+        }
+        Ok(event)
+    }
+
+    /// The changes made to the edited event. Moving it to another day keeps
+    /// its length, so multi-day events stay whole unless the times change.
+    pub fn to_patch(&self, duration: Duration) -> Result<EventPatch> {
+        let original = self.original.as_ref().context("No event is being edited")?;
+        let before = Self::for_event(original);
+        let event = self.to_event(duration)?;
+
+        let mut patch = EventPatch::default();
+        if self.title.trim() != before.title {
+            patch.summary = event.summary;
+        }
+        if self.location.trim() != before.location {
+            patch.location = Some(self.location.trim().to_string());
+        }
+        if self.description.trim() != before.description {
+            patch.description = Some(self.description.trim().to_string());
+        }
+
+        let times_changed =
+            (self.start.trim(), self.end.trim()) != (before.start.as_str(), before.end.as_str());
+        if times_changed {
+            patch.start = Some(event.start);
+            patch.end = Some(event.end);
+        } else if self.date.trim() != before.date {
+            let days = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d")?
+                - NaiveDate::parse_from_str(&before.date, "%Y-%m-%d")?;
+            patch.start = Some(shifted(&original.start, days)?);
+            patch.end = Some(shifted(&original.end, days)?);
+        }
+        Ok(patch)
+    }
+}
+
+/// `date_time` moved by whole days, keeping its local time of day
+fn shifted(date_time: &EventDateTime, days: Duration) -> Result<EventDateTime> {
+    let at = date_time
+        .to_local()
+        .context("The event has an invalid time")?;
+    if date_time.is_all_day() {
+        Ok(EventDateTime::from_date(at.date_naive() + days))
+    } else {
+        EventDateTime::from_local(at.naive_local() + days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dentist() -> EventForm {
+        let mut form = EventForm::new(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap(), 0);
+        form.title = "Dentist".to_string();
+        form
+    }
+
+    #[test]
+    fn test_typing_goes_to_the_focused_field() {
+        let mut form = dentist();
+        form.move_focus(2);
+        for c in "9:30".chars() {
+            form.input(c);
+        }
+        form.backspace();
+        form.input('5');
+
+        assert_eq!(form.focused, FormField::Start);
+        assert_eq!(form.start, "9:35");
+        assert_eq!(form.title, "Dentist");
+
+        form.move_focus(-3);
+        assert_eq!(form.focused, FormField::Description);
+        form.move_focus(-1);
+        form.input('x');
+        assert_eq!(form.calendar, 0);
+
+        form.cycle_calendar(-1, 3);
+        assert_eq!(form.calendar, 2);
+    }
+
+    #[test]
+    fn test_to_event() {
+        let mut form = dentist();
+        let event = form.to_event(Duration::hours(1)).unwrap();
+        assert_eq!(event.summary.as_deref(), Some("Dentist"));
+        assert_eq!(event.start.date.as_deref(), Some("2025-06-16"));
+        assert_eq!(event.end.date.as_deref(), Some("2025-06-17"));
+
+        form.start = "14:00".to_string();
+        form.end = "15:30".to_string();
+        form.location = "Main St 4".to_string();
+        form.description = " Bring the forms ".to_string();
+        let event = form.to_event(Duration::hours(1)).unwrap();
+        let start = event.start.to_local().unwrap();
+        let end = event.end.to_local().unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-06-16 14:00"
+        );
+        assert_eq!(end - start, chrono::Duration::minutes(90));
+        assert_eq!(event.location.as_deref(), Some("Main St 4"));
+        assert_eq!(event.description.as_deref(), Some("Bring the forms"));
+    }
+
+    fn offsite() -> Event {
+        let mut form = dentist();
+        form.title = "Offsite".to_string();
+        form.start = "09:00".to_string();
+        form.end = "17:00".to_string();
+        form.location = "Lisbon".to_string();
+        let mut event = form.to_event(Duration::hours(1)).unwrap();
+        event.id = "offsite".to_string();
+        // Three days long
+        event.end = EventDateTime::from_local(
+            NaiveDate::from_ymd_opt(2025, 6, 18)
+                .unwrap()
+                .and_hms_opt(17, 0, 0)
+                .unwrap(),
+        )
+        .unwrap();
+        event
+    }
+
+    #[test]
+    fn test_for_event() {
+        let form = EventForm::for_event(&offsite());
+        assert_eq!(form.title, "Offsite");
+        assert_eq!(form.date, "2025-06-16");
+        assert_eq!((form.start.as_str(), form.end.as_str()), ("09:00", "17:00"));
+        assert_eq!(form.location, "Lisbon");
+        assert!(!form.fields().contains(&FormField::Calendar));
+
+        assert_eq!(
+            form.to_patch(Duration::hours(1)).unwrap(),
+            EventPatch::default()
+        );
+    }
+
+    #[test]
+    fn test_to_patch() {
+        let mut form = EventForm::for_event(&offsite());
+        form.title = "Team offsite".to_string();
+        form.location = String::new();
+        form.date = "2025-06-23".to_string();
+        let patch = form.to_patch(Duration::hours(1)).unwrap();
+        assert_eq!(patch.summary.as_deref(), Some("Team offsite"));
+        // Cleared on the server
+        assert_eq!(patch.location.as_deref(), Some(""));
+        assert_eq!(patch.description, None);
+        // A week later, still three days long
+        let start = patch.start.unwrap().to_local().unwrap();
+        let end = patch.end.unwrap().to_local().unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M").to_string(),
+            "2025-06-23 09:00"
+        );
+        assert_eq!(end.format("%Y-%m-%d %H:%M").to_string(), "2025-06-25 17:00");
+
+        let mut form = EventForm::for_event(&offsite());
+        form.end = "12:00".to_string();
+        let patch = form.to_patch(Duration::hours(1)).unwrap();
+        assert_eq!(patch.summary, None);
+        let end = patch.end.unwrap().to_local().unwrap();
+        assert_eq!(end.format("%Y-%m-%d %H:%M").to_string(), "2025-06-16 12:00");
+
+        form.title = String::new();
+        assert!(form.to_patch(Duration::hours(1)).is_err());
+        assert!(dentist().to_patch(Duration::hours(1)).is_err());
+    }
+
+    #[test]
+    fn test_to_event_errors() {
+        let mut form = dentist();
+        form.title = "  ".to_string();
+        assert!(form.to_event(Duration::hours(1)).is_err());
+
+        let mut form = dentist();
+        form.date = "16.06.2025".to_string();
+        assert!(form.to_event(Duration::hours(1)).is_err());
+
+        let mut form = dentist();
+        form.end = "10:00".to_string();
+        assert!(form.to_event(Duration::hours(1)).is_err());
+
+        form.start = "11:00".to_string();
+        assert!(form.to_event(Duration::hours(1)).is_err());
+    }
+}
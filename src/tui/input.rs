@@ -1,110 +1,243 @@
 use crossterm::event::{KeyCode, KeyEvent};
 
+use super::action::{Action, Effect};
+use super::form::FormField;
 use super::state::{AppState, EventsViewMode, ViewFocus};
+use crate::commands::rsvp::Response;
+
+/// Multi-key sequences, started by a leader key ('g' or space)
+const CHORDS: &[(&str, Action)] = &[
+    ("gt", Action::JumpToToday),
+    ("gw", Action::ToggleWeek),
+    ("ga", Action::ToggleAgenda),
+    (" c", Action::ToggleSidebar),
+    (" i", Action::ToggleStats),
+    (" l", Action::ToggleLog),
+];
+
+/// Translate a key press into an action and reduce it
+pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Effect {
+    match chord_or_key_to_action(key, state) {
+        Some(action) => state.reduce(action),
+        None => Effect::None,
+    }
+}
+
+/// Track leader keys in `state.pending_keys` until a chord completes.
+/// Any key that doesn't continue a chord cancels it.
+fn chord_or_key_to_action(key: KeyEvent, state: &mut AppState) -> Option<Action> {
+    if !state.pending_keys.is_empty() {
+        let mut sequence = std::mem::take(&mut state.pending_keys);
+        let KeyCode::Char(c) = key.code else {
+            return None;
+        };
+        sequence.push(c);
+
+        return match CHORDS.iter().find(|(keys, _)| *keys == sequence) {
+            Some((_, action)) => Some(*action),
+            None if CHORDS.iter().any(|(keys, _)| keys.starts_with(&sequence)) => {
+                state.pending_keys = sequence;
+                None
+            }
+            None => None,
+        };
+    }
+
+    let in_prompt = state.confirming_quit
+        || state.confirming_delete
+        || state.editing_keyword
+        || state.is_form_open();
+    if let KeyCode::Char(c) = key.code {
+        let is_leader = CHORDS.iter().any(|(keys, _)| keys.starts_with(c));
+        if !in_prompt && is_leader {
+            state.pending_keys.push(c);
+            return None;
+        }
+    }
 
-pub enum InputAction {
-    Quit,
-    Refresh,
-    None,
+    key_to_action(key, state)
 }
 
-pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> InputAction {
+/// Human-readable pending keys, e.g. "space" for a pending space leader
+pub fn display_keys(keys: &str) -> String {
+    keys.chars()
+        .map(|c| match c {
+            ' ' => "space".to_string(),
+            c => c.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn key_to_action(key: KeyEvent, state: &AppState) -> Option<Action> {
+    if state.confirming_quit {
+        return quit_confirmation_key_to_action(key);
+    }
+    if state.confirming_delete {
+        return delete_confirmation_key_to_action(key);
+    }
+
+    // While typing a filter every key is text, including the global ones
+    if state.editing_keyword {
+        return keyword_key_to_action(key);
+    }
+
+    // Likewise in the event form, where Tab moves between fields
+    if state.is_form_open() {
+        return form_key_to_action(key, state);
+    }
+
+    // In the details `t` answers "tentative"; `gt` still jumps to today
+    let in_details = state.view_focus == ViewFocus::Events
+        && matches!(state.events_view_mode, EventsViewMode::Details { .. });
+
     // Global keys that work regardless of focus
     match key.code {
-        KeyCode::Char('q') => return InputAction::Quit,
-        KeyCode::Char('r') => return InputAction::Refresh,
-        KeyCode::Char('t') => {
-            state.jump_to_today();
-            return InputAction::None;
-        }
-        KeyCode::Tab => {
-            state.toggle_focus();
-            return InputAction::None;
-        }
+        KeyCode::Char('q') => return Some(Action::Quit),
+        KeyCode::Char('r') => return Some(Action::Refresh),
+        KeyCode::Char('R') if state.error.is_some() => return Some(Action::Refresh),
+        KeyCode::Char('t') if !in_details => return Some(Action::JumpToToday),
+        KeyCode::Char('/') => return Some(Action::StartKeywordFilter),
+        KeyCode::Char('D') => return Some(Action::ToggleDeclined),
+        KeyCode::Char('U') => return Some(Action::ShowAllCalendars),
+        KeyCode::Char('i') => return Some(Action::ToggleStats),
+        KeyCode::Char('L') => return Some(Action::ToggleLog),
+        KeyCode::Char('N') => return Some(Action::ToggleNotifications),
+        KeyCode::Char('z') => return Some(Action::SnoozeNotification(5)),
+        KeyCode::Char('Z') => return Some(Action::SnoozeNotification(10)),
+        KeyCode::Char('.') => return Some(Action::RepeatLast),
+        KeyCode::Char('v') => return Some(Action::ToggleTimeline),
+        KeyCode::Tab => return Some(Action::FocusNext),
+        KeyCode::BackTab => return Some(Action::FocusPrev),
         _ => {}
     }
 
     // Focus-aware routing - NO SHARED LOGIC
     match state.view_focus {
-        ViewFocus::Calendar => handle_calendar_input(key, state),
-        ViewFocus::Events => handle_events_input(key, state),
+        ViewFocus::Calendar => calendar_key_to_action(key, state),
+        ViewFocus::Events => events_key_to_action(key, state),
+        ViewFocus::Sidebar => sidebar_key_to_action(key),
     }
 }
 
-fn handle_calendar_input(key: KeyEvent, state: &mut AppState) -> InputAction {
+fn calendar_key_to_action(key: KeyEvent, state: &AppState) -> Option<Action> {
     match key.code {
-        KeyCode::Esc => InputAction::Quit,
-        KeyCode::Left | KeyCode::Char('h') => {
-            state.move_selected_date(-1);
-            state.reset_event_selection();
-            InputAction::None
-        }
-        KeyCode::Right | KeyCode::Char('l') => {
-            state.move_selected_date(1);
-            state.reset_event_selection();
-            InputAction::None
-        }
-        KeyCode::Up | KeyCode::Char('k') => {
-            state.move_to_prev_week();
-            state.reset_event_selection();
-            InputAction::None
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            state.move_to_next_week();
-            state.reset_event_selection();
-            InputAction::None
-        }
-        _ => InputAction::None,
+        KeyCode::Char(c) if c.is_ascii_digit() => c.to_digit(10).map(Action::DayDigit),
+        KeyCode::Enter if !state.typed_day.is_empty() => Some(Action::JumpToTypedDay),
+        KeyCode::Esc if !state.typed_day.is_empty() => Some(Action::ClearTypedDay),
+        KeyCode::Esc => Some(Action::Quit),
+        KeyCode::Left | KeyCode::Char('h') => Some(Action::MoveDay(-1)),
+        KeyCode::Right | KeyCode::Char('l') => Some(Action::MoveDay(1)),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::MoveWeek(-1)),
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::MoveWeek(1)),
+        KeyCode::Char('a') | KeyCode::Char('n') => Some(Action::NewEvent),
+        _ => None,
     }
 }
 
-fn handle_events_input(key: KeyEvent, state: &mut AppState) -> InputAction {
+fn sidebar_key_to_action(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::Quit),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::MoveSidebarSelection(-1)),
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::MoveSidebarSelection(1)),
+        KeyCode::Enter => Some(Action::ToggleSidebarCalendar),
+        KeyCode::Char('K') => Some(Action::MoveSidebarCalendar(-1)),
+        KeyCode::Char('J') => Some(Action::MoveSidebarCalendar(1)),
+        _ => None,
+    }
+}
+
+fn events_key_to_action(key: KeyEvent, state: &AppState) -> Option<Action> {
     match state.events_view_mode {
-        EventsViewMode::List => handle_events_list_input(key, state),
-        EventsViewMode::Details { .. } => handle_events_details_input(key, state),
+        EventsViewMode::List => events_list_key_to_action(key),
+        EventsViewMode::Details { .. } => events_details_key_to_action(key),
+        EventsViewMode::Create | EventsViewMode::Edit { .. } => form_key_to_action(key, state),
     }
 }
 
-fn handle_events_list_input(key: KeyEvent, state: &mut AppState) -> InputAction {
+fn events_list_key_to_action(key: KeyEvent) -> Option<Action> {
     match key.code {
-        KeyCode::Esc => InputAction::Quit,
-        KeyCode::Up | KeyCode::Char('k') => {
-            state.move_event_selection_up();
-            InputAction::None
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            state.move_event_selection_down();
-            InputAction::None
-        }
-        KeyCode::Enter => {
-            state.select_event();
-            InputAction::None
-        }
-        _ => InputAction::None,
+        KeyCode::Esc => Some(Action::Quit),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::SelectPrevEvent),
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::SelectNextEvent),
+        KeyCode::Enter => Some(Action::OpenDetails),
+        KeyCode::Char('H') => Some(Action::HideSelectedCalendar),
+        KeyCode::Char('J') => Some(Action::JoinCall),
+        KeyCode::Char('m') => Some(Action::MailAttendees),
+        KeyCode::Char('a') | KeyCode::Char('n') => Some(Action::NewEvent),
+        KeyCode::Char('d') => Some(Action::DeleteEvent),
+        _ => None,
     }
 }
 
-fn handle_events_details_input(key: KeyEvent, state: &mut AppState) -> InputAction {
+fn quit_confirmation_key_to_action(key: KeyEvent) -> Option<Action> {
     match key.code {
-        KeyCode::Esc => {
-            state.exit_event_details();
-            InputAction::None
-        }
-        KeyCode::Char('k') => {
-            state.scroll_event_details_up();
-            InputAction::None
-        }
-        KeyCode::Char('j') => {
-            state.scroll_event_details_down();
-            InputAction::None
-        }
-        _ => InputAction::None,
+        KeyCode::Char('y') | KeyCode::Char('q') => Some(Action::ConfirmQuit),
+        KeyCode::Char('n') | KeyCode::Esc => Some(Action::CancelQuit),
+        _ => None,
+    }
+}
+
+fn delete_confirmation_key_to_action(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => Some(Action::ConfirmDelete),
+        KeyCode::Char('n') | KeyCode::Esc => Some(Action::CancelDelete),
+        _ => None,
+    }
+}
+
+fn keyword_key_to_action(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Enter => Some(Action::FinishKeywordFilter),
+        KeyCode::Esc => Some(Action::CancelKeywordFilter),
+        KeyCode::Backspace => Some(Action::KeywordBackspace),
+        KeyCode::Char(c) => Some(Action::KeywordInput(c)),
+        _ => None,
+    }
+}
+
+fn form_key_to_action(key: KeyEvent, state: &AppState) -> Option<Action> {
+    let on_calendar = state
+        .event_form
+        .as_ref()
+        .is_some_and(|form| form.focused == FormField::Calendar);
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelForm),
+        KeyCode::Enter => Some(Action::SubmitForm),
+        KeyCode::Tab | KeyCode::Down => Some(Action::MoveFormFocus(1)),
+        KeyCode::BackTab | KeyCode::Up => Some(Action::MoveFormFocus(-1)),
+        KeyCode::Left if on_calendar => Some(Action::CycleFormCalendar(-1)),
+        KeyCode::Right if on_calendar => Some(Action::CycleFormCalendar(1)),
+        KeyCode::Backspace => Some(Action::FormBackspace),
+        KeyCode::Char(c) => Some(Action::FormInput(c)),
+        _ => None,
+    }
+}
+
+fn events_details_key_to_action(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CloseDetails),
+        KeyCode::Char('k') => Some(Action::ScrollDetails(-1)),
+        KeyCode::Char('j') => Some(Action::ScrollDetails(1)),
+        KeyCode::Char('x') => Some(Action::ExportEvent),
+        KeyCode::Char('e') => Some(Action::EditEvent),
+        KeyCode::Char('E') => Some(Action::EditEventInEditor),
+        KeyCode::Char('a') => Some(Action::Respond(Response::Accept)),
+        KeyCode::Char('t') => Some(Action::Respond(Response::Tentative)),
+        KeyCode::Char('d') => Some(Action::Respond(Response::Decline)),
+        KeyCode::Char('n') => Some(Action::NextAttachment),
+        KeyCode::Char('o') => Some(Action::OpenAttachment),
+        KeyCode::Char('J') => Some(Action::JoinCall),
+        KeyCode::Char('m') => Some(Action::MailAttendees),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tui::form::EventForm;
+    use crate::tui::state::ListLayout;
     use chrono::NaiveDate;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
@@ -117,10 +250,10 @@ mod tests {
         let mut state = AppState::new();
 
         let action = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
-        assert!(matches!(action, InputAction::Quit));
+        assert!(matches!(action, Effect::Quit));
 
         let action = handle_key_event(create_key_event(KeyCode::Esc), &mut state);
-        assert!(matches!(action, InputAction::Quit));
+        assert!(matches!(action, Effect::Quit));
     }
 
     #[test]
@@ -128,7 +261,7 @@ mod tests {
         let mut state = AppState::new();
 
         let action = handle_key_event(create_key_event(KeyCode::Char('r')), &mut state);
-        assert!(matches!(action, InputAction::Refresh));
+        assert!(matches!(action, Effect::Refresh));
     }
 
     #[test]
@@ -224,7 +357,7 @@ mod tests {
 
         let action = handle_key_event(create_key_event(KeyCode::Char('t')), &mut state);
 
-        assert!(matches!(action, InputAction::None));
+        assert!(matches!(action, Effect::None));
         assert_eq!(state.selected_date, state.today);
     }
 
@@ -265,23 +398,19 @@ mod tests {
         // Add events to test selection
         let events = vec![Event {
             id: "1".to_string(),
-            summary: Some("Event 1".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Event 1",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         }];
         state.events.insert(date, events);
 
@@ -305,23 +434,19 @@ mod tests {
         // Add an event
         let events = vec![Event {
             id: "1".to_string(),
-            summary: Some("Event 1".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Event 1",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         }];
         state.events.insert(date, events);
 
@@ -354,7 +479,7 @@ mod tests {
 
         let action = handle_key_event(create_key_event(KeyCode::Esc), &mut state);
 
-        assert!(matches!(action, InputAction::None)); // Doesn't quit
+        assert!(matches!(action, Effect::None)); // Doesn't quit
         assert!(matches!(state.events_view_mode, EventsViewMode::List)); // Back to list
     }
 
@@ -403,12 +528,12 @@ mod tests {
         // Test 'q' key from Calendar
         state.view_focus = ViewFocus::Calendar;
         let action = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
-        assert!(matches!(action, InputAction::Quit));
+        assert!(matches!(action, Effect::Quit));
 
         // Test 'q' key from Events
         state.view_focus = ViewFocus::Events;
         let action = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
-        assert!(matches!(action, InputAction::Quit));
+        assert!(matches!(action, Effect::Quit));
     }
 
     #[test]
@@ -423,23 +548,19 @@ mod tests {
         // Add an event and select it
         let events = vec![Event {
             id: "1".to_string(),
-            summary: Some("Event 1".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Event 1",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         }];
         state.events.insert(date, events);
         state.selected_event_index = Some(0);
@@ -465,7 +586,7 @@ mod tests {
 
         let action = handle_key_event(create_key_event(KeyCode::Esc), &mut state);
 
-        assert!(matches!(action, InputAction::Quit));
+        assert!(matches!(action, Effect::Quit));
     }
 
     #[test]
@@ -541,7 +662,310 @@ mod tests {
 
         let action = handle_key_event(create_key_event(KeyCode::Esc), &mut state);
 
-        assert!(matches!(action, InputAction::None));
+        assert!(matches!(action, Effect::None));
         assert!(matches!(state.events_view_mode, EventsViewMode::List));
     }
+
+    #[test]
+    fn test_key_to_action_depends_on_focus() {
+        let mut state = AppState::new();
+
+        state.view_focus = ViewFocus::Calendar;
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('j')), &state),
+            Some(Action::MoveWeek(1))
+        );
+
+        state.view_focus = ViewFocus::Events;
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('j')), &state),
+            Some(Action::SelectNextEvent)
+        );
+
+        state.events_view_mode = EventsViewMode::Details {
+            event_index: 0,
+            scroll_offset: 0,
+            max_scroll: 0,
+        };
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('j')), &state),
+            Some(Action::ScrollDetails(1))
+        );
+    }
+
+    #[test]
+    fn test_unmapped_key_has_no_action() {
+        let state = AppState::new();
+
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('Y')), &state),
+            None
+        );
+    }
+
+    #[test]
+    fn test_keys_type_into_keyword_filter_while_editing() {
+        let mut state = AppState::new();
+
+        handle_key_event(create_key_event(KeyCode::Char('/')), &mut state);
+        assert!(state.editing_keyword);
+
+        // 'q' is text here, not quit
+        let effect = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
+        assert_eq!(effect, Effect::None);
+        assert_eq!(state.filter.keyword, "q");
+
+        handle_key_event(create_key_event(KeyCode::Enter), &mut state);
+        assert!(!state.editing_keyword);
+        assert_eq!(state.filter.keyword, "q");
+    }
+
+    #[test]
+    fn test_quit_confirmation_keys() {
+        let mut state = AppState::new();
+        state.pending_mutations = 3;
+
+        let effect = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
+        assert_eq!(effect, Effect::None);
+        assert!(state.confirming_quit);
+
+        // Other keys are ignored while the prompt is open
+        handle_key_event(create_key_event(KeyCode::Char('j')), &mut state);
+        assert!(state.confirming_quit);
+
+        let effect = handle_key_event(create_key_event(KeyCode::Char('y')), &mut state);
+        assert_eq!(effect, Effect::Quit);
+    }
+
+    #[test]
+    fn test_delete_confirmation_keys() {
+        let mut state = AppState::new();
+        state.confirming_delete = true;
+
+        // 'q' neither quits nor confirms
+        let effect = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
+        assert_eq!(effect, Effect::None);
+        assert!(state.confirming_delete);
+
+        handle_key_event(create_key_event(KeyCode::Esc), &mut state);
+        assert!(!state.confirming_delete);
+
+        state.confirming_delete = true;
+        let effect = handle_key_event(create_key_event(KeyCode::Char('y')), &mut state);
+        assert_eq!(effect, Effect::DeleteSelectedEvent);
+    }
+
+    #[test]
+    fn test_shift_r_retries_only_in_error_state() {
+        let mut state = AppState::new();
+
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('R')), &state),
+            None
+        );
+
+        state.error = Some("Failed to fetch calendars".to_string());
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('R')), &state),
+            Some(Action::Refresh)
+        );
+    }
+
+    #[test]
+    fn test_leader_chord_g_t_jumps_to_today() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+        assert_eq!(state.pending_keys, "g");
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+        );
+
+        handle_key_event(create_key_event(KeyCode::Char('t')), &mut state);
+        assert!(state.pending_keys.is_empty());
+        assert_eq!(state.selected_date, state.today);
+    }
+
+    #[test]
+    fn test_leader_chord_g_w_toggles_week() {
+        let mut state = AppState::new();
+
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+        assert_eq!(state.pending_keys, "g");
+        assert_eq!(state.list_layout, ListLayout::Day);
+
+        handle_key_event(create_key_event(KeyCode::Char('w')), &mut state);
+        assert!(state.pending_keys.is_empty());
+        assert_eq!(state.list_layout, ListLayout::Week);
+
+        // Again goes back to the day's list, and `v` swaps in the timeline
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('w')), &mut state);
+        assert_eq!(state.list_layout, ListLayout::Day);
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('w')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('v')), &mut state);
+        assert_eq!(state.list_layout, ListLayout::Timeline);
+    }
+
+    #[test]
+    fn test_space_c_toggles_sidebar() {
+        let mut state = AppState::new();
+        assert!(state.show_sidebar);
+
+        handle_key_event(create_key_event(KeyCode::Char(' ')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('c')), &mut state);
+
+        assert!(!state.show_sidebar);
+    }
+
+    #[test]
+    fn test_unknown_chord_is_cancelled() {
+        let mut state = AppState::new();
+
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+        // 'q' does not complete a chord: cancels instead of quitting
+        let effect = handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
+
+        assert_eq!(effect, Effect::None);
+        assert!(state.pending_keys.is_empty());
+    }
+
+    #[test]
+    fn test_leader_is_text_while_editing_keyword() {
+        let mut state = AppState::new();
+        state.editing_keyword = true;
+
+        handle_key_event(create_key_event(KeyCode::Char('g')), &mut state);
+
+        assert!(state.pending_keys.is_empty());
+        assert_eq!(state.filter.keyword, "g");
+    }
+
+    #[test]
+    fn test_display_keys() {
+        assert_eq!(display_keys("g"), "g");
+        assert_eq!(display_keys(" c"), "space c");
+    }
+
+    #[test]
+    fn test_type_day_number_and_enter() {
+        let mut state = AppState::new();
+        state.view_focus = ViewFocus::Calendar;
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 4, 2).unwrap();
+
+        handle_key_event(create_key_event(KeyCode::Char('3')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('1')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Enter), &mut state);
+
+        // April has 30 days
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 4, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_esc_abandons_typed_day_without_quitting() {
+        let mut state = AppState::new();
+        state.view_focus = ViewFocus::Calendar;
+
+        handle_key_event(create_key_event(KeyCode::Char('1')), &mut state);
+        let effect = handle_key_event(create_key_event(KeyCode::Esc), &mut state);
+
+        assert_eq!(effect, Effect::None);
+        assert!(state.typed_day.is_empty());
+    }
+
+    #[test]
+    fn test_back_tab_cycles_focus_backwards() {
+        let mut state = AppState::new();
+
+        handle_key_event(create_key_event(KeyCode::BackTab), &mut state);
+
+        assert_eq!(state.view_focus, ViewFocus::Events);
+    }
+
+    #[test]
+    fn test_sidebar_keys() {
+        let mut state = AppState::new();
+        state.view_focus = ViewFocus::Sidebar;
+
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('j')), &state),
+            Some(Action::MoveSidebarSelection(1))
+        );
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Enter), &state),
+            Some(Action::ToggleSidebarCalendar)
+        );
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('K')), &state),
+            Some(Action::MoveSidebarCalendar(-1))
+        );
+    }
+
+    #[test]
+    fn test_details_keys_answer_invitations() {
+        let mut state = AppState::new();
+        state.view_focus = ViewFocus::Events;
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('t')), &state),
+            Some(Action::JumpToToday)
+        );
+
+        state.events_view_mode = EventsViewMode::Details {
+            event_index: 0,
+            scroll_offset: 0,
+            max_scroll: 0,
+        };
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('a')), &state),
+            Some(Action::Respond(Response::Accept))
+        );
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('t')), &state),
+            Some(Action::Respond(Response::Tentative))
+        );
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('d')), &state),
+            Some(Action::Respond(Response::Decline))
+        );
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('n')), &state),
+            Some(Action::NextAttachment)
+        );
+    }
+
+    #[test]
+    fn test_form_keys_are_text() {
+        let mut state = AppState::new();
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Char('a')), &state),
+            Some(Action::NewEvent)
+        );
+
+        state.event_form = Some(EventForm::new(
+            NaiveDate::from_ymd_opt(2025, 6, 16).unwrap(),
+            0,
+        ));
+        state.events_view_mode = EventsViewMode::Create;
+
+        // Global keys and the space leader are typed into the field
+        handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char(' ')), &mut state);
+        handle_key_event(create_key_event(KeyCode::Char('t')), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().title, "q t");
+        assert!(state.pending_keys.is_empty());
+
+        handle_key_event(create_key_event(KeyCode::Tab), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().focused, FormField::Date);
+        assert_eq!(key_to_action(create_key_event(KeyCode::Left), &state), None);
+        assert_eq!(
+            key_to_action(create_key_event(KeyCode::Esc), &state),
+            Some(Action::CancelForm)
+        );
+    }
 }
@@ -1,33 +1,63 @@
 use chrono::NaiveDate;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::sync::oneshot;
 
-use super::fetcher::fetch_calendar_data;
+use super::fetcher::{fetch_calendar_data, CalendarData, FailedCalendar};
 use super::state::DateRange;
 use crate::calendar::client::CalendarClient;
 use crate::calendar::models::{Calendar, Event};
+use crate::calendar::queue::{MutationQueue, ReplayReport};
+use crate::settings::Settings;
 
 #[derive(Debug)]
 pub enum DataMessage {
     Loading,
+    /// A calendar finished loading; `done` of `total` calendars are fetched.
+    /// `events` is None when this calendar failed to load.
+    Progress {
+        calendar: String,
+        calendar_id: String,
+        events: Option<HashMap<NaiveDate, Vec<Event>>>,
+        done: usize,
+        total: usize,
+    },
+    /// All calendars were processed; their events already arrived via `Progress`
     Success {
         calendars: Vec<Calendar>,
-        events: HashMap<NaiveDate, Vec<Event>>,
+        failed: Vec<FailedCalendar>,
+        sync: ReplayReport,
         client: CalendarClient,
     },
     Error {
         error: String,
         client: CalendarClient,
     },
+    /// The fetch was superseded before it finished; hands the client back
+    Cancelled {
+        client: CalendarClient,
+    },
 }
 
 pub struct DataLoader {
     receiver: UnboundedReceiver<DataMessage>,
+    cancel: Option<oneshot::Sender<()>>,
 }
 
 impl DataLoader {
-    pub fn new(mut client: CalendarClient, date_range: DateRange) -> Self {
+    /// Fetch `date_range` in the background; `only` limits the calendars fetched.
+    /// In `read_only` mode queued offline changes are left in the queue.
+    pub fn new(
+        mut client: CalendarClient,
+        date_range: DateRange,
+        only: Vec<String>,
+        read_only: bool,
+        settings: Arc<Settings>,
+    ) -> Self {
         let (sender, receiver) = unbounded_channel();
+        let (cancel, cancelled) = oneshot::channel();
 
         // Send initial loading message
         sender
@@ -36,15 +66,49 @@ impl DataLoader {
 
         // Spawn async task using existing tokio runtime
         tokio::spawn(async move {
-            // Run the async fetch operation
-            let result = fetch_calendar_data(&mut client, date_range).await;
+            // Replay offline changes first so the fetch below already reflects them
+            let sync = if read_only {
+                ReplayReport {
+                    pending: MutationQueue::load().map_or(0, |queue| queue.len()),
+                    ..Default::default()
+                }
+            } else {
+                Self::replay_pending_mutations(&mut client).await
+            };
+
+            // Run the async fetch operation. Only the fetch is cancellable: the replay
+            // above must not be interrupted halfway through sending a mutation.
+            let progress_sender = sender.clone();
+            let fetch = fetch_calendar_data(
+                &mut client,
+                date_range,
+                &only,
+                &settings,
+                |calendar, events, done, total| {
+                    let _ = progress_sender.send(DataMessage::Progress {
+                        calendar: calendar.summary.clone(),
+                        calendar_id: calendar.id.clone(),
+                        events,
+                        done,
+                        total,
+                    });
+                },
+            );
+            let result = tokio::select! {
+                result = fetch => result,
+                _ = cancelled => {
+                    let _ = sender.send(DataMessage::Cancelled { client });
+                    return;
+                }
+            };
 
             // Send result through channel
             match result {
-                Ok((calendars, events)) => {
+                Ok(CalendarData { calendars, failed }) => {
                     let _ = sender.send(DataMessage::Success {
                         calendars,
-                        events,
+                        failed,
+                        sync,
                         client,
                     });
                 }
@@ -57,7 +121,40 @@ impl DataLoader {
             }
         });
 
-        Self { receiver }
+        Self {
+            receiver,
+            cancel: Some(cancel),
+        }
+    }
+
+    /// Abort the fetch; the client comes back through `DataMessage::Cancelled`
+    /// unless the fetch already completed
+    pub fn cancel(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            let _ = cancel.send(());
+        }
+    }
+
+    async fn replay_pending_mutations(client: &mut CalendarClient) -> ReplayReport {
+        let mut queue = match MutationQueue::load() {
+            Ok(queue) => queue,
+            Err(e) => {
+                return ReplayReport {
+                    discarded: vec![format!("Could not read offline changes: {}", e)],
+                    ..Default::default()
+                }
+            }
+        };
+
+        if queue.is_empty() {
+            return ReplayReport::default();
+        }
+
+        queue.replay(client).await.unwrap_or_else(|e| ReplayReport {
+            discarded: vec![format!("Could not save offline changes: {}", e)],
+            pending: queue.len(),
+            ..Default::default()
+        })
     }
 
     pub fn try_recv(&mut self) -> Option<DataMessage> {
@@ -65,6 +162,17 @@ impl DataLoader {
     }
 }
 
+/// Whether the periodic refresh every `interval` (`--refresh-interval`) is
+/// due, `finished_at` being when the last fetch ended, successfully or not
+pub fn refresh_due(interval: Option<Duration>, finished_at: Option<Instant>, now: Instant) -> bool {
+    match (interval, finished_at) {
+        (Some(interval), Some(finished_at)) => {
+            now.saturating_duration_since(finished_at) >= interval
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,10 +183,51 @@ mod tests {
         let loading = DataMessage::Loading;
         assert!(matches!(loading, DataMessage::Loading));
 
+        let progress = DataMessage::Progress {
+            calendar: "Team Calendar".to_string(),
+            calendar_id: "team@group.calendar.google.com".to_string(),
+            events: None,
+            done: 3,
+            total: 12,
+        };
+        assert!(matches!(
+            progress,
+            DataMessage::Progress {
+                done: 3,
+                total: 12,
+                ..
+            }
+        ));
+
         // Note: We can't easily create CalendarClient instances in tests without OAuth setup,
         // so Success and Error variants are tested via integration tests
     }
 
+    #[test]
+    fn test_refresh_due() {
+        let finished_at = Instant::now();
+        let interval = Duration::from_secs(300);
+
+        assert!(!refresh_due(Some(interval), Some(finished_at), finished_at));
+        assert!(!refresh_due(
+            Some(interval),
+            Some(finished_at),
+            finished_at + Duration::from_secs(299)
+        ));
+        assert!(refresh_due(
+            Some(interval),
+            Some(finished_at),
+            finished_at + Duration::from_secs(300)
+        ));
+        // Turned off, or the first load is still running
+        assert!(!refresh_due(
+            None,
+            Some(finished_at),
+            finished_at + interval
+        ));
+        assert!(!refresh_due(Some(interval), None, finished_at + interval));
+    }
+
     #[test]
     fn test_channel_communication() {
         let (sender, mut receiver) = unbounded_channel();
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+/// Oldest entries are dropped beyond this many
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub message: String,
+}
+
+/// Shared ring buffer of recent log entries, filled by `LogLayer` and read by the log pane
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+}
+
+impl LogBuffer {
+    /// Install a global tracing subscriber that records into a new buffer.
+    /// Without it, tracing macros elsewhere are no-ops (e.g. for CLI commands).
+    pub fn install() -> Self {
+        let buffer = Self::default();
+        let subscriber = tracing_subscriber::registry().with(LogLayer {
+            buffer: buffer.clone(),
+        });
+        // Fails only if a subscriber is already set, which then keeps receiving events
+        let _ = tracing::subscriber::set_global_default(subscriber);
+        buffer
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// The newest `count` entries, oldest first
+    pub fn recent(&self, count: usize) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let skip = entries.len().saturating_sub(count);
+        entries.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// Records INFO and above; DEBUG/TRACE would drown out the warnings this pane is for
+struct LogLayer {
+    buffer: LogBuffer,
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let level = *event.metadata().level();
+        if level > Level::INFO {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        self.buffer.push(LogEntry {
+            time: Local::now(),
+            level,
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Joins the `message` field and any structured fields into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else {
+            format!("{} ({})", self.message, self.fields.join(", "))
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(f: impl FnOnce()) -> LogBuffer {
+        let buffer = LogBuffer::default();
+        let subscriber = tracing_subscriber::registry().with(LogLayer {
+            buffer: buffer.clone(),
+        });
+        tracing::subscriber::with_default(subscriber, f);
+        buffer
+    }
+
+    #[test]
+    fn test_layer_records_message_and_fields() {
+        let buffer = capture(|| {
+            tracing::warn!(calendar = "Work", "Failed to load events");
+        });
+
+        let entries = buffer.recent(10);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, Level::WARN);
+        assert_eq!(entries[0].message, "Failed to load events (calendar=Work)");
+    }
+
+    #[test]
+    fn test_layer_ignores_debug() {
+        let buffer = capture(|| {
+            tracing::debug!("noisy");
+            tracing::info!("useful");
+        });
+
+        let entries = buffer.recent(10);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "useful");
+    }
+
+    #[test]
+    fn test_buffer_is_bounded_and_returns_newest() {
+        let buffer = LogBuffer::default();
+        for i in 0..MAX_ENTRIES + 5 {
+            buffer.push(LogEntry {
+                time: Local::now(),
+                level: Level::INFO,
+                message: i.to_string(),
+            });
+        }
+
+        let recent = buffer.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].message, (MAX_ENTRIES + 3).to_string());
+        assert_eq!(recent[1].message, (MAX_ENTRIES + 4).to_string());
+        assert_eq!(buffer.recent(usize::MAX).len(), MAX_ENTRIES);
+    }
+}
@@ -1,9 +1,16 @@
+pub mod action;
 pub mod app;
+pub mod calendar_prefs;
 pub mod color_utils;
+pub mod export;
 pub mod fetcher;
+pub mod form;
 pub mod input;
 pub mod loader;
+pub mod log;
+pub mod notify;
 pub mod state;
 pub mod widgets;
+pub mod zones;
 
 pub use app::run_tui;
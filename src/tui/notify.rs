@@ -0,0 +1,380 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local};
+use std::collections::HashSet;
+use std::process::Command;
+
+use super::state::AppState;
+use crate::calendar::models::Event;
+
+/// Minutes before an event starts when neither `--notify` nor the event's
+/// reminders say otherwise
+pub const DEFAULT_LEAD_MINUTES: u32 = 5;
+/// Upcoming events looked at on each check; more than can start within any lead
+const LOOKAHEAD_EVENTS: usize = 50;
+
+/// A desktop notification about an event that is about to start
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// An event a notification was shown for, kept so it can be snoozed
+#[derive(Debug, Clone)]
+struct Reminded {
+    key: String,
+    title: String,
+    location: Option<String>,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+}
+
+impl Reminded {
+    fn notification(&self, now: DateTime<Local>, time_format: &str) -> Notification {
+        Notification {
+            title: self.title.clone(),
+            body: body(self.start, self.location.as_deref(), now, time_format),
+        }
+    }
+}
+
+/// Decides which events to notify about, each once per lead time
+#[derive(Debug)]
+pub struct Notifier {
+    /// Lead times from `--notify`; when empty each event's popup reminders are used
+    leads: Vec<Duration>,
+    /// Events that had started when the TUI opened are not announced
+    since: DateTime<Local>,
+    sent: HashSet<String>,
+    /// The latest notification, the one snoozing repeats
+    last: Option<Reminded>,
+    snoozed: Vec<(DateTime<Local>, Reminded)>,
+}
+
+impl Notifier {
+    pub fn new(lead_minutes: &[u32], since: DateTime<Local>) -> Self {
+        Self {
+            leads: lead_minutes
+                .iter()
+                .map(|&minutes| Duration::minutes(i64::from(minutes)))
+                .collect(),
+            since,
+            sent: HashSet::new(),
+            last: None,
+            snoozed: Vec::new(),
+        }
+    }
+
+    /// Notifications for visible timed events whose lead time has come, and
+    /// for snoozed reminders whose snooze is over. When several leads are due
+    /// at once (e.g. right after launch) only one notification is produced.
+    pub fn take_due(&mut self, state: &AppState, now: DateTime<Local>) -> Vec<Notification> {
+        let mut due = Vec::new();
+        let time_format = &state.settings.formats.time;
+
+        let (ready, waiting) = std::mem::take(&mut self.snoozed)
+            .into_iter()
+            .partition(|(until, _)| *until <= now);
+        self.snoozed = waiting;
+        for (_, reminded) in ready {
+            due.push(reminded.notification(now, time_format));
+            self.last = Some(reminded);
+        }
+
+        for event in state.upcoming_from(now, LOOKAHEAD_EVENTS) {
+            let Some(start) = event.start.to_local() else {
+                continue;
+            };
+            if event.start.is_all_day() || start <= self.since || start <= now {
+                continue;
+            }
+
+            let key = format!("{}@{:?}", event.id, event.start.date_time);
+            let mut newly_due = false;
+            for lead in self.leads_for(event, state) {
+                if start - lead <= now
+                    && self.sent.insert(format!("{}@{}", key, lead.num_minutes()))
+                {
+                    newly_due = true;
+                }
+            }
+
+            if newly_due {
+                let reminded = Reminded {
+                    key,
+                    title: event.summary.as_deref().unwrap_or("(No title)").to_string(),
+                    location: event.location.clone(),
+                    start,
+                    end: event.end.to_local().unwrap_or(start),
+                };
+                due.push(reminded.notification(now, time_format));
+                self.last = Some(reminded);
+            }
+        }
+
+        due
+    }
+
+    /// Show the latest notification again in `minutes`, unless its event is
+    /// over; returns the event's title
+    pub fn snooze(&mut self, minutes: u32, now: DateTime<Local>) -> Option<String> {
+        let reminded = self.last.clone().filter(|reminded| reminded.end > now)?;
+        let title = reminded.title.clone();
+
+        self.snoozed
+            .retain(|(_, snoozed)| snoozed.key != reminded.key);
+        self.snoozed
+            .push((now + Duration::minutes(i64::from(minutes)), reminded));
+        Some(title)
+    }
+
+    /// `--notify` lead times, else the event's popup reminders; events without
+    /// any reminder information get the default lead
+    fn leads_for(&self, event: &Event, state: &AppState) -> Vec<Duration> {
+        if !self.leads.is_empty() {
+            return self.leads.clone();
+        }
+
+        let calendar = event
+            .calendar_id
+            .as_deref()
+            .and_then(|id| state.get_calendar_by_id(id));
+        let reminders = event.effective_reminders(calendar);
+        if event.reminders.is_none() && reminders.is_empty() {
+            return vec![Duration::minutes(i64::from(DEFAULT_LEAD_MINUTES))];
+        }
+
+        reminders
+            .iter()
+            .filter(|reminder| reminder.is_popup())
+            .map(|reminder| Duration::minutes(i64::from(reminder.minutes)))
+            .collect()
+    }
+}
+
+/// e.g. `In 5 min at 14:00 — Room 4`, or `Started at 14:00` once snoozed past the start
+fn body(
+    start: DateTime<Local>,
+    location: Option<&str>,
+    now: DateTime<Local>,
+    time_format: &str,
+) -> String {
+    // Round up so an event 4m30s away reads "in 5 min"
+    let minutes = ((start - now).num_seconds() + 59).div_euclid(60);
+    let mut body = if minutes > 0 {
+        format!("In {} min at {}", minutes, start.format(time_format))
+    } else {
+        format!("Started at {}", start.format(time_format))
+    };
+    if let Some(location) = location.filter(|location| !location.is_empty()) {
+        body.push_str(" — ");
+        body.push_str(location);
+    }
+    body
+}
+
+/// Show the notification with the platform's notifier, without waiting for it
+pub fn send(notification: &Notification) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            apple_script_string(&notification.body),
+            apple_script_string(&notification.title)
+        ));
+        command
+    } else if cfg!(unix) {
+        let mut command = Command::new("notify-send");
+        command
+            .arg("--app-name=oxidate")
+            .arg(&notification.title)
+            .arg(&notification.body);
+        command
+    } else {
+        bail!("Desktop notifications are not supported on this platform");
+    };
+
+    let mut child = command
+        .spawn()
+        .context("Failed to run the desktop notifier")?;
+    // Reap the process in the background so the UI never waits on it
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn apple_script_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::{Calendar, EventDateTime, Reminder, Reminders};
+    use chrono::NaiveDate;
+
+    fn local_time(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn timed(id: &str, start: &str, end: &str) -> Event {
+        let at = |time: &str| EventDateTime {
+            date_time: Some(local_time(time).to_rfc3339()),
+            date: None,
+            time_zone: None,
+        };
+        Event {
+            location: Some("Room 4".to_string()),
+            calendar_id: Some("me".to_string()),
+            ..Event::test(id, at(start), at(end))
+        }
+    }
+
+    fn state(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        for event in events {
+            let date: NaiveDate = event.start.to_local().unwrap().date_naive();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    #[test]
+    fn test_take_due_once_per_lead() {
+        let state = state(vec![timed("Standup", "10:00", "10:15")]);
+        let mut notifier = Notifier::new(&[10, 1], local_time("08:00"));
+
+        assert!(notifier.take_due(&state, local_time("09:49")).is_empty());
+
+        let due = notifier.take_due(&state, local_time("09:50"));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].title, "Standup");
+        assert!(due[0].body.starts_with("In 10 min at"));
+        assert!(due[0].body.ends_with("— Room 4"));
+
+        assert!(notifier.take_due(&state, local_time("09:55")).is_empty());
+        assert_eq!(notifier.take_due(&state, local_time("09:59")).len(), 1);
+        assert!(notifier.take_due(&state, local_time("09:59")).is_empty());
+    }
+
+    #[test]
+    fn test_take_due_merges_leads_due_together() {
+        let state = state(vec![timed("Standup", "10:00", "10:15")]);
+        let mut notifier = Notifier::new(&[10, 1], local_time("09:59"));
+
+        assert_eq!(notifier.take_due(&state, local_time("09:59")).len(), 1);
+        assert!(notifier.take_due(&state, local_time("09:59")).is_empty());
+    }
+
+    #[test]
+    fn test_take_due_skips_started_events() {
+        let state = state(vec![timed("Running", "09:00", "11:00")]);
+        let mut notifier = Notifier::new(&[], local_time("09:30"));
+
+        assert!(notifier.take_due(&state, local_time("09:30")).is_empty());
+    }
+
+    #[test]
+    fn test_default_lead() {
+        let state = state(vec![timed("Standup", "10:00", "10:15")]);
+        let mut notifier = Notifier::new(&[], local_time("08:00"));
+
+        assert!(notifier.take_due(&state, local_time("09:54")).is_empty());
+        assert_eq!(notifier.take_due(&state, local_time("09:55")).len(), 1);
+    }
+
+    fn popup(minutes: u32) -> Reminder {
+        Reminder {
+            method: "popup".to_string(),
+            minutes,
+        }
+    }
+
+    #[test]
+    fn test_leads_follow_popup_reminders() {
+        let mut overridden = timed("Standup", "10:00", "10:15");
+        overridden.reminders = Some(Reminders {
+            use_default: false,
+            overrides: Some(vec![
+                popup(15),
+                Reminder {
+                    method: "email".to_string(),
+                    minutes: 30,
+                },
+            ]),
+        });
+        let mut defaults = timed("Review", "11:00", "11:30");
+        defaults.reminders = Some(Reminders {
+            use_default: true,
+            overrides: None,
+        });
+        let mut state = state(vec![overridden, defaults]);
+        state.calendars.push(Calendar {
+            id: "me".to_string(),
+            summary: "Me".to_string(),
+            primary: true,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: vec![popup(20)],
+            selected: true,
+        });
+        let mut notifier = Notifier::new(&[], local_time("08:00"));
+
+        // The email reminder is not a desktop notification
+        assert!(notifier.take_due(&state, local_time("09:30")).is_empty());
+
+        let due = notifier.take_due(&state, local_time("09:45"));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].title, "Standup");
+
+        assert!(notifier.take_due(&state, local_time("10:39")).is_empty());
+        assert_eq!(notifier.take_due(&state, local_time("10:40")).len(), 1);
+    }
+
+    #[test]
+    fn test_no_reminders_means_no_notification() {
+        let mut event = timed("Standup", "10:00", "10:15");
+        event.reminders = Some(Reminders {
+            use_default: false,
+            overrides: None,
+        });
+        let state = state(vec![event]);
+        let mut notifier = Notifier::new(&[], local_time("08:00"));
+
+        assert!(notifier.take_due(&state, local_time("09:59")).is_empty());
+    }
+
+    #[test]
+    fn test_snooze_repeats_the_last_notification() {
+        let state = state(vec![timed("Standup", "10:00", "10:15")]);
+        let mut notifier = Notifier::new(&[], local_time("08:00"));
+
+        assert_eq!(notifier.snooze(5, local_time("09:50")), None);
+        assert_eq!(notifier.take_due(&state, local_time("09:55")).len(), 1);
+        assert_eq!(
+            notifier.snooze(5, local_time("09:55")).as_deref(),
+            Some("Standup")
+        );
+
+        assert!(notifier.take_due(&state, local_time("09:59")).is_empty());
+        let due = notifier.take_due(&state, local_time("10:00"));
+        assert_eq!(due.len(), 1);
+        assert!(due[0].body.starts_with("Started at"));
+
+        // Snoozing again replaces the pending snooze
+        notifier.snooze(10, local_time("10:00"));
+        notifier.snooze(5, local_time("10:01"));
+        assert_eq!(notifier.take_due(&state, local_time("10:10")).len(), 1);
+
+        // Nothing to snooze once the event is over
+        assert_eq!(notifier.snooze(5, local_time("10:15")), None);
+    }
+
+    #[test]
+    fn test_apple_script_string_escapes_quotes() {
+        assert_eq!(apple_script_string(r#"Say "hi""#), r#""Say \"hi\"""#);
+    }
+}
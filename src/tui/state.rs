@@ -1,12 +1,55 @@
-use chrono::{Datelike, Local, NaiveDate};
-use std::collections::HashMap;
-
-use crate::calendar::models::{Calendar, Event};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::action::Action;
+use super::calendar_prefs::CalendarPrefs;
+use super::fetcher::{event_dates, extract_date_from_event, FailedCalendar};
+use super::form::EventForm;
+use crate::calendar::client::ApiStats;
+use crate::calendar::models::{Attachment, Attendee, Calendar, Event, EventPatch};
+use crate::calendar::queue::Mutation;
+use crate::commands;
+use crate::settings::Settings;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewFocus {
     Calendar,
     Events,
+    Sidebar,
+}
+
+/// What the TUI shows first (`--view`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartView {
+    /// The month calendar
+    #[default]
+    Month,
+    /// Today's events
+    Day,
+    /// Today's events on a 24-hour timeline
+    Timeline,
+    /// This week, a column per day
+    Week,
+    /// Today's and the coming days' events
+    Agenda,
+}
+
+/// How the events pane draws `EventsViewMode::List`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListLayout {
+    /// The selected day's events, one per row
+    #[default]
+    Day,
+    /// The selected day on a 24-hour timeline (`v`)
+    Timeline,
+    /// The week of the selected day, a column per day (`g w`)
+    Week,
+    /// The selected day and the following ones with events (`g a`)
+    Agenda,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +60,164 @@ pub enum EventsViewMode {
         scroll_offset: usize,
         max_scroll: usize,
     },
+    /// The new event form, `AppState::event_form`
+    Create,
+    /// The same form editing the event shown in the details
+    Edit {
+        event_index: usize,
+    },
+}
+
+/// How long navigation has to settle before the date range is extended
+pub const NAVIGATION_DEBOUNCE: Duration = Duration::from_millis(300);
+/// How long a toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// A short message shown over the UI until it expires
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadProgress {
+    pub calendar: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Criteria every view applies before showing events
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    pub hidden_calendars: HashSet<String>,
+    /// Case-insensitive match against summary, description and location
+    pub keyword: String,
+    pub show_declined: bool,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        if event
+            .calendar_id
+            .as_ref()
+            .is_some_and(|id| self.hidden_calendars.contains(id))
+        {
+            return false;
+        }
+
+        if !self.show_declined && event.is_declined_by_self() {
+            return false;
+        }
+
+        self.matches_keyword(event)
+    }
+
+    fn matches_keyword(&self, event: &Event) -> bool {
+        if self.keyword.is_empty() {
+            return true;
+        }
+
+        [&event.summary, &event.description, &event.location]
+            .into_iter()
+            .flatten()
+            .any(|text| contains_ignore_case(text, &self.keyword))
+    }
+
+    /// e.g. "\"standup\", 2 calendars hidden"; None when nothing is filtered
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+
+        if !self.keyword.is_empty() {
+            parts.push(format!("\"{}\"", self.keyword));
+        }
+        if !self.hidden_calendars.is_empty() {
+            let count = self.hidden_calendars.len();
+            parts.push(format!(
+                "{} calendar{} hidden",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        if self.show_declined {
+            parts.push("showing declined".to_string());
+        }
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
+    }
+}
+
+/// Case-insensitive `text.contains(needle)` without lowercased copies, as the
+/// filter runs for every event on every frame
+fn contains_ignore_case(text: &str, needle: &str) -> bool {
+    text.char_indices().any(|(i, _)| {
+        let mut rest = text[i..].chars().flat_map(char::to_lowercase);
+        needle
+            .chars()
+            .flat_map(char::to_lowercase)
+            .all(|c| rest.next() == Some(c))
+    })
+}
+
+/// Days are stored in `Event::start_order`, an event's copies in other
+/// calendars (see `Event::is_copy_of`) next to each other
+fn day_order(event: &Event) -> (bool, Option<DateTime<Local>>, String) {
+    let (timed, start) = event.start_order();
+    let uid = event.ical_uid.as_ref().unwrap_or(&event.id);
+    (timed, start, uid.clone())
+}
+
+fn sort_day_events(day_events: &mut [Event]) {
+    day_events.sort_by_cached_key(day_order);
+}
+
+/// Merge `new_events`, in start order as fetched, into the sorted
+/// `day_events` without sorting those again
+fn merge_sorted(day_events: &mut Vec<Event>, mut new_events: Vec<Event>) {
+    // Only ties in start order move
+    sort_day_events(&mut new_events);
+    let existing = std::mem::take(day_events);
+    day_events.reserve(existing.len() + new_events.len());
+
+    let mut existing = existing.into_iter().peekable();
+    let mut new_events = new_events.into_iter().peekable();
+    loop {
+        let take_new = match (existing.peek(), new_events.peek()) {
+            (Some(old), Some(new)) => day_order(new) < day_order(old),
+            (Some(_), None) => false,
+            (None, Some(_)) => true,
+            (None, None) => break,
+        };
+        day_events.extend(if take_new {
+            new_events.next()
+        } else {
+            existing.next()
+        });
+    }
+}
+
+/// Identifies an event independently of its position in the day's list
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EventKey {
+    calendar_id: Option<String>,
+    event_id: String,
+}
+
+impl EventKey {
+    fn of(event: &Event) -> Self {
+        Self {
+            calendar_id: event.calendar_id.clone(),
+            event_id: event.id.clone(),
+        }
+    }
+
+    fn matches(&self, event: &Event) -> bool {
+        self.event_id == event.id && self.calendar_id == event.calendar_id
+    }
 }
 
 #[derive(Debug)]
@@ -26,12 +227,62 @@ pub struct AppState {
     pub calendars: Vec<Calendar>,
     pub events: HashMap<NaiveDate, Vec<Event>>,
     pub loading: bool,
+    pub load_progress: Option<LoadProgress>,
     pub error: Option<String>,
+    /// Calendars whose events failed to load in the last fetch
+    pub failed_calendars: Vec<FailedCalendar>,
     pub view_focus: ViewFocus,
     pub selected_event_index: Option<usize>,
     pub events_view_mode: EventsViewMode,
+    /// Attachment of the event in the details view that `o` opens
+    pub selected_attachment: usize,
+    /// What is typed into the new event form while it is open
+    pub event_form: Option<EventForm>,
     pub current_date_range: DateRange,
     pub current_month: (i32, u32),
+    /// Offline changes still waiting to be sent to the server
+    pub pending_mutations: usize,
+    /// A refresh asked for while a fetch was running, started once it ends
+    pub refresh_pending: bool,
+    /// Events deleted locally whose deletion the server has not confirmed yet
+    pub unconfirmed_deletes: Vec<Event>,
+    /// When the selected date last changed through navigation
+    pub last_navigation: Option<Instant>,
+    pub filter: EventFilter,
+    /// Keys are typed into the keyword filter instead of being treated as commands
+    pub editing_keyword: bool,
+    /// Quit was requested with unsaved changes and awaits confirmation
+    pub confirming_quit: bool,
+    /// Deleting the selected event awaits confirmation
+    pub confirming_delete: bool,
+    /// User-defined calendar order (ids); see `ordered_calendars`
+    pub calendar_order: Vec<String>,
+    /// Calendar ids whose visibility was settled in an earlier load
+    pub seen_calendars: HashSet<String>,
+    /// Calendar ids or names to limit fetching to (`--calendar`); empty for all
+    pub only_calendars: Vec<String>,
+    /// Launched with `--read-only`: nothing is sent to the server
+    pub read_only: bool,
+    /// Desktop notifications silenced for this session with `N`
+    pub notifications_muted: bool,
+    /// Formats, working hours and the other options the session was started with
+    pub settings: Arc<Settings>,
+    pub show_sidebar: bool,
+    pub list_layout: ListLayout,
+    /// Cursor position in the sidebar, an index into `ordered_calendars`
+    pub sidebar_index: usize,
+    pub show_stats: bool,
+    pub show_log: bool,
+    /// Keys typed so far of an unfinished chord such as "g t"
+    pub pending_keys: String,
+    /// Digits typed in the calendar pane, jumped to on Enter
+    pub typed_day: String,
+    /// Last navigation or mutating action, replayed by `.`
+    pub last_repeatable: Option<Action>,
+    /// When the last fetch of calendars and events completed
+    pub last_sync: Option<DateTime<Local>>,
+    pub api_stats: ApiStats,
+    pub toast: Option<Toast>,
 }
 
 impl AppState {
@@ -45,27 +296,118 @@ impl AppState {
             calendars: Vec::new(),
             events: HashMap::new(),
             loading: true,
+            load_progress: None,
             error: None,
+            failed_calendars: Vec::new(),
             view_focus: ViewFocus::Calendar,
             selected_event_index: None,
+            selected_attachment: 0,
+            event_form: None,
             events_view_mode: EventsViewMode::List,
             current_date_range,
             current_month,
+            pending_mutations: 0,
+            refresh_pending: false,
+            unconfirmed_deletes: Vec::new(),
+            last_navigation: None,
+            filter: EventFilter::default(),
+            editing_keyword: false,
+            confirming_quit: false,
+            confirming_delete: false,
+            calendar_order: Vec::new(),
+            seen_calendars: HashSet::new(),
+            only_calendars: Vec::new(),
+            read_only: false,
+            notifications_muted: false,
+            settings: Arc::default(),
+            show_sidebar: true,
+            list_layout: ListLayout::Day,
+            sidebar_index: 0,
+            show_stats: false,
+            show_log: false,
+            pending_keys: String::new(),
+            typed_day: String::new(),
+            last_repeatable: None,
+            last_sync: None,
+            api_stats: ApiStats::default(),
+            toast: None,
         }
     }
 
-    pub fn get_events_for_date(&self, date: NaiveDate) -> Vec<&Event> {
+    /// Events on `date` that pass the current filter, in storage order, i.e.
+    /// sorted once when merged rather than on every frame. An event on several
+    /// visible calendars is listed once, see `other_calendars_of`; merging
+    /// stores such copies next to each other, so only neighbours are compared.
+    pub fn get_events_for_date(&self, date: NaiveDate) -> impl Iterator<Item = &Event> {
+        let mut previous: Option<&Event> = None;
         self.events
             .get(&date)
-            .map(|v| v.iter().collect())
-            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter(|event| self.filter.matches(event))
+            .filter(move |event| {
+                let is_copy = previous.is_some_and(|shown| shown.is_copy_of(event));
+                previous = Some(event);
+                !is_copy
+            })
+    }
+
+    /// The other visible calendars `event` is also on, whose copies
+    /// `get_events_for_date` leaves out
+    pub fn other_calendars_of(&self, event: &Event) -> Vec<&Calendar> {
+        let Some(date) = extract_date_from_event(event) else {
+            return Vec::new();
+        };
+        self.events
+            .get(&date)
+            .into_iter()
+            .flatten()
+            .filter(|other| self.filter.matches(other) && other.is_copy_of(event))
+            .filter_map(|other| self.get_calendar_by_id(other.calendar_id.as_deref()?))
+            .collect()
     }
 
     pub fn has_events(&self, date: NaiveDate) -> bool {
         self.events
             .get(&date)
-            .map(|v| !v.is_empty())
-            .unwrap_or(false)
+            .is_some_and(|v| v.iter().any(|event| self.filter.matches(event)))
+    }
+
+    /// Change the filter while keeping the selected event selected if it stays visible
+    pub fn update_filter(&mut self, update: impl FnOnce(&mut EventFilter)) {
+        let selected = self.selected_event_key();
+        update(&mut self.filter);
+        self.restore_event_selection(selected);
+    }
+
+    /// The event under the cursor in the events pane
+    pub fn selected_event(&self) -> Option<&Event> {
+        let index = self.selected_event_index?;
+        self.get_events_for_date(self.selected_date).nth(index)
+    }
+
+    /// Calendar of the currently selected event
+    pub fn selected_event_calendar(&self) -> Option<String> {
+        self.selected_event()
+            .and_then(|event| event.calendar_id.clone())
+    }
+
+    pub fn show_toast(&mut self, message: impl Into<String>) {
+        self.toast = Some(Toast {
+            message: message.into(),
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Drop the toast once it has been shown long enough
+    pub fn expire_toast(&mut self, now: Instant) {
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| now.saturating_duration_since(toast.shown_at) >= TOAST_DURATION)
+        {
+            self.toast = None;
+        }
     }
 
     pub fn move_selected_date(&mut self, days: i64) {
@@ -77,28 +419,119 @@ impl AppState {
         }
     }
 
-    pub fn move_to_next_week(&mut self) {
-        self.move_selected_date(7);
+    /// Panes in Tab order; the sidebar only takes focus while it is shown
+    fn focus_order(&self) -> Vec<ViewFocus> {
+        let mut order = vec![ViewFocus::Calendar, ViewFocus::Events];
+        if self.show_sidebar && !self.calendars.is_empty() {
+            order.push(ViewFocus::Sidebar);
+        }
+        order
+    }
+
+    pub fn focus_next(&mut self) {
+        self.cycle_focus(1);
     }
 
-    pub fn move_to_prev_week(&mut self) {
-        self.move_selected_date(-7);
+    pub fn focus_prev(&mut self) {
+        self.cycle_focus(-1);
     }
 
-    pub fn toggle_focus(&mut self) {
-        self.view_focus = match self.view_focus {
-            ViewFocus::Calendar => ViewFocus::Events,
-            ViewFocus::Events => ViewFocus::Calendar,
-        };
+    fn cycle_focus(&mut self, step: isize) {
+        let order = self.focus_order();
+        let current = order
+            .iter()
+            .position(|focus| *focus == self.view_focus)
+            .unwrap_or(0);
+        let next = (current as isize + step).rem_euclid(order.len() as isize) as usize;
+        self.view_focus = order[next];
+    }
+
+    /// Move the sidebar cursor, wrapping around like the event selection
+    pub fn move_sidebar_selection(&mut self, delta: i64) {
+        let count = self.calendars.len() as i64;
+        if count == 0 {
+            return;
+        }
+        self.sidebar_index = (self.sidebar_index as i64 + delta).rem_euclid(count) as usize;
+    }
+
+    /// Calendar under the sidebar cursor
+    pub fn sidebar_calendar_id(&self) -> Option<String> {
+        self.ordered_calendars()
+            .get(self.sidebar_index)
+            .map(|calendar| calendar.id.clone())
+    }
+
+    pub fn toggle_sidebar_calendar(&mut self) {
+        if let Some(id) = self.sidebar_calendar_id() {
+            self.update_filter(|filter| {
+                if !filter.hidden_calendars.remove(&id) {
+                    filter.hidden_calendars.insert(id);
+                }
+            });
+        }
+    }
+
+    /// Move the calendar under the cursor up or down in the user-defined order.
+    /// The cursor follows it. The primary calendar always sorts first, so moving
+    /// other calendars above it has no visible effect.
+    pub fn move_sidebar_calendar(&mut self, delta: i64) {
+        let mut ids: Vec<String> = self
+            .ordered_calendars()
+            .iter()
+            .map(|calendar| calendar.id.clone())
+            .collect();
+        let target = self.sidebar_index as i64 + delta;
+        if self.sidebar_index >= ids.len() || target < 0 || target >= ids.len() as i64 {
+            return;
+        }
+
+        ids.swap(self.sidebar_index, target as usize);
+        // Keep the place of calendars not loaded this session (e.g. with --calendar)
+        let unloaded: Vec<String> = self
+            .calendar_order
+            .iter()
+            .filter(|id| !ids.contains(id))
+            .cloned()
+            .collect();
+        ids.extend(unloaded);
+        self.calendar_order = ids;
+        self.sidebar_index = target as usize;
     }
 
     pub fn jump_to_today(&mut self) {
         self.selected_date = self.today;
     }
 
+    /// Select `day` of the selected month, clamped to the month's length
+    pub fn jump_to_day_of_month(&mut self, day: u32) {
+        let last_day =
+            DateRange::last_day_of_month(self.selected_date.year(), self.selected_date.month());
+        if let Some(date) = self.selected_date.with_day(day.clamp(1, last_day.day())) {
+            self.selected_date = date;
+        }
+    }
+
+    /// Advance "today" when the wall-clock date changed while the app was open.
+    /// A selection that was sitting on today follows it to the new day.
+    /// Returns true if the date changed.
+    pub fn roll_over_to(&mut self, today: NaiveDate) -> bool {
+        if today == self.today {
+            return false;
+        }
+
+        if self.selected_date == self.today {
+            self.selected_date = today;
+            self.reset_event_selection();
+        }
+
+        self.today = today;
+        self.current_month = (today.year(), today.month());
+        true
+    }
+
     pub fn move_event_selection_down(&mut self) {
-        let events = self.get_events_for_date(self.selected_date);
-        let event_count = events.len();
+        let event_count = self.get_events_for_date(self.selected_date).count();
 
         if event_count == 0 {
             return;
@@ -111,8 +544,7 @@ impl AppState {
     }
 
     pub fn move_event_selection_up(&mut self) {
-        let events = self.get_events_for_date(self.selected_date);
-        let event_count = events.len();
+        let event_count = self.get_events_for_date(self.selected_date).count();
 
         if event_count == 0 {
             return;
@@ -132,6 +564,26 @@ impl AppState {
                 scroll_offset: 0,
                 max_scroll: 0,
             };
+            self.selected_attachment = 0;
+        }
+    }
+
+    /// The attachment `o` opens in the details view
+    pub fn selected_attachment(&self) -> Option<&Attachment> {
+        self.selected_event()?
+            .attachments
+            .as_ref()?
+            .get(self.selected_attachment)
+    }
+
+    /// Move to the next attachment of the selected event, wrapping around
+    pub fn select_next_attachment(&mut self) {
+        let count = self
+            .selected_event()
+            .and_then(|event| event.attachments.as_ref())
+            .map_or(0, Vec::len);
+        if count > 0 {
+            self.selected_attachment = (self.selected_attachment + 1) % count;
         }
     }
 
@@ -139,6 +591,87 @@ impl AppState {
         self.events_view_mode = EventsViewMode::List;
     }
 
+    /// Calendars new events can go to, in display order; local calendars are
+    /// read-only
+    pub fn writable_calendars(&self) -> Vec<&Calendar> {
+        self.ordered_calendars()
+            .into_iter()
+            .filter(|calendar| matches!(calendar.access_role.as_str(), "owner" | "writer"))
+            .collect()
+    }
+
+    /// Open the new event form for the selected date, on the default calendar
+    /// if it is writable
+    pub fn open_event_form(&mut self) {
+        let default =
+            commands::resolve_calendar(&self.calendars, None, &self.settings.event_defaults)
+                .ok()
+                .map(|calendar| calendar.id.clone());
+        let calendar = self
+            .writable_calendars()
+            .iter()
+            .position(|calendar| Some(&calendar.id) == default.as_ref())
+            .unwrap_or(0);
+
+        self.event_form = Some(EventForm::new(self.selected_date, calendar));
+        self.events_view_mode = EventsViewMode::Create;
+        self.view_focus = ViewFocus::Events;
+    }
+
+    /// Open the form filled in with the event shown in the details
+    pub fn open_edit_form(&mut self) {
+        let EventsViewMode::Details { event_index, .. } = self.events_view_mode else {
+            return;
+        };
+        let Some(event) = self.selected_event() else {
+            return;
+        };
+        self.event_form = Some(EventForm::for_event(event));
+        self.events_view_mode = EventsViewMode::Edit { event_index };
+    }
+
+    /// Close the form, back to the details of an edited event
+    pub fn close_event_form(&mut self) {
+        self.event_form = None;
+        self.events_view_mode = match self.events_view_mode {
+            EventsViewMode::Edit { event_index } => EventsViewMode::Details {
+                event_index,
+                scroll_offset: 0,
+                max_scroll: 0,
+            },
+            _ => EventsViewMode::List,
+        };
+    }
+
+    /// Whether the event form is open, for a new event or an edited one
+    pub fn is_form_open(&self) -> bool {
+        matches!(
+            self.events_view_mode,
+            EventsViewMode::Create | EventsViewMode::Edit { .. }
+        )
+    }
+
+    /// The calendar id and event of the open form, or why it can't be saved
+    pub fn form_event(&self) -> Result<(String, Event)> {
+        let form = self.event_form.as_ref().context("No event form is open")?;
+        let calendar = self
+            .writable_calendars()
+            .get(form.calendar)
+            .map(|calendar| calendar.id.clone())
+            .context("No calendar to add the event to")?;
+        Ok((
+            calendar,
+            form.to_event(self.settings.event_defaults.duration)?,
+        ))
+    }
+
+    /// The event being edited and the changes made to it in the form
+    pub fn form_patch(&self) -> Result<(Event, EventPatch)> {
+        let form = self.event_form.as_ref().context("No event form is open")?;
+        let event = form.original.clone().context("No event is being edited")?;
+        Ok((event, form.to_patch(self.settings.event_defaults.duration)?))
+    }
+
     pub fn scroll_event_details_down(&mut self) {
         if let EventsViewMode::Details {
             event_index,
@@ -209,14 +742,307 @@ impl AppState {
         selected_month == start_month || selected_month == end_month
     }
 
+    /// Like `needs_date_range_refresh`, but holds off while the user is still
+    /// navigating so holding a key across months triggers a single fetch
+    pub fn range_refresh_due(&self, now: Instant) -> bool {
+        self.needs_date_range_refresh()
+            && self
+                .last_navigation
+                .is_none_or(|at| now.saturating_duration_since(at) >= NAVIGATION_DEBOUNCE)
+    }
+
     pub fn update_date_range(&mut self, new_range: DateRange) {
         self.current_date_range = new_range;
     }
 
-    pub fn trim_events_to_25_month_span(&mut self) {
-        let cache_range = DateRange::twenty_five_month_span(self.selected_date);
+    fn selected_event_key(&self) -> Option<EventKey> {
+        let index = self.selected_event_index?;
+        self.get_events_for_date(self.selected_date)
+            .nth(index)
+            .map(EventKey::of)
+    }
+
+    /// Point the selection (and open details) back at the event identified by
+    /// `key` after the day's events were replaced; clears it if the event is gone
+    fn restore_event_selection(&mut self, key: Option<EventKey>) {
+        let Some(key) = key else {
+            return;
+        };
+
+        let position = self
+            .get_events_for_date(self.selected_date)
+            .position(|event| key.matches(event));
+
+        match position {
+            Some(index) => {
+                self.selected_event_index = Some(index);
+                if let EventsViewMode::Details { event_index, .. } = &mut self.events_view_mode {
+                    *event_index = index;
+                }
+            }
+            None => self.reset_event_selection(),
+        }
+    }
+
+    /// Replace the attendees of an event on every day it spans, e.g. with the
+    /// user's answer to the invitation, without waiting for the next fetch
+    pub fn update_attendees(&mut self, calendar_id: &str, event_id: &str, attendees: &[Attendee]) {
+        let selected = self.selected_event_key();
+        for event in self.events.values_mut().flatten() {
+            if event.id == event_id && event.calendar_id.as_deref() == Some(calendar_id) {
+                event.attendees = Some(attendees.to_vec());
+            }
+        }
+        // A declined event may now be filtered out
+        self.restore_event_selection(selected);
+    }
+
+    /// Drop a deleted event from every day it spans, without waiting for
+    /// the next fetch; the selection stays at the same place in the list
+    pub fn remove_event(&mut self, calendar_id: &str, event_id: &str) {
+        for day_events in self.events.values_mut() {
+            day_events.retain(|event| {
+                event.id != event_id || event.calendar_id.as_deref() != Some(calendar_id)
+            });
+        }
+        self.events.retain(|_, day_events| !day_events.is_empty());
+
+        let count = self.get_events_for_date(self.selected_date).count();
+        self.events_view_mode = EventsViewMode::List;
+        self.selected_event_index = match self.selected_event_index {
+            _ if count == 0 => None,
+            Some(index) => Some(index.min(count - 1)),
+            None => None,
+        };
+    }
+
+    /// Put back deleted events whose deletion a replay dropped (conflict or
+    /// rejection). With nothing left pending, the other deletions went through.
+    pub fn restore_dropped_deletes(&mut self, dropped: &[Mutation], pending: usize) {
+        let selected = self.selected_event_key();
+        let mut touched = HashSet::new();
+
+        for event in std::mem::take(&mut self.unconfirmed_deletes) {
+            let was_dropped = dropped.iter().any(|mutation| {
+                matches!(mutation, Mutation::Delete { calendar_id, event_id, .. }
+                    if *event_id == event.id && event.calendar_id.as_deref() == Some(calendar_id))
+            });
+            if !was_dropped {
+                if pending > 0 {
+                    self.unconfirmed_deletes.push(event);
+                }
+                continue;
+            }
+
+            // The fetch after the replay usually brought it back already
+            for date in event_dates(&event) {
+                let day_events = self.events.entry(date).or_default();
+                let cached = day_events
+                    .iter()
+                    .any(|cached| cached.id == event.id && cached.calendar_id == event.calendar_id);
+                if !cached {
+                    day_events.push(event.clone());
+                    touched.insert(date);
+                }
+            }
+        }
+
+        for date in touched {
+            if let Some(day_events) = self.events.get_mut(&date) {
+                sort_day_events(day_events);
+            }
+        }
+        self.restore_event_selection(selected);
+    }
+
+    /// Replace one calendar's cached events inside `range` with freshly fetched ones,
+    /// which each day lists in start order (see `fetch_calendar_data`).
+    /// Called as each calendar finishes loading, so other calendars - including ones
+    /// that failed to load - keep showing their last known events.
+    pub fn merge_calendar_events(
+        &mut self,
+        range: &DateRange,
+        calendar_id: &str,
+        events: HashMap<NaiveDate, Vec<Event>>,
+    ) {
+        let selected = self.selected_event_key();
+
+        for (date, day_events) in self.events.iter_mut() {
+            if *date >= range.start && *date <= range.end {
+                day_events.retain(|event| event.calendar_id.as_deref() != Some(calendar_id));
+            }
+        }
+
+        for (date, new_events) in events {
+            merge_sorted(self.events.entry(date).or_default(), new_events);
+        }
+
+        self.events.retain(|_, day_events| !day_events.is_empty());
+        self.restore_event_selection(selected);
+    }
+
+    /// Add events found in the local index on days outside the fetched range,
+    /// where the fetched events are authoritative; returns how many were new
+    pub fn merge_indexed_events(&mut self, events: Vec<Event>) -> usize {
+        let range = self.current_date_range.clone();
+        let mut added = 0;
+        let mut touched = HashSet::new();
+
+        for event in events {
+            let mut is_new = false;
+            for date in event_dates(&event) {
+                if date >= range.start && date <= range.end {
+                    continue;
+                }
+                let day_events = self.events.entry(date).or_default();
+                let cached = day_events
+                    .iter()
+                    .any(|cached| cached.id == event.id && cached.calendar_id == event.calendar_id);
+                if !cached {
+                    day_events.push(event.clone());
+                    touched.insert(date);
+                    is_new = true;
+                }
+            }
+            if is_new {
+                added += 1;
+            }
+        }
+
+        for date in touched {
+            if let Some(day_events) = self.events.get_mut(&date) {
+                sort_day_events(day_events);
+            }
+        }
+
+        added
+    }
+
+    /// Drop cached events of calendars that are no longer in `self.calendars`,
+    /// e.g. after unsubscribing from a calendar elsewhere
+    pub fn retain_known_calendars(&mut self) {
+        let selected = self.selected_event_key();
+        let known: HashSet<&str> = self.calendars.iter().map(|c| c.id.as_str()).collect();
+
+        for day_events in self.events.values_mut() {
+            day_events.retain(|event| {
+                event
+                    .calendar_id
+                    .as_deref()
+                    .is_none_or(|id| known.contains(id))
+            });
+        }
+
+        self.events.retain(|_, day_events| !day_events.is_empty());
+        self.restore_event_selection(selected);
+    }
+
+    /// The next `n` visible events that have not ended yet, soonest first
+    pub fn upcoming(&self, n: usize) -> Vec<&Event> {
+        self.upcoming_from(Local::now(), n)
+    }
+
+    pub fn upcoming_from(&self, now: DateTime<Local>, n: usize) -> Vec<&Event> {
+        let mut dates: Vec<&NaiveDate> = self
+            .events
+            .keys()
+            .filter(|date| **date >= now.date_naive())
+            .collect();
+        dates.sort();
+
+        let mut upcoming: Vec<&Event> = Vec::new();
+        // Multi-day all-day events are listed on each of their days
+        let mut seen = HashSet::new();
+        for date in dates {
+            // Days are stored in start order
+            upcoming.extend(
+                self.get_events_for_date(*date)
+                    .filter(|event| event.end.to_local().is_none_or(|end| end > now))
+                    .filter(|event| event.start.to_local().is_some())
+                    .filter(|event| seen.insert((event.calendar_id.as_deref(), event.id.as_str()))),
+            );
+            if upcoming.len() >= n {
+                break;
+            }
+        }
+
+        upcoming.truncate(n);
+        upcoming
+    }
+
+    /// Number of cached events, regardless of the filter; multi-day events
+    /// count once, on their first day
+    pub fn event_count(&self) -> usize {
+        self.events
+            .iter()
+            .flat_map(|(date, events)| events.iter().map(move |event| (date, event)))
+            .filter(|(date, event)| {
+                extract_date_from_event(event).is_none_or(|start| start == **date)
+            })
+            .count()
+    }
+
+    /// Lines for the statistics overlay
+    pub fn stats_lines(&self) -> Vec<String> {
+        let last_sync = match self.last_sync {
+            Some(at) => at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => "never".to_string(),
+        };
+
+        vec![
+            format!("Last sync: {}", last_sync),
+            format!("Calendars loaded: {}", self.calendars.len()),
+            format!("Events loaded: {}", self.event_count()),
+            format!("API calls this session: {}", self.api_stats.calls),
+            format!("Rate-limit backoffs: {}", self.api_stats.backoffs),
+        ]
+    }
+
+    /// Work that would be lost or left unsynced by quitting now
+    pub fn unsaved_changes(&self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if self.pending_mutations > 0 {
+            changes.push(format!(
+                "{} offline change{} not synced yet",
+                self.pending_mutations,
+                if self.pending_mutations == 1 { "" } else { "s" }
+            ));
+        }
+
+        changes
+    }
+
+    /// e.g. "2 calendars failed to load (Work, Holidays)"
+    pub fn failure_summary(&self) -> Option<String> {
+        if self.failed_calendars.is_empty() {
+            return None;
+        }
+
+        let count = self.failed_calendars.len();
+        let names: Vec<&str> = self
+            .failed_calendars
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+
+        Some(format!(
+            "{} calendar{} failed to load ({})",
+            count,
+            if count == 1 { "" } else { "s" },
+            names.join(", ")
+        ))
+    }
+
+    /// Drop the events of months farther than the memory horizon from the
+    /// selected date, so browsing years of history doesn't pile them up in
+    /// memory; they are fetched again, or read from the index, when
+    /// navigated back to
+    pub fn evict_distant_events(&mut self) {
+        let cache_range =
+            DateRange::months_around(self.selected_date, self.settings.memory_horizon);
 
-        // Collect dates to remove (those outside the 25-month span)
+        // Collect dates to remove (those outside the horizon)
         let dates_to_remove: Vec<NaiveDate> = self
             .events
             .keys()
@@ -239,6 +1065,17 @@ impl AppState {
         }
     }
 
+    /// Status text while loading, e.g. "Loading 3/12: Team Calendar"
+    pub fn loading_message(&self) -> String {
+        match self.load_progress {
+            Some(ref progress) => format!(
+                "Loading {}/{}: {}",
+                progress.done, progress.total, progress.calendar
+            ),
+            None => "Loading calendars and events...".to_string(),
+        }
+    }
+
     /// Get calendar background color by calendar_id
     /// Returns None if calendar not found or has no color
     pub fn get_calendar_color(&self, calendar_id: &str) -> Option<String> {
@@ -253,28 +1090,130 @@ impl AppState {
     pub fn get_calendar_by_id(&self, calendar_id: &str) -> Option<&Calendar> {
         self.calendars.iter().find(|cal| cal.id == calendar_id)
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct DateRange {
-    pub start: NaiveDate,
-    pub end: NaiveDate,
-}
+    /// The selected event is on a calendar the user may only read, such as
+    /// a subscribed or a local khal calendar
+    pub fn selected_event_is_read_only(&self) -> bool {
+        self.selected_event()
+            .and_then(|event| event.calendar_id.as_deref())
+            .and_then(|id| self.get_calendar_by_id(id))
+            .is_some_and(|calendar| {
+                matches!(calendar.access_role.as_str(), "reader" | "freeBusyReader")
+            })
+    }
 
-impl DateRange {
-    pub fn five_month_span(center_date: NaiveDate) -> Self {
-        // Calculate start: 2 months before
-        let start = if center_date.month() <= 2 {
-            // Handle year boundary
-            let year = center_date.year() - 1;
-            let month = center_date.month() + 10; // 12 - (2 - month)
-            NaiveDate::from_ymd_opt(year, month, 1).unwrap()
-        } else {
-            let month = center_date.month() - 2;
-            NaiveDate::from_ymd_opt(center_date.year(), month, 1).unwrap()
+    /// Calendars in display order: primary first, then the user-defined order,
+    /// then any calendars not yet ordered, alphabetically
+    pub fn ordered_calendars(&self) -> Vec<&Calendar> {
+        let mut calendars: Vec<&Calendar> = self.calendars.iter().collect();
+        calendars.sort_by_key(|cal| {
+            let position = self
+                .calendar_order
+                .iter()
+                .position(|id| id == &cal.id)
+                .unwrap_or(usize::MAX);
+            (!cal.primary, position, cal.summary.to_lowercase())
+        });
+        calendars
+    }
+
+    pub fn is_calendar_visible(&self, calendar_id: &str) -> bool {
+        !self.filter.hidden_calendars.contains(calendar_id)
+    }
+
+    pub fn open_view(&mut self, view: StartView) {
+        self.view_focus = match view {
+            StartView::Month => ViewFocus::Calendar,
+            StartView::Day | StartView::Timeline | StartView::Week | StartView::Agenda => {
+                ViewFocus::Events
+            }
+        };
+        self.list_layout = match view {
+            StartView::Timeline => ListLayout::Timeline,
+            StartView::Week => ListLayout::Week,
+            StartView::Agenda => ListLayout::Agenda,
+            StartView::Month | StartView::Day => ListLayout::Day,
         };
+    }
 
-        // Calculate end: 2 months after, last day of that month
+    /// Draw the event list as `layout`, or as the day's list again when it
+    /// already is
+    pub fn toggle_list_layout(&mut self, layout: ListLayout) {
+        self.list_layout = if self.list_layout == layout {
+            ListLayout::Day
+        } else {
+            layout
+        };
+    }
+
+    pub fn apply_calendar_prefs(&mut self, prefs: CalendarPrefs) {
+        self.calendar_order = prefs.order;
+        self.filter.hidden_calendars = prefs.hidden.into_iter().collect();
+        self.seen_calendars = prefs.seen.into_iter().collect();
+    }
+
+    pub fn calendar_prefs(&self) -> CalendarPrefs {
+        let sorted = |ids: &HashSet<String>| {
+            let mut ids: Vec<String> = ids.iter().cloned().collect();
+            ids.sort();
+            ids
+        };
+
+        CalendarPrefs {
+            order: self.calendar_order.clone(),
+            hidden: sorted(&self.filter.hidden_calendars),
+            seen: sorted(&self.seen_calendars),
+        }
+    }
+
+    /// Hide calendars seen for the first time that are unselected in Google
+    /// Calendar, so its own choice carries over; after that the sidebar
+    /// decides. Returns whether the preferences changed.
+    pub fn adopt_calendar_selection(&mut self) -> bool {
+        let new: Vec<(String, bool)> = self
+            .calendars
+            .iter()
+            .filter(|calendar| !self.seen_calendars.contains(&calendar.id))
+            .map(|calendar| (calendar.id.clone(), calendar.selected))
+            .collect();
+        if new.is_empty() {
+            return false;
+        }
+
+        let unselected: Vec<String> = new
+            .iter()
+            .filter(|(_, selected)| !selected)
+            .map(|(id, _)| id.clone())
+            .collect();
+        if !unselected.is_empty() {
+            self.update_filter(|filter| filter.hidden_calendars.extend(unselected));
+        }
+        self.seen_calendars
+            .extend(new.into_iter().map(|(id, _)| id));
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn five_month_span(center_date: NaiveDate) -> Self {
+        // Calculate start: 2 months before
+        let start = if center_date.month() <= 2 {
+            // Handle year boundary
+            let year = center_date.year() - 1;
+            let month = center_date.month() + 10; // 12 - (2 - month)
+            NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+        } else {
+            let month = center_date.month() - 2;
+            NaiveDate::from_ymd_opt(center_date.year(), month, 1).unwrap()
+        };
+
+        // Calculate end: 2 months after, last day of that month
         let end = if center_date.month() >= 11 {
             // Handle year boundary
             let year = center_date.year() + 1;
@@ -288,45 +1227,17 @@ impl DateRange {
         Self { start, end }
     }
 
-    pub fn twenty_five_month_span(center_date: NaiveDate) -> Self {
-        // Calculate start: 12 months before center date
-        let start_year;
-        let start_month;
-
-        if center_date.month() <= 12 {
-            let months_back = 12;
-            if center_date.month() as i32 - months_back <= 0 {
-                // Need to go to previous year
-                start_year = center_date.year() - 1;
-                start_month = (12 + center_date.month() as i32 - months_back) as u32;
-            } else {
-                start_year = center_date.year();
-                start_month = center_date.month() - months_back as u32;
-            }
-        } else {
-            start_year = center_date.year();
-            start_month = center_date.month();
-        }
-
-        let start = NaiveDate::from_ymd_opt(start_year, start_month, 1).unwrap();
-
-        // Calculate end: 12 months after center date, last day of that month
-        let end_year;
-        let end_month;
+    /// The whole months from `months` before `center_date`'s month to
+    /// `months` after it
+    pub fn months_around(center_date: NaiveDate, months: u32) -> Self {
+        let first = center_date.with_day(1).unwrap();
+        let start = first - Months::new(months);
+        let last_month = first + Months::new(months);
 
-        let months_ahead = 12;
-        if center_date.month() + months_ahead > 12 {
-            // Need to go to next year
-            end_year = center_date.year() + 1;
-            end_month = center_date.month() + months_ahead - 12;
-        } else {
-            end_year = center_date.year();
-            end_month = center_date.month() + months_ahead;
+        Self {
+            start,
+            end: Self::last_day_of_month(last_month.year(), last_month.month()),
         }
-
-        let end = Self::last_day_of_month(end_year, end_month);
-
-        Self { start, end }
     }
 
     fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
@@ -343,6 +1254,32 @@ impl DateRange {
     }
 }
 
+/// Status-bar countdown to the first timed event of `upcoming`, e.g.
+/// "Standup in 12m" or "Standup now" once it started
+pub fn next_event_countdown(upcoming: &[&Event], now: DateTime<Local>) -> Option<String> {
+    let event = upcoming.iter().find(|event| !event.start.is_all_day())?;
+    let start = event.start.to_local()?;
+    let summary = event.summary.as_deref().unwrap_or("(No title)");
+
+    Some(format!("{} {}", summary, relative_start(start, now)))
+}
+
+/// "now" once `start` has passed, otherwise e.g. "in 12m", "in 2h 5m" or "in 3d"
+pub fn relative_start(start: DateTime<Local>, now: DateTime<Local>) -> String {
+    if start <= now {
+        return "now".to_string();
+    }
+
+    let minutes = (start - now).num_minutes();
+    if minutes < 60 {
+        format!("in {}m", minutes.max(1))
+    } else if minutes < 24 * 60 {
+        format!("in {}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("in {}d", minutes / (24 * 60))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -379,35 +1316,91 @@ mod tests {
     }
 
     #[test]
-    fn test_move_week() {
+    fn test_toggle_focus() {
         let mut state = AppState::new();
-        let initial_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
-        state.selected_date = initial_date;
 
-        state.move_to_next_week();
-        assert_eq!(
-            state.selected_date,
-            NaiveDate::from_ymd_opt(2025, 6, 22).unwrap()
-        );
+        assert_eq!(state.view_focus, ViewFocus::Calendar);
 
-        state.move_to_prev_week();
-        assert_eq!(
-            state.selected_date,
-            NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
-        );
+        state.focus_next();
+        assert_eq!(state.view_focus, ViewFocus::Events);
+
+        state.focus_next();
+        assert_eq!(state.view_focus, ViewFocus::Calendar);
     }
 
     #[test]
-    fn test_toggle_focus() {
+    fn test_focus_cycles_through_sidebar_both_ways() {
         let mut state = AppState::new();
+        state.calendars = vec![calendar("work", "Work", false)];
+
+        state.focus_next();
+        state.focus_next();
+        assert_eq!(state.view_focus, ViewFocus::Sidebar);
 
+        state.focus_next();
         assert_eq!(state.view_focus, ViewFocus::Calendar);
 
-        state.toggle_focus();
+        state.focus_prev();
+        assert_eq!(state.view_focus, ViewFocus::Sidebar);
+
+        state.focus_prev();
         assert_eq!(state.view_focus, ViewFocus::Events);
+    }
 
-        state.toggle_focus();
-        assert_eq!(state.view_focus, ViewFocus::Calendar);
+    #[test]
+    fn test_hidden_sidebar_is_skipped_by_focus() {
+        let mut state = AppState::new();
+        state.calendars = vec![calendar("work", "Work", false)];
+        state.show_sidebar = false;
+
+        state.focus_prev();
+
+        assert_eq!(state.view_focus, ViewFocus::Events);
+    }
+
+    #[test]
+    fn test_sidebar_toggle_and_reorder() {
+        let mut state = AppState::new();
+        state.calendars = vec![
+            calendar("me", "Me", true),
+            calendar("team", "Team", false),
+            calendar("zoo", "Zoo", false),
+        ];
+
+        state.move_sidebar_selection(1);
+        assert_eq!(state.sidebar_calendar_id(), Some("team".to_string()));
+
+        state.toggle_sidebar_calendar();
+        assert!(!state.is_calendar_visible("team"));
+        state.toggle_sidebar_calendar();
+        assert!(state.is_calendar_visible("team"));
+
+        state.move_sidebar_calendar(1);
+        assert_eq!(state.calendar_order, vec!["me", "zoo", "team"]);
+        assert_eq!(state.sidebar_index, 2);
+
+        // Already last
+        state.move_sidebar_calendar(1);
+        assert_eq!(state.sidebar_index, 2);
+
+        state.move_sidebar_selection(1);
+        assert_eq!(state.sidebar_index, 0);
+    }
+
+    #[test]
+    fn test_move_sidebar_calendar_keeps_unloaded_calendars_in_order() {
+        let mut state = AppState::new();
+        state.calendars = vec![
+            calendar("me", "Me", true),
+            calendar("team", "Team", false),
+            calendar("zoo", "Zoo", false),
+        ];
+        state.calendar_order = vec!["me".to_string(), "other".to_string()];
+
+        state.move_sidebar_selection(1);
+        state.move_sidebar_calendar(1);
+
+        assert_eq!(state.calendar_order, vec!["me", "zoo", "team", "other"]);
     }
 
     #[test]
@@ -486,23 +1479,19 @@ mod tests {
         use crate::calendar::models::{Event, EventDateTime};
         let event = Event {
             id: "test".to_string(),
-            summary: Some("Test Event".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Test Event",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
         state.events.insert(date, vec![event]);
         assert!(state.has_events(date));
@@ -532,7 +1521,7 @@ mod tests {
         let original_today = state.today;
 
         state.move_selected_date(5);
-        state.move_to_next_week();
+        state.move_selected_date(7);
 
         assert_eq!(state.today, original_today);
     }
@@ -555,43 +1544,35 @@ mod tests {
         let events = vec![
             Event {
                 id: "1".to_string(),
-                summary: Some("Event 1".to_string()),
-                description: None,
-                location: None,
-                start: EventDateTime {
-                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                end: EventDateTime {
-                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                status: None,
-                html_link: None,
-                attendees: None,
-            calendar_id: None,
+                ..Event::test(
+                    "Event 1",
+                    EventDateTime {
+                        date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                    EventDateTime {
+                        date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                )
             },
             Event {
                 id: "2".to_string(),
-                summary: Some("Event 2".to_string()),
-                description: None,
-                location: None,
-                start: EventDateTime {
-                    date_time: Some("2025-06-15T14:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                end: EventDateTime {
-                    date_time: Some("2025-06-15T15:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                status: None,
-                html_link: None,
-                attendees: None,
-            calendar_id: None,
+                ..Event::test(
+                    "Event 2",
+                    EventDateTime {
+                        date_time: Some("2025-06-15T14:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                    EventDateTime {
+                        date_time: Some("2025-06-15T15:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                )
             },
         ];
         state.events.insert(date, events);
@@ -621,43 +1602,35 @@ mod tests {
         let events = vec![
             Event {
                 id: "1".to_string(),
-                summary: Some("Event 1".to_string()),
-                description: None,
-                location: None,
-                start: EventDateTime {
-                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                end: EventDateTime {
-                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                status: None,
-                html_link: None,
-                attendees: None,
-            calendar_id: None,
+                ..Event::test(
+                    "Event 1",
+                    EventDateTime {
+                        date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                    EventDateTime {
+                        date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                )
             },
             Event {
                 id: "2".to_string(),
-                summary: Some("Event 2".to_string()),
-                description: None,
-                location: None,
-                start: EventDateTime {
-                    date_time: Some("2025-06-15T14:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                end: EventDateTime {
-                    date_time: Some("2025-06-15T15:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                status: None,
-                html_link: None,
-                attendees: None,
-            calendar_id: None,
+                ..Event::test(
+                    "Event 2",
+                    EventDateTime {
+                        date_time: Some("2025-06-15T14:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                    EventDateTime {
+                        date_time: Some("2025-06-15T15:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                )
             },
         ];
         state.events.insert(date, events);
@@ -753,9 +1726,9 @@ mod tests {
     }
 
     #[test]
-    fn test_twenty_five_month_span_calculation() {
+    fn test_months_around_calculation() {
         let center = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
-        let range = DateRange::twenty_five_month_span(center);
+        let range = DateRange::months_around(center, 12);
 
         // 12 months before June 2025 = June 2024
         assert_eq!(range.start, NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
@@ -765,15 +1738,19 @@ mod tests {
     }
 
     #[test]
-    fn test_twenty_five_month_span_year_boundary() {
+    fn test_months_around_year_boundary() {
         let center = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
-        let range = DateRange::twenty_five_month_span(center);
+        let range = DateRange::months_around(center, 12);
 
         // 12 months before January 2025 = January 2024
         assert_eq!(range.start, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
 
         // 12 months after January 2025 = January 2026
         assert_eq!(range.end, NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+
+        let range = DateRange::months_around(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(), 2);
+        assert_eq!(range.start, NaiveDate::from_ymd_opt(2024, 10, 1).unwrap());
+        assert_eq!(range.end, NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
     }
 
     #[test]
@@ -833,7 +1810,7 @@ mod tests {
     }
 
     #[test]
-    fn test_trim_events_to_25_month_span() {
+    fn test_evict_distant_events() {
         let mut state = AppState::new();
         let selected = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
         state.selected_date = selected;
@@ -847,23 +1824,19 @@ mod tests {
                 .unwrap();
             let event = Event {
                 id: format!("event_{}", month_offset),
-                summary: Some("Test".to_string()),
-                description: None,
-                location: None,
-                start: EventDateTime {
-                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                end: EventDateTime {
-                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                    date: None,
-                    time_zone: None,
-                },
-                status: None,
-                html_link: None,
-                attendees: None,
-            calendar_id: None,
+                ..Event::test(
+                    "Test",
+                    EventDateTime {
+                        date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                    EventDateTime {
+                        date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                        date: None,
+                        time_zone: None,
+                    },
+                )
             };
             state.events.insert(date, vec![event]);
         }
@@ -871,7 +1844,7 @@ mod tests {
         let initial_count = state.events.len();
         assert!(initial_count > 25); // We added 30 months worth
 
-        state.trim_events_to_25_month_span();
+        state.evict_distant_events();
 
         // Should be trimmed to approximately 25 months (may vary slightly due to month lengths)
         assert!(state.events.len() <= 26); // Allow small variance
@@ -879,7 +1852,7 @@ mod tests {
     }
 
     #[test]
-    fn test_trim_preserves_current_month() {
+    fn test_eviction_preserves_current_month() {
         let mut state = AppState::new();
         // Set selected date far from current month
         state.selected_date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
@@ -890,23 +1863,19 @@ mod tests {
         let current_month_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
         let event = Event {
             id: "current_month_event".to_string(),
-            summary: Some("Current Month".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Current Month",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
         state.events.insert(current_month_date, vec![event]);
 
@@ -921,29 +1890,25 @@ mod tests {
             {
                 let event = Event {
                     id: format!("event_{}", month_offset),
-                    summary: Some("Test".to_string()),
-                    description: None,
-                    location: None,
-                    start: EventDateTime {
-                        date_time: Some("2024-01-15T10:00:00Z".to_string()),
-                        date: None,
-                        time_zone: None,
-                    },
-                    end: EventDateTime {
-                        date_time: Some("2024-01-15T11:00:00Z".to_string()),
-                        date: None,
-                        time_zone: None,
-                    },
-                    status: None,
-                    html_link: None,
-                    attendees: None,
-            calendar_id: None,
+                    ..Event::test(
+                        "Test",
+                        EventDateTime {
+                            date_time: Some("2024-01-15T10:00:00Z".to_string()),
+                            date: None,
+                            time_zone: None,
+                        },
+                        EventDateTime {
+                            date_time: Some("2024-01-15T11:00:00Z".to_string()),
+                            date: None,
+                            time_zone: None,
+                        },
+                    )
                 };
                 state.events.insert(date, vec![event]);
             }
         }
 
-        state.trim_events_to_25_month_span();
+        state.evict_distant_events();
 
         // Current month event should still be there
         assert!(state.events.contains_key(&current_month_date));
@@ -1056,6 +2021,8 @@ mod tests {
             access_role: "owner".to_string(),
             background_color: Some("#FF0000".to_string()),
             description: None,
+            default_reminders: Vec::new(),
+            selected: true,
         };
         state.calendars.push(calendar);
 
@@ -1083,6 +2050,8 @@ mod tests {
             access_role: "owner".to_string(),
             background_color: None,
             description: None,
+            default_reminders: Vec::new(),
+            selected: true,
         };
         state.calendars.push(calendar);
 
@@ -1103,6 +2072,8 @@ mod tests {
             access_role: "owner".to_string(),
             background_color: Some("#0088aa".to_string()),
             description: None,
+            default_reminders: Vec::new(),
+            selected: true,
         };
         state.calendars.push(calendar);
 
@@ -1117,4 +2088,998 @@ mod tests {
         let result = state.get_calendar_by_id("nonexistent");
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_toast_expires() {
+        let mut state = AppState::new();
+        state.show_toast("Saved");
+        let shown_at = state.toast.as_ref().unwrap().shown_at;
+
+        state.expire_toast(shown_at + TOAST_DURATION / 2);
+        assert_eq!(state.toast.as_ref().unwrap().message, "Saved");
+
+        state.expire_toast(shown_at + TOAST_DURATION);
+        assert_eq!(state.toast, None);
+    }
+
+    #[test]
+    fn test_loading_message_without_progress() {
+        let state = AppState::new();
+        assert_eq!(state.loading_message(), "Loading calendars and events...");
+    }
+
+    #[test]
+    fn test_loading_message_with_progress() {
+        let mut state = AppState::new();
+        state.load_progress = Some(LoadProgress {
+            calendar: "Team Calendar".to_string(),
+            done: 3,
+            total: 12,
+        });
+
+        assert_eq!(state.loading_message(), "Loading 3/12: Team Calendar");
+    }
+
+    fn create_event_for_calendar(id: &str, date_time: &str, calendar_id: &str) -> Event {
+        use crate::calendar::models::EventDateTime;
+
+        Event {
+            calendar_id: Some(calendar_id.to_string()),
+            ..Event::test(
+                id,
+                EventDateTime {
+                    date_time: Some(date_time.to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some(date_time.to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
+        }
+    }
+
+    fn failed_calendar(id: &str, name: &str) -> FailedCalendar {
+        FailedCalendar {
+            id: id.to_string(),
+            name: name.to_string(),
+            error: "API returned error status".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_calendar_events_replaces_events_in_range() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.events.insert(
+            date,
+            vec![create_event_for_calendar(
+                "deleted",
+                "2025-06-15T10:00:00Z",
+                "work",
+            )],
+        );
+
+        let mut fetched = HashMap::new();
+        fetched.insert(
+            date,
+            vec![create_event_for_calendar(
+                "kept",
+                "2025-06-15T11:00:00Z",
+                "work",
+            )],
+        );
+        state.merge_calendar_events(&range, "work", fetched);
+
+        let events: Vec<&Event> = state.get_events_for_date(date).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "kept");
+    }
+
+    #[test]
+    fn test_merge_calendar_events_keeps_days_sorted() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+
+        let mut personal = HashMap::new();
+        personal.insert(
+            date,
+            vec![create_event_for_calendar(
+                "lunch",
+                "2025-06-15T12:00:00Z",
+                "personal",
+            )],
+        );
+        state.merge_calendar_events(&range, "personal", personal);
+
+        let mut work = HashMap::new();
+        work.insert(
+            date,
+            vec![
+                create_event_for_calendar("standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("review", "2025-06-15T15:00:00Z", "work"),
+            ],
+        );
+        state.merge_calendar_events(&range, "work", work);
+
+        let ids: Vec<&str> = state
+            .get_events_for_date(date)
+            .map(|event| event.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["standup", "lunch", "review"]);
+    }
+
+    #[test]
+    fn test_contains_ignore_case() {
+        assert!(contains_ignore_case("Weekly Standup", "standup"));
+        assert!(contains_ignore_case("Café STRASSE", "café"));
+        assert!(!contains_ignore_case("Standup", "standups"));
+        assert!(!contains_ignore_case("", "a"));
+    }
+
+    #[test]
+    fn test_merge_calendar_events_drops_days_that_became_empty() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 10).unwrap();
+        state.events.insert(
+            date,
+            vec![create_event_for_calendar(
+                "gone",
+                "2025-06-10T10:00:00Z",
+                "work",
+            )],
+        );
+
+        state.merge_calendar_events(&range, "work", HashMap::new());
+
+        assert!(!state.events.contains_key(&date));
+    }
+
+    #[test]
+    fn test_merge_calendar_events_leaves_other_calendars() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("holiday", "2025-06-15T00:00:00Z", "holidays"),
+                create_event_for_calendar("old", "2025-06-15T10:00:00Z", "work"),
+            ],
+        );
+
+        state.merge_calendar_events(&range, "work", HashMap::new());
+
+        let events: Vec<&Event> = state.get_events_for_date(date).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "holiday");
+    }
+
+    #[test]
+    fn test_merge_calendar_events_leaves_events_outside_range() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let outside = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        state.events.insert(
+            outside,
+            vec![create_event_for_calendar(
+                "january",
+                "2025-01-10T10:00:00Z",
+                "work",
+            )],
+        );
+
+        state.merge_calendar_events(&range, "work", HashMap::new());
+
+        assert!(state.has_events(outside));
+    }
+
+    #[test]
+    fn test_merge_indexed_events_only_outside_range() {
+        let mut state = AppState::new();
+        state.current_date_range =
+            DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let inside = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let outside = NaiveDate::from_ymd_opt(2024, 11, 5).unwrap();
+        state.events.insert(
+            outside,
+            vec![create_event_for_calendar(
+                "cached",
+                "2024-11-05T09:00:00Z",
+                "work",
+            )],
+        );
+
+        let added = state.merge_indexed_events(vec![
+            create_event_for_calendar("fetched", "2025-06-15T10:00:00Z", "work"),
+            create_event_for_calendar("cached", "2024-11-05T09:00:00Z", "work"),
+            create_event_for_calendar("old", "2024-11-05T14:00:00Z", "work"),
+        ]);
+
+        assert_eq!(added, 1);
+        assert!(!state.has_events(inside));
+        let ids: Vec<&str> = state
+            .get_events_for_date(outside)
+            .map(|event| event.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["cached", "old"]);
+    }
+
+    #[test]
+    fn test_merge_calendar_events_keeps_selection_on_reordered_event() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("review", "2025-06-15T14:00:00Z", "work"),
+            ],
+        );
+        state.selected_event_index = Some(1);
+        state.select_event();
+
+        let mut fetched = HashMap::new();
+        fetched.insert(
+            date,
+            vec![
+                create_event_for_calendar("early", "2025-06-15T08:00:00Z", "work"),
+                create_event_for_calendar("standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("review", "2025-06-15T14:00:00Z", "work"),
+            ],
+        );
+        state.merge_calendar_events(&range, "work", fetched);
+
+        // Merged days are sorted, so the earlier event pushes review down
+        assert_eq!(state.selected_event_index, Some(2));
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details { event_index: 2, .. }
+        ));
+
+        let mut fetched = HashMap::new();
+        fetched.insert(
+            date,
+            vec![create_event_for_calendar(
+                "review",
+                "2025-06-15T14:00:00Z",
+                "work",
+            )],
+        );
+        state.merge_calendar_events(&range, "work", fetched);
+
+        assert_eq!(state.selected_event_index, Some(0));
+        assert!(matches!(
+            state.events_view_mode,
+            EventsViewMode::Details { event_index: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_merge_calendar_events_clears_selection_of_removed_event() {
+        let mut state = AppState::new();
+        let range = DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        state.events.insert(
+            date,
+            vec![create_event_for_calendar(
+                "cancelled",
+                "2025-06-15T09:00:00Z",
+                "work",
+            )],
+        );
+        state.selected_event_index = Some(0);
+        state.select_event();
+
+        state.merge_calendar_events(&range, "work", HashMap::new());
+
+        assert_eq!(state.selected_event_index, None);
+        assert!(matches!(state.events_view_mode, EventsViewMode::List));
+    }
+
+    #[test]
+    fn test_update_attendees() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        let mut invitation = create_event_for_calendar("planning", "2025-06-15T09:00:00Z", "work");
+        invitation.attendees = Some(vec![Attendee {
+            email: "me@example.com".to_string(),
+            display_name: None,
+            response_status: Some("needsAction".to_string()),
+            optional: None,
+            is_self: Some(true),
+            comment: None,
+        }]);
+        state.events.insert(date, vec![invitation]);
+        state.selected_event_index = Some(0);
+        state.select_event();
+
+        let mut attendees = state.selected_event().unwrap().attendees.clone().unwrap();
+        attendees[0].response_status = Some("accepted".to_string());
+        state.update_attendees("work", "planning", &attendees);
+        assert_eq!(
+            state.selected_event().unwrap().attendees,
+            Some(attendees.clone())
+        );
+
+        // Declined events are hidden by default
+        attendees[0].response_status = Some("declined".to_string());
+        state.update_attendees("work", "planning", &attendees);
+        assert_eq!(state.selected_event_index, None);
+        assert!(matches!(state.events_view_mode, EventsViewMode::List));
+    }
+
+    #[test]
+    fn test_remove_event() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("gym", "2025-06-15T10:00:00Z", "home"),
+                create_event_for_calendar("lunch", "2025-06-15T12:00:00Z", "work"),
+            ],
+        );
+        state.events.insert(
+            date + chrono::Duration::days(1),
+            vec![create_event_for_calendar(
+                "lunch",
+                "2025-06-16T12:00:00Z",
+                "work",
+            )],
+        );
+        state.selected_event_index = Some(2);
+        state.select_event();
+
+        state.remove_event("work", "lunch");
+
+        let ids: Vec<&str> = state
+            .get_events_for_date(date)
+            .map(|event| event.id.as_str())
+            .collect();
+        assert_eq!(ids, ["standup", "gym"]);
+        assert_eq!(state.events.len(), 1);
+        assert_eq!(state.selected_event_index, Some(1));
+        assert!(matches!(state.events_view_mode, EventsViewMode::List));
+
+        state.remove_event("work", "standup");
+        state.remove_event("home", "gym");
+        assert!(state.events.is_empty());
+        assert_eq!(state.selected_event_index, None);
+    }
+
+    #[test]
+    fn test_restore_dropped_deletes() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        let standup = create_event_for_calendar("standup", "2025-06-15T11:00:00Z", "work");
+        let lunch = create_event_for_calendar("lunch", "2025-06-15T12:00:00Z", "work");
+        state
+            .events
+            .insert(date, vec![standup.clone(), lunch.clone()]);
+        state.remove_event("work", "standup");
+        state.remove_event("work", "lunch");
+        state.unconfirmed_deletes = vec![standup, lunch];
+
+        // Still offline: nothing is restored, nothing forgotten
+        state.restore_dropped_deletes(&[], 2);
+        assert!(state.events.is_empty());
+        assert_eq!(state.unconfirmed_deletes.len(), 2);
+
+        let conflict = Mutation::Delete {
+            calendar_id: "work".to_string(),
+            event_id: "lunch".to_string(),
+            base: Default::default(),
+        };
+        state.restore_dropped_deletes(&[conflict], 0);
+
+        let ids: Vec<&str> = state
+            .get_events_for_date(date)
+            .map(|event| event.id.as_str())
+            .collect();
+        assert_eq!(ids, ["lunch"]);
+        assert!(state.unconfirmed_deletes.is_empty());
+    }
+
+    #[test]
+    fn test_retain_known_calendars_drops_removed_calendars() {
+        let mut state = AppState::new();
+        state.calendars = vec![Calendar {
+            id: "work".to_string(),
+            summary: "Work".to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("kept", "2025-01-10T09:00:00Z", "work"),
+                create_event_for_calendar("stale", "2025-01-10T10:00:00Z", "unsubscribed"),
+            ],
+        );
+
+        state.retain_known_calendars();
+
+        let events: Vec<&Event> = state.get_events_for_date(date).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "kept");
+    }
+
+    fn declined_by_self(mut event: Event) -> Event {
+        use crate::calendar::models::Attendee;
+
+        event.attendees = Some(vec![Attendee {
+            email: "me@example.com".to_string(),
+            display_name: None,
+            response_status: Some("declined".to_string()),
+            optional: None,
+            is_self: Some(true),
+            comment: None,
+        }]);
+        event
+    }
+
+    #[test]
+    fn test_filter_hides_calendars_keywords_and_declined() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("Standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("Gym", "2025-06-15T18:00:00Z", "personal"),
+                declined_by_self(create_event_for_calendar(
+                    "Offsite",
+                    "2025-06-15T12:00:00Z",
+                    "work",
+                )),
+            ],
+        );
+
+        let visible: Vec<&str> = state
+            .get_events_for_date(date)
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(visible, vec!["Standup", "Gym"]);
+
+        state.filter.show_declined = true;
+        assert_eq!(state.get_events_for_date(date).count(), 3);
+
+        state.filter.hidden_calendars.insert("work".to_string());
+        let visible: Vec<&str> = state
+            .get_events_for_date(date)
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(visible, vec!["Gym"]);
+
+        state.filter.keyword = "standup".to_string();
+        assert!(!state.has_events(date));
+    }
+
+    #[test]
+    fn test_update_filter_keeps_selected_event() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("Standup", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("Gym", "2025-06-15T18:00:00Z", "personal"),
+            ],
+        );
+        state.selected_event_index = Some(1);
+
+        state.update_filter(|filter| {
+            filter.hidden_calendars.insert("work".to_string());
+        });
+        assert_eq!(state.selected_event_index, Some(0));
+
+        state.update_filter(|filter| filter.keyword = "standup".to_string());
+        assert_eq!(state.selected_event_index, None);
+    }
+
+    #[test]
+    fn test_select_next_attachment_wraps() {
+        use crate::calendar::models::Attachment;
+
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = date;
+        let mut event = create_event_for_calendar("Review", "2025-06-15T09:00:00Z", "work");
+        event.attachments = Some(
+            ["Agenda", "Slides"]
+                .iter()
+                .map(|title| Attachment {
+                    file_url: format!("https://drive.google.com/{}", title),
+                    title: Some(title.to_string()),
+                    mime_type: None,
+                })
+                .collect(),
+        );
+        state.events.insert(date, vec![event]);
+        state.selected_event_index = Some(0);
+        state.select_event();
+
+        assert_eq!(state.selected_attachment().unwrap().name(), "Agenda");
+        state.select_next_attachment();
+        assert_eq!(state.selected_attachment().unwrap().name(), "Slides");
+        state.select_next_attachment();
+        assert_eq!(state.selected_attachment().unwrap().name(), "Agenda");
+
+        state.selected_event_index = None;
+        assert!(state.selected_attachment().is_none());
+    }
+
+    #[test]
+    fn test_filter_summary() {
+        let mut filter = EventFilter::default();
+        assert_eq!(filter.summary(), None);
+
+        filter.keyword = "1:1".to_string();
+        filter.hidden_calendars.insert("work".to_string());
+        filter.hidden_calendars.insert("holidays".to_string());
+        assert_eq!(
+            filter.summary(),
+            Some("\"1:1\", 2 calendars hidden".to_string())
+        );
+    }
+
+    #[test]
+    fn test_failure_summary() {
+        let mut state = AppState::new();
+        assert_eq!(state.failure_summary(), None);
+
+        state.failed_calendars = vec![failed_calendar("work", "Work")];
+        assert_eq!(
+            state.failure_summary(),
+            Some("1 calendar failed to load (Work)".to_string())
+        );
+
+        state
+            .failed_calendars
+            .push(failed_calendar("holidays", "Holidays"));
+        assert_eq!(
+            state.failure_summary(),
+            Some("2 calendars failed to load (Work, Holidays)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_roll_over_moves_selection_that_followed_today() {
+        let mut state = AppState::new();
+        state.today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        state.selected_date = state.today;
+        state.current_month = (2025, 6);
+
+        let changed = state.roll_over_to(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+
+        assert!(changed);
+        assert_eq!(state.today, NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(state.selected_date, state.today);
+        assert_eq!(state.current_month, (2025, 7));
+    }
+
+    #[test]
+    fn test_roll_over_keeps_selection_away_from_today() {
+        let mut state = AppState::new();
+        state.today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
+
+        state.roll_over_to(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap());
+
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 6, 20).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_roll_over_same_day_is_noop() {
+        let mut state = AppState::new();
+        let today = state.today;
+
+        assert!(!state.roll_over_to(today));
+        assert_eq!(state.today, today);
+    }
+
+    #[test]
+    fn test_range_refresh_waits_for_navigation_to_settle() {
+        let mut state = AppState::new();
+        state.current_date_range =
+            DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+
+        let navigated_at = Instant::now();
+        state.last_navigation = Some(navigated_at);
+
+        assert!(!state.range_refresh_due(navigated_at + Duration::from_millis(100)));
+        assert!(state.range_refresh_due(navigated_at + NAVIGATION_DEBOUNCE));
+    }
+
+    #[test]
+    fn test_range_refresh_not_due_inside_range() {
+        let mut state = AppState::new();
+        state.current_date_range =
+            DateRange::five_month_span(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap());
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 20).unwrap();
+
+        assert!(!state.range_refresh_due(Instant::now()));
+    }
+
+    #[test]
+    fn test_unsaved_changes_lists_pending_mutations() {
+        let mut state = AppState::new();
+        assert!(state.unsaved_changes().is_empty());
+
+        state.pending_mutations = 2;
+        assert_eq!(
+            state.unsaved_changes(),
+            vec!["2 offline changes not synced yet".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_stats_lines() {
+        let mut state = AppState::new();
+        state.events.insert(
+            NaiveDate::from_ymd_opt(2025, 6, 15).unwrap(),
+            vec![
+                create_event_for_calendar("a", "2025-06-15T09:00:00Z", "work"),
+                create_event_for_calendar("b", "2025-06-15T10:00:00Z", "work"),
+            ],
+        );
+        state.api_stats = ApiStats {
+            calls: 14,
+            backoffs: 1,
+        };
+
+        assert_eq!(
+            state.stats_lines(),
+            vec![
+                "Last sync: never".to_string(),
+                "Calendars loaded: 0".to_string(),
+                "Events loaded: 2".to_string(),
+                "API calls this session: 14".to_string(),
+                "Rate-limit backoffs: 1".to_string(),
+            ]
+        );
+    }
+
+    fn calendar(id: &str, summary: &str, primary: bool) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_ordered_calendars_primary_then_custom_then_alphabetical() {
+        let mut state = AppState::new();
+        state.calendars = vec![
+            calendar("zoo", "Zoo", false),
+            calendar("team", "Team", false),
+            calendar("me", "Me", true),
+            calendar("birthdays", "Birthdays", false),
+        ];
+        state.calendar_order = vec!["team".to_string(), "me".to_string()];
+
+        let ids: Vec<&str> = state
+            .ordered_calendars()
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["me", "team", "birthdays", "zoo"]);
+    }
+
+    #[test]
+    fn test_selected_event_is_read_only() {
+        let mut state = AppState::new();
+        let mut local = calendar("khal:home", "home", false);
+        local.access_role = "reader".to_string();
+        state.calendars = vec![calendar("me", "Me", true), local];
+        let date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        state.selected_date = date;
+        state.events.insert(
+            date,
+            vec![
+                create_event_for_calendar("mine", "2025-06-16T09:00:00Z", "me"),
+                create_event_for_calendar("local", "2025-06-16T10:00:00Z", "khal:home"),
+            ],
+        );
+
+        assert!(!state.selected_event_is_read_only());
+        state.selected_event_index = Some(0);
+        assert!(!state.selected_event_is_read_only());
+        state.selected_event_index = Some(1);
+        assert!(state.selected_event_is_read_only());
+    }
+
+    #[test]
+    fn test_calendar_prefs_roundtrip_through_state() {
+        let mut state = AppState::new();
+        state.apply_calendar_prefs(CalendarPrefs {
+            order: vec!["work".to_string()],
+            hidden: vec!["holidays".to_string(), "birthdays".to_string()],
+            seen: vec!["work".to_string()],
+        });
+
+        assert!(!state.is_calendar_visible("holidays"));
+        assert!(state.is_calendar_visible("work"));
+        assert_eq!(
+            state.calendar_prefs(),
+            CalendarPrefs {
+                order: vec!["work".to_string()],
+                hidden: vec!["birthdays".to_string(), "holidays".to_string()],
+                seen: vec!["work".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_open_view() {
+        let mut state = AppState::new();
+        assert_eq!(state.view_focus, ViewFocus::Calendar);
+
+        state.open_view(StartView::Day);
+        assert_eq!(state.view_focus, ViewFocus::Events);
+        assert_eq!(state.selected_date, state.today);
+        assert_eq!(state.list_layout, ListLayout::Day);
+
+        state.open_view(StartView::Timeline);
+        assert_eq!(state.view_focus, ViewFocus::Events);
+        assert_eq!(state.list_layout, ListLayout::Timeline);
+
+        state.open_view(StartView::Week);
+        assert_eq!(state.list_layout, ListLayout::Week);
+        state.open_view(StartView::Agenda);
+        assert_eq!(state.view_focus, ViewFocus::Events);
+        assert_eq!(state.list_layout, ListLayout::Agenda);
+    }
+
+    #[test]
+    fn test_adopt_calendar_selection_only_for_new_calendars() {
+        let mut state = AppState::new();
+        let mut team = calendar("team", "Team", false);
+        team.selected = false;
+        state.calendars = vec![calendar("work", "Work", false), team];
+
+        assert!(state.adopt_calendar_selection());
+        assert!(state.is_calendar_visible("work"));
+        assert!(!state.is_calendar_visible("team"));
+
+        // Shown again in the sidebar, it stays shown on the next load
+        state.filter.hidden_calendars.remove("team");
+        assert!(!state.adopt_calendar_selection());
+        assert!(state.is_calendar_visible("team"));
+    }
+
+    #[test]
+    fn test_jump_to_day_of_month_clamps() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 2, 10).unwrap();
+
+        state.jump_to_day_of_month(21);
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 2, 21).unwrap()
+        );
+
+        state.jump_to_day_of_month(31);
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()
+        );
+
+        state.jump_to_day_of_month(0);
+        assert_eq!(
+            state.selected_date,
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()
+        );
+    }
+
+    fn local_time(date_time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(date_time)
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn timed_event(id: &str, start: &str, end: &str, calendar_id: &str) -> Event {
+        let mut event = create_event_for_calendar(id, start, calendar_id);
+        event.end.date_time = Some(end.to_string());
+        event
+    }
+
+    fn insert_by_local_date(state: &mut AppState, event: Event) {
+        let date = event.start.to_local().unwrap().date_naive();
+        state.events.entry(date).or_default().push(event);
+    }
+
+    #[test]
+    fn test_copies_on_several_calendars_are_listed_once() {
+        let mut state = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        state.calendars = vec![
+            calendar("work", "Work", true),
+            calendar("team", "Team", false),
+        ];
+        let copy = |calendar_id: &str| {
+            let mut event =
+                create_event_for_calendar("invite", "2025-06-15T10:00:00Z", calendar_id);
+            event.ical_uid = Some("invite@google.com".to_string());
+            event
+        };
+        let mut later = copy("work");
+        later.start.date_time = Some("2025-06-15T15:00:00Z".to_string());
+        later.id = "invite_20250615T150000Z".to_string();
+        // Starts with the invitation, so merging must not put it between the copies
+        let mut standup = create_event_for_calendar("standup", "2025-06-15T10:00:00Z", "work");
+        standup.ical_uid = Some("standup@google.com".to_string());
+        let range = DateRange {
+            start: date,
+            end: date,
+        };
+        state.merge_calendar_events(
+            &range,
+            "work",
+            HashMap::from([(date, vec![standup, copy("work"), later])]),
+        );
+        state.merge_calendar_events(&range, "team", HashMap::from([(date, vec![copy("team")])]));
+
+        let events: Vec<&Event> = state.get_events_for_date(date).collect();
+        let ids: Vec<&str> = events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, vec!["invite", "standup", "invite_20250615T150000Z"]);
+        assert_eq!(events[0].calendar_id.as_deref(), Some("work"));
+        let others: Vec<&str> = state
+            .other_calendars_of(events[0])
+            .iter()
+            .map(|calendar| calendar.id.as_str())
+            .collect();
+        assert_eq!(others, vec!["team"]);
+
+        // Hiding one calendar shows the copy on the other
+        state.filter.hidden_calendars.insert("work".to_string());
+        let events: Vec<&Event> = state.get_events_for_date(date).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].calendar_id.as_deref(), Some("team"));
+        assert!(state.other_calendars_of(events[0]).is_empty());
+    }
+
+    #[test]
+    fn test_upcoming_spans_days_and_skips_ended() {
+        let mut state = AppState::new();
+        for event in [
+            timed_event(
+                "ended",
+                "2025-06-15T08:00:00Z",
+                "2025-06-15T09:00:00Z",
+                "work",
+            ),
+            timed_event(
+                "ongoing",
+                "2025-06-15T09:30:00Z",
+                "2025-06-15T11:00:00Z",
+                "work",
+            ),
+            timed_event(
+                "tomorrow",
+                "2025-06-16T09:00:00Z",
+                "2025-06-16T10:00:00Z",
+                "work",
+            ),
+            timed_event(
+                "later",
+                "2025-06-15T15:00:00Z",
+                "2025-06-15T16:00:00Z",
+                "work",
+            ),
+            timed_event(
+                "next week",
+                "2025-06-22T09:00:00Z",
+                "2025-06-22T10:00:00Z",
+                "work",
+            ),
+        ] {
+            insert_by_local_date(&mut state, event);
+        }
+
+        let now = local_time("2025-06-15T10:00:00Z");
+        let ids: Vec<&str> = state
+            .upcoming_from(now, 3)
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+
+        assert_eq!(ids, vec!["ongoing", "later", "tomorrow"]);
+    }
+
+    #[test]
+    fn test_upcoming_respects_hidden_calendars() {
+        let mut state = AppState::new();
+        insert_by_local_date(
+            &mut state,
+            timed_event(
+                "hidden",
+                "2025-06-15T11:00:00Z",
+                "2025-06-15T12:00:00Z",
+                "holidays",
+            ),
+        );
+        insert_by_local_date(
+            &mut state,
+            timed_event(
+                "shown",
+                "2025-06-15T12:00:00Z",
+                "2025-06-15T13:00:00Z",
+                "work",
+            ),
+        );
+        state.filter.hidden_calendars.insert("holidays".to_string());
+
+        let upcoming = state.upcoming_from(local_time("2025-06-15T10:00:00Z"), 5);
+
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0].id, "shown");
+    }
+
+    fn countdown_at(state: &AppState, now: DateTime<Local>) -> Option<String> {
+        next_event_countdown(&state.upcoming_from(now, 10), now)
+    }
+
+    #[test]
+    fn test_next_event_countdown() {
+        let mut state = AppState::new();
+        insert_by_local_date(
+            &mut state,
+            timed_event(
+                "Standup",
+                "2025-06-15T10:12:00Z",
+                "2025-06-15T10:30:00Z",
+                "work",
+            ),
+        );
+
+        assert_eq!(
+            countdown_at(&state, local_time("2025-06-15T10:00:00Z")),
+            Some("Standup in 12m".to_string())
+        );
+        assert_eq!(
+            countdown_at(&state, local_time("2025-06-15T07:30:00Z")),
+            Some("Standup in 2h 42m".to_string())
+        );
+        assert_eq!(
+            countdown_at(&state, local_time("2025-06-15T10:15:00Z")),
+            Some("Standup now".to_string())
+        );
+        assert_eq!(
+            countdown_at(&state, local_time("2025-06-15T11:00:00Z")),
+            None
+        );
+    }
 }
@@ -0,0 +1,174 @@
+use chrono::NaiveDate;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::calendar::models::Event;
+use crate::formats::Localized;
+use crate::tui::color_utils::{default_event_color, parse_color};
+use crate::tui::state::{AppState, ViewFocus};
+
+/// Days listed from the selected one on
+const AGENDA_DAYS: usize = 14;
+
+/// The selected day and the following ones with events, each under its date,
+/// as far as the pane reaches
+pub struct AgendaWidget<'a> {
+    state: &'a AppState,
+}
+
+impl<'a> AgendaWidget<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        Self { state }
+    }
+
+    fn event_line(&self, event: &Event, selected: bool) -> Line<'a> {
+        let formats = &self.state.settings.formats;
+        let time = match (event.start.to_local(), event.end.to_local()) {
+            (Some(start), Some(end)) if !event.start.is_all_day() => format!(
+                "{} - {}",
+                start.format(&formats.time),
+                end.format(&formats.time)
+            ),
+            _ => "All day".to_string(),
+        };
+        let color = event
+            .calendar_id
+            .as_ref()
+            .and_then(|cal_id| self.state.get_calendar_color(cal_id))
+            .and_then(|color| parse_color(&color))
+            .unwrap_or_else(default_event_color);
+        let style = if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!(
+                    "{:<15} {}",
+                    time,
+                    event.summary.as_deref().unwrap_or("(No title)")
+                ),
+                style,
+            ),
+        ])
+    }
+
+    fn day_line(&self, date: NaiveDate) -> Line<'a> {
+        let formats = &self.state.settings.formats;
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        let style = if date == self.state.selected_date {
+            style.fg(Color::Cyan)
+        } else if date == self.state.today {
+            style.fg(Color::Yellow)
+        } else {
+            style
+        };
+        Line::from(Span::styled(
+            date.localized(&formats.day_title, formats.locale),
+            style,
+        ))
+    }
+}
+
+impl<'a> Widget for AgendaWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let focused = self.state.view_focus == ViewFocus::Events;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            })
+            .title(" Agenda ");
+
+        let selected_date = self.state.selected_date;
+        let selected = self.state.selected_event_index.filter(|_| focused);
+        let mut lines = Vec::new();
+        for date in selected_date.iter_days().take(AGENDA_DAYS) {
+            let mut events = self.state.get_events_for_date(date).peekable();
+            // The selected day is always listed, so it is clear where it is
+            if events.peek().is_none() && date != selected_date {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(self.day_line(date));
+            if events.peek().is_none() {
+                lines.push(Line::from(Span::styled(
+                    "  No events",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            for (index, event) in events.enumerate() {
+                let is_selected = date == selected_date && selected == Some(index);
+                lines.push(self.event_line(event, is_selected));
+            }
+            if lines.len() >= usize::from(area.height) {
+                break;
+            }
+        }
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+    use chrono::{Local, TimeZone};
+
+    fn timed(id: &str, day: u32, hour: u32) -> Event {
+        let at = |hour: u32| EventDateTime {
+            date_time: Some(
+                Local
+                    .with_ymd_and_hms(2025, 6, day, hour, 0, 0)
+                    .unwrap()
+                    .to_rfc3339(),
+            ),
+            date: None,
+            time_zone: None,
+        };
+        Event::test(id, at(hour), at(hour + 1))
+    }
+
+    #[test]
+    fn test_agenda_lists_days_with_events() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+        // Before the selected day, so left out
+        for event in [timed("Retro", 13, 15), timed("Review", 18, 14)] {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.insert(date, vec![event]);
+        }
+
+        let area = Rect::new(0, 0, 50, 8);
+        let mut buf = Buffer::empty(area);
+        AgendaWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(0).contains("Agenda"));
+        assert!(row(1).contains("June 16, 2025"));
+        assert!(row(2).contains("No events"));
+        assert!(row(4).contains("June 18, 2025"));
+        assert!(row(5).contains("14:00 - 15:00   Review"));
+        assert!(!(0..area.height).any(|y| row(y).contains("Retro")));
+    }
+}
@@ -7,6 +7,8 @@ use ratatui::{
     widgets::{Block, Borders, Widget},
 };
 
+use crate::formats::{Formats, Localized};
+use crate::locale::Locale;
 use crate::tui::state::{AppState, ViewFocus};
 
 pub struct CalendarWidget<'a> {
@@ -63,7 +65,10 @@ impl<'a> Widget for CalendarWidget<'a> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
-            .title(format!(" {} {} ", month_name(month), year));
+            .title(format!(
+                " {} ",
+                month_title(selected_date, &self.state.settings.formats)
+            ));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -74,11 +79,10 @@ impl<'a> Widget for CalendarWidget<'a> {
         }
 
         // Render day names header with larger spacing
-        let day_names = vec!["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
         let mut x = inner.x;
         let y = inner.y;
 
-        for day_name in day_names {
+        for day_name in day_names(self.state.settings.formats.locale) {
             let span = Span::styled(day_name, Style::default().add_modifier(Modifier::BOLD));
             buf.set_span(x, y, &span, 4);
             x += 4;
@@ -138,6 +142,10 @@ impl<'a> Widget for CalendarWidget<'a> {
                 else if self.state.has_events(date) {
                     style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
                 }
+                // Priority 5: Days off are shaded
+                else if !self.state.settings.working_hours.is_working_day(date) {
+                    style = style.fg(Color::DarkGray);
+                }
 
                 let is_single_digit = day < 10;
                 if is_single_digit {
@@ -156,22 +164,21 @@ impl<'a> Widget for CalendarWidget<'a> {
     }
 }
 
-fn month_name(month: u32) -> &'static str {
-    match month {
-        1 => "January",
-        2 => "February",
-        3 => "March",
-        4 => "April",
-        5 => "May",
-        6 => "June",
-        7 => "July",
-        8 => "August",
-        9 => "September",
-        10 => "October",
-        11 => "November",
-        12 => "December",
-        _ => "Unknown",
-    }
+/// Weekday headers from Sunday, cut to three letters to fit a column
+fn day_names(locale: &Locale) -> Vec<String> {
+    let mut weekday = Weekday::Sun;
+    (0..7)
+        .map(|_| {
+            let name = locale.weekday_abbr(weekday).chars().take(3).collect();
+            weekday = weekday.succ();
+            name
+        })
+        .collect()
+}
+
+/// e.g. `June 2025`, or as `month_title_format` says
+fn month_title(date: NaiveDate, formats: &Formats) -> String {
+    date.localized(&formats.month_title, formats.locale)
 }
 
 #[cfg(test)]
@@ -211,11 +218,17 @@ mod tests {
     }
 
     #[test]
-    fn test_month_name() {
-        assert_eq!(month_name(1), "January");
-        assert_eq!(month_name(6), "June");
-        assert_eq!(month_name(12), "December");
-        assert_eq!(month_name(13), "Unknown");
+    fn test_day_names() {
+        assert_eq!(
+            day_names(crate::locale::english()),
+            vec!["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"]
+        );
+    }
+
+    #[test]
+    fn test_month_title() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        assert_eq!(month_title(date, &Formats::default()), "June 2025");
     }
 
     #[test]
@@ -10,9 +10,11 @@ use ratatui::{
     },
 };
 
-use crate::calendar::models::Event;
-use crate::tui::color_utils::{default_event_color, parse_hex_color};
+use crate::calendar::conference;
+use crate::calendar::models::{Event, Reminder};
+use crate::tui::color_utils::{default_event_color, parse_color};
 use crate::tui::state::{AppState, ViewFocus};
+use crate::tui::zones;
 
 pub struct EventDetailsWidget<'a> {
     state: &'a mut AppState,
@@ -29,14 +31,14 @@ impl<'a> EventDetailsWidget<'a> {
         }
     }
 
-    fn format_time(event: &Event) -> String {
+    fn format_time(event: &Event, time_format: &str) -> String {
         if let Some(ref date_time_str) = event.start.date_time {
             if let Ok(start_dt) = DateTime::parse_from_rfc3339(date_time_str) {
-                let start_time = start_dt.format("%H:%M").to_string();
+                let start_time = start_dt.format(time_format).to_string();
 
                 if let Some(ref end_date_time_str) = event.end.date_time {
                     if let Ok(end_dt) = DateTime::parse_from_rfc3339(end_date_time_str) {
-                        let end_time = end_dt.format("%H:%M").to_string();
+                        let end_time = end_dt.format(time_format).to_string();
                         return format!("{} - {}", start_time, end_time);
                     }
                 }
@@ -48,6 +50,26 @@ impl<'a> EventDetailsWidget<'a> {
         "All day".to_string()
     }
 
+    /// e.g. `10 min (popup), 1 day (email)`
+    fn format_reminders(reminders: &[Reminder]) -> String {
+        reminders
+            .iter()
+            .map(|reminder| {
+                let minutes = reminder.minutes;
+                let lead = if minutes > 0 && minutes % (24 * 60) == 0 {
+                    let days = minutes / (24 * 60);
+                    format!("{} day{}", days, if days == 1 { "" } else { "s" })
+                } else if minutes > 0 && minutes % 60 == 0 {
+                    format!("{} h", minutes / 60)
+                } else {
+                    format!("{} min", minutes)
+                };
+                format!("{} ({})", lead, reminder.method)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// Calculate maximum scroll offset for given content and visible area
     /// Returns 0 if content fits, otherwise returns lines that can be scrolled past
     fn calculate_max_scroll(content_lines: usize, visible_height: usize) -> usize {
@@ -62,7 +84,10 @@ impl<'a> EventDetailsWidget<'a> {
 impl<'a> Widget for EventDetailsWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let selected_date = self.state.selected_date;
-        let events = self.state.get_events_for_date(selected_date);
+        let event = self
+            .state
+            .get_events_for_date(selected_date)
+            .nth(self.event_index);
 
         // Create border with focus indicator
         let border_style = if self.state.view_focus == ViewFocus::Events {
@@ -80,7 +105,7 @@ impl<'a> Widget for EventDetailsWidget<'a> {
         block.render(area, buf);
 
         // Check if event_index is valid
-        if self.event_index >= events.len() {
+        let Some(event) = event else {
             let error_text = vec![Line::from(Span::styled(
                 "Error: Event not found",
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -88,9 +113,8 @@ impl<'a> Widget for EventDetailsWidget<'a> {
             let paragraph = Paragraph::new(error_text);
             paragraph.render(inner, buf);
             return;
-        }
+        };
 
-        let event = &events[self.event_index];
         let mut lines = Vec::new();
 
         if let Some(ref calendar_id) = event.calendar_id {
@@ -98,13 +122,22 @@ impl<'a> Widget for EventDetailsWidget<'a> {
                 let cal_color = cal
                     .background_color
                     .as_ref()
-                    .and_then(|hex| parse_hex_color(hex))
+                    .and_then(|color| parse_color(color))
                     .unwrap_or_else(default_event_color);
 
-                lines.push(Line::from(vec![
+                let mut spans = vec![
                     Span::styled("▊▊ ", Style::default().fg(cal_color)),
                     Span::styled(&cal.summary, Style::default().fg(Color::DarkGray)),
-                ]));
+                ];
+                let others = self.state.other_calendars_of(event);
+                if !others.is_empty() {
+                    let names: Vec<&str> = others.iter().map(|c| c.summary.as_str()).collect();
+                    spans.push(Span::styled(
+                        format!(" (also in {})", names.join(", ")),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                lines.push(Line::from(spans));
                 lines.push(Line::from(""));
             }
         }
@@ -118,14 +151,54 @@ impl<'a> Widget for EventDetailsWidget<'a> {
         )));
         lines.push(Line::from(""));
 
-        // Time
-        let time_str = Self::format_time(event);
+        // Time, also in the other zones for timed events
+        let settings = &self.state.settings;
+        let time_str = match (event.start.to_local(), event.end.to_local()) {
+            (Some(start), Some(end))
+                if !event.start.is_all_day() && !settings.other_timezones.is_empty() =>
+            {
+                zones::event_times(
+                    start,
+                    end,
+                    &settings.other_timezones,
+                    &settings.formats.time,
+                )
+            }
+            _ => Self::format_time(event, &settings.formats.time),
+        };
         lines.push(Line::from(vec![
             Span::styled("Time: ", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(time_str),
         ]));
         lines.push(Line::from(""));
 
+        // Reminders
+        let calendar = event
+            .calendar_id
+            .as_deref()
+            .and_then(|id| self.state.get_calendar_by_id(id));
+        let reminders = event.effective_reminders(calendar);
+        if !reminders.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Reminders: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(Self::format_reminders(&reminders)),
+            ]));
+            lines.push(Line::from(""));
+        }
+
+        // Video call, wherever its link was found
+        if let Some(call) = conference::video_call(event) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", call.name()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(call.url, Style::default().fg(Color::Blue)),
+                Span::styled(" (J to join)", Style::default().fg(Color::DarkGray)),
+            ]));
+            lines.push(Line::from(""));
+        }
+
         // Location
         if let Some(ref location) = event.location {
             lines.push(Line::from(vec![
@@ -177,9 +250,50 @@ impl<'a> Widget for EventDetailsWidget<'a> {
                         ""
                     };
 
+                    // The user's own answer, which a/t/d change
+                    let (own_marker, style) = if attendee.is_self == Some(true) {
+                        (" (you)", Style::default().add_modifier(Modifier::BOLD))
+                    } else {
+                        ("", Style::default())
+                    };
+
                     lines.push(Line::from(Span::styled(
-                        format!("  {} {}{}", status_icon, name, optional_marker),
-                        Style::default(),
+                        format!(
+                            "  {} {}{}{}",
+                            status_icon, name, optional_marker, own_marker
+                        ),
+                        style,
+                    )));
+                }
+
+                lines.push(Line::from(""));
+            }
+        }
+
+        // Attachments; `n` moves the marker, `o` opens the marked one
+        if let Some(ref attachments) = event.attachments {
+            if !attachments.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "Attachments:",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+
+                for (index, attachment) in attachments.iter().enumerate() {
+                    let selected = index == self.state.selected_attachment;
+                    let style = if selected {
+                        Style::default()
+                            .fg(Color::Blue)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::Blue)
+                    };
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "  {} {}",
+                            if selected { ">" } else { " " },
+                            attachment.name()
+                        ),
+                        style,
                     )));
                 }
 
@@ -220,14 +334,12 @@ impl<'a> Widget for EventDetailsWidget<'a> {
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓"));
 
-            let mut scrollbar_state = ScrollbarState::new(max_scroll)
-                .position(scroll_offset);
+            let mut scrollbar_state = ScrollbarState::new(max_scroll).position(scroll_offset);
 
             scrollbar.render(inner, buf, &mut scrollbar_state);
         }
 
         self.state.update_event_details_max_scroll(max_scroll);
-
     }
 }
 
@@ -252,19 +364,8 @@ mod tests {
 
         let event = Event {
             id: "1".to_string(),
-            summary: Some("Team Meeting".to_string()),
             description: Some("Discuss Q2 roadmap and priorities".to_string()),
             location: Some("Conference Room A".to_string()),
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:00:00Z".to_string()),
-                date: None,
-                time_zone: None,
-            },
             status: Some("confirmed".to_string()),
             html_link: Some("https://calendar.google.com/event?eid=test123".to_string()),
             attendees: Some(vec![
@@ -273,15 +374,31 @@ mod tests {
                     display_name: Some("Alice Smith".to_string()),
                     response_status: Some("accepted".to_string()),
                     optional: Some(false),
+                    is_self: None,
+                    comment: None,
                 },
                 Attendee {
                     email: "bob@example.com".to_string(),
                     display_name: Some("Bob Jones".to_string()),
                     response_status: Some("tentative".to_string()),
                     optional: Some(true),
+                    is_self: None,
+                    comment: None,
                 },
             ]),
-            calendar_id: None,
+            ..Event::test(
+                "Team Meeting",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:00:00Z".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
 
         state.events.insert(date, vec![event]);
@@ -300,24 +417,20 @@ mod tests {
         state.selected_date = date;
 
         let event = Event {
-            id: "1".to_string(),
             summary: None,
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "1",
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+            )
         };
 
         state.events.insert(date, vec![event]);
@@ -328,6 +441,25 @@ mod tests {
         assert_eq!(widget.event_index, 0);
     }
 
+    #[test]
+    fn test_format_reminders() {
+        let reminder = |method: &str, minutes| Reminder {
+            method: method.to_string(),
+            minutes,
+        };
+
+        assert_eq!(
+            EventDetailsWidget::format_reminders(&[
+                reminder("popup", 10),
+                reminder("popup", 0),
+                reminder("email", 120),
+                reminder("email", 2880),
+            ]),
+            "10 min (popup), 0 min (popup), 2 h (email), 2 days (email)"
+        );
+        assert_eq!(EventDetailsWidget::format_reminders(&[]), "");
+    }
+
     #[test]
     fn test_handles_invalid_index() {
         let mut state = AppState::new();
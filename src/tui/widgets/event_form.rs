@@ -0,0 +1,191 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::tui::color_utils::{default_event_color, parse_color};
+use crate::tui::form::{EventForm, FormField};
+use crate::tui::state::AppState;
+
+/// Width of the field labels, so the values line up
+const LABEL_WIDTH: usize = 13;
+
+/// The event form, one line per field, for a new event or an edited one
+pub struct EventFormWidget<'a> {
+    state: &'a AppState,
+}
+
+impl<'a> EventFormWidget<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        Self { state }
+    }
+
+    fn field_line(&self, form: &'a EventForm, field: FormField) -> Line<'a> {
+        let focused = form.focused == field;
+        let label_style = if focused {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        let mut spans = vec![
+            Span::raw(if focused { "> " } else { "  " }),
+            Span::styled(
+                format!("{:<width$}", field.label(), width = LABEL_WIDTH),
+                label_style,
+            ),
+        ];
+
+        match form.text(field) {
+            Some(text) => {
+                if text.is_empty() && !focused {
+                    spans.push(Span::styled(
+                        field.placeholder(),
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                } else {
+                    spans.push(Span::raw(text));
+                }
+                if focused {
+                    spans.push(Span::styled("_", Style::default().fg(Color::Cyan)));
+                }
+            }
+            None => {
+                let calendars = self.state.writable_calendars();
+                let calendar = calendars.get(form.calendar);
+                let color = calendar
+                    .and_then(|calendar| self.state.get_calendar_color(&calendar.id))
+                    .and_then(|color| parse_color(&color))
+                    .unwrap_or_else(default_event_color);
+                let name = calendar.map_or_else(String::new, |calendar| calendar.summary.clone());
+                spans.push(Span::styled("■ ", Style::default().fg(color)));
+                spans.push(Span::raw(if focused {
+                    format!("\u{2039} {} \u{203a}", name)
+                } else {
+                    name
+                }));
+            }
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl<'a> Widget for EventFormWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let form = self.state.event_form.as_ref();
+        let editing = form.is_some_and(|form| form.original.is_some());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(if editing {
+                " Edit event "
+            } else {
+                " New event "
+            });
+
+        let Some(form) = form else {
+            block.render(area, buf);
+            return;
+        };
+
+        let mut lines: Vec<Line> = form
+            .fields()
+            .iter()
+            .map(|field| self.field_line(form, *field))
+            .collect();
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            if editing {
+                "(Tab/\u{2191}\u{2193} next field, Enter to save, Esc to cancel)"
+            } else {
+                "(Tab/\u{2191}\u{2193} next field, \u{2190}\u{2192} calendar, Enter to save, Esc to cancel)"
+            },
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )));
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::Calendar;
+    use chrono::{Duration, NaiveDate};
+
+    fn calendar(id: &str, summary: &str, access_role: &str) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: access_role.to_string(),
+            background_color: None,
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_form_shows_fields_and_calendar() {
+        let mut state = AppState::new();
+        state.calendars = vec![
+            calendar("holidays", "Holidays", "reader"),
+            calendar("work", "Work", "owner"),
+        ];
+        let mut form = EventForm::new(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap(), 0);
+        form.title = "Dentist".to_string();
+        state.event_form = Some(form);
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        EventFormWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(1).contains("> Title        Dentist_"));
+        assert!(row(2).contains("Date         2025-06-16"));
+        assert!(row(3).contains("HH:MM, empty for all day"));
+        assert!(row(6).contains("■ Work"));
+    }
+
+    #[test]
+    fn test_edit_form_has_no_calendar() {
+        let mut form = EventForm::new(NaiveDate::from_ymd_opt(2025, 6, 16).unwrap(), 0);
+        form.title = "Dentist".to_string();
+        let mut event = form.to_event(Duration::hours(1)).unwrap();
+        event.location = Some("Main St 4".to_string());
+        let mut state = AppState::new();
+        state.event_form = Some(EventForm::for_event(&event));
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        EventFormWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(0).contains("Edit event"));
+        assert!(row(5).contains("Location     Main St 4"));
+        assert!(row(6).contains("Description"));
+    }
+}
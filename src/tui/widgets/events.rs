@@ -1,4 +1,4 @@
-use chrono::DateTime;
+use chrono::{DateTime, Local};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -6,10 +6,14 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
+use std::ops::Range;
 
+use crate::calendar::local;
 use crate::calendar::models::Event;
-use crate::tui::color_utils::{default_event_color, parse_hex_color};
+use crate::formats::Localized;
+use crate::tui::color_utils::{default_event_color, parse_color};
 use crate::tui::state::{AppState, ViewFocus};
+use crate::tui::zones;
 
 pub struct EventListWidget<'a> {
     state: &'a AppState,
@@ -20,16 +24,20 @@ impl<'a> EventListWidget<'a> {
         Self { state }
     }
 
-    fn format_event_time(event: &Event) -> String {
+    fn format_event_time(event: &Event, time_format: &str) -> String {
         // Try to extract time from dateTime field
         if let Some(ref date_time_str) = event.start.date_time {
             if let Ok(start_dt) = DateTime::parse_from_rfc3339(date_time_str) {
-                let start_time = start_dt.format("%H:%M").to_string();
+                let start_time = start_dt.format(time_format).to_string();
 
                 // Try to get end time
                 if let Some(ref end_date_time_str) = event.end.date_time {
                     if let Ok(end_dt) = DateTime::parse_from_rfc3339(end_date_time_str) {
-                        let end_time = end_dt.format("%H:%M").to_string();
+                        // Deadlines such as task due dates have no duration
+                        if end_dt == start_dt {
+                            return start_time;
+                        }
+                        let end_time = end_dt.format(time_format).to_string();
                         return format!("{} - {}", start_time, end_time);
                     }
                 }
@@ -46,7 +54,7 @@ impl<'a> EventListWidget<'a> {
 impl<'a> Widget for EventListWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let selected_date = self.state.selected_date;
-        let events = self.state.get_events_for_date(selected_date);
+        let events: Vec<&Event> = self.state.get_events_for_date(selected_date).collect();
 
         // Create border with focus indicator
         let border_style = if self.state.view_focus == ViewFocus::Events {
@@ -54,16 +62,50 @@ impl<'a> Widget for EventListWidget<'a> {
         } else {
             Style::default()
         };
-
-        let title = format!(" Events for {} ", selected_date.format("%B %d, %Y"));
-
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(title);
-
+            .border_style(border_style);
         let inner = block.inner(area);
-        block.render(area, buf);
+
+        // Only the events that fit are turned into lines, keeping the selected
+        // one in view, so days with hundreds of events stay cheap to draw
+        let hint_rows = if self.state.view_focus == ViewFocus::Events {
+            2
+        } else {
+            0
+        };
+        let rows = usize::from(inner.height).saturating_sub(hint_rows);
+        let heights: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                let spacer = usize::from(i + 1 < events.len());
+                Self::event_height(event, usize::from(inner.width)) + spacer
+            })
+            .collect();
+        let window = visible_window(&heights, self.state.selected_event_index, rows);
+
+        let formats = &self.state.settings.formats;
+        let mut title = format!(
+            " Events for {} ",
+            selected_date.localized(&formats.day_title, formats.locale)
+        );
+        if window.len() < events.len() {
+            title.push_str(&format!(
+                "· {}-{} of {} ",
+                window.start + 1,
+                window.end,
+                events.len()
+            ));
+        }
+        let other_timezones = &self.state.settings.other_timezones;
+        if !other_timezones.is_empty() {
+            title.push_str(&format!(
+                "· {} ",
+                zones::clock(Local::now(), other_timezones, &formats.time)
+            ));
+        }
+        block.title(title).render(area, buf);
 
         if events.is_empty() {
             // No events for this date
@@ -79,71 +121,16 @@ impl<'a> Widget for EventListWidget<'a> {
             return;
         }
 
-        // Render events
         let mut lines = Vec::new();
-
-        for (i, event) in events.iter().enumerate() {
-            let is_selected = self.state.selected_event_index == Some(i)
-                && self.state.view_focus == ViewFocus::Events;
-
-            let bar_color = event
-                .calendar_id
-                .as_ref()
-                .and_then(|cal_id| self.state.get_calendar_color(cal_id))
-                .and_then(|hex| parse_hex_color(&hex))
-                .unwrap_or_else(default_event_color);
-
-            let bar_span = Span::styled("▊▊ ", Style::default().fg(bar_color));
-
-            let time_str = Self::format_event_time(event);
-            let indicator = if is_selected { "> " } else { "  " };
-
-            let time_span = Span::styled(
-                format!("{}{}", indicator, time_str),
-                if is_selected {
-                    Style::default()
-                        .fg(Color::Green)
-                        .bg(Color::DarkGray)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD)
-                },
-            );
-
-            let summary = event.summary.as_deref().unwrap_or("(No title)");
-            let summary_span = Span::styled(
-                format!(" {}", summary),
-                if is_selected {
-                    Style::default().bg(Color::DarkGray)
-                } else {
-                    Style::default()
-                },
-            );
-
-            lines.push(Line::from(vec![bar_span.clone(), time_span, summary_span]));
-
-            if let Some(ref location) = event.location {
-                let location_style = if is_selected {
-                    Style::default().fg(Color::Yellow).bg(Color::DarkGray)
-                } else {
-                    Style::default().fg(Color::Yellow)
-                };
-
-                let location_span = Span::styled(
-                    format!("  \u{1f4cd} {}", location),
-                    location_style,
-                );
-
-                lines.push(Line::from(vec![bar_span, location_span]));
-            }
+        for i in window {
+            lines.extend(self.event_lines(events[i], i));
 
             // Add spacing between events (except last one)
             if i < events.len() - 1 {
                 lines.push(Line::from(""));
             }
         }
+        lines.truncate(rows);
 
         // Add help hint when focused
         if self.state.view_focus == ViewFocus::Events {
@@ -161,6 +148,118 @@ impl<'a> Widget for EventListWidget<'a> {
     }
 }
 
+impl<'a> EventListWidget<'a> {
+    /// The time and title line of the `i`th event, and its location if any
+    fn event_lines(&self, event: &'a Event, i: usize) -> Vec<Line<'a>> {
+        let is_selected = self.state.selected_event_index == Some(i)
+            && self.state.view_focus == ViewFocus::Events;
+
+        let bar_color = event
+            .calendar_id
+            .as_ref()
+            .and_then(|cal_id| self.state.get_calendar_color(cal_id))
+            .and_then(|color| parse_color(&color))
+            .unwrap_or_else(default_event_color);
+
+        // Tasks get a checkbox and an italic title to set them apart from events
+        let is_task = event
+            .calendar_id
+            .as_deref()
+            .is_some_and(local::is_task_calendar);
+        let bar = if is_task { "\u{2610}  " } else { "▊▊ " };
+        let bar_span = Span::styled(bar, Style::default().fg(bar_color));
+
+        let time_str = Self::format_event_time(event, &self.state.settings.formats.time);
+        let indicator = if is_selected { "> " } else { "  " };
+
+        let time_span = Span::styled(
+            format!("{}{}", indicator, time_str),
+            if is_selected {
+                Style::default()
+                    .fg(Color::Green)
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            },
+        );
+
+        let summary = event.summary.as_deref().unwrap_or("(No title)");
+        let mut summary_style = if is_selected {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        if is_task {
+            summary_style = summary_style.add_modifier(Modifier::ITALIC);
+        }
+        let summary_span = Span::styled(format!(" {}", summary), summary_style);
+
+        let mut spans = vec![bar_span.clone(), time_span, summary_span];
+        // e.g. a 2 a.m. hold sent from another time zone
+        if self.state.settings.working_hours.is_off_hours(event) {
+            spans.push(Span::styled(
+                " \u{263e} off hours",
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        let mut lines = vec![Line::from(spans)];
+
+        if let Some(ref location) = event.location {
+            let location_style = if is_selected {
+                Style::default().fg(Color::Yellow).bg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+
+            let location_span = Span::styled(format!("  \u{1f4cd} {}", location), location_style);
+
+            lines.push(Line::from(vec![bar_span, location_span]));
+        }
+
+        lines
+    }
+
+    /// Rows the event's lines take once wrapped to `width`, estimated from
+    /// their lengths without building them
+    fn event_height(event: &Event, width: usize) -> usize {
+        let wrapped = |chars: usize| chars.div_ceil(width.max(1)).max(1);
+        // Bar, selection indicator, a 13-column time range and the spaces between
+        let title = event.summary.as_deref().map_or(10, |s| s.chars().count()) + 19;
+        let location = event
+            .location
+            .as_deref()
+            .map_or(0, |location| wrapped(location.chars().count() + 7));
+        wrapped(title) + location
+    }
+}
+
+/// The events, by index, to draw in `rows` rows given each one's `heights`:
+/// as many as fit around the selected one, earlier ones first, so the list
+/// starts at the top until the selection moves past the bottom
+fn visible_window(heights: &[usize], selected: Option<usize>, rows: usize) -> Range<usize> {
+    let selected = selected
+        .filter(|selected| *selected < heights.len())
+        .unwrap_or(0);
+
+    let mut used = heights.get(selected).copied().unwrap_or(0);
+    let mut start = selected;
+    while start > 0 && used + heights[start - 1] <= rows {
+        start -= 1;
+        used += heights[start];
+    }
+
+    let mut end = (selected + 1).min(heights.len());
+    while end < heights.len() && used + heights[end] <= rows {
+        used += heights[end];
+        end += 1;
+    }
+
+    start..end
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,26 +270,22 @@ mod tests {
     fn test_format_event_time_with_datetime() {
         let event = Event {
             id: "test".to_string(),
-            summary: Some("Meeting".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: Some("2025-06-15T10:30:00-05:00".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: Some("2025-06-15T11:30:00-05:00".to_string()),
-                date: None,
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "Meeting",
+                EventDateTime {
+                    date_time: Some("2025-06-15T10:30:00-05:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: Some("2025-06-15T11:30:00-05:00".to_string()),
+                    date: None,
+                    time_zone: None,
+                },
+            )
         };
 
-        let time_str = EventListWidget::format_event_time(&event);
+        let time_str = EventListWidget::format_event_time(&event, "%H:%M");
         assert!(time_str.contains("10:30"));
         assert!(time_str.contains("11:30"));
         assert!(time_str.contains(" - "));
@@ -200,29 +295,39 @@ mod tests {
     fn test_format_event_time_with_date_only() {
         let event = Event {
             id: "test".to_string(),
-            summary: Some("All-day event".to_string()),
-            description: None,
-            location: None,
-            start: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            end: EventDateTime {
-                date_time: None,
-                date: Some("2025-06-15".to_string()),
-                time_zone: None,
-            },
-            status: None,
-            html_link: None,
-            attendees: None,
-            calendar_id: None,
+            ..Event::test(
+                "All-day event",
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+                EventDateTime {
+                    date_time: None,
+                    date: Some("2025-06-15".to_string()),
+                    time_zone: None,
+                },
+            )
         };
 
-        let time_str = EventListWidget::format_event_time(&event);
+        let time_str = EventListWidget::format_event_time(&event, "%H:%M");
         assert_eq!(time_str, "All day");
     }
 
+    #[test]
+    fn test_visible_window() {
+        let heights = [2; 50];
+
+        assert_eq!(visible_window(&heights, None, 10), 0..5);
+        assert_eq!(visible_window(&heights, Some(3), 10), 0..5);
+        // The selection past the bottom ends the window
+        assert_eq!(visible_window(&heights, Some(20), 10), 16..21);
+        assert_eq!(visible_window(&heights, Some(49), 10), 45..50);
+        // The selected event is drawn even when it alone doesn't fit
+        assert_eq!(visible_window(&heights, Some(7), 1), 7..8);
+        assert_eq!(visible_window(&[], None, 10), 0..0);
+    }
+
     #[test]
     fn test_event_list_widget_new() {
         let state = AppState::new();
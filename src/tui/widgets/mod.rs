@@ -1,7 +1,17 @@
+pub mod agenda;
 pub mod calendar;
 pub mod event_details;
+pub mod event_form;
 pub mod events;
+pub mod sidebar;
+pub mod timeline;
+pub mod week;
 
+pub use agenda::AgendaWidget;
 pub use calendar::CalendarWidget;
 pub use event_details::EventDetailsWidget;
+pub use event_form::EventFormWidget;
 pub use events::EventListWidget;
+pub use sidebar::CalendarSidebarWidget;
+pub use timeline::TimelineWidget;
+pub use week::WeekWidget;
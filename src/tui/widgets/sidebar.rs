@@ -0,0 +1,113 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::tui::color_utils::{default_event_color, parse_color};
+use crate::tui::state::{AppState, ViewFocus};
+
+/// Calendars in display order with their color and visibility
+pub struct CalendarSidebarWidget<'a> {
+    state: &'a AppState,
+}
+
+impl<'a> CalendarSidebarWidget<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        Self { state }
+    }
+}
+
+impl<'a> Widget for CalendarSidebarWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let focused = self.state.view_focus == ViewFocus::Sidebar;
+        let border_style = if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(" Calendars ");
+
+        let lines: Vec<Line> = self
+            .state
+            .ordered_calendars()
+            .into_iter()
+            .enumerate()
+            .map(|(index, calendar)| {
+                let visible = self.state.is_calendar_visible(&calendar.id);
+                let color = calendar
+                    .background_color
+                    .as_deref()
+                    .and_then(parse_color)
+                    .unwrap_or_else(default_event_color);
+
+                let mut name_style = if visible {
+                    Style::default()
+                } else {
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::CROSSED_OUT)
+                };
+
+                let is_selected = focused && index == self.state.sidebar_index;
+                if is_selected {
+                    name_style = name_style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                }
+
+                Line::from(vec![
+                    Span::raw(if is_selected { ">" } else { " " }),
+                    Span::styled("■ ", Style::default().fg(color)),
+                    Span::raw(if visible { "[x] " } else { "[ ] " }),
+                    Span::styled(calendar.summary.clone(), name_style),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::Calendar;
+
+    fn calendar(id: &str, summary: &str) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: Some("#0088aa".to_string()),
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    #[test]
+    fn test_sidebar_lists_calendars_with_visibility() {
+        let mut state = AppState::new();
+        state.calendars = vec![calendar("work", "Work"), calendar("holidays", "Holidays")];
+        state.filter.hidden_calendars.insert("holidays".to_string());
+
+        let area = Rect::new(0, 0, 30, 5);
+        let mut buf = Buffer::empty(area);
+        CalendarSidebarWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(1).contains("[ ] Holidays"));
+        assert!(row(2).contains("[x] Work"));
+    }
+}
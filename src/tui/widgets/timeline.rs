@@ -0,0 +1,437 @@
+use chrono::{DateTime, Local, NaiveDate, Timelike};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::calendar::models::Event;
+use crate::formats::Localized;
+use crate::tui::color_utils::{default_event_color, parse_color};
+use crate::tui::state::{AppState, ViewFocus};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+/// "HH:MM " before each hour
+const GUTTER_WIDTH: u16 = 6;
+
+/// The selected day as a 24-hour timeline: timed events are blocks as tall
+/// as they are long, side by side where they overlap, all-day ones are
+/// listed on top, and on today a line marks the current time
+pub struct TimelineWidget<'a> {
+    state: &'a AppState,
+    now: DateTime<Local>,
+}
+
+/// A timed event placed on the timeline, in minutes since midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placed {
+    /// Index into the day's events
+    index: usize,
+    start: i64,
+    end: i64,
+    /// Column among the events it overlaps with, and how many there are
+    column: usize,
+    columns: usize,
+}
+
+impl<'a> TimelineWidget<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        Self {
+            state,
+            now: Local::now(),
+        }
+    }
+
+    fn event_style(&self, event: &Event, selected: bool) -> Style {
+        let color = event
+            .calendar_id
+            .as_ref()
+            .and_then(|cal_id| self.state.get_calendar_color(cal_id))
+            .and_then(|color| parse_color(&color))
+            .unwrap_or_else(default_event_color);
+        if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        }
+    }
+}
+
+impl<'a> Widget for TimelineWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let date = self.state.selected_date;
+        let focused = self.state.view_focus == ViewFocus::Events;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            })
+            .title(format!(
+                " Timeline for {} ",
+                date.localized(
+                    &self.state.settings.formats.day_title,
+                    self.state.settings.formats.locale
+                )
+            ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.width <= GUTTER_WIDTH || inner.height == 0 {
+            return;
+        }
+
+        let events: Vec<&Event> = self.state.get_events_for_date(date).collect();
+        let selected = self.state.selected_event_index.filter(|_| focused);
+
+        // All-day events take the first row
+        let mut top = inner.y;
+        let all_day: Vec<usize> = (0..events.len())
+            .filter(|i| events[*i].start.is_all_day())
+            .collect();
+        if !all_day.is_empty() {
+            let mut x = inner.x;
+            for i in all_day {
+                let title = format!(" {} ", events[i].summary.as_deref().unwrap_or("(No title)"));
+                let style = self.event_style(events[i], selected == Some(i));
+                let (end, _) = buf.set_stringn(
+                    x,
+                    top,
+                    title,
+                    usize::from(inner.right().saturating_sub(x)),
+                    style.add_modifier(Modifier::REVERSED),
+                );
+                x = end + 1;
+                if x >= inner.right() {
+                    break;
+                }
+            }
+            top += 1;
+        }
+        let height = inner.bottom().saturating_sub(top);
+        if height == 0 {
+            return;
+        }
+
+        let placed = place(&events, date);
+        let is_today = date == self.now.date_naive();
+        let now = i64::from(self.now.hour() * 60 + self.now.minute());
+        // Keep the selected event, or else the current time or the first event,
+        // in view when the whole day doesn't fit
+        let focus = selected
+            .and_then(|i| placed.iter().find(|placed| placed.index == i))
+            .map(|placed| placed.start)
+            .or(is_today.then_some(now))
+            .or(placed.first().map(|placed| placed.start))
+            .unwrap_or(8 * 60);
+        let window = Window::new(height, focus);
+
+        // Hour labels
+        for row in 0..height {
+            let minute = window.minute_at(row);
+            if minute % 60 == 0 && minute < MINUTES_PER_DAY {
+                buf.set_string(
+                    inner.x,
+                    top + row,
+                    format!("{:02}:00", minute / 60),
+                    Style::default().fg(Color::DarkGray),
+                );
+            }
+        }
+
+        // Event blocks
+        let x = inner.x + GUTTER_WIDTH;
+        let width = inner.width - GUTTER_WIDTH;
+        for placed in &placed {
+            let Some((first, last)) = window.rows(placed.start, placed.end) else {
+                continue;
+            };
+            let column_width = width / placed.columns as u16;
+            // Room for the bar and a gap at least
+            if column_width < 2 {
+                continue;
+            }
+            let left = x + column_width * placed.column as u16;
+            // The last column takes what the division leaves over
+            let right = if placed.column + 1 == placed.columns {
+                x + width
+            } else {
+                left + column_width - 1
+            };
+            let event = events[placed.index];
+            let style = self.event_style(event, selected == Some(placed.index));
+
+            let title = event.summary.as_deref().unwrap_or("(No title)");
+            let time = format!(
+                "{:02}:{:02}-{:02}:{:02}",
+                placed.start / 60,
+                placed.start % 60,
+                placed.end / 60,
+                placed.end % 60
+            );
+            for row in first..=last {
+                let y = top + row;
+                buf.set_string(left, y, "\u{258c}", style);
+                let text = match row - first {
+                    0 => title,
+                    1 => time.as_str(),
+                    _ => "",
+                };
+                let fill = format!("{:<width$}", text, width = usize::from(right - left));
+                buf.set_stringn(left + 1, y, fill, usize::from(right - left - 1), style);
+            }
+        }
+
+        // The current time, across the free space
+        if is_today {
+            if let Some(row) = window.row_of(now) {
+                let y = top + row;
+                let line_style = Style::default().fg(Color::Red);
+                buf.set_string(inner.x, y, self.now.format("%H:%M").to_string(), line_style);
+                for column in x..inner.right() {
+                    let cell = &mut buf[(column, y)];
+                    let blank = cell.fg == Color::Reset && cell.bg == Color::Reset;
+                    if blank && cell.symbol() == " " {
+                        cell.set_symbol("\u{2500}").set_style(line_style);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The part of the day shown: whole hours, with as many rows per hour as fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    first_minute: i64,
+    minutes_per_row: i64,
+    rows: u16,
+}
+
+impl Window {
+    /// `rows` rows of the day around `focus`, a minute of the day
+    fn new(rows: u16, focus: i64) -> Self {
+        let rows_per_hour = (i64::from(rows) / 24).max(1);
+        let minutes_per_row = 60 / rows_per_hour.min(4);
+        let hours = (i64::from(rows) * minutes_per_row / 60).min(24);
+        // The focused hour sits a quarter down the window
+        let first_hour = (focus / 60 - hours / 4).clamp(0, 24 - hours);
+        Self {
+            first_minute: first_hour * 60,
+            minutes_per_row,
+            rows,
+        }
+    }
+
+    fn minute_at(&self, row: u16) -> i64 {
+        self.first_minute + i64::from(row) * self.minutes_per_row
+    }
+
+    /// The row `minute` falls on, if it is in view
+    fn row_of(&self, minute: i64) -> Option<u16> {
+        let row = (minute - self.first_minute).div_euclid(self.minutes_per_row);
+        (0..i64::from(self.rows))
+            .contains(&row)
+            .then_some(row as u16)
+    }
+
+    /// The first and last rows of an event from `start` to `end`, clipped to
+    /// the window; short events still take a row
+    fn rows(&self, start: i64, end: i64) -> Option<(u16, u16)> {
+        let last_minute = self.minute_at(self.rows);
+        let end = end.max(start + 1);
+        if end <= self.first_minute || start >= last_minute {
+            return None;
+        }
+        let first = (start.max(self.first_minute) - self.first_minute) / self.minutes_per_row;
+        let last = ((end.min(last_minute) - self.first_minute - 1) / self.minutes_per_row)
+            .max(first)
+            .min(i64::from(self.rows) - 1);
+        Some((first as u16, last as u16))
+    }
+}
+
+/// Lay the timed events of `date` out in columns: events overlapping each
+/// other, directly or through others, share the width, and each takes the
+/// leftmost column that is free at its start
+fn place(events: &[&Event], date: NaiveDate) -> Vec<Placed> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    let minute = |event_time: Option<DateTime<Local>>| {
+        event_time.map(|at| {
+            (at.naive_local() - midnight)
+                .num_minutes()
+                .clamp(0, MINUTES_PER_DAY)
+        })
+    };
+
+    let mut placed: Vec<Placed> = events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| !event.start.is_all_day())
+        .filter_map(|(index, event)| {
+            let start = minute(event.start.to_local())?;
+            let end = minute(event.end.to_local()).unwrap_or(start).max(start);
+            Some(Placed {
+                index,
+                start,
+                end,
+                column: 0,
+                columns: 1,
+            })
+        })
+        .collect();
+    placed.sort_by_key(|placed| (placed.start, placed.end));
+
+    // Groups of overlapping events, each laid out on its own
+    let mut group_start = 0;
+    let mut group_end = i64::MIN;
+    let mut column_ends: Vec<i64> = Vec::new();
+    for i in 0..placed.len() {
+        // Deadlines have no length but still take a row
+        let end = placed[i].end.max(placed[i].start + 1);
+        if placed[i].start >= group_end {
+            finish_group(&mut placed[group_start..i], column_ends.len());
+            group_start = i;
+            column_ends.clear();
+        }
+        let column = match column_ends.iter().position(|free| *free <= placed[i].start) {
+            Some(column) => column,
+            None => {
+                column_ends.push(0);
+                column_ends.len() - 1
+            }
+        };
+        column_ends[column] = end;
+        placed[i].column = column;
+        group_end = group_end.max(end);
+    }
+    let count = column_ends.len();
+    finish_group(&mut placed[group_start..], count);
+
+    placed
+}
+
+fn finish_group(group: &mut [Placed], columns: usize) {
+    for placed in group {
+        placed.columns = columns.max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+    use chrono::TimeZone;
+
+    fn timed(id: &str, start: (u32, u32), end: (u32, u32)) -> Event {
+        let at = |(hour, minute): (u32, u32)| EventDateTime {
+            date_time: Some(
+                Local
+                    .with_ymd_and_hms(2025, 6, 16, hour, minute, 0)
+                    .unwrap()
+                    .to_rfc3339(),
+            ),
+            date: None,
+            time_zone: None,
+        };
+        Event::test(id, at(start), at(end))
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 16).unwrap()
+    }
+
+    #[test]
+    fn test_place_overlapping_events_side_by_side() {
+        let events = [
+            timed("standup", (9, 0), (9, 30)),
+            timed("review", (9, 15), (10, 0)),
+            timed("coffee", (9, 30), (9, 45)),
+            timed("lunch", (12, 0), (13, 0)),
+        ];
+        let events: Vec<&Event> = events.iter().collect();
+
+        let placed = place(&events, date());
+        let layout: Vec<(usize, usize, usize)> = placed
+            .iter()
+            .map(|placed| (placed.index, placed.column, placed.columns))
+            .collect();
+        // Coffee reuses the column standup frees at 9:30
+        assert_eq!(layout, [(0, 0, 2), (1, 1, 2), (2, 0, 2), (3, 0, 1)]);
+        assert_eq!((placed[3].start, placed[3].end), (12 * 60, 13 * 60));
+    }
+
+    #[test]
+    fn test_place_clips_events_to_the_day() {
+        let mut overnight = timed("flight", (22, 0), (23, 0));
+        overnight.end.date_time = Some(
+            Local
+                .with_ymd_and_hms(2025, 6, 17, 6, 0, 0)
+                .unwrap()
+                .to_rfc3339(),
+        );
+        let all_day = Event {
+            start: EventDateTime::from_date(date()),
+            end: EventDateTime::from_date(date().succ_opt().unwrap()),
+            ..timed("holiday", (0, 0), (0, 0))
+        };
+
+        let placed = place(&[&overnight, &all_day], date());
+        assert_eq!(placed.len(), 1);
+        assert_eq!((placed[0].start, placed[0].end), (22 * 60, MINUTES_PER_DAY));
+    }
+
+    #[test]
+    fn test_window() {
+        // Two rows per hour show the whole day
+        let window = Window::new(48, 14 * 60);
+        assert_eq!(window.first_minute, 0);
+        assert_eq!(window.row_of(14 * 60 + 40), Some(29));
+        assert_eq!(window.rows(9 * 60, 10 * 60), Some((18, 19)));
+
+        // Ten hours, the focus a quarter down
+        let window = Window::new(10, 14 * 60);
+        assert_eq!(window.first_minute, 12 * 60);
+        assert_eq!(window.row_of(11 * 60), None);
+        assert_eq!(window.rows(11 * 60, 13 * 60 + 30), Some((0, 1)));
+        assert_eq!(window.rows(9 * 60, 10 * 60), None);
+        // A five minute event still takes its row
+        assert_eq!(window.rows(15 * 60, 15 * 60 + 5), Some((3, 3)));
+
+        assert_eq!(Window::new(10, 23 * 60).first_minute, 14 * 60);
+    }
+
+    #[test]
+    fn test_renders_blocks_and_hours() {
+        let mut state = AppState::new();
+        state.selected_date = date();
+        state.events.insert(
+            date(),
+            vec![
+                timed("Standup", (9, 0), (10, 0)),
+                timed("Review", (9, 30), (11, 0)),
+            ],
+        );
+
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        TimelineWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        // Ten hours from 07:00, one row each
+        assert!(row(1).contains("07:00"));
+        assert!(row(3).contains("09:00"));
+        assert!(row(3).contains("Standup"));
+        assert!(row(3).contains("Review"));
+        assert!(row(4).contains("09:30-11:00"));
+        assert!(!row(5).contains("Standup"));
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::NaiveDate;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+use std::ops::Range;
+
+use crate::calendar::models::Event;
+use crate::commands::week::week_start;
+use crate::formats::Localized;
+use crate::tui::color_utils::{default_event_color, parse_color};
+use crate::tui::state::{AppState, ViewFocus};
+
+/// The Sunday-to-Saturday week of the selected day, a column per day with
+/// its events in storage order (all-day ones first), like `oxidate week`
+pub struct WeekWidget<'a> {
+    state: &'a AppState,
+}
+
+impl<'a> WeekWidget<'a> {
+    pub fn new(state: &'a AppState) -> Self {
+        Self { state }
+    }
+
+    fn event_style(&self, event: &Event, selected: bool) -> Style {
+        let color = event
+            .calendar_id
+            .as_ref()
+            .and_then(|cal_id| self.state.get_calendar_color(cal_id))
+            .and_then(|color| parse_color(&color))
+            .unwrap_or_else(default_event_color);
+        if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        }
+    }
+
+    fn header_style(&self, date: NaiveDate) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        if date == self.state.selected_date {
+            style.fg(Color::Black).bg(Color::Cyan)
+        } else if date == self.state.today {
+            style.fg(Color::Yellow)
+        } else {
+            style
+        }
+    }
+}
+
+impl<'a> Widget for WeekWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let formats = &self.state.settings.formats;
+        let start = week_start(self.state.selected_date);
+        let focused = self.state.view_focus == ViewFocus::Events;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            })
+            .title(format!(
+                " Week of {} ",
+                start.localized(&formats.day_title, formats.locale)
+            ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.width < 7 || inner.height < 2 {
+            return;
+        }
+
+        let column_width = inner.width / 7;
+        let rows = usize::from(inner.height - 1);
+        for (column, date) in start.iter_days().take(7).enumerate() {
+            let x = inner.x + column as u16 * column_width;
+            // A space between columns, and the last one takes what is left
+            let width = if column == 6 {
+                inner.right() - x
+            } else {
+                column_width - 1
+            };
+            let header = date.localized("%a %-d", formats.locale);
+            buf.set_stringn(
+                x,
+                inner.y,
+                header,
+                usize::from(width),
+                self.header_style(date),
+            );
+
+            let events: Vec<&Event> = self.state.get_events_for_date(date).collect();
+            let selected = self
+                .state
+                .selected_event_index
+                .filter(|_| focused && date == self.state.selected_date);
+            let (shown, hidden) = visible(events.len(), rows, selected);
+            let mut y = inner.y + 1;
+            for index in shown {
+                let event = events[index];
+                let summary = event.summary.as_deref().unwrap_or("(No title)");
+                let text = match event.start.to_local() {
+                    Some(start) if !event.start.is_all_day() => {
+                        format!("{} {}", start.format(&formats.time), summary)
+                    }
+                    _ => summary.to_string(),
+                };
+                let style = self.event_style(event, selected == Some(index));
+                buf.set_stringn(x, y, text, usize::from(width), style);
+                y += 1;
+            }
+            if hidden > 0 {
+                buf.set_stringn(
+                    x,
+                    y,
+                    format!("+{} more", hidden),
+                    usize::from(width),
+                    Style::default().fg(Color::DarkGray),
+                );
+            }
+        }
+    }
+}
+
+/// Which of a day's `count` events fit in `rows`, keeping `selected` in view,
+/// and how many others are left out; a row is kept to say so
+fn visible(count: usize, rows: usize, selected: Option<usize>) -> (Range<usize>, usize) {
+    if count <= rows {
+        return (0..count, 0);
+    }
+    let shown = rows.saturating_sub(1);
+    let first = selected.map_or(0, |index| (index + 1).saturating_sub(shown));
+    (first..first + shown, count - shown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::models::EventDateTime;
+    use chrono::{Local, TimeZone};
+
+    fn timed(id: &str, day: u32, hour: u32) -> Event {
+        let at = |hour: u32| EventDateTime {
+            date_time: Some(
+                Local
+                    .with_ymd_and_hms(2025, 6, day, hour, 0, 0)
+                    .unwrap()
+                    .to_rfc3339(),
+            ),
+            date: None,
+            time_zone: None,
+        };
+        Event::test(id, at(hour), at(hour + 1))
+    }
+
+    #[test]
+    fn test_visible_keeps_selection_in_view() {
+        assert_eq!(visible(3, 5, None), (0..3, 0));
+        assert_eq!(visible(6, 4, None), (0..3, 3));
+        assert_eq!(visible(6, 4, Some(4)), (2..5, 3));
+        assert_eq!(visible(6, 4, Some(1)), (0..3, 3));
+    }
+
+    #[test]
+    fn test_week_shows_each_day_of_the_selected_week() {
+        let mut state = AppState::new();
+        // A Wednesday; its week starts on Sunday the 15th
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 6, 18).unwrap();
+        for event in [timed("Standup", 16, 9), timed("Review", 18, 14)] {
+            let date = event.start.to_local().unwrap().date_naive();
+            state.events.insert(date, vec![event]);
+        }
+
+        let area = Rect::new(0, 0, 72, 6);
+        let mut buf = Buffer::empty(area);
+        WeekWidget::new(&state).render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(row(0).contains("Week of June 15, 2025"));
+        assert!(row(1).contains("Sun 15    Mon 16    Tue 17    Wed 18"));
+        assert!(row(1).contains("Sat 21"));
+        assert!(row(2).contains("09:00 Sta"));
+        assert!(row(2).contains("14:00 Rev"));
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Local, NaiveDate};
+use chrono_tz::Tz;
+
+/// The local zone by name, so times can carry an abbreviation like `PDT`;
+/// `--timezone` sets `TZ`, otherwise the system zone is asked for
+fn local_zone() -> Option<Tz> {
+    let parse = |name: String| name.parse().ok();
+    std::env::var("TZ")
+        .ok()
+        .and_then(parse)
+        .or_else(|| iana_time_zone::get_timezone().ok().and_then(parse))
+}
+
+/// `10:00 PDT`, falling back to the UTC offset for an unnamed local zone
+fn local_time(at: DateTime<Local>, with_end: Option<DateTime<Local>>, time_format: &str) -> String {
+    match local_zone() {
+        Some(zone) => zoned(at, with_end, zone, at.date_naive(), time_format),
+        None => {
+            let times = match with_end {
+                Some(end) => format!("{} - {}", at.format(time_format), end.format(time_format)),
+                None => at.format(time_format).to_string(),
+            };
+            format!("{} UTC{}", times, at.format("%:z"))
+        }
+    }
+}
+
+/// The times in `zone` with its abbreviation, and the day difference to
+/// `local_date` when the zone is already on another date, e.g. `02:00 JST (+1)`
+fn zoned(
+    start: DateTime<Local>,
+    end: Option<DateTime<Local>>,
+    zone: Tz,
+    local_date: NaiveDate,
+    time_format: &str,
+) -> String {
+    let start_there = start.with_timezone(&zone);
+    let mut text = start_there.format(time_format).to_string();
+    if let Some(end) = end {
+        text.push_str(" - ");
+        text.push_str(&end.with_timezone(&zone).format(time_format).to_string());
+    }
+    text.push_str(&start_there.format(" %Z").to_string());
+
+    let days = (start_there.date_naive() - local_date).num_days();
+    if days != 0 {
+        text.push_str(&format!(" ({:+})", days));
+    }
+    text
+}
+
+/// `10:00 - 11:00 PDT / 19:00 - 20:00 CEST` for an event across `zones`
+pub fn event_times(
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    zones: &[Tz],
+    time_format: &str,
+) -> String {
+    std::iter::once(local_time(start, Some(end), time_format))
+        .chain(
+            zones
+                .iter()
+                .map(|&zone| zoned(start, Some(end), zone, start.date_naive(), time_format)),
+        )
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// `10:00 PDT / 19:00 CEST`: the time `now` across `zones`
+pub fn clock(now: DateTime<Local>, zones: &[Tz], time_format: &str) -> String {
+    std::iter::once(local_time(now, None, time_format))
+        .chain(
+            zones
+                .iter()
+                .map(|&zone| zoned(now, None, zone, now.date_naive(), time_format)),
+        )
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// Parse an IANA zone name for `--other-timezones`
+pub fn parse_zone(name: &str) -> Result<Tz, String> {
+    name.trim().parse().map_err(|_| {
+        format!(
+            "unknown time zone \"{}\", expected e.g. Europe/Berlin",
+            name
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_utc(time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(&format!("2025-06-16T{}:00Z", time))
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    fn zone(name: &str) -> Tz {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn test_zoned_with_abbreviation_and_day_shift() {
+        // 17:00 UTC in June: 19:00 in Berlin, 02:00 the next day in Tokyo
+        let start = at_utc("17:00");
+        let end = at_utc("18:00");
+        let local = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+
+        assert_eq!(
+            zoned(start, Some(end), zone("Europe/Berlin"), local, "%H:%M"),
+            "19:00 - 20:00 CEST"
+        );
+        assert_eq!(
+            zoned(start, None, zone("Asia/Tokyo"), local, "%H:%M"),
+            "02:00 JST (+1)"
+        );
+        assert_eq!(
+            zoned(start, None, zone("America/Los_Angeles"), local, "%H:%M"),
+            "10:00 PDT"
+        );
+    }
+
+    #[test]
+    fn test_event_times_and_clock_list_every_zone() {
+        let start = at_utc("17:00");
+        let end = at_utc("18:00");
+        let zones = [zone("Europe/Berlin"), zone("America/New_York")];
+
+        let times = event_times(start, end, &zones, "%H:%M");
+        assert_eq!(times.split(" / ").count(), 3);
+        assert!(times.contains(" / 19:00 - 20:00 CEST"));
+        assert!(times.contains(" / 13:00 - 14:00 EDT"));
+
+        let clock = clock(start, &zones[..1], "%H:%M");
+        assert!(clock.contains(" / 19:00 CEST"));
+    }
+
+    #[test]
+    fn test_parse_zone() {
+        assert_eq!(parse_zone("Europe/Berlin"), Ok(zone("Europe/Berlin")));
+        assert!(parse_zone("Mars/Olympus").is_err());
+    }
+}
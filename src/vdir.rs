@@ -0,0 +1,296 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::calendar::ics;
+use crate::calendar::models::{Calendar, Event, EventDateTime};
+use crate::tui::state::{AppState, DateRange};
+
+/// A local copy of the fetched events in the vdir layout (`--vdir`): one
+/// directory per calendar holding one .ics file per event, with the
+/// calendar's `displayname` and `color` beside them, for khal, vdirsyncer
+/// and other tools reading that layout
+#[derive(Debug)]
+pub struct Vdir {
+    dir: PathBuf,
+}
+
+/// Files touched by one mirror
+#[derive(Debug, Default, PartialEq)]
+pub struct MirrorStats {
+    pub written: usize,
+    pub removed: usize,
+}
+
+impl Vdir {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Write the events of visible calendars starting within `range` and
+    /// remove the files of events in `range` that are gone. Files whose
+    /// content is unchanged are not rewritten, so sync tools see no churn.
+    pub fn mirror(&self, state: &AppState, range: &DateRange) -> Result<MirrorStats> {
+        let mut stats = MirrorStats::default();
+
+        for calendar in state
+            .calendars
+            .iter()
+            .filter(|calendar| state.is_calendar_visible(&calendar.id))
+        {
+            let events: Vec<&Event> = state
+                .events
+                .iter()
+                .filter(|(date, _)| in_range(**date, range))
+                .flat_map(|(_, events)| events)
+                .filter(|event| event.calendar_id.as_deref() == Some(calendar.id.as_str()))
+                .collect();
+            self.mirror_calendar(calendar, &events, range, &mut stats)
+                .with_context(|| format!("Failed to mirror {}", calendar.summary))?;
+        }
+
+        Ok(stats)
+    }
+
+    fn mirror_calendar(
+        &self,
+        calendar: &Calendar,
+        events: &[&Event],
+        range: &DateRange,
+        stats: &mut MirrorStats,
+    ) -> Result<()> {
+        let collection = self.dir.join(file_name(&calendar.id));
+        fs::create_dir_all(&collection)
+            .with_context(|| format!("Failed to create {}", collection.display()))?;
+        write_if_changed(&collection.join("displayname"), &calendar.summary)?;
+        // Overridden colors may be terminal names, which other tools can't read
+        if let Some(color) = calendar
+            .background_color
+            .as_ref()
+            .filter(|color| color.starts_with('#'))
+        {
+            write_if_changed(&collection.join("color"), color)?;
+        }
+
+        let mut wanted = HashMap::new();
+        for event in events {
+            let name = format!("{}.ics", file_name(&event.id));
+            wanted.insert(name, ics::write_event(event, stamp(event))?);
+        }
+        for (name, contents) in &wanted {
+            if write_if_changed(&collection.join(name), contents)? {
+                stats.written += 1;
+            }
+        }
+
+        let entries = fs::read_dir(&collection)
+            .with_context(|| format!("Failed to read {}", collection.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            if !name.ends_with(".ics") || wanted.contains_key(name) {
+                continue;
+            }
+            // Events outside the fetched days are kept, as are files oxidate can't read
+            if file_start(&path).is_some_and(|date| in_range(date, range)) {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                stats.removed += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Keeps the DTSTAMP stable between mirrors of an unchanged event
+fn stamp(event: &Event) -> DateTime<Utc> {
+    event
+        .updated
+        .as_deref()
+        .and_then(|updated| DateTime::parse_from_rfc3339(updated).ok())
+        .map_or(DateTime::UNIX_EPOCH, |updated| updated.with_timezone(&Utc))
+}
+
+/// The date an existing event file starts on
+fn file_start(path: &Path) -> Option<NaiveDate> {
+    let contents = fs::read_to_string(path).ok()?;
+    let events = ics::parse_events(&contents).ok()?;
+    start_date(&events.first()?.start)
+}
+
+fn start_date(start: &EventDateTime) -> Option<NaiveDate> {
+    match start.to_local() {
+        Some(start) => Some(start.date_naive()),
+        None => start.date.as_deref()?.parse().ok(),
+    }
+}
+
+fn in_range(date: NaiveDate, range: &DateRange) -> bool {
+    date >= range.start && date < range.end
+}
+
+/// Calendar and event ids as file names; Google ids are mostly safe already
+fn file_name(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Write through a temporary file and rename, so readers never see half a
+/// file; returns whether anything was written
+fn write_if_changed(path: &Path, contents: &str) -> Result<bool> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return Ok(false);
+    }
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temporary = path.with_file_name(format!(".{}.tmp", name));
+    fs::write(&temporary, contents)
+        .with_context(|| format!("Failed to write {}", temporary.display()))?;
+    fs::rename(&temporary, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(id: &str, summary: &str) -> Calendar {
+        Calendar {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            primary: false,
+            time_zone: "UTC".to_string(),
+            access_role: "owner".to_string(),
+            background_color: Some("#9fe1e7".to_string()),
+            description: None,
+            default_reminders: Vec::new(),
+            selected: true,
+        }
+    }
+
+    fn event(id: &str, calendar_id: &str, date: &str) -> Event {
+        let at = |date: &str| EventDateTime {
+            date_time: None,
+            date: Some(date.to_string()),
+            time_zone: None,
+        };
+        Event {
+            calendar_id: Some(calendar_id.to_string()),
+            updated: Some("2025-06-01T08:00:00Z".to_string()),
+            ..Event::test(id, at(date), at(date))
+        }
+    }
+
+    fn state(events: Vec<Event>) -> AppState {
+        let mut state = AppState::new();
+        state.calendars = vec![calendar("me@example.com", "Me")];
+        for event in events {
+            let date = start_date(&event.start).unwrap();
+            state.events.entry(date).or_default().push(event);
+        }
+        state
+    }
+
+    fn range(start: &str, end: &str) -> DateRange {
+        DateRange {
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_mirror_writes_collections_and_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let vdir = Vdir::new(dir.path().to_path_buf());
+        let state = state(vec![
+            event("standup", "me@example.com", "2025-06-16"),
+            event("review", "me@example.com", "2025-06-17"),
+        ]);
+
+        let stats = vdir
+            .mirror(&state, &range("2025-06-01", "2025-07-01"))
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            MirrorStats {
+                written: 2,
+                removed: 0
+            }
+        );
+        let collection = dir.path().join("me@example.com");
+        assert_eq!(
+            fs::read_to_string(collection.join("displayname")).unwrap(),
+            "Me"
+        );
+        assert_eq!(
+            fs::read_to_string(collection.join("color")).unwrap(),
+            "#9fe1e7"
+        );
+        let standup = fs::read_to_string(collection.join("standup.ics")).unwrap();
+        assert!(standup.contains("UID:standup@google.com"));
+        assert!(standup.contains("DTSTAMP:20250601T080000Z"));
+
+        // A second mirror of the same data touches nothing
+        let stats = vdir
+            .mirror(&state, &range("2025-06-01", "2025-07-01"))
+            .unwrap();
+        assert_eq!(stats, MirrorStats::default());
+    }
+
+    #[test]
+    fn test_mirror_removes_only_gone_events_in_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let vdir = Vdir::new(dir.path().to_path_buf());
+        let old = state(vec![
+            event("standup", "me@example.com", "2025-06-16"),
+            event("offsite", "me@example.com", "2025-09-01"),
+        ]);
+        vdir.mirror(&old, &range("2025-06-01", "2025-10-01"))
+            .unwrap();
+        fs::write(dir.path().join("me@example.com/notes.ics"), "not ics").unwrap();
+
+        let stats = vdir
+            .mirror(&state(Vec::new()), &range("2025-06-01", "2025-07-01"))
+            .unwrap();
+
+        assert_eq!(stats.removed, 1);
+        let collection = dir.path().join("me@example.com");
+        assert!(!collection.join("standup.ics").exists());
+        assert!(collection.join("offsite.ics").exists());
+        assert!(collection.join("notes.ics").exists());
+    }
+
+    #[test]
+    fn test_mirror_skips_hidden_calendars() {
+        let dir = tempfile::tempdir().unwrap();
+        let vdir = Vdir::new(dir.path().to_path_buf());
+        let mut state = state(vec![event("standup", "me@example.com", "2025-06-16")]);
+        state
+            .filter
+            .hidden_calendars
+            .insert("me@example.com".to_string());
+
+        vdir.mirror(&state, &range("2025-06-01", "2025-07-01"))
+            .unwrap();
+
+        assert!(!dir.path().join("me@example.com").exists());
+    }
+
+    #[test]
+    fn test_file_name_replaces_separators() {
+        assert_eq!(file_name("abc_20250616T090000Z"), "abc_20250616T090000Z");
+        assert_eq!(file_name("a/b:c"), "a_b_c");
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, Weekday};
+
+use crate::calendar::models::Event;
+
+/// Working hours within each day, e.g. `9-17` or `09:30-18:00`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// Working days; an alias so clap takes the whole list as one value
+pub type Days = Vec<Weekday>;
+
+/// When the user works (`--working-hours`, `--working-days`): find-slot
+/// proposes slots within it, and the TUI and `next` flag what falls outside
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkingHours {
+    pub hours: Hours,
+    pub days: Days,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            hours: Hours {
+                start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            },
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        }
+    }
+}
+
+impl WorkingHours {
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.days.contains(&date.weekday())
+    }
+
+    /// Whether `at` is on a working day within the working hours, e.g. not a
+    /// 2 a.m. hold sent from another zone
+    pub fn contains(&self, at: DateTime<Local>) -> bool {
+        self.is_working_day(at.date_naive())
+            && at.time() >= self.hours.start
+            && at.time() < self.hours.end
+    }
+
+    /// Whether a timed `event` starts outside the working hours; all-day
+    /// events never do
+    pub fn is_off_hours(&self, event: &Event) -> bool {
+        !event.start.is_all_day()
+            && event
+                .start
+                .to_local()
+                .is_some_and(|start| !self.contains(start))
+    }
+}
+
+/// `9-17` or `09:30-18:00`
+pub fn parse_hours(value: &str) -> Result<Hours, String> {
+    let error = || format!("expected hours like 9-17 or 09:30-18:00, got \"{}\"", value);
+    let time = |part: &str| {
+        let part = part.trim();
+        if part.contains(':') {
+            NaiveTime::parse_from_str(part, "%H:%M").ok()
+        } else {
+            part.parse()
+                .ok()
+                .and_then(|hour| NaiveTime::from_hms_opt(hour, 0, 0))
+        }
+    };
+
+    let (start, end) = value.split_once('-').ok_or_else(error)?;
+    let hours = Hours {
+        start: time(start).ok_or_else(error)?,
+        end: time(end).ok_or_else(error)?,
+    };
+    if hours.end <= hours.start {
+        return Err(error());
+    }
+    Ok(hours)
+}
+
+/// Weekdays as English names or abbreviations, single or as ranges:
+/// `mon-fri`, `mon,tue,thu`, `sun-thu`
+pub fn parse_days(value: &str) -> Result<Days, String> {
+    let error = || {
+        format!(
+            "expected days like mon-fri or mon,wed,fri, got \"{}\"",
+            value
+        )
+    };
+    let day = |name: &str| name.trim().parse::<Weekday>().map_err(|_| error());
+
+    let mut days = Vec::new();
+    for part in value.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (mut current, last) = (day(first)?, day(last)?);
+                // Ranges may wrap around the weekend, e.g. sun-thu
+                loop {
+                    if !days.contains(&current) {
+                        days.push(current);
+                    }
+                    if current == last {
+                        break;
+                    }
+                    current = current.succ();
+                }
+            }
+            None => {
+                let single = day(part)?;
+                if !days.contains(&single) {
+                    days.push(single);
+                }
+            }
+        }
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_hours() {
+        assert_eq!(
+            parse_hours("9-17"),
+            Ok(Hours {
+                start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            })
+        );
+        assert_eq!(
+            parse_hours("09:30-18:00").unwrap().start,
+            NaiveTime::from_hms_opt(9, 30, 0).unwrap()
+        );
+        assert!(parse_hours("17-9").is_err());
+        assert!(parse_hours("9").is_err());
+        assert!(parse_hours("9-25").is_err());
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("mon-fri"), Ok(WorkingHours::default().days));
+        assert_eq!(
+            parse_days("sun-tue"),
+            Ok(vec![Weekday::Sun, Weekday::Mon, Weekday::Tue])
+        );
+        assert_eq!(
+            parse_days("Monday, wed,fri"),
+            Ok(vec![Weekday::Mon, Weekday::Wed, Weekday::Fri])
+        );
+        assert!(parse_days("weekdays").is_err());
+        assert!(parse_days("").is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        let working_hours = WorkingHours::default();
+        let at = |day: u32, hour: u32| Local.with_ymd_and_hms(2025, 6, day, hour, 0, 0).unwrap();
+
+        // Monday, June 16 2025
+        assert!(working_hours.contains(at(16, 9)));
+        assert!(!working_hours.contains(at(16, 17)));
+        assert!(!working_hours.contains(at(16, 2)));
+        // Sunday
+        assert!(!working_hours.contains(at(15, 10)));
+    }
+}
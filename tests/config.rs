@@ -0,0 +1,26 @@
+//! How the binary treats a config.toml it cannot use
+
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_unknown_config_key_is_an_error() {
+    let home = tempfile::tempdir().expect("Failed to create temp dir");
+    let config_path = home.path().join("config.toml");
+    // A typo of `read_only`, which must not quietly leave the client read-write
+    fs::write(&config_path, "read_olny = true\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_oxidate"))
+        .env_clear()
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("OXIDATE_CONFIG", &config_path)
+        .arg("list")
+        .output()
+        .expect("Failed to run oxidate");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("read_olny"), "unexpected error: {}", stderr);
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,96 @@
+//! Commands whose output other programs read (status bars, scripts, MCP
+//! clients) must print nothing on stdout but that output. These run the
+//! binary against a stored login and a cached, empty calendar list, so no
+//! request leaves the machine; the events come from a text calendar.
+
+use chrono::{Duration, Utc};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A config directory holding credentials and a fresh calendar list cache,
+/// and a text calendar with an event tomorrow at 09:00
+fn setup() -> TempDir {
+    let home = tempfile::tempdir().expect("Failed to create temp dir");
+    let app_dir = home.path().join("oxidate");
+    fs::create_dir_all(&app_dir).unwrap();
+    fs::write(
+        app_dir.join("token.json"),
+        r#"{"access_token": "stub", "refresh_token": "stub"}"#,
+    )
+    .unwrap();
+    fs::write(
+        app_dir.join("calendar_list.json"),
+        format!(
+            r#"{{"fetched_at": "{}", "calendars": []}}"#,
+            Utc::now().to_rfc3339()
+        ),
+    )
+    .unwrap();
+
+    let tomorrow = Utc::now().date_naive() + Duration::days(1);
+    fs::write(
+        home.path().join("personal.txt"),
+        format!("{} 09:00 Standup\n", tomorrow),
+    )
+    .unwrap();
+    home
+}
+
+/// The output of a successful run, or None if this build has no OAuth client
+/// credentials (see build.rs), without which no command gets past startup
+fn oxidate(home: &Path, args: &[&str]) -> Option<Output> {
+    option_env!("GOOGLE_CLIENT_ID")?;
+    let output = Command::new(env!("CARGO_BIN_EXE_oxidate"))
+        .env_clear()
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home)
+        .env("TZ", "UTC")
+        .arg("--text-calendar")
+        .arg(home.join("personal.txt"))
+        .args(args)
+        .output()
+        .expect("Failed to run oxidate");
+    assert!(
+        output.status.success(),
+        "oxidate {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(output)
+}
+
+#[test]
+fn test_bar_output_is_only_the_payload() {
+    let home = setup();
+    let Some(output) = oxidate(home.path(), &["next", "--bar", "waybar", "--days", "2"]) else {
+        return;
+    };
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    let payload: serde_json::Value =
+        serde_json::from_str(lines.next().expect("No output")).expect("Not JSON");
+    assert!(payload["text"].as_str().unwrap().contains("Standup"));
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn test_json_output_parses() {
+    let home = setup();
+    let tomorrow = (Utc::now().date_naive() + Duration::days(1)).to_string();
+    let range = ["--from", tomorrow.as_str(), "--to", tomorrow.as_str()];
+
+    for args in [
+        [&["-o", "json", "list"][..], &range].concat(),
+        [&["list", "--json"][..], &range].concat(),
+    ] {
+        let Some(output) = oxidate(home.path(), &args) else {
+            return;
+        };
+        let events: serde_json::Value = serde_json::from_slice(&output.stdout)
+            .unwrap_or_else(|e| panic!("oxidate {:?} printed no JSON: {}", args, e));
+        assert_eq!(events[0]["summary"], "Standup");
+    }
+}